@@ -0,0 +1,43 @@
+//! Edit a wheel held in memory through the async API
+//!
+//! Stands in for a wheel fetched from object storage: the bytes are wrapped
+//! in a `Cursor`, which tokio reads and seeks asynchronously, the version is
+//! bumped, and the result is written to another in-memory `Cursor` before
+//! being stored next to the input under its new filename.
+//!
+//! Run with:
+//!   cargo run --features async --example async_edit -- <wheel> <new-version>
+
+use std::io::Cursor;
+use std::path::Path;
+
+use editwheel::AsyncWheelEditor;
+
+#[tokio::main(flavor = "current_thread")]
+async fn main() {
+    let mut args = std::env::args().skip(1);
+    let (Some(wheel), Some(version)) = (args.next(), args.next()) else {
+        eprintln!("usage: async_edit <wheel> <new-version>");
+        std::process::exit(2);
+    };
+
+    let data = tokio::fs::read(&wheel).await.expect("Failed to read wheel");
+    let mut editor = AsyncWheelEditor::from_async_reader(Cursor::new(data))
+        .await
+        .expect("Failed to open wheel");
+    println!("{} {} -> {}", editor.name(), editor.version(), version);
+    editor.set_version(version);
+
+    let mut output = Cursor::new(Vec::new());
+    editor
+        .save_to_async_writer(&mut output)
+        .await
+        .expect("Failed to save wheel");
+
+    let dir = Path::new(&wheel).parent().unwrap_or(Path::new(""));
+    let output_path = dir.join(editor.filename());
+    tokio::fs::write(&output_path, output.into_inner())
+        .await
+        .expect("Failed to write wheel");
+    println!("Saved to: {}", output_path.display());
+}
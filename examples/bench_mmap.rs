@@ -0,0 +1,72 @@
+//! Benchmark file-backed vs memory-mapped reads of ELF members
+//!
+//! Reads the RPATH of every `.so` in a wheel using both `WheelEditor::open`
+//! and `WheelEditor::open_mmap`.
+//!
+//! Run with:
+//!   cargo run --release --features mmap --example bench_mmap -- <wheel>
+
+use std::fs::File;
+use std::path::Path;
+use std::time::Duration;
+use std::time::Instant;
+
+use editwheel::WheelEditor;
+
+/// List the `.so` members of the wheel
+fn shared_libraries(wheel: &Path) -> Vec<String> {
+    let file = File::open(wheel).expect("Failed to open wheel");
+    let archive = zip::ZipArchive::new(file).expect("Failed to read wheel");
+    archive
+        .file_names()
+        .filter(|name| name.ends_with(".so") || name.contains(".so."))
+        .map(|name| name.to_string())
+        .collect()
+}
+
+/// Read the RPATH of every library, returning the elapsed time
+fn read_rpaths(editor: &WheelEditor, libraries: &[String]) -> Duration {
+    let start = Instant::now();
+    for library in libraries {
+        if let Err(err) = editor.get_rpath(library) {
+            eprintln!("  {}: {}", library, err);
+        }
+    }
+    start.elapsed()
+}
+
+fn main() {
+    let Some(wheel) = std::env::args().nth(1) else {
+        eprintln!("Usage: bench_mmap <wheel>");
+        std::process::exit(1);
+    };
+    let wheel = Path::new(&wheel);
+
+    println!("=== editwheel-rs mmap Benchmark ===\n");
+
+    let libraries = shared_libraries(wheel);
+    println!("Input wheel: {:?}", wheel);
+    println!("Libraries:   {}", libraries.len());
+    println!();
+
+    let start = Instant::now();
+    let file_editor = WheelEditor::open(wheel).expect("Failed to open wheel");
+    println!("Open (File):  {:?}", start.elapsed());
+
+    let start = Instant::now();
+    let mmap_editor = WheelEditor::open_mmap(wheel).expect("Failed to mmap wheel");
+    println!("Open (mmap):  {:?}", start.elapsed());
+
+    let file_time = read_rpaths(&file_editor, &libraries);
+    let mmap_time = read_rpaths(&mmap_editor, &libraries);
+
+    println!("\n=== Summary ===");
+    println!("get_rpath x{} (File): {:?}", libraries.len(), file_time);
+    println!("get_rpath x{} (mmap): {:?}", libraries.len(), mmap_time);
+    if !mmap_time.is_zero() {
+        println!(
+            "Speedup:              {:.2}x",
+            file_time.as_secs_f64() / mmap_time.as_secs_f64()
+        );
+    }
+}
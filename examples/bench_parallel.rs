@@ -0,0 +1,138 @@
+//! Benchmark serial vs parallel ELF patching
+//!
+//! Builds a synthetic wheel holding many copies of a shared library, then
+//! sets the RUNPATH of every one and saves, once on a single-thread rayon
+//! pool and once on the default pool. The two outputs are checked to have
+//! identical members.
+//!
+//! Run with:
+//!   cargo run --release --features rayon --example bench_parallel -- [libraries] [size_mb]
+
+use std::fs::File;
+use std::io::Read;
+use std::io::Write;
+use std::path::Path;
+use std::time::Duration;
+use std::time::Instant;
+
+use editwheel::WheelEditor;
+use editwheel::hash_content;
+use zip::ZipWriter;
+use zip::write::SimpleFileOptions;
+
+const DIST_INFO: &str = "bench_pkg-1.0.0.dist-info";
+
+/// Write a wheel with `count` shared libraries, each padded to `size` bytes
+fn create_wheel(path: &Path, count: usize, size: usize) {
+    let mut library = std::fs::read(concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/tests/fixtures/librpath.so"
+    ))
+    .expect("Failed to read fixture library");
+    // Trailing bytes aren't covered by any segment, so the library stays valid
+    library.resize(size.max(library.len()), 0);
+
+    let mut files: Vec<(String, Vec<u8>)> = (0..count)
+        .map(|i| (format!("bench_pkg/lib/libbench{}.so", i), library.clone()))
+        .collect();
+    files.push((
+        format!("{}/METADATA", DIST_INFO),
+        b"Metadata-Version: 2.1\nName: bench-pkg\nVersion: 1.0.0\n".to_vec(),
+    ));
+    files.push((
+        format!("{}/WHEEL", DIST_INFO),
+        b"Wheel-Version: 1.0\nRoot-Is-Purelib: false\nTag: py3-none-linux_x86_64\n".to_vec(),
+    ));
+
+    let mut zip = ZipWriter::new(File::create(path).expect("Failed to create wheel"));
+    let options = SimpleFileOptions::default();
+    let mut record = String::new();
+    for (name, content) in &files {
+        zip.start_file(name.as_str(), options).unwrap();
+        zip.write_all(content).unwrap();
+        record.push_str(&format!(
+            "{},{},{}\n",
+            name,
+            hash_content(content),
+            content.len()
+        ));
+    }
+    record.push_str(&format!("{}/RECORD,,\n", DIST_INFO));
+    zip.start_file(format!("{}/RECORD", DIST_INFO), options)
+        .unwrap();
+    zip.write_all(record.as_bytes()).unwrap();
+    zip.finish().unwrap();
+}
+
+/// Set the RUNPATH of every library and save, returning the elapsed time
+fn patch(input: &Path, output: &Path) -> Duration {
+    let start = Instant::now();
+    let mut editor = WheelEditor::open(input).expect("Failed to open wheel");
+    editor
+        .set_rpath("bench_pkg/lib/*.so", "$ORIGIN/../../bench_pkg.libs")
+        .expect("Failed to set RPATH");
+    editor.save(output).expect("Failed to save wheel");
+    start.elapsed()
+}
+
+/// Every member of a wheel as `(name, content)`, in archive order
+fn members(path: &Path) -> Vec<(String, Vec<u8>)> {
+    let mut archive = zip::ZipArchive::new(File::open(path).unwrap()).unwrap();
+    (0..archive.len())
+        .map(|i| {
+            let mut entry = archive.by_index(i).unwrap();
+            let mut content = Vec::new();
+            entry.read_to_end(&mut content).unwrap();
+            (entry.name().to_string(), content)
+        })
+        .collect()
+}
+
+fn main() {
+    let mut args = std::env::args().skip(1);
+    let count: usize = args.next().map_or(48, |n| n.parse().expect("libraries"));
+    let size_mb: usize = args.next().map_or(8, |n| n.parse().expect("size_mb"));
+
+    println!("=== editwheel-rs parallel ELF patching Benchmark ===\n");
+
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let input = temp_dir
+        .path()
+        .join("bench_pkg-1.0.0-py3-none-linux_x86_64.whl");
+    let serial_output = temp_dir.path().join("serial.whl");
+    let parallel_output = temp_dir.path().join("parallel.whl");
+
+    let start = Instant::now();
+    create_wheel(&input, count, size_mb << 20);
+    println!("Libraries:   {} x {} MB", count, size_mb);
+    println!("Build wheel: {:?}", start.elapsed());
+
+    let serial_pool = rayon::ThreadPoolBuilder::new()
+        .num_threads(1)
+        .build()
+        .unwrap();
+    let parallel_pool = rayon::ThreadPoolBuilder::new().build().unwrap();
+
+    let serial_time = serial_pool.install(|| patch(&input, &serial_output));
+    let parallel_time = parallel_pool.install(|| patch(&input, &parallel_output));
+
+    let identical = members(&serial_output) == members(&parallel_output);
+
+    println!("\n=== Summary ===");
+    println!("set_rpath + save (1 thread):   {:?}", serial_time);
+    println!(
+        "set_rpath + save ({} threads): {:?}",
+        parallel_pool.current_num_threads(),
+        parallel_time
+    );
+    if !parallel_time.is_zero() {
+        println!(
+            "Speedup:                       {:.2}x",
+            serial_time.as_secs_f64() / parallel_time.as_secs_f64()
+        );
+    }
+    println!("Identical members:             {}", identical);
+    if !identical {
+        std::process::exit(1);
+    }
+}
@@ -10,9 +10,41 @@ performance by copying unchanged files as raw compressed bytes.
 
 # Re-export from the Rust extension module
 from editwheel.editwheel import (
+    ArchiveLimitError,
+    EditwheelError,
+    ElfError,
+    EntryPointsError,
+    InvalidWheelError,
+    MetadataError,
+    PatternError,
+    RecordError,
+    ValidationIssue,
     ValidationResult,
     WheelEditor,
+    WheelInfoError,
+    canonical_wheel_filename,
+    format_address_list,
+    metadata_differences,
+    metadata_matches,
     normalize_dist_info_name,
 )
 
-__all__ = ["ValidationResult", "WheelEditor", "normalize_dist_info_name"]
+__all__ = [
+    "ArchiveLimitError",
+    "EditwheelError",
+    "ElfError",
+    "EntryPointsError",
+    "InvalidWheelError",
+    "MetadataError",
+    "PatternError",
+    "RecordError",
+    "ValidationIssue",
+    "ValidationResult",
+    "WheelEditor",
+    "WheelInfoError",
+    "canonical_wheel_filename",
+    "format_address_list",
+    "metadata_differences",
+    "metadata_matches",
+    "normalize_dist_info_name",
+]
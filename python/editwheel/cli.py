@@ -16,10 +16,13 @@ import sys
 from importlib.metadata import version as _pkg_version
 from typing import List, Optional
 
-from editwheel.editwheel import WheelEditor
+from editwheel.editwheel import WheelEditor, metadata_differences
 
 __version__ = _pkg_version("editwheel")
 
+# 1980-01-01T00:00:00Z, the earliest timestamp a zip entry can hold
+_DOS_EPOCH = 315532800
+
 
 def _existing_path(path: str) -> str:
     """Argparse type that validates the path exists."""
@@ -28,34 +31,143 @@ def _existing_path(path: str) -> str:
     return path
 
 
+def _existing_path_or_stdin(path: str) -> str:
+    """Argparse type like `_existing_path` that also accepts '-' for stdin."""
+    return path if path == "-" else _existing_path(path)
+
+
 def _show(args: argparse.Namespace) -> None:
     """Handle the 'show' subcommand."""
     wheel = args.wheel
 
     try:
-        editor = WheelEditor(wheel)
+        if wheel == "-":
+            editor = WheelEditor.from_bytes(sys.stdin.buffer.read())
+        else:
+            editor = WheelEditor(wheel)
     except Exception as e:
         print(f"Error: {e}", file=sys.stderr)
         sys.exit(1)
 
+    if args.pep566:
+        print(json.dumps(json.loads(editor.metadata_json()), indent=2))
+        return
+
+    if args.members:
+        members = [
+            {
+                "name": name,
+                "compressed_size": compressed_size,
+                "uncompressed_size": uncompressed_size,
+                "method": method,
+                "crc32": f"{crc32:08x}",
+                "is_dir": is_dir,
+            }
+            for name, compressed_size, uncompressed_size, method, crc32, is_dir in (
+                editor.members()
+            )
+        ]
+        if args.as_json:
+            print(json.dumps(members, indent=2))
+        else:
+            for m in members:
+                print(
+                    f"{m['uncompressed_size']:>12} {m['compressed_size']:>12} "
+                    f"{m['method']:<10} {m['crc32']} {m['name']}"
+                )
+        return
+
+    # Raw bytes of one member replace every other view
+    if args.cat:
+        try:
+            content = editor.read_file(args.cat)
+        except ValueError as e:
+            print(f"Error: {e}", file=sys.stderr)
+            sys.exit(1)
+        sys.stdout.buffer.write(content)
+        sys.stdout.flush()
+        return
+
     # Build metadata dict
     metadata = {
         "name": editor.name,
         "version": editor.version,
         "summary": editor.summary,
+        "description_content_type": editor.description_content_type,
+        "description_byte_length": editor.description_byte_length,
         "author": editor.author,
         "author_email": editor.author_email,
+        "contributors": [
+            " ".join(
+                part
+                for part in (name, f"<{email}>" if email else None, f"({role})")
+                if part
+            )
+            for name, email, role in editor.contributors
+        ],
         "license": editor.license,
         "requires_python": editor.requires_python,
+        "keywords": editor.keywords_list,
         "classifiers": editor.classifiers,
         "requires_dist": editor.requires_dist,
+        "obsoletes_dist": editor.obsoletes_dist,
+        "dynamic": editor.dynamic,
         "project_urls": editor.project_urls,
         "python_tag": editor.python_tag,
         "abi_tag": editor.abi_tag,
         "platform_tag": editor.platform_tag,
+        "tags": editor.compressed_tags,
+        "libc_version": (
+            ".".join(map(str, editor.libc_version)) if editor.libc_version else None
+        ),
+        "generator": editor.generator,
+        "build_tag": (
+            f"{editor.build_tag[0]}{editor.build_tag[1]}" if editor.build_tag else None
+        ),
         "dist_info_dir": editor.dist_info_dir,
+        "dist_info_version": editor.dist_info_version,
+        "canonical_filename": editor.canonical_filename,
     }
 
+    # Raw METADATA headers replace the summary view
+    if args.header:
+        metadata = {header: editor.get_metadata(header) for header in args.header}
+
+    # Effective RPATH/RUNPATH of ELF members replaces the summary view
+    if args.rpath:
+        try:
+            metadata = {member: editor.get_rpath(member) for member in args.rpath}
+        except Exception as e:
+            print(f"Error: {e}", file=sys.stderr)
+            sys.exit(1)
+
+    # DT_NEEDED libraries of ELF members replace the summary view
+    if args.needed:
+        try:
+            metadata = {
+                member: editor.needed_libraries(member) for member in args.needed
+            }
+        except Exception as e:
+            print(f"Error: {e}", file=sys.stderr)
+            sys.exit(1)
+
+    # Full dynamic-linking details of ELF members replace the summary view
+    if args.elf_info:
+        try:
+            metadata = {}
+            for member in args.elf_info:
+                rpath, runpath, needed, soname, interpreter = editor.elf_info(member)
+                metadata[member] = {
+                    "rpath": rpath,
+                    "runpath": runpath,
+                    "needed": needed,
+                    "soname": soname,
+                    "interpreter": interpreter,
+                }
+        except Exception as e:
+            print(f"Error: {e}", file=sys.stderr)
+            sys.exit(1)
+
     # Filter to specific fields if requested
     if args.field:
         # Normalize field names (allow both underscore and hyphen)
@@ -98,6 +210,16 @@ def _edit(args: argparse.Namespace) -> None:
 
     changes_made = False
 
+    # Replace the whole metadata first so individual flags below win
+    if args.metadata_json is not None:
+        try:
+            with open(args.metadata_json, encoding="utf-8") as f:
+                editor.set_metadata_json(f.read())
+        except (OSError, ValueError) as e:
+            print(f"Error: {e}", file=sys.stderr)
+            sys.exit(1)
+        changes_made = True
+
     # Apply single-value field changes
     if args.pkg_name is not None:
         editor.name = args.pkg_name
@@ -111,6 +233,14 @@ def _edit(args: argparse.Namespace) -> None:
         editor.summary = args.summary
         changes_made = True
 
+    if args.description_content_type is not None:
+        try:
+            editor.set_description_content_type_checked(args.description_content_type)
+        except ValueError as e:
+            print(f"Error: {e}", file=sys.stderr)
+            sys.exit(1)
+        changes_made = True
+
     if args.author is not None:
         editor.author = args.author
         changes_made = True
@@ -127,6 +257,18 @@ def _edit(args: argparse.Namespace) -> None:
         editor.requires_python = args.requires_python
         changes_made = True
 
+    if args.set_keywords is not None:
+        editor.keywords_list = [
+            k.strip() for k in args.set_keywords.split(",") if k.strip()
+        ]
+        changes_made = True
+    elif args.normalize_keywords:
+        editor.normalize_keywords()
+        changes_made = True
+    for keyword in args.add_keyword:
+        if editor.add_keyword(keyword):
+            changes_made = True
+
     # Handle classifiers
     if args.set_classifiers is not None:
         editor.classifiers = [
@@ -151,36 +293,235 @@ def _edit(args: argparse.Namespace) -> None:
         editor.requires_dist = deps
         changes_made = True
 
+    if args.set_dynamic is not None:
+        editor.dynamic = [f.strip() for f in args.set_dynamic.split(",") if f.strip()]
+        changes_made = True
+
+    for field in args.remove_field:
+        try:
+            removed = editor.remove_metadata(field)
+        except ValueError as e:
+            print(f"Error: {e}", file=sys.stderr)
+            sys.exit(1)
+        if removed:
+            print(f"Removed {field}")
+            changes_made = True
+
+    # Handle obsoletes_dist
+    for name in args.remove_obsoletes_dist:
+        removed = editor.remove_obsoletes_dist(name)
+        print(f"Removed {removed} Obsoletes-Dist entry(ies) for '{name}'")
+        if removed:
+            changes_made = True
+    for entry in args.add_obsoletes_dist:
+        editor.add_obsoletes_dist(entry)
+        changes_made = True
+
+    # Union classifiers and dependencies from other wheels
+    for other_wheel in args.merge_metadata_from:
+        try:
+            other = WheelEditor(other_wheel)
+        except Exception as e:
+            print(f"Error opening '{other_wheel}': {e}", file=sys.stderr)
+            sys.exit(1)
+        added_classifiers = editor.merge_classifiers_from(other)
+        added_deps = editor.merge_requires_dist_from(other)
+        print(
+            f"Merged {added_classifiers} classifier(s) and {added_deps} "
+            f"dependency(ies) from {other_wheel}"
+        )
+        if added_classifiers or added_deps:
+            changes_made = True
+
+    if args.metadata_version is not None:
+        try:
+            major, minor = (int(part) for part in args.metadata_version.split("."))
+            changes = editor.downgrade_metadata_version((major, minor))
+        except ValueError as e:
+            print(f"Error: {e}", file=sys.stderr)
+            sys.exit(1)
+        for change in changes:
+            print(f"Metadata-Version downgrade: {change}")
+        changes_made = True
+
     # Handle RPATH modifications
     if args.set_rpath:
         for pattern, rpath in args.set_rpath:
             try:
-                count = editor.set_rpath(pattern, rpath)
+                if args.check_rpath:
+                    count, warnings = editor.set_rpath_checked(pattern, rpath)
+                else:
+                    count, warnings = editor.set_rpath(pattern, rpath), []
                 print(f"Set RPATH on {count} file(s) matching '{pattern}'")
+                for warning in warnings:
+                    print(f"Warning: {warning}", file=sys.stderr)
                 if count > 0:
                     changes_made = True
             except Exception as e:
                 print(f"Error setting RPATH for '{pattern}': {e}", file=sys.stderr)
                 sys.exit(1)
 
+    if args.set_soname:
+        for pattern, soname in args.set_soname:
+            try:
+                count = editor.set_soname(pattern, soname)
+            except Exception as e:
+                print(f"Error setting SONAME for '{pattern}': {e}", file=sys.stderr)
+                sys.exit(1)
+            print(f"Set SONAME on {count} file(s) matching '{pattern}'")
+            if count > 0:
+                changes_made = True
+
+    if args.set_interpreter:
+        for pattern, interpreter in args.set_interpreter:
+            try:
+                count = editor.set_interpreter(pattern, interpreter)
+            except Exception as e:
+                print(
+                    f"Error setting interpreter for '{pattern}': {e}", file=sys.stderr
+                )
+                sys.exit(1)
+            print(f"Set interpreter on {count} file(s) matching '{pattern}'")
+            if count > 0:
+                changes_made = True
+
+    if args.remove_rpath:
+        for pattern in args.remove_rpath:
+            try:
+                count = editor.remove_rpath(pattern)
+            except Exception as e:
+                print(f"Error removing RPATH for '{pattern}': {e}", file=sys.stderr)
+                sys.exit(1)
+            print(f"Removed RPATH from {count} file(s) matching '{pattern}'")
+            if count > 0:
+                changes_made = True
+
+    if args.elf_rules:
+        try:
+            with open(args.elf_rules) as f:
+                rules = [
+                    (rule["pattern"], rule["modifications"]) for rule in json.load(f)
+                ]
+            applied, failures, skipped = editor.apply_elf_rules(rules)
+        except Exception as e:
+            print(f"Error applying ELF rules: {e}", file=sys.stderr)
+            sys.exit(1)
+        for path, specs in applied.items():
+            print(f"{path}: {', '.join(specs)}")
+        for path, error in failures:
+            print(f"Warning: failed to modify {path}: {error}", file=sys.stderr)
+        for path, spec in skipped:
+            print(f"Warning: skipped {spec} on {path}: not applicable", file=sys.stderr)
+        if applied:
+            changes_made = True
+
+    if args.strip_debug:
+        for pattern in args.strip_debug:
+            try:
+                saved = editor.strip_debug(pattern)
+            except Exception as e:
+                print(f"Error stripping '{pattern}': {e}", file=sys.stderr)
+                sys.exit(1)
+            print(f"Stripped debug sections matching '{pattern}' ({saved} bytes saved)")
+            if saved > 0:
+                changes_made = True
+
+    for path, error in editor.warnings:
+        print(f"Warning: failed to modify {path}: {error}", file=sys.stderr)
+
+    # Handle content replacements
+    if args.replace_text:
+        for pattern, old, new in args.replace_text:
+            old_bytes, new_bytes = old.encode(), new.encode()
+
+            def replace(_path, content, old_bytes=old_bytes, new_bytes=new_bytes):
+                if old_bytes not in content:
+                    return None
+                return content.replace(old_bytes, new_bytes)
+
+            try:
+                count = editor.modify_matching(pattern, replace)
+                print(f"Replaced text in {count} file(s) matching '{pattern}'")
+                if count > 0:
+                    changes_made = True
+            except Exception as e:
+                print(f"Error replacing text in '{pattern}': {e}", file=sys.stderr)
+                sys.exit(1)
+
     # Handle platform tag
     if args.platform_tag is not None:
-        editor.platform_tag = args.platform_tag
+        try:
+            if args.check_libc:
+                editor.set_platform_tag_checked(args.platform_tag)
+            else:
+                editor.retag(platform=args.platform_tag)
+        except ValueError as e:
+            print(f"Error: {e}", file=sys.stderr)
+            sys.exit(1)
         print(f"Set Platform tag to: {args.platform_tag}")
         changes_made = True
 
     # Handle python tag
     if args.python_tag is not None:
-        editor.python_tag = args.python_tag
+        try:
+            editor.set_python_tag_checked(args.python_tag)
+        except ValueError as e:
+            print(f"Error: {e}", file=sys.stderr)
+            sys.exit(1)
         print(f"Set Python tag to: {args.python_tag}")
         changes_made = True
 
     # Handle ABI tag
     if args.abi_tag is not None:
-        editor.abi_tag = args.abi_tag
+        try:
+            editor.retag(abi=args.abi_tag)
+        except ValueError as e:
+            print(f"Error: {e}", file=sys.stderr)
+            sys.exit(1)
         print(f"Set ABI tag to: {args.abi_tag}")
         changes_made = True
 
+    if args.dedup_tags:
+        removed = editor.dedup_tags()
+        print(f"Removed {removed} duplicate tag(s)")
+        if removed:
+            changes_made = True
+
+    for tag in args.add_tag:
+        try:
+            added = editor.add_tag(tag)
+        except ValueError as e:
+            print(f"Error: {e}", file=sys.stderr)
+            sys.exit(1)
+        if added:
+            print(f"Added tag: {tag}")
+            changes_made = True
+
+    for tag in args.remove_tag:
+        try:
+            removed = editor.remove_tag(tag)
+        except ValueError as e:
+            print(f"Error: {e}", file=sys.stderr)
+            sys.exit(1)
+        if removed:
+            print(f"Removed tag: {tag}")
+            changes_made = True
+        else:
+            print(f"Warning: wheel has no tag '{tag}'", file=sys.stderr)
+
+    # Removals go first, so a path can be removed and re-added in one run
+    for archive_path in args.remove_file:
+        try:
+            removed = editor.remove_file(archive_path)
+        except ValueError as e:
+            print(f"Error: {e}", file=sys.stderr)
+            sys.exit(1)
+        if removed:
+            print(f"Removed file: {archive_path}")
+            changes_made = True
+        else:
+            print(f"Warning: '{archive_path}' is not in the wheel", file=sys.stderr)
+
     # Handle file injection. --add-file accepts the full archive path;
     # --add-dist-info-file is a convenience that prefixes with the wheel's
     # dist-info directory (resolved against the *post-edit* metadata).
@@ -216,18 +557,180 @@ def _edit(args: argparse.Namespace) -> None:
             print(f"Added dist-info file: {archive_path} ({len(content)} bytes from {src})")
             changes_made = True
 
+    if args.rename_package:
+        old_top, new_top = args.rename_package
+        try:
+            moved = editor.rename_package(old_top, new_top)
+        except ValueError as e:
+            print(f"Error: {e}", file=sys.stderr)
+            sys.exit(1)
+        if moved:
+            print(f"Renamed package: {old_top}/ -> {new_top}/ ({moved} file(s))")
+            changes_made = True
+        else:
+            print(f"Warning: wheel has no package '{old_top}/'", file=sys.stderr)
+
+    # entry_points.txt edits: removals, then renames, then sets, so a script
+    # can be renamed and pointed at a new module in one run
+    try:
+        for group, name in args.remove_entry_point:
+            if editor.remove_entry_point(group, name):
+                print(f"Removed entry point: [{group}] {name}")
+                changes_made = True
+            else:
+                print(f"Warning: no entry point [{group}] {name}", file=sys.stderr)
+        for group, old, new in args.rename_entry_point:
+            if editor.rename_entry_point(group, old, new):
+                print(f"Renamed entry point: [{group}] {old} -> {new}")
+                changes_made = True
+            else:
+                print(f"Warning: no entry point [{group}] {old}", file=sys.stderr)
+        for group, name, value in args.set_entry_point:
+            editor.set_entry_point(group, name, value)
+            print(f"Set entry point: [{group}] {name} = {value}")
+            changes_made = True
+    except ValueError as e:
+        print(f"Error: {e}", file=sys.stderr)
+        sys.exit(1)
+
+    # Handle generator: an exact value first, then optionally stamp on top
+    if args.generator is not None:
+        editor.generator = args.generator
+        changes_made = True
+
+    if args.stamp_generator:
+        editor.stamp_generator()
+        print(f"Set Generator to: {editor.generator}")
+        changes_made = True
+
+    if args.build_number is not None:
+        editor.set_build_number(args.build_number)
+        number, suffix = editor.build_tag
+        print(f"Set Build to: {number}{suffix}")
+        changes_made = True
+
+    if args.max_compatibility:
+        editor.max_compatibility = True
+        print("Writing output for maximum compatibility (Stored/Deflate only)")
+        changes_made = True
+
+    if args.strict_record:
+        editor.strict_record = True
+
+    if args.strict_metadata:
+        editor.strict_metadata = True
+
+    if args.hash_algorithm:
+        editor.hash_algorithm = args.hash_algorithm
+
+    if args.compression:
+        editor.compression = args.compression
+
+    if args.compression_level is not None:
+        try:
+            editor.compression_level = args.compression_level
+        except ValueError as e:
+            print(f"Error: {e}", file=sys.stderr)
+            sys.exit(1)
+
+    if args.verify_hashes:
+        editor.verify_hashes = True
+
+    if args.reproducible:
+        epoch = os.environ.get("SOURCE_DATE_EPOCH", str(_DOS_EPOCH))
+        try:
+            editor.source_date_epoch = int(epoch)
+        except (ValueError, OverflowError):
+            print(f"Error: invalid SOURCE_DATE_EPOCH '{epoch}'", file=sys.stderr)
+            sys.exit(1)
+
+    if args.hashless:
+        try:
+            editor.set_hashless_patterns(args.hashless)
+        except ValueError as e:
+            print(f"Error: {e}", file=sys.stderr)
+            sys.exit(1)
+        print(f"Writing RECORD without hashes for: {', '.join(args.hashless)}")
+        changes_made = True
+
+    if args.editable_fields is not None:
+        editor.set_editable_fields(
+            [f.strip() for f in args.editable_fields.split(",") if f.strip()]
+        )
+
+    # Handle RECORD hash refresh (runs after other edits so it sees any
+    # patched content)
+    if args.refresh_record_hash:
+        for path in args.refresh_record_hash:
+            try:
+                editor.refresh_record_hash(path)
+            except Exception as e:
+                print(f"Error refreshing RECORD hash for '{path}': {e}", file=sys.stderr)
+                sys.exit(1)
+            print(f"Refreshed RECORD hash: {path}")
+            changes_made = True
+
     if not changes_made:
         print(
             "No changes specified. Use --help to see available options.", file=sys.stderr
         )
         sys.exit(1)
 
+    if args.show_changes:
+        try:
+            changes = editor.changes()
+        except Exception as e:
+            print(f"Error listing changes: {e}", file=sys.stderr)
+            sys.exit(1)
+        for field, old, new in changes:
+            print(f"{field}: {old!r} -> {new!r}")
+
+    if args.check_in_place:
+        possible = "yes" if editor.can_patch_in_place() else "no"
+        print(f"In-place patching possible (experimental): {possible}")
+
+    if args.show_record_changes:
+        for path, old, new in editor.record_hash_changes():
+            print(f"RECORD {path}: {old!r} -> {new!r}")
+
+    if args.require_constant_time:
+        blockers = editor.constant_time_blockers()
+        if blockers:
+            print("Error: save would not be constant-time:", file=sys.stderr)
+            for reason in blockers:
+                print(f"  - {reason}", file=sys.stderr)
+            sys.exit(1)
+
+    if args.dry_run:
+        dist_info, copied, rewritten, removed = editor.plan()
+        print(f"Dist-info: {dist_info}")
+        print(f"Will copy {len(copied)} file(s) unchanged")
+        print(f"Will rewrite {len(rewritten)} file(s):")
+        for path, reason in rewritten:
+            print(f"  {reason:<11} {path}")
+        for path in removed:
+            print(f"Will remove {path}")
+        return
+
+    if not args.strict_record:
+        for path in editor.unrecorded_files():
+            print(f"Warning: {path} is not in RECORD; hashing it", file=sys.stderr)
+    for path in editor.dropped_signatures():
+        print(
+            f"Warning: dropping {path}: it signs the old RECORD and can't be "
+            "regenerated without the signing key",
+            file=sys.stderr,
+        )
+
     # Save the wheel
     output = args.output
     try:
         if output and os.path.isdir(output):
             output = os.path.join(output, editor.filename)
-        editor.save(output)
+        if args.durable:
+            editor.save_durable(output)
+        else:
+            editor.save(output)
         if output:
             print(f"Saved to: {output}")
         else:
@@ -236,36 +739,118 @@ def _edit(args: argparse.Namespace) -> None:
         print(f"Error saving wheel: {e}", file=sys.stderr)
         sys.exit(1)
 
+    if args.write_metadata_sidecar:
+        sidecar = f"{output or wheel}.metadata"
+        try:
+            with open(sidecar, "wb") as f:
+                f.write(editor.extract_metadata_bytes())
+        except OSError as e:
+            print(f"Error writing '{sidecar}': {e}", file=sys.stderr)
+            sys.exit(1)
+        print(f"Wrote PEP 658 metadata: {sidecar}")
+
 
 def _validate(args: argparse.Namespace) -> None:
     """Handle the 'validate' subcommand."""
     wheel = args.wheel
 
     try:
-        editor = WheelEditor(wheel)
+        editor = WheelEditor(
+            wheel,
+            max_entries=args.max_entries,
+            max_total_uncompressed=args.max_total_uncompressed,
+            max_ratio=args.max_ratio,
+        )
     except Exception as e:
         print(f"Error: {e}", file=sys.stderr)
         sys.exit(1)
 
-    result = editor.validate()
+    if args.against is not None:
+        try:
+            with open(args.against, encoding="utf-8") as f:
+                result = editor.validate_against(f.read())
+        except (OSError, ValueError) as e:
+            print(f"Error: {e}", file=sys.stderr)
+            sys.exit(1)
+    else:
+        result = editor.validate()
+    errors = list(result.errors)
+
+    if args.max_description_size is not None:
+        try:
+            editor.check_description_size(args.max_description_size)
+        except ValueError as e:
+            errors.append(str(e))
+
+    if args.check_metadata:
+        errors.extend(editor.validate_metadata())
+
+    if args.check_layout:
+        errors.extend(
+            f"member does not map to an install location: {path}"
+            for path in editor.check_install_layout()
+        )
+
+    if args.check_safe_paths:
+        errors.extend(
+            f"member is unsafe to extract (absolute, '..' or symlink): {path}"
+            for path in editor.check_safe_paths()
+        )
+
+    warnings = []
+    if args.lint:
+        for code, severity, message in editor.lint():
+            (errors if severity == "error" else warnings).append(f"{code}: {message}")
+
+    is_valid = not errors
 
     if args.as_json:
         print(
             json.dumps(
-                {"is_valid": result.is_valid, "errors": result.errors},
+                {"is_valid": is_valid, "errors": errors, "warnings": warnings},
                 indent=2,
             )
         )
     else:
-        if result.is_valid:
+        for warning in warnings:
+            print(f"warning: {warning}", file=sys.stderr)
+        if is_valid:
             print(f"OK: {wheel} is valid")
         else:
-            print(f"FAIL: {wheel} has {len(result.errors)} error(s):", file=sys.stderr)
-            for err in result.errors:
+            print(f"FAIL: {wheel} has {len(errors)} error(s):", file=sys.stderr)
+            for err in errors:
                 print(f"  - {err}", file=sys.stderr)
 
-    if not result.is_valid:
+    if not is_valid:
+        sys.exit(1)
+
+
+def _unpack(args: argparse.Namespace) -> None:
+    """Handle the 'unpack' subcommand."""
+    try:
+        editor = WheelEditor(args.wheel)
+        editor.unpack(args.dest)
+    except Exception as e:
+        print(f"Error: {e}", file=sys.stderr)
         sys.exit(1)
+    print(f"Unpacked {args.wheel} to {args.dest}")
+
+
+def _compare(args: argparse.Namespace) -> None:
+    """Handle the 'compare' subcommand."""
+    try:
+        diff = metadata_differences(args.a, args.b, args.ignore)
+    except Exception as e:
+        print(f"Error: {e}", file=sys.stderr)
+        sys.exit(1)
+
+    if not diff:
+        print(f"OK: {args.a} and {args.b} have matching metadata")
+        return
+    print(f"FAIL: metadata differs in {len(diff)} field(s):", file=sys.stderr)
+    for field, old, new in diff:
+        print(f"  - {field}: {old!r} != {new!r}", file=sys.stderr)
+    sys.exit(1)
 
 
 def _build_parser() -> argparse.ArgumentParser:
@@ -292,16 +877,76 @@ def _build_parser() -> argparse.ArgumentParser:
         help="Display wheel metadata",
         description="Display wheel metadata.",
     )
-    show_parser.add_argument("wheel", type=_existing_path, help="Path to a .whl file to inspect")
+    show_parser.add_argument(
+        "wheel",
+        type=_existing_path_or_stdin,
+        help="Path to a .whl file to inspect, or '-' to read it from stdin",
+    )
     show_parser.add_argument(
         "--json", dest="as_json", action="store_true", help="Output as JSON"
     )
+    show_parser.add_argument(
+        "--pep566",
+        action="store_true",
+        help="Output the full metadata as PEP 566 JSON",
+    )
+    show_parser.add_argument(
+        "--members",
+        action="store_true",
+        help=(
+            "List every archive member with its sizes, compression method "
+            "and CRC-32 instead of the metadata"
+        ),
+    )
+    show_parser.add_argument(
+        "--cat",
+        metavar="MEMBER",
+        help=(
+            "Write the decompressed content of a file in the wheel (e.g. "
+            "'pkg-1.0.dist-info/entry_points.txt') to stdout"
+        ),
+    )
     show_parser.add_argument(
         "--field",
         "-f",
         action="append",
         help="Show only specific field(s). Can be repeated.",
     )
+    show_parser.add_argument(
+        "--header",
+        action="append",
+        help=(
+            "Show a raw METADATA header by name (case-insensitive, e.g. "
+            "'home-page'). Can be repeated."
+        ),
+    )
+    show_parser.add_argument(
+        "--rpath",
+        action="append",
+        metavar="MEMBER",
+        help=(
+            "Show the effective RUNPATH (or RPATH) of an ELF file in the wheel, "
+            "e.g. 'torch/lib/libtorch.so'. Can be repeated."
+        ),
+    )
+    show_parser.add_argument(
+        "--needed",
+        action="append",
+        metavar="MEMBER",
+        help=(
+            "Show the shared libraries (DT_NEEDED entries) an ELF file in the "
+            "wheel links against. Can be repeated."
+        ),
+    )
+    show_parser.add_argument(
+        "--elf-info",
+        action="append",
+        metavar="MEMBER",
+        help=(
+            "Show the RPATH, RUNPATH, DT_NEEDED entries, SONAME and "
+            "interpreter of an ELF file in the wheel. Can be repeated."
+        ),
+    )
 
     # --- edit subcommand ---
     edit_parser = subparsers.add_parser(
@@ -315,9 +960,25 @@ def _build_parser() -> argparse.ArgumentParser:
         "-o",
         help="Output path or directory (default: overwrite in-place)",
     )
+    edit_parser.add_argument(
+        "--metadata-json",
+        type=_existing_path,
+        metavar="FILE",
+        help=(
+            "Replace the metadata with the PEP 566 JSON in FILE (e.g. saved "
+            "from 'show --pep566'); other field flags apply on top"
+        ),
+    )
     edit_parser.add_argument("--name", dest="pkg_name", help="Set package name")
     edit_parser.add_argument("--version", help="Set version")
     edit_parser.add_argument("--summary", help="Set summary/description")
+    edit_parser.add_argument(
+        "--description-content-type",
+        help=(
+            "Set the description content type, validated against the allowed "
+            "values (e.g., 'text/markdown; charset=UTF-8; variant=GFM')"
+        ),
+    )
     edit_parser.add_argument("--author", help="Set author name")
     edit_parser.add_argument("--author-email", help="Set author email")
     edit_parser.add_argument("--license", dest="pkg_license", help="Set license")
@@ -325,6 +986,22 @@ def _build_parser() -> argparse.ArgumentParser:
         "--requires-python",
         help="Set Python version requirement (e.g., '>=3.8')",
     )
+    edit_parser.add_argument(
+        "--set-keywords",
+        help="Replace all keywords (comma-separated)",
+    )
+    edit_parser.add_argument(
+        "--add-keyword",
+        action="append",
+        default=[],
+        metavar="KEYWORD",
+        help="Add a keyword unless already present. Can be repeated.",
+    )
+    edit_parser.add_argument(
+        "--normalize-keywords",
+        action="store_true",
+        help="Rewrite space-separated keywords in the comma-separated form",
+    )
     edit_parser.add_argument(
         "--add-classifier",
         action="append",
@@ -341,10 +1018,64 @@ def _build_parser() -> argparse.ArgumentParser:
         default=[],
         help="Add a dependency. Can be repeated.",
     )
+    edit_parser.add_argument(
+        "--add-obsoletes-dist",
+        action="append",
+        default=[],
+        metavar="ENTRY",
+        help="Add an Obsoletes-Dist entry. Can be repeated.",
+    )
+    edit_parser.add_argument(
+        "--remove-obsoletes-dist",
+        action="append",
+        default=[],
+        metavar="NAME",
+        help=(
+            "Remove the Obsoletes-Dist entries for a project (matched by "
+            "normalized name). Can be repeated."
+        ),
+    )
+    edit_parser.add_argument(
+        "--merge-metadata-from",
+        action="append",
+        default=[],
+        type=_existing_path,
+        metavar="WHEEL",
+        help=(
+            "Add classifiers and dependencies from another wheel that aren't "
+            "already present. Can be repeated."
+        ),
+    )
     edit_parser.add_argument(
         "--set-requires-dist",
         help="Replace all dependencies (comma-separated)",
     )
+    edit_parser.add_argument(
+        "--remove-field",
+        action="append",
+        default=[],
+        metavar="NAME",
+        help=(
+            "Remove a metadata field, e.g. 'Home-page'. Name, Version and "
+            "Metadata-Version can't be removed. Can be repeated."
+        ),
+    )
+    edit_parser.add_argument(
+        "--set-dynamic",
+        metavar="FIELDS",
+        help=(
+            "Replace the fields declared Dynamic (comma-separated, e.g. "
+            "'Requires-Dist,License'); pass '' to remove them all"
+        ),
+    )
+    edit_parser.add_argument(
+        "--metadata-version",
+        metavar="X.Y",
+        help=(
+            "Downgrade METADATA to this Metadata-Version (2.1 to 2.4), "
+            "dropping or translating fields it can't express"
+        ),
+    )
     edit_parser.add_argument(
         "--set-rpath",
         nargs=2,
@@ -356,10 +1087,91 @@ def _build_parser() -> argparse.ArgumentParser:
             "Example: --set-rpath 'torch/lib/*.so' '$ORIGIN'"
         ),
     )
+    edit_parser.add_argument(
+        "--strip-debug",
+        action="append",
+        default=[],
+        metavar="PATTERN",
+        help=(
+            "Strip .debug_* sections from ELF files matching PATTERN. Can be "
+            "repeated. Example: --strip-debug 'torch/lib/*.so'"
+        ),
+    )
+    edit_parser.add_argument(
+        "--check-rpath",
+        action="store_true",
+        help=(
+            "With --set-rpath, warn about $ORIGIN-relative entries that point "
+            "at directories with no files in the wheel"
+        ),
+    )
+    edit_parser.add_argument(
+        "--set-soname",
+        nargs=2,
+        action="append",
+        default=[],
+        metavar=("PATTERN", "SONAME"),
+        help=(
+            "Set the SONAME of ELF files matching a glob pattern, e.g. to rename "
+            "a vendored library. Can be repeated."
+        ),
+    )
+    edit_parser.add_argument(
+        "--set-interpreter",
+        nargs=2,
+        action="append",
+        default=[],
+        metavar=("PATTERN", "INTERPRETER"),
+        help=(
+            "Set the program interpreter of ELF executables matching a glob "
+            "pattern (shared libraries are skipped). Can be repeated."
+        ),
+    )
+    edit_parser.add_argument(
+        "--elf-rules",
+        metavar="FILE",
+        help=(
+            "Apply a JSON recipe of ELF modification rules, a list of "
+            '{"pattern": GLOB, "modifications": [SPEC, ...]} objects where SPEC '
+            "is set-rpath=PATH, set-runpath=PATH, set-soname=NAME, "
+            "set-interpreter=PATH, remove-rpath, remove-runpath, or strip-debug. "
+            "Rules are applied in order and compose."
+        ),
+    )
+    edit_parser.add_argument(
+        "--remove-rpath",
+        action="append",
+        default=[],
+        metavar="PATTERN",
+        help=(
+            "Delete the RPATH and RUNPATH of ELF files matching a glob pattern. "
+            "Can be repeated."
+        ),
+    )
+    edit_parser.add_argument(
+        "--replace-text",
+        nargs=3,
+        action="append",
+        default=[],
+        metavar=("PATTERN", "OLD", "NEW"),
+        help=(
+            "Replace every occurrence of OLD with NEW in files matching "
+            "PATTERN. Can be repeated. "
+            "Example: --replace-text 'pkg/*.cfg' /build/src /opt/src"
+        ),
+    )
     edit_parser.add_argument(
         "--platform-tag",
         help="Set platform tag for the wheel (e.g., 'manylinux_2_28_x86_64')",
     )
+    edit_parser.add_argument(
+        "--check-libc",
+        action="store_true",
+        help=(
+            "With --platform-tag, refuse to retag to a manylinux/musllinux "
+            "version requiring an older libc than the current tag"
+        ),
+    )
     edit_parser.add_argument(
         "--python-tag",
         help="Set python tag for the wheel (e.g., 'cp312')",
@@ -368,6 +1180,25 @@ def _build_parser() -> argparse.ArgumentParser:
         "--abi-tag",
         help="Set ABI tag for the wheel (e.g., 'cp312')",
     )
+    edit_parser.add_argument(
+        "--dedup-tags",
+        action="store_true",
+        help="Collapse duplicate identical Tag lines in WHEEL",
+    )
+    edit_parser.add_argument(
+        "--add-tag",
+        action="append",
+        default=[],
+        metavar="TAG",
+        help="Add a WHEEL Tag line (e.g., 'py2-none-any'); may be repeated",
+    )
+    edit_parser.add_argument(
+        "--remove-tag",
+        action="append",
+        default=[],
+        metavar="TAG",
+        help="Remove a WHEEL Tag line; may be repeated",
+    )
     edit_parser.add_argument(
         "--add-file",
         nargs=2,
@@ -377,10 +1208,22 @@ def _build_parser() -> argparse.ArgumentParser:
         help=(
             "Add a new file to the wheel. ARCHIVE_PATH is the full path "
             "inside the archive; SRC is a local file whose bytes are copied "
-            "in. Can be repeated. Example: --add-file "
+            "in. An existing ARCHIVE_PATH is replaced. Can be repeated. "
+            "Example: --add-file "
             "'pkg-1.0.0.dist-info/build-details.json' ./details.json"
         ),
     )
+    edit_parser.add_argument(
+        "--remove-file",
+        action="append",
+        default=[],
+        metavar="ARCHIVE_PATH",
+        help=(
+            "Remove the file at ARCHIVE_PATH from the wheel and its RECORD. "
+            "The dist-info METADATA, RECORD and WHEEL can't be removed. "
+            "Can be repeated."
+        ),
+    )
     edit_parser.add_argument(
         "--add-dist-info-file",
         nargs=2,
@@ -394,6 +1237,201 @@ def _build_parser() -> argparse.ArgumentParser:
             "--add-dist-info-file build-details.json ./details.json"
         ),
     )
+    edit_parser.add_argument(
+        "--rename-package",
+        nargs=2,
+        metavar=("OLD", "NEW"),
+        help=(
+            "Move the top-level package directory OLD/ to NEW/ and update "
+            "RECORD and top_level.txt. Imports, .pth files and entry point "
+            "values are not rewritten."
+        ),
+    )
+    edit_parser.add_argument(
+        "--set-entry-point",
+        nargs=3,
+        action="append",
+        default=[],
+        metavar=("GROUP", "NAME", "VALUE"),
+        help=(
+            "Set an entry point in the dist-info entry_points.txt, creating "
+            "it if needed. Can be repeated. Example: --set-entry-point "
+            "console_scripts my-tool my_pkg.cli:main"
+        ),
+    )
+    edit_parser.add_argument(
+        "--rename-entry-point",
+        nargs=3,
+        action="append",
+        default=[],
+        metavar=("GROUP", "OLD", "NEW"),
+        help="Rename an entry point, keeping its value. Can be repeated.",
+    )
+    edit_parser.add_argument(
+        "--remove-entry-point",
+        nargs=2,
+        action="append",
+        default=[],
+        metavar=("GROUP", "NAME"),
+        help="Remove an entry point. Can be repeated.",
+    )
+    edit_parser.add_argument(
+        "--refresh-record-hash",
+        action="append",
+        default=[],
+        metavar="PATH",
+        help=(
+            "Recompute the RECORD hash and size for the file at PATH inside "
+            "the archive. Can be repeated."
+        ),
+    )
+    edit_parser.add_argument(
+        "--strict-record",
+        action="store_true",
+        help=(
+            "Fail instead of silently hashing files that are missing from "
+            "RECORD"
+        ),
+    )
+    edit_parser.add_argument(
+        "--strict-metadata",
+        action="store_true",
+        help=(
+            "Fail instead of saving metadata that 'validate --check-metadata' "
+            "would reject (e.g. fields newer than the Metadata-Version)"
+        ),
+    )
+    edit_parser.add_argument(
+        "--verify-hashes",
+        action="store_true",
+        help=(
+            "Check every copied file against its RECORD hash while saving "
+            "and fail on a mismatch. Reads the whole wheel, so saving is no "
+            "longer constant-time."
+        ),
+    )
+    edit_parser.add_argument(
+        "--reproducible",
+        action="store_true",
+        help=(
+            "Timestamp rewritten files with $SOURCE_DATE_EPOCH (or "
+            "1980-01-01 if unset) so the same edit gives identical bytes"
+        ),
+    )
+    edit_parser.add_argument(
+        "--hash-algorithm",
+        choices=["sha256", "sha384", "sha512", "blake2b", "blake2s"],
+        help=(
+            "Hash algorithm for the RECORD entries of rewritten and added "
+            "files (default: sha256)"
+        ),
+    )
+    edit_parser.add_argument(
+        "--compression",
+        choices=["stored", "deflated"],
+        help=(
+            "Compression for rewritten and added files (default: keep Stored "
+            "files Stored, deflate the rest)"
+        ),
+    )
+    edit_parser.add_argument(
+        "--compression-level",
+        type=int,
+        metavar="LEVEL",
+        help="Deflate level (0-9) for rewritten and added files",
+    )
+    edit_parser.add_argument(
+        "--hashless",
+        action="append",
+        default=[],
+        metavar="PATTERN",
+        help=(
+            "Write RECORD entries without a hash or size for files matching a "
+            "glob pattern (e.g. '*.pyc'). METADATA and WHEEL are always "
+            "hashed. Can be repeated."
+        ),
+    )
+    edit_parser.add_argument(
+        "--editable-fields",
+        metavar="FIELDS",
+        help=(
+            "Comma-separated METADATA fields that may change (e.g. "
+            "'Summary,Classifier'); saving fails if any other field was edited"
+        ),
+    )
+    edit_parser.add_argument(
+        "--generator",
+        help="Replace the WHEEL Generator field",
+    )
+    edit_parser.add_argument(
+        "--stamp-generator",
+        action="store_true",
+        help="Append 'editwheel-rs <version>' to the WHEEL Generator field",
+    )
+    edit_parser.add_argument(
+        "--build-number",
+        type=int,
+        help="Set the numeric part of the WHEEL Build tag, keeping any suffix",
+    )
+    edit_parser.add_argument(
+        "--max-compatibility",
+        action="store_true",
+        help=(
+            "Write every entry as Stored or Deflate with the minimal "
+            "version-needed-to-extract, re-encoding entries that use other "
+            "compression methods. For very old installers."
+        ),
+    )
+    edit_parser.add_argument(
+        "--require-constant-time",
+        action="store_true",
+        help=(
+            "Refuse to save (and list the reasons) if the edit would require "
+            "rewriting payload files instead of raw-copying them"
+        ),
+    )
+    edit_parser.add_argument(
+        "--durable",
+        action="store_true",
+        help=(
+            "Fsync the output wheel (and its directory) before exiting, so a "
+            "crash can't leave a partial wheel behind. Slower than the default."
+        ),
+    )
+    edit_parser.add_argument(
+        "--write-metadata-sidecar",
+        action="store_true",
+        help=(
+            "Also write the PEP 658 '<wheel>.metadata' sidecar next to the "
+            "saved wheel, byte-identical to its METADATA"
+        ),
+    )
+    edit_parser.add_argument(
+        "--show-changes",
+        action="store_true",
+        help="Print every changed field and file (old -> new) before saving",
+    )
+    edit_parser.add_argument(
+        "--check-in-place",
+        action="store_true",
+        help=(
+            "Report whether every pending rewrite fits its original member's "
+            "compressed slot (experimental in-place patching precondition)"
+        ),
+    )
+    edit_parser.add_argument(
+        "--dry-run",
+        action="store_true",
+        help=(
+            "Print which files the save would copy, rewrite (with the reason) "
+            "or remove, then exit without writing anything"
+        ),
+    )
+    edit_parser.add_argument(
+        "--show-record-changes",
+        action="store_true",
+        help="Print every RECORD entry whose hash the save would change (old -> new)",
+    )
 
     # --- validate subcommand ---
     validate_parser = subparsers.add_parser(
@@ -411,6 +1449,117 @@ def _build_parser() -> argparse.ArgumentParser:
     validate_parser.add_argument(
         "--json", dest="as_json", action="store_true", help="Output as JSON"
     )
+    validate_parser.add_argument(
+        "--against",
+        type=_existing_path,
+        metavar="RECORD",
+        help=(
+            "Verify the archive against this trusted RECORD file instead of "
+            "the RECORD embedded in the wheel"
+        ),
+    )
+    validate_parser.add_argument(
+        "--max-entries",
+        type=int,
+        metavar="N",
+        help="Refuse to open the wheel if it has more than N entries",
+    )
+    validate_parser.add_argument(
+        "--max-total-uncompressed",
+        type=int,
+        metavar="BYTES",
+        help=(
+            "Refuse to open the wheel if its entries' declared uncompressed "
+            "sizes add up to more than BYTES"
+        ),
+    )
+    validate_parser.add_argument(
+        "--max-ratio",
+        type=float,
+        metavar="RATIO",
+        help=(
+            "Refuse to open the wheel if any entry's uncompressed size is more "
+            "than RATIO times its compressed size (zip-bomb guard)"
+        ),
+    )
+    validate_parser.add_argument(
+        "--max-description-size",
+        type=int,
+        metavar="BYTES",
+        help=(
+            "Also fail if the long description is larger than BYTES "
+            "(e.g. to match an index's upload limit)"
+        ),
+    )
+    validate_parser.add_argument(
+        "--check-metadata",
+        action="store_true",
+        help=(
+            "Also fail on invalid metadata (missing Name/Version, bad "
+            "Description-Content-Type, or Requires-Dist entries that aren't "
+            "valid PEP 508)"
+        ),
+    )
+    validate_parser.add_argument(
+        "--check-layout",
+        action="store_true",
+        help=(
+            "Also fail on members that don't map to an install location "
+            "(e.g. stray files at the archive root)"
+        ),
+    )
+    validate_parser.add_argument(
+        "--check-safe-paths",
+        action="store_true",
+        help=(
+            "Also fail on members that are unsafe to extract (absolute "
+            "paths, '..' segments, or symlinks)"
+        ),
+    )
+    validate_parser.add_argument(
+        "--lint",
+        action="store_true",
+        help=(
+            "Also run non-fatal checks (tag/filename drift, Requires-Python "
+            "conflicts, weak hashes, ...); error-level findings fail, "
+            "warnings are only printed"
+        ),
+    )
+
+    # --- unpack subcommand ---
+    unpack_parser = subparsers.add_parser(
+        "unpack",
+        help="Extract every file of a wheel into a directory",
+        description=(
+            "Extract every member of the wheel into DEST, preserving paths "
+            "and permissions (without setuid, setgid or sticky bits). Refuses "
+            "wheels with unsafe member paths."
+        ),
+    )
+    unpack_parser.add_argument(
+        "wheel", type=_existing_path, help="Path to a .whl file to unpack"
+    )
+    unpack_parser.add_argument("dest", help="Directory to extract into")
+
+    # --- compare subcommand ---
+    compare_parser = subparsers.add_parser(
+        "compare",
+        help="Check that two wheels have identical metadata",
+        description=(
+            "Compare the METADATA of two wheels, e.g. the per-platform wheels "
+            "of one release. Exits non-zero and lists the differing fields on "
+            "mismatch."
+        ),
+    )
+    compare_parser.add_argument("a", type=_existing_path, help="First wheel")
+    compare_parser.add_argument("b", type=_existing_path, help="Second wheel")
+    compare_parser.add_argument(
+        "--ignore",
+        action="append",
+        default=[],
+        metavar="FIELD",
+        help="METADATA field to skip (e.g. Platform). Can be repeated.",
+    )
 
     return parser
 
@@ -430,6 +1579,10 @@ def cli(args: Optional[List[str]] = None) -> None:
         _edit(parsed)
     elif parsed.command == "validate":
         _validate(parsed)
+    elif parsed.command == "unpack":
+        _unpack(parsed)
+    elif parsed.command == "compare":
+        _compare(parsed)
 
 
 if __name__ == "__main__":
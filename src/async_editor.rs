@@ -0,0 +1,202 @@
+//! Editing wheels read from and written to async streams
+//!
+//! `AsyncWheelEditor` is a `WheelEditor` whose source and destination are
+//! tokio streams instead of files, for services that can't block their
+//! reactor on file IO. The wheel is read into memory up front, parsed and
+//! edited with the usual synchronous code, and saved into a buffer - with
+//! `block_in_place` on multi-threaded runtimes, unchanged members still
+//! copied as raw compressed bytes - which is then written out. Memory use
+//! is therefore about twice the wheel's size while saving.
+//!
+//! Edits made through `Deref` (`set_rpath`, `modify_matching`, ...) still
+//! run synchronously on the calling task.
+
+use std::io::Cursor;
+use std::ops::Deref;
+use std::ops::DerefMut;
+use std::path::PathBuf;
+
+use tokio::io::AsyncRead;
+use tokio::io::AsyncReadExt;
+use tokio::io::AsyncSeek;
+use tokio::io::AsyncSeekExt;
+use tokio::io::AsyncWrite;
+use tokio::io::AsyncWriteExt;
+use tokio::runtime::Handle;
+use tokio::runtime::RuntimeFlavor;
+
+use crate::OpenLimits;
+use crate::SourceData;
+use crate::WheelEditor;
+use crate::WheelError;
+
+/// A `WheelEditor` over async IO
+///
+/// Every editing method of `WheelEditor` is available through `Deref`.
+///
+/// ```no_run
+/// # async fn edit() -> Result<(), editwheel::WheelError> {
+/// use editwheel::AsyncWheelEditor;
+///
+/// let input = tokio::fs::File::open("package-1.0.0-py3-none-any.whl").await?;
+/// let mut editor = AsyncWheelEditor::from_async_reader(input).await?;
+/// editor.set_version("1.0.1");
+///
+/// let output = tokio::fs::File::create("package-1.0.1-py3-none-any.whl").await?;
+/// editor.save_to_async_writer(output).await?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct AsyncWheelEditor {
+    editor: WheelEditor,
+}
+
+impl AsyncWheelEditor {
+    /// Read a whole wheel from `reader`, from its start, and open it
+    ///
+    /// Like `WheelEditor::from_reader`, the wheel is kept in memory, so
+    /// there is no file behind the editor and `save_in_place` fails.
+    pub async fn from_async_reader<R>(reader: R) -> Result<Self, WheelError>
+    where
+        R: AsyncRead + AsyncSeek + Unpin,
+    {
+        Self::from_async_reader_with_limits(reader, OpenLimits::default()).await
+    }
+
+    /// Like `from_async_reader`, but reject archives that exceed `limits`
+    ///
+    /// The limits are checked from the central directory once the wheel is
+    /// in memory, before anything is decompressed (see
+    /// `WheelEditor::open_with_limits`).
+    pub async fn from_async_reader_with_limits<R>(
+        mut reader: R,
+        limits: OpenLimits,
+    ) -> Result<Self, WheelError>
+    where
+        R: AsyncRead + AsyncSeek + Unpin,
+    {
+        let mut data = Vec::new();
+        reader.rewind().await?;
+        reader.read_to_end(&mut data).await?;
+        let editor =
+            WheelEditor::open_source(PathBuf::new(), SourceData::Bytes(data.into()), &limits)?;
+        Ok(Self { editor })
+    }
+
+    /// Save the edited wheel to `writer` and flush it
+    ///
+    /// The wheel is built with `WheelEditor::save_to_writer`, inside
+    /// `tokio::task::block_in_place` on a multi-threaded runtime so that
+    /// rewriting and hashing large members doesn't stall the other tasks;
+    /// it performs the same checks, and nothing is written if they fail.
+    /// Must be called from within a tokio runtime. The editor is only
+    /// borrowed, so dropping this future part way leaves it usable.
+    pub async fn save_to_async_writer<W>(&self, mut writer: W) -> Result<(), WheelError>
+    where
+        W: AsyncWrite + Unpin,
+    {
+        let build = || -> Result<Vec<u8>, WheelError> {
+            let mut buffer = Cursor::new(Vec::new());
+            self.editor.save_to_writer(&mut buffer)?;
+            Ok(buffer.into_inner())
+        };
+        // A current-thread runtime has no other worker to hand its tasks
+        // to, so `block_in_place` would panic there; build inline instead
+        let buffer = match Handle::current().runtime_flavor() {
+            RuntimeFlavor::MultiThread => tokio::task::block_in_place(build)?,
+            _ => build()?,
+        };
+
+        writer.write_all(&buffer).await?;
+        writer.flush().await?;
+        Ok(())
+    }
+
+    /// The synchronous editor underneath
+    pub fn into_inner(self) -> WheelEditor {
+        self.editor
+    }
+}
+
+impl Deref for AsyncWheelEditor {
+    type Target = WheelEditor;
+
+    fn deref(&self) -> &WheelEditor {
+        &self.editor
+    }
+}
+
+impl DerefMut for AsyncWheelEditor {
+    fn deref_mut(&mut self) -> &mut WheelEditor {
+        &mut self.editor
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tempfile::TempDir;
+
+    use super::*;
+    use crate::tests::create_test_wheel;
+
+    #[tokio::test]
+    async fn test_edit_through_async_cursor() {
+        let temp_dir = TempDir::new().unwrap();
+        let wheel_path = create_test_wheel(temp_dir.path());
+        let input = Cursor::new(std::fs::read(&wheel_path).unwrap());
+
+        let mut editor = AsyncWheelEditor::from_async_reader(input).await.unwrap();
+        editor.set_version("2.0.0");
+        let mut output = Cursor::new(Vec::new());
+        editor.save_to_async_writer(&mut output).await.unwrap();
+
+        let saved = WheelEditor::from_bytes(output.get_ref()).unwrap();
+        assert_eq!(saved.version(), "2.0.0");
+        assert!(saved.validate().unwrap().is_valid());
+
+        // Unchanged members come through byte for byte
+        let original = WheelEditor::open(&wheel_path).unwrap();
+        assert_eq!(
+            saved.read_file("test_pkg/__init__.py").unwrap(),
+            original.read_file("test_pkg/__init__.py").unwrap()
+        );
+
+        // The editor is still usable after saving
+        assert_eq!(editor.version(), "2.0.0");
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_save_on_multi_thread_runtime() {
+        let temp_dir = TempDir::new().unwrap();
+        let wheel_path = create_test_wheel(temp_dir.path());
+        let input = Cursor::new(std::fs::read(&wheel_path).unwrap());
+
+        let mut editor = AsyncWheelEditor::from_async_reader(input).await.unwrap();
+        editor.set_version("2.0.0");
+        let mut output = Cursor::new(Vec::new());
+        editor.save_to_async_writer(&mut output).await.unwrap();
+        assert_eq!(
+            WheelEditor::from_bytes(output.get_ref()).unwrap().version(),
+            "2.0.0"
+        );
+
+        // A save that is dropped before it runs leaves the editor usable
+        drop(editor.save_to_async_writer(Cursor::new(Vec::new())));
+        assert_eq!(editor.version(), "2.0.0");
+    }
+
+    #[tokio::test]
+    async fn test_from_async_reader_with_limits() {
+        let temp_dir = TempDir::new().unwrap();
+        let wheel_path = create_test_wheel(temp_dir.path());
+        let data = std::fs::read(&wheel_path).unwrap();
+
+        let limits = OpenLimits {
+            max_entries: Some(1),
+            ..OpenLimits::default()
+        };
+        let result =
+            AsyncWheelEditor::from_async_reader_with_limits(Cursor::new(data), limits).await;
+        assert!(matches!(result, Err(WheelError::LimitExceeded { .. })));
+    }
+}
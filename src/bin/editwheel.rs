@@ -0,0 +1,254 @@
+//! Command-line wheel editor built on the Rust API
+//!
+//! A small counterpart of the Python `editwheel` CLI for environments
+//! without Python: inspect a wheel, validate it, or make one edit and save
+//! the result. Edits are written next to the input under the wheel's new
+//! canonical filename unless `-o/--output` says otherwise. An edit that
+//! leaves the filename unchanged (such as `set-rpath`) needs `-o` or
+//! `--in-place`, so the input is never overwritten by accident.
+//!
+//! Build with:
+//!   cargo build --release --features cli --bin editwheel
+//!
+//! Exit codes: 0 on success, 1 if `validate` finds problems, 2 for bad
+//! usage, 3 if the wheel can't be read, edited or written.
+
+use std::path::Path;
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+use clap::Parser;
+use clap::Subcommand;
+use editwheel::WheelEditor;
+use editwheel::WheelError;
+
+/// `validate` found problems with the wheel
+const EXIT_INVALID: u8 = 1;
+/// The command line was wrong, as for clap's own errors
+const EXIT_USAGE: u8 = 2;
+/// The wheel couldn't be opened, edited or saved
+const EXIT_ERROR: u8 = 3;
+
+#[derive(Parser)]
+#[command(name = "editwheel", version, about = "Inspect and edit Python wheels")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Show the wheel's name, version, tags and main metadata
+    Show { wheel: PathBuf },
+    /// List the wheel's members with their sizes
+    List { wheel: PathBuf },
+    /// Check RECORD hashes and METADATA, exiting with 1 on problems
+    Validate { wheel: PathBuf },
+    /// Set the version
+    SetVersion {
+        version: String,
+        wheel: PathBuf,
+        #[command(flatten)]
+        output: Output,
+    },
+    /// Set the project name
+    SetName {
+        name: String,
+        wheel: PathBuf,
+        #[command(flatten)]
+        output: Output,
+    },
+    /// Set the platform tag, refusing to lower the required libc version
+    SetPlatform {
+        platform: String,
+        wheel: PathBuf,
+        #[command(flatten)]
+        output: Output,
+    },
+    /// Set the RUNPATH of ELF files matching a glob pattern
+    SetRpath {
+        pattern: String,
+        rpath: String,
+        wheel: PathBuf,
+        #[command(flatten)]
+        output: Output,
+    },
+}
+
+#[derive(clap::Args)]
+struct Output {
+    /// Output file, or a directory to save into under the wheel's new
+    /// filename (default: the input's directory)
+    #[arg(short, long)]
+    output: Option<PathBuf>,
+    /// Overwrite the input wheel
+    #[arg(long, conflicts_with = "output")]
+    in_place: bool,
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    match run(cli.command) {
+        Ok(code) => code,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            ExitCode::from(EXIT_ERROR)
+        }
+    }
+}
+
+fn run(command: Command) -> Result<ExitCode, WheelError> {
+    match command {
+        Command::Show { wheel } => show(&WheelEditor::open(wheel)?),
+        Command::List { wheel } => list(&WheelEditor::open(wheel)?)?,
+        Command::Validate { wheel } => return validate(&wheel),
+        Command::SetVersion {
+            version,
+            wheel,
+            output,
+        } => {
+            let mut editor = WheelEditor::open(&wheel)?;
+            let old = editor.version().to_string();
+            editor.set_version(version.as_str());
+            println!("Version: {} -> {}", old, version);
+            return save(&mut editor, &wheel, output);
+        }
+        Command::SetName {
+            name,
+            wheel,
+            output,
+        } => {
+            let mut editor = WheelEditor::open(&wheel)?;
+            let old = editor.name().to_string();
+            editor.set_name(name.as_str());
+            println!("Name: {} -> {}", old, name);
+            return save(&mut editor, &wheel, output);
+        }
+        Command::SetPlatform {
+            platform,
+            wheel,
+            output,
+        } => {
+            let mut editor = WheelEditor::open(&wheel)?;
+            let old = editor.platform_tag().unwrap_or_default().to_string();
+            editor.set_platform_tag_checked(&platform)?;
+            println!("Platform: {} -> {}", old, platform);
+            return save(&mut editor, &wheel, output);
+        }
+        Command::SetRpath {
+            pattern,
+            rpath,
+            wheel,
+            output,
+        } => {
+            let mut editor = WheelEditor::open(&wheel)?;
+            let count = editor.set_rpath(&pattern, &rpath)?;
+            println!("Set RPATH on {} file(s) matching '{}'", count, pattern);
+            for warning in editor.warnings() {
+                eprintln!("Warning: skipped {}: {}", warning.path, warning.error);
+            }
+            return save(&mut editor, &wheel, output);
+        }
+    }
+    Ok(ExitCode::SUCCESS)
+}
+
+fn show(editor: &WheelEditor) {
+    let metadata = editor.metadata();
+    println!("Name: {}", metadata.name);
+    println!("Version: {}", metadata.version);
+    if let Some(summary) = &metadata.summary {
+        println!("Summary: {}", summary);
+    }
+    if let Some(requires_python) = &metadata.requires_python {
+        println!("Requires-Python: {}", requires_python);
+    }
+    for requirement in &metadata.requires_dist {
+        println!("Requires-Dist: {}", requirement);
+    }
+    for tag in &editor.wheel_info().tags {
+        println!("Tag: {}", tag.serialize());
+    }
+    println!("Filename: {}", editor.wheel_filename());
+}
+
+fn list(editor: &WheelEditor) -> Result<(), WheelError> {
+    for member in editor.members()? {
+        if !member.is_dir {
+            println!(
+                "{:>12} {:>12} {}",
+                member.uncompressed_size, member.compressed_size, member.name
+            );
+        }
+    }
+    Ok(())
+}
+
+fn validate(wheel: &Path) -> Result<ExitCode, WheelError> {
+    let editor = WheelEditor::open(wheel)?;
+    let mut errors: Vec<String> = editor
+        .validate()?
+        .errors
+        .iter()
+        .map(ToString::to_string)
+        .collect();
+    if let Err(metadata_errors) = editor.validate_metadata() {
+        errors.extend(metadata_errors);
+    }
+
+    if errors.is_empty() {
+        println!("OK: {} is valid", wheel.display());
+        return Ok(ExitCode::SUCCESS);
+    }
+    eprintln!("FAIL: {} has {} error(s):", wheel.display(), errors.len());
+    for error in &errors {
+        eprintln!("  - {}", error);
+    }
+    Ok(ExitCode::from(EXIT_INVALID))
+}
+
+/// Save to `-o` if given (a directory gets the generated name), over the
+/// input with `--in-place`, otherwise next to the input under the generated
+/// name
+///
+/// A generated name that is the input's own is refused with exit code 2:
+/// overwriting the input has to be asked for.
+fn save(editor: &mut WheelEditor, wheel: &Path, output: Output) -> Result<ExitCode, WheelError> {
+    for path in editor.dropped_signatures()? {
+        eprintln!("Warning: dropping {}: it signs the old RECORD", path);
+    }
+    let saved = match output.output {
+        _ if output.in_place => {
+            editor.save_in_place()?;
+            wheel.to_path_buf()
+        }
+        Some(path) if !path.is_dir() => {
+            editor.save(&path)?;
+            path
+        }
+        dir => {
+            let dir = dir.unwrap_or_else(|| wheel.parent().unwrap_or(Path::new("")).to_path_buf());
+            let path = dir.join(editor.wheel_filename());
+            if same_file(&path, wheel) {
+                eprintln!(
+                    "Error: the output name {} is the input wheel; pass -o to write elsewhere \
+                     or --in-place to overwrite it",
+                    path.display()
+                );
+                return Ok(ExitCode::from(EXIT_USAGE));
+            }
+            editor.save(&path)?;
+            path
+        }
+    };
+    println!("Saved to: {}", saved.display());
+    Ok(ExitCode::SUCCESS)
+}
+
+/// Whether `a` and `b` name the same existing file
+fn same_file(a: &Path, b: &Path) -> bool {
+    match (std::fs::canonicalize(a), std::fs::canonicalize(b)) {
+        (Ok(a), Ok(b)) => a == b,
+        _ => false,
+    }
+}
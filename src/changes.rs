@@ -0,0 +1,151 @@
+//! Change tracking for an audit trail of edits
+
+use crate::metadata::Metadata;
+
+/// A single change made by a `WheelEditor`, as reported by
+/// `WheelEditor::changes`
+///
+/// `field` is a METADATA header name (e.g. `Version`), a WHEEL header name
+/// prefixed with `WHEEL:` (e.g. `WHEEL:Tag`), or `file:<path>` for file
+/// operations. Multi-value fields join their values with newlines. For
+/// files, `old` and `new` are RECORD-style hashes, with `None` for a file
+/// that didn't exist (added) or no longer exists (removed). Both use the
+/// algorithm of the file's RECORD entry, or `sha256=` for a new file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FieldChange {
+    pub field: String,
+    pub old: Option<String>,
+    pub new: Option<String>,
+}
+
+/// Diff two `(name, values)` field lists, appending one change per field
+/// whose values differ
+///
+/// Changes are reported in the order fields appear in `new`, followed by
+/// fields that only exist in `old`.
+pub(crate) fn diff_fields(
+    prefix: &str,
+    old: &[(String, Vec<String>)],
+    new: &[(String, Vec<String>)],
+    changes: &mut Vec<FieldChange>,
+) {
+    let lookup = |fields: &[(String, Vec<String>)], key: &str| {
+        fields
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, values)| values.join("\n"))
+    };
+
+    for (key, _) in new {
+        let (old_value, new_value) = (lookup(old, key), lookup(new, key));
+        if old_value != new_value {
+            changes.push(FieldChange {
+                field: format!("{}{}", prefix, key),
+                old: old_value,
+                new: new_value,
+            });
+        }
+    }
+    for (key, values) in old {
+        if lookup(new, key).is_none() {
+            changes.push(FieldChange {
+                field: format!("{}{}", prefix, key),
+                old: Some(values.join("\n")),
+                new: None,
+            });
+        }
+    }
+}
+
+/// Diff the METADATA of two wheels, skipping the fields named in `ignore`
+///
+/// `ignore` holds header names (e.g. `"Platform"`), matched
+/// case-insensitively. Each change reports `a`'s value as `old` and `b`'s
+/// as `new`, in the same order as `WheelEditor::changes`. An empty result
+/// means the metadata matches.
+pub fn metadata_diff(a: &Metadata, b: &Metadata, ignore: &[&str]) -> Vec<FieldChange> {
+    let kept = |fields: Vec<(String, Vec<String>)>| -> Vec<(String, Vec<String>)> {
+        fields
+            .into_iter()
+            .filter(|(key, _)| !ignore.iter().any(|name| name.eq_ignore_ascii_case(key)))
+            .collect()
+    };
+    let mut changes = Vec::new();
+    diff_fields("", &kept(a.fields()), &kept(b.fields()), &mut changes);
+    changes
+}
+
+/// Whether two METADATA are identical apart from the fields in `ignore`
+///
+/// See `metadata_diff` for what differs when they aren't.
+pub fn metadata_equal_ignoring(a: &Metadata, b: &Metadata, ignore: &[&str]) -> bool {
+    metadata_diff(a, b, ignore).is_empty()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fields(pairs: &[(&str, &[&str])]) -> Vec<(String, Vec<String>)> {
+        pairs
+            .iter()
+            .map(|(k, vs)| (k.to_string(), vs.iter().map(|v| v.to_string()).collect()))
+            .collect()
+    }
+
+    #[test]
+    fn test_diff_fields() {
+        let old = fields(&[("Name", &["pkg"]), ("Summary", &["old"]), ("License", &["MIT"])]);
+        let new = fields(&[
+            ("Name", &["pkg"]),
+            ("Summary", &["new"]),
+            ("Classifier", &["A", "B"]),
+        ]);
+
+        let mut changes = Vec::new();
+        diff_fields("", &old, &new, &mut changes);
+        assert_eq!(
+            changes,
+            vec![
+                FieldChange {
+                    field: "Summary".to_string(),
+                    old: Some("old".to_string()),
+                    new: Some("new".to_string()),
+                },
+                FieldChange {
+                    field: "Classifier".to_string(),
+                    old: None,
+                    new: Some("A\nB".to_string()),
+                },
+                FieldChange {
+                    field: "License".to_string(),
+                    old: Some("MIT".to_string()),
+                    new: None,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_metadata_diff_ignoring_platform() {
+        let linux = Metadata::parse(
+            "Metadata-Version: 2.1\nName: pkg\nVersion: 1.0\nPlatform: linux\nSummary: S\n",
+        )
+        .unwrap();
+        let macos = Metadata::parse(
+            "Metadata-Version: 2.1\nName: pkg\nVersion: 1.0\nPlatform: macosx\nSummary: S\n",
+        )
+        .unwrap();
+
+        assert!(metadata_equal_ignoring(&linux, &macos, &["platform"]));
+        assert_eq!(
+            metadata_diff(&linux, &macos, &[]),
+            vec![FieldChange {
+                field: "Platform".to_string(),
+                old: Some("linux".to_string()),
+                new: Some("macosx".to_string()),
+            }]
+        );
+    }
+}
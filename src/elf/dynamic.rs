@@ -0,0 +1,308 @@
+//! Reading string values out of the dynamic section
+//!
+//! elb's dynamic table only exposes raw `d_val`s, which for string entries
+//! are offsets into the dynamic string table. This resolves them by hand:
+//! find `PT_DYNAMIC`, map `DT_STRTAB`'s address to a file offset through the
+//! `PT_LOAD` segments, and read the NUL-terminated string at each offset.
+//! Entries are removed the same way, since elb can only set them. The
+//! `PT_INTERP` path is read from its segment directly.
+
+use crate::error::ElfError;
+
+use super::strip::Layout;
+use super::strip::span;
+
+const PT_LOAD: u64 = 1;
+const PT_DYNAMIC: u64 = 2;
+const PT_INTERP: u64 = 3;
+
+const DT_NULL: u64 = 0;
+pub(super) const DT_NEEDED: u64 = 1;
+const DT_STRTAB: u64 = 5;
+pub(super) const DT_SONAME: u64 = 14;
+pub(super) const DT_RPATH: u64 = 15;
+pub(super) const DT_RUNPATH: u64 = 29;
+
+/// A program header, reduced to the fields needed for address translation
+struct Segment {
+    kind: u64,
+    offset: u64,
+    vaddr: u64,
+    filesz: u64,
+}
+
+/// The program headers of an ELF file
+fn read_segments(data: &[u8], layout: &Layout) -> Result<Vec<Segment>, ElfError> {
+    // ELF header fields: (e_phoff, e_phentsize) offsets
+    let (phoff_at, phentsize_at) = if layout.is_64 { (32, 54) } else { (28, 42) };
+    let phoff = layout.read_word(data, phoff_at)?;
+    let phentsize = layout.read(data, phentsize_at, 2)? as usize;
+    let phnum = layout.read(data, phentsize_at + 2, 2)? as usize;
+
+    // The fields below must lie inside each header
+    let min_phentsize = if layout.is_64 { 56 } else { 32 };
+    if phnum != 0 && phentsize < min_phentsize {
+        return Err(ElfError::InvalidElf(format!(
+            "program header size {} is smaller than {}",
+            phentsize, min_phentsize
+        )));
+    }
+    let (phoff, _) = span(
+        data,
+        phoff,
+        (phnum * phentsize) as u64,
+        "program header table",
+    )?;
+
+    // Program header fields: (p_offset, p_vaddr, p_filesz) offsets
+    let (offset_at, vaddr_at, filesz_at) = if layout.is_64 {
+        (8, 16, 32)
+    } else {
+        (4, 8, 16)
+    };
+    let mut segments = Vec::with_capacity(phnum);
+    for i in 0..phnum {
+        let header = phoff + i * phentsize;
+        segments.push(Segment {
+            kind: layout.read(data, header, 4)?,
+            offset: layout.read_word(data, header + offset_at)?,
+            vaddr: layout.read_word(data, header + vaddr_at)?,
+            filesz: layout.read_word(data, header + filesz_at)?,
+        });
+    }
+    Ok(segments)
+}
+
+/// Read the dynamic section as `(d_tag, d_val)` pairs up to `DT_NULL`
+///
+/// Returns the file offset of the section and its entries, or `None` for
+/// files without a dynamic section (static executables, object files).
+fn read_dynamic_entries(
+    data: &[u8],
+    layout: &Layout,
+    segments: &[Segment],
+) -> Result<Option<(usize, Vec<(u64, u64)>)>, ElfError> {
+    let Some(dynamic) = segments.iter().find(|s| s.kind == PT_DYNAMIC) else {
+        return Ok(None);
+    };
+
+    let word = layout.word_size();
+    let (start, end) = span(data, dynamic.offset, dynamic.filesz, "dynamic section")?;
+    let mut entries = Vec::new();
+    let mut entry = start;
+    while end - entry >= 2 * word {
+        let tag = layout.read_word(data, entry)?;
+        if tag == DT_NULL {
+            break;
+        }
+        entries.push((tag, layout.read_word(data, entry + word)?));
+        entry += 2 * word;
+    }
+    Ok(Some((start, entries)))
+}
+
+/// Read the string-valued entries (`DT_NEEDED`, `DT_SONAME`, `DT_RPATH`,
+/// `DT_RUNPATH`) of the dynamic section, in table order
+///
+/// Returns an empty list for files without a dynamic section (static
+/// executables, object files).
+pub(super) fn dynamic_strings(data: &[u8]) -> Result<Vec<(u64, String)>, ElfError> {
+    let layout = Layout::detect(data)?;
+    let segments = read_segments(data, &layout)?;
+    let Some((_, dynamic)) = read_dynamic_entries(data, &layout, &segments)? else {
+        return Ok(Vec::new());
+    };
+
+    let strtab_addr = dynamic
+        .iter()
+        .find(|(tag, _)| *tag == DT_STRTAB)
+        .map(|&(_, value)| value);
+    let entries: Vec<(u64, u64)> = dynamic
+        .into_iter()
+        .filter(|(tag, _)| matches!(*tag, DT_NEEDED | DT_SONAME | DT_RPATH | DT_RUNPATH))
+        .collect();
+    if entries.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let strtab_addr = strtab_addr
+        .ok_or_else(|| ElfError::InvalidElf("dynamic section has no DT_STRTAB".to_string()))?;
+    let strtab = segments
+        .iter()
+        .filter(|s| s.kind == PT_LOAD)
+        .find(|s| s.vaddr <= strtab_addr && strtab_addr - s.vaddr < s.filesz)
+        .and_then(|s| (strtab_addr - s.vaddr).checked_add(s.offset))
+        .ok_or_else(|| {
+            ElfError::InvalidElf(format!(
+                "DT_STRTAB address {:#x} is not in any loaded segment",
+                strtab_addr
+            ))
+        })?;
+
+    entries
+        .into_iter()
+        .map(|(tag, value)| {
+            let offset = strtab.checked_add(value).ok_or_else(|| {
+                ElfError::InvalidElf(format!("string offset {:#x} out of bounds", value))
+            })?;
+            Ok((tag, read_string(data, offset)?))
+        })
+        .collect()
+}
+
+/// Read the program interpreter path from `PT_INTERP`
+///
+/// Returns `None` for files without one (shared libraries, static
+/// executables).
+pub(super) fn interpreter(data: &[u8]) -> Result<Option<String>, ElfError> {
+    let layout = Layout::detect(data)?;
+    let segments = read_segments(data, &layout)?;
+    segments
+        .iter()
+        .find(|s| s.kind == PT_INTERP)
+        .map(|s| read_string(data, s.offset))
+        .transpose()
+}
+
+/// Delete every `tag` entry from the dynamic section
+///
+/// Later entries are shifted up and the freed slots become `DT_NULL`, so
+/// the section keeps its size and nothing else in the file moves. The
+/// string the entry pointed at stays in `.dynstr`, unreferenced. Returns
+/// the input unchanged if there is no such entry.
+pub(super) fn remove_dynamic_entries(data: &[u8], tag: u64) -> Result<Vec<u8>, ElfError> {
+    let layout = Layout::detect(data)?;
+    let segments = read_segments(data, &layout)?;
+    let Some((offset, entries)) = read_dynamic_entries(data, &layout, &segments)? else {
+        return Ok(data.to_vec());
+    };
+    if !entries.iter().any(|&(t, _)| t == tag) {
+        return Ok(data.to_vec());
+    }
+
+    let mut output = data.to_vec();
+    let word = layout.word_size();
+    let kept = entries.iter().filter(|&&(t, _)| t != tag);
+    for (i, &(t, value)) in kept.enumerate() {
+        let entry = offset + i * 2 * word;
+        layout.write_word(&mut output, entry, t);
+        layout.write_word(&mut output, entry + word, value);
+    }
+    let removed = entries.iter().filter(|&&(t, _)| t == tag).count();
+    let freed_start = offset + (entries.len() - removed) * 2 * word;
+    let freed_end = offset + entries.len() * 2 * word;
+    output[freed_start..freed_end].fill(0);
+
+    Ok(output)
+}
+
+/// Read the NUL-terminated string starting at `offset`
+fn read_string(data: &[u8], offset: u64) -> Result<String, ElfError> {
+    let bytes = usize::try_from(offset)
+        .ok()
+        .and_then(|start| data.get(start..))
+        .ok_or_else(|| {
+            ElfError::InvalidElf(format!("string offset {:#x} out of bounds", offset))
+        })?;
+    let len = bytes
+        .iter()
+        .position(|&b| b == 0)
+        .ok_or_else(|| ElfError::InvalidElf(format!("unterminated string at {:#x}", offset)))?;
+    Ok(String::from_utf8_lossy(&bytes[..len]).into_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_no_dynamic_section() {
+        // The synthetic debug ELF has a PT_LOAD but no PT_DYNAMIC
+        let elf = crate::elf::synthetic_elf_with_debug();
+        assert!(dynamic_strings(&elf).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_remove_runpath_keeps_other_entries() {
+        let data = std::fs::read(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/tests/fixtures/librpath.so"
+        ))
+        .unwrap();
+        let removed = remove_dynamic_entries(&data, DT_RUNPATH).unwrap();
+        assert_eq!(removed.len(), data.len());
+        assert_eq!(
+            dynamic_strings(&removed).unwrap(),
+            vec![
+                (DT_NEEDED, "libc.so.6".to_string()),
+                (DT_SONAME, "librpath.so.1".to_string())
+            ]
+        );
+
+        // Nothing left to remove
+        assert_eq!(
+            remove_dynamic_entries(&removed, DT_RUNPATH).unwrap(),
+            removed
+        );
+    }
+
+    #[test]
+    fn test_interpreter() {
+        let executable =
+            std::fs::read(concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/hello")).unwrap();
+        let interp = interpreter(&executable).unwrap().unwrap();
+        assert!(interp.contains("ld-"), "{}", interp);
+
+        let library = std::fs::read(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/tests/fixtures/librpath.so"
+        ))
+        .unwrap();
+        assert_eq!(interpreter(&library).unwrap(), None);
+    }
+
+    #[test]
+    fn test_rejects_corrupt_headers() {
+        let data = std::fs::read(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/tests/fixtures/librpath.so"
+        ))
+        .unwrap();
+        let layout = Layout::detect(&data).unwrap();
+        assert!(layout.is_64 && layout.little_endian);
+        let invalid = |data: &[u8]| matches!(dynamic_strings(data), Err(ElfError::InvalidElf(_)));
+
+        let mut corrupt = data.clone();
+        corrupt[32..40].copy_from_slice(&u64::MAX.to_le_bytes()); // e_phoff
+        assert!(invalid(&corrupt));
+
+        let mut corrupt = data.clone();
+        corrupt[54..56].copy_from_slice(&8u16.to_le_bytes()); // e_phentsize
+        assert!(invalid(&corrupt));
+        assert!(interpreter(&corrupt).is_err());
+
+        // p_offset of PT_DYNAMIC
+        let segments = read_segments(&data, &layout).unwrap();
+        let index = segments.iter().position(|s| s.kind == PT_DYNAMIC).unwrap();
+        let header = layout.read_word(&data, 32).unwrap() as usize + index * 56;
+        let mut corrupt = data.clone();
+        layout.write_word(&mut corrupt, header + 8, u64::MAX - 1);
+        assert!(invalid(&corrupt));
+
+        // DT_STRTAB and DT_NEEDED values
+        let (offset, entries) = read_dynamic_entries(&data, &layout, &segments)
+            .unwrap()
+            .unwrap();
+        for tag in [DT_STRTAB, DT_NEEDED] {
+            let index = entries.iter().position(|&(t, _)| t == tag).unwrap();
+            let mut corrupt = data.clone();
+            layout.write_word(&mut corrupt, offset + index * 16 + 8, u64::MAX);
+            assert!(invalid(&corrupt));
+        }
+    }
+
+    #[test]
+    fn test_rejects_non_elf() {
+        assert!(dynamic_strings(b"not an elf file").is_err());
+    }
+}
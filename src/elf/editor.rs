@@ -2,10 +2,11 @@
 //!
 //! This module uses the `elb` crate to parse and modify ELF binaries.
 //! The elb crate is a pure Rust library specifically designed for patching
-//! RPATH, RUNPATH, and interpreter in ELF files.
+//! RPATH, RUNPATH, and interpreter in ELF files. Everything happens in
+//! memory; no temp files are written.
 
 use std::ffi::CString;
-use std::sync::atomic::{AtomicU64, Ordering};
+use std::io::Cursor;
 use std::sync::OnceLock;
 
 use elb::DynamicTag;
@@ -14,12 +15,17 @@ use elb::ElfPatcher;
 
 use crate::error::ElfError;
 
+use super::dynamic::DT_NEEDED;
+use super::dynamic::DT_RPATH;
+use super::dynamic::DT_RUNPATH;
+use super::dynamic::DT_SONAME;
+use super::dynamic::dynamic_strings;
+use super::dynamic::interpreter;
+use super::dynamic::remove_dynamic_entries;
+use super::strip::strip_debug_sections;
 use super::types::ElfInfo;
 use super::types::ElfModification;
 
-// Counter for generating unique temp file names
-static TEMP_FILE_COUNTER: AtomicU64 = AtomicU64::new(0);
-
 /// System page size, detected at runtime.
 /// Falls back to 4096 if detection fails.
 static PAGE_SIZE: OnceLock<u64> = OnceLock::new();
@@ -42,63 +48,40 @@ fn get_page_size() -> u64 {
     })
 }
 
-/// Generate a unique temp file path
-fn temp_elf_path() -> std::path::PathBuf {
-    let counter = TEMP_FILE_COUNTER.fetch_add(1, Ordering::SeqCst);
-    let pid = std::process::id();
-    std::env::temp_dir().join(format!("editwheel_elf_{}_{}.so", pid, counter))
-}
-
 /// Parse an ELF file from bytes and extract information
+///
+/// elb validates the file; the string values are then resolved from the
+/// same bytes by `dynamic_strings`.
 pub fn parse_elf(data: &[u8]) -> Result<ElfInfo, ElfError> {
-    // Write to temp file (elb requires a seekable file)
-    let temp_path = temp_elf_path();
-    std::fs::write(&temp_path, data)
-        .map_err(|e| ElfError::Lief(format!("Failed to write temp file: {}", e)))?;
-
-    let result = parse_elf_from_path(&temp_path);
-
-    // Clean up
-    let _ = std::fs::remove_file(&temp_path);
-
-    result
-}
-
-/// Parse an ELF file from a file path
-fn parse_elf_from_path(path: &std::path::Path) -> Result<ElfInfo, ElfError> {
-    let mut file = std::fs::File::open(path)
-        .map_err(|e| ElfError::Lief(format!("Failed to open file: {}", e)))?;
-
-    let elf = Elf::read(&mut file, get_page_size())
+    Elf::read(&mut Cursor::new(data), get_page_size())
         .map_err(|e| ElfError::InvalidElf(format!("Failed to parse ELF: {}", e)))?;
 
-    let mut info = ElfInfo::default();
-
-    // Extract dynamic entries if present
-    // elb's DynamicTable entries are (DynamicTag, u64) tuples where value is an offset
-    // The elb library doesn't provide a convenient way to read string values from
-    // the dynamic string table directly, so we check for tag presence only.
-    // For a full implementation, we'd need to manually read the string table.
-    if let Ok(Some(dynamic_table)) = elf.read_dynamic_table(&mut file) {
-        for (tag, _value) in dynamic_table.iter() {
-            match tag {
-                DynamicTag::Rpath => {
-                    // We know RPATH exists but can't easily get the value
-                    // Set a placeholder indicating presence
-                    if info.rpath.is_none() {
-                        info.rpath = Some("<rpath-present>".to_string());
-                    }
+    let mut info = ElfInfo {
+        interpreter: interpreter(data)?,
+        ..Default::default()
+    };
+
+    // elb's dynamic table only carries string-table offsets, so the string
+    // values are resolved by `dynamic_strings`
+    for (tag, value) in dynamic_strings(data)? {
+        match tag {
+            DT_RPATH => {
+                if info.rpath.is_none() {
+                    info.rpath = Some(value);
                 }
-                DynamicTag::Runpath => {
-                    if info.runpath.is_none() {
-                        info.runpath = Some("<runpath-present>".to_string());
-                    }
+            }
+            DT_RUNPATH => {
+                if info.runpath.is_none() {
+                    info.runpath = Some(value);
                 }
-                DynamicTag::Needed => {
-                    // Can't read the actual library name easily
+            }
+            DT_NEEDED => info.needed.push(value),
+            DT_SONAME => {
+                if info.soname.is_none() {
+                    info.soname = Some(value);
                 }
-                _ => {}
             }
+            _ => {}
         }
     }
 
@@ -112,76 +95,116 @@ pub fn get_rpath(data: &[u8]) -> Result<Option<String>, ElfError> {
     Ok(info.runpath.or(info.rpath))
 }
 
+/// Get the libraries an ELF file depends on (its `DT_NEEDED` entries)
+pub fn needed_libraries(data: &[u8]) -> Result<Vec<String>, ElfError> {
+    Ok(parse_elf(data)?.needed)
+}
+
 /// Modify an ELF file and return the modified bytes
 ///
-/// This function writes the input data to a temp file, uses elb to modify it,
-/// and reads back the modified bytes.
+/// Dynamic-section edits go through elb (see `patch_dynamic`); removing
+/// RPATH/RUNPATH entries (`remove_dynamic_entries`) and debug stripping
+/// (`strip_debug_sections`), which elb can't do, are applied afterwards in
+/// that order.
 pub fn modify_elf(data: &[u8], modifications: &[ElfModification]) -> Result<Vec<u8>, ElfError> {
-    // Write to temp file (elb requires a file for the patcher)
-    let temp_path = temp_elf_path();
-    std::fs::write(&temp_path, data)
-        .map_err(|e| ElfError::Lief(format!("Failed to write temp file: {}", e)))?;
-
-    // Open for read+write
-    let mut file = std::fs::OpenOptions::new()
-        .read(true)
-        .write(true)
-        .open(&temp_path)
-        .map_err(|e| {
-            let _ = std::fs::remove_file(&temp_path);
-            ElfError::Lief(format!("Failed to open temp file: {}", e))
-        })?;
+    let needs_elb = modifications.iter().any(|m| {
+        matches!(
+            m,
+            ElfModification::SetRpath(_)
+                | ElfModification::SetRunpath(_)
+                | ElfModification::SetSoname(_)
+                | ElfModification::SetInterpreter(_)
+        )
+    });
+    let mut patched = if needs_elb {
+        patch_dynamic(data, modifications)?
+    } else {
+        data.to_vec()
+    };
+
+    for modification in modifications {
+        match modification {
+            ElfModification::RemoveRpath => {
+                patched = remove_dynamic_entries(&patched, DT_RPATH)?;
+            }
+            ElfModification::RemoveRunpath => {
+                patched = remove_dynamic_entries(&patched, DT_RUNPATH)?;
+            }
+            _ => {}
+        }
+    }
+
+    if modifications
+        .iter()
+        .any(|m| matches!(m, ElfModification::StripDebug))
+    {
+        strip_debug_sections(&patched)
+    } else {
+        Ok(patched)
+    }
+}
+
+/// Apply the dynamic-section modifications with elb
+///
+/// The patcher works on an in-memory copy of `data`, which grows as needed
+/// when elb relocates the dynamic string table.
+fn patch_dynamic(data: &[u8], modifications: &[ElfModification]) -> Result<Vec<u8>, ElfError> {
+    let has_interpreter = interpreter(data)?.is_some();
+    let mut buffer = Cursor::new(data.to_vec());
 
     // Parse and create patcher
-    let elf = Elf::read(&mut file, get_page_size()).map_err(|e| {
-        let _ = std::fs::remove_file(&temp_path);
-        ElfError::InvalidElf(format!("Failed to parse ELF: {}", e))
-    })?;
+    let elf = Elf::read(&mut buffer, get_page_size())
+        .map_err(|e| ElfError::InvalidElf(format!("Failed to parse ELF: {}", e)))?;
 
-    let mut patcher = ElfPatcher::new(elf, file);
+    let mut patcher = ElfPatcher::new(elf, &mut buffer);
 
     // Apply modifications
     for modification in modifications {
         match modification {
             ElfModification::SetRpath(rpath) => {
-                let cstring = CString::new(rpath.as_str()).map_err(|e| {
-                    let _ = std::fs::remove_file(&temp_path);
-                    ElfError::Lief(format!("Invalid RPATH string: {}", e))
-                })?;
-                patcher.set_dynamic_tag(DynamicTag::Rpath, cstring.as_c_str()).map_err(|e| {
-                    let _ = std::fs::remove_file(&temp_path);
-                    ElfError::Lief(format!("Failed to set RPATH: {}", e))
-                })?;
+                let cstring = CString::new(rpath.as_str())
+                    .map_err(|e| ElfError::Lief(format!("Invalid RPATH string: {}", e)))?;
+                patcher
+                    .set_dynamic_tag(DynamicTag::Rpath, cstring.as_c_str())
+                    .map_err(|e| ElfError::Lief(format!("Failed to set RPATH: {}", e)))?;
             }
             ElfModification::SetRunpath(runpath) => {
-                let cstring = CString::new(runpath.as_str()).map_err(|e| {
-                    let _ = std::fs::remove_file(&temp_path);
-                    ElfError::Lief(format!("Invalid RUNPATH string: {}", e))
-                })?;
-                patcher.set_dynamic_tag(DynamicTag::Runpath, cstring.as_c_str()).map_err(|e| {
-                    let _ = std::fs::remove_file(&temp_path);
-                    ElfError::Lief(format!("Failed to set RUNPATH: {}", e))
-                })?;
+                let cstring = CString::new(runpath.as_str())
+                    .map_err(|e| ElfError::Lief(format!("Invalid RUNPATH string: {}", e)))?;
+                patcher
+                    .set_dynamic_tag(DynamicTag::Runpath, cstring.as_c_str())
+                    .map_err(|e| ElfError::Lief(format!("Failed to set RUNPATH: {}", e)))?;
             }
+            ElfModification::SetSoname(soname) => {
+                let cstring = CString::new(soname.as_str())
+                    .map_err(|e| ElfError::Lief(format!("Invalid SONAME string: {}", e)))?;
+                patcher
+                    .set_dynamic_tag(DynamicTag::Soname, cstring.as_c_str())
+                    .map_err(|e| ElfError::Lief(format!("Failed to set SONAME: {}", e)))?;
+            }
+            ElfModification::SetInterpreter(interp) => {
+                // Shared libraries have no PT_INTERP to patch
+                if !has_interpreter {
+                    continue;
+                }
+                let cstring = CString::new(interp.as_str())
+                    .map_err(|e| ElfError::Lief(format!("Invalid interpreter string: {}", e)))?;
+                patcher
+                    .set_interpreter(cstring.as_c_str())
+                    .map_err(|e| ElfError::Lief(format!("Failed to set interpreter: {}", e)))?;
+            }
+            ElfModification::RemoveRpath
+            | ElfModification::RemoveRunpath
+            | ElfModification::StripDebug => {}
         }
     }
 
     // Finish patching
-    patcher.finish().map_err(|e| {
-        let _ = std::fs::remove_file(&temp_path);
-        ElfError::Lief(format!("Failed to finish patching: {}", e))
-    })?;
-
-    // Read back the modified bytes
-    let modified_data = std::fs::read(&temp_path).map_err(|e| {
-        let _ = std::fs::remove_file(&temp_path);
-        ElfError::Lief(format!("Failed to read modified ELF: {}", e))
-    })?;
+    patcher
+        .finish()
+        .map_err(|e| ElfError::Lief(format!("Failed to finish patching: {}", e)))?;
 
-    // Clean up temp file
-    let _ = std::fs::remove_file(&temp_path);
-
-    Ok(modified_data)
+    Ok(buffer.into_inner())
 }
 
 #[cfg(test)]
@@ -202,10 +225,115 @@ mod tests {
     }
 
     #[test]
-    #[ignore] // Requires actual ELF binary
     fn test_get_rpath() {
-        let data = std::fs::read("/bin/ls").expect("Failed to read /bin/ls");
+        let data = std::fs::read(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/tests/fixtures/librpath.so"
+        ))
+        .expect("Failed to read fixture");
+        let info = parse_elf(&data).expect("Failed to parse ELF");
+        assert_eq!(info.runpath.as_deref(), Some("$ORIGIN/../lib:$ORIGIN"));
+        assert_eq!(info.rpath, None);
+
         let rpath = get_rpath(&data).expect("Failed to get RPATH");
-        println!("RPATH: {:?}", rpath);
+        assert_eq!(rpath.as_deref(), Some("$ORIGIN/../lib:$ORIGIN"));
+    }
+
+    #[test]
+    fn test_needed_libraries() {
+        let data = std::fs::read(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/tests/fixtures/librpath.so"
+        ))
+        .expect("Failed to read fixture");
+        let needed = needed_libraries(&data).expect("Failed to parse ELF");
+        assert_eq!(needed, vec!["libc.so.6".to_string()]);
+    }
+
+    #[test]
+    fn test_remove_runpath() {
+        let data = std::fs::read(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/tests/fixtures/librpath.so"
+        ))
+        .expect("Failed to read fixture");
+        let modified = modify_elf(
+            &data,
+            &[ElfModification::RemoveRpath, ElfModification::RemoveRunpath],
+        )
+        .expect("Failed to modify ELF");
+        assert_eq!(get_rpath(&modified).unwrap(), None);
+        assert_eq!(needed_libraries(&modified).unwrap(), vec!["libc.so.6"]);
+    }
+
+    #[test]
+    fn test_set_runpath_in_memory() {
+        let data = std::fs::read(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/tests/fixtures/librpath.so"
+        ))
+        .expect("Failed to read fixture");
+        let modified = modify_elf(
+            &data,
+            &[ElfModification::SetRunpath(
+                "$ORIGIN/../torch.libs".to_string(),
+            )],
+        )
+        .expect("Failed to modify ELF");
+        assert_eq!(
+            get_rpath(&modified).unwrap().as_deref(),
+            Some("$ORIGIN/../torch.libs")
+        );
+        assert_eq!(needed_libraries(&modified).unwrap(), vec!["libc.so.6"]);
+    }
+
+    #[test]
+    fn test_set_soname_round_trip() {
+        let data = std::fs::read(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/tests/fixtures/librpath.so"
+        ))
+        .expect("Failed to read fixture");
+        let info = parse_elf(&data).expect("Failed to parse ELF");
+        assert_eq!(info.soname.as_deref(), Some("librpath.so.1"));
+
+        let modified = modify_elf(
+            &data,
+            &[ElfModification::SetSoname(
+                "librpath-3f2a1b.so.1".to_string(),
+            )],
+        )
+        .expect("Failed to modify ELF");
+        let info = parse_elf(&modified).expect("Failed to parse ELF");
+        assert_eq!(info.soname.as_deref(), Some("librpath-3f2a1b.so.1"));
+        assert_eq!(info.runpath.as_deref(), Some("$ORIGIN/../lib:$ORIGIN"));
+        assert_eq!(info.needed, vec!["libc.so.6"]);
+    }
+
+    #[test]
+    fn test_set_interpreter() {
+        let executable =
+            std::fs::read(concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/hello"))
+                .expect("Failed to read fixture");
+        let interp = "/opt/glibc/lib/ld-linux-x86-64.so.2".to_string();
+        let modified = modify_elf(
+            &executable,
+            &[ElfModification::SetInterpreter(interp.clone())],
+        )
+        .expect("Failed to modify ELF");
+        assert_eq!(
+            parse_elf(&modified).unwrap().interpreter,
+            Some(interp.clone())
+        );
+
+        // A shared library has no PT_INTERP and is left alone
+        let library = std::fs::read(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/tests/fixtures/librpath.so"
+        ))
+        .expect("Failed to read fixture");
+        let modified = modify_elf(&library, &[ElfModification::SetInterpreter(interp)])
+            .expect("Failed to modify ELF");
+        assert_eq!(parse_elf(&modified).unwrap().interpreter, None);
     }
 }
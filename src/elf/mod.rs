@@ -2,8 +2,21 @@
 
 mod types;
 mod editor;
+mod dynamic;
+mod rpath;
+mod strip;
 
 pub use editor::get_rpath;
 pub use editor::modify_elf;
+pub use editor::needed_libraries;
+pub use editor::parse_elf;
+pub use rpath::check_rpath_targets;
+pub use strip::strip_debug_sections;
+#[cfg(test)]
+pub(crate) use strip::synthetic_elf_with_debug;
+pub use types::ElfEditReport;
 pub use types::ElfInfo;
 pub use types::ElfModification;
+pub use types::ElfWarning;
+pub use types::RpathResult;
+pub use types::queue_modifications;
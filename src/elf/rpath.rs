@@ -0,0 +1,112 @@
+//! Best-effort resolution of RPATH entries against the members of a wheel
+
+/// Check that each `$ORIGIN`-relative entry of `rpath` points at a directory
+/// that exists in the wheel
+///
+/// `library` is the archive path of the shared library the RPATH is set on;
+/// `$ORIGIN` (or `${ORIGIN}`) resolves to its directory. An entry counts as
+/// existing if at least one of `members` lives under it. Entries that don't
+/// start with `$ORIGIN` (system paths, `$LIB`, ...) can't be resolved inside
+/// the wheel and are skipped.
+///
+/// Returns one warning per entry that resolves to an empty directory or to
+/// somewhere outside the wheel.
+pub fn check_rpath_targets<'a>(
+    library: &str,
+    rpath: &str,
+    members: impl IntoIterator<Item = &'a str> + Clone,
+) -> Vec<String> {
+    let origin = library.rsplit_once('/').map_or("", |(dir, _)| dir);
+    let mut warnings = Vec::new();
+
+    for entry in rpath.split(':').filter(|e| !e.is_empty()) {
+        let Some(rest) = entry
+            .strip_prefix("${ORIGIN}")
+            .or_else(|| entry.strip_prefix("$ORIGIN"))
+        else {
+            continue;
+        };
+        if !rest.is_empty() && !rest.starts_with('/') {
+            // e.g. "$ORIGINAL"; not an $ORIGIN reference
+            continue;
+        }
+
+        let Some(resolved) = resolve(origin, rest) else {
+            warnings.push(format!(
+                "{}: RPATH entry '{}' resolves outside the wheel",
+                library, entry
+            ));
+            continue;
+        };
+        if resolved.is_empty() {
+            continue; // the wheel root always has members
+        }
+
+        let prefix = format!("{}/", resolved);
+        if !members.clone().into_iter().any(|m| m.starts_with(&prefix)) {
+            warnings.push(format!(
+                "{}: RPATH entry '{}' resolves to '{}', which has no members in the wheel",
+                library, entry, resolved
+            ));
+        }
+    }
+
+    warnings
+}
+
+/// Join `rest` onto `origin` and normalize `.` and `..` segments
+///
+/// Returns `None` if the path climbs above the wheel root.
+fn resolve(origin: &str, rest: &str) -> Option<String> {
+    let mut parts: Vec<&str> = origin.split('/').filter(|p| !p.is_empty()).collect();
+    for segment in rest.split('/') {
+        match segment {
+            "" | "." => {}
+            ".." => {
+                parts.pop()?;
+            }
+            segment => parts.push(segment),
+        }
+    }
+    Some(parts.join("/"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MEMBERS: [&str; 3] = [
+        "torch/lib/libtorch.so",
+        "torch.libs/libgomp.so.1",
+        "torch/__init__.py",
+    ];
+
+    #[test]
+    fn test_existing_dirs_have_no_warnings() {
+        let warnings = check_rpath_targets(
+            "torch/lib/libtorch.so",
+            "$ORIGIN:$ORIGIN/../../torch.libs:${ORIGIN}/..:/usr/lib",
+            MEMBERS,
+        );
+        assert!(warnings.is_empty(), "{:?}", warnings);
+    }
+
+    #[test]
+    fn test_missing_dir_warns() {
+        let warnings =
+            check_rpath_targets("torch/lib/libtorch.so", "$ORIGIN/../torch.libs", MEMBERS);
+        assert_eq!(warnings.len(), 1);
+        assert!(
+            warnings[0].contains("'torch/torch.libs'"),
+            "{}",
+            warnings[0]
+        );
+    }
+
+    #[test]
+    fn test_escaping_the_wheel_warns() {
+        let warnings = check_rpath_targets("torch/lib/libtorch.so", "$ORIGIN/../../..", MEMBERS);
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].contains("outside the wheel"));
+    }
+}
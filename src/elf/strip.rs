@@ -0,0 +1,408 @@
+//! Removal of debug sections from ELF files
+//!
+//! elb only patches the dynamic section, so this is a minimal section-table
+//! rewrite that handles the debug-section case and nothing else. Nothing
+//! covered by a program header moves, so the loaded image is untouched.
+
+use crate::error::ElfError;
+
+const SHT_NULL: u32 = 0;
+const SHT_RELA: u32 = 4;
+const SHT_NOBITS: u32 = 8;
+const SHT_REL: u32 = 9;
+const SHF_ALLOC: u64 = 0x2;
+
+/// Byte layout of the header fields we touch, for one ELF class
+pub(super) struct Layout {
+    pub(super) is_64: bool,
+    pub(super) little_endian: bool,
+}
+
+impl Layout {
+    /// Check the ELF magic and read the class and data encoding
+    pub(super) fn detect(data: &[u8]) -> Result<Self, ElfError> {
+        if data.len() < 16 || &data[0..4] != b"\x7FELF" {
+            return Err(ElfError::InvalidElf("missing ELF magic".to_string()));
+        }
+        match (data[4], data[5]) {
+            (1 | 2, 1 | 2) => Ok(Layout {
+                is_64: data[4] == 2,
+                little_endian: data[5] == 1,
+            }),
+            (class, encoding) => Err(ElfError::InvalidElf(format!(
+                "unsupported ELF class {} / data encoding {}",
+                class, encoding
+            ))),
+        }
+    }
+
+    pub(super) fn word_size(&self) -> usize {
+        if self.is_64 { 8 } else { 4 }
+    }
+
+    pub(super) fn read(&self, data: &[u8], offset: usize, size: usize) -> Result<u64, ElfError> {
+        let bytes = offset
+            .checked_add(size)
+            .and_then(|end| data.get(offset..end))
+            .ok_or_else(|| ElfError::InvalidElf(format!("truncated at offset {}", offset)))?;
+        let mut buf = [0u8; 8];
+        if self.little_endian {
+            buf[..size].copy_from_slice(bytes);
+            Ok(u64::from_le_bytes(buf))
+        } else {
+            buf[8 - size..].copy_from_slice(bytes);
+            Ok(u64::from_be_bytes(buf))
+        }
+    }
+
+    fn write(&self, data: &mut [u8], offset: usize, size: usize, value: u64) {
+        let target = &mut data[offset..offset + size];
+        if self.little_endian {
+            target.copy_from_slice(&value.to_le_bytes()[..size]);
+        } else {
+            target.copy_from_slice(&value.to_be_bytes()[8 - size..]);
+        }
+    }
+
+    /// Read an address-sized field (4 bytes on ELF32, 8 on ELF64)
+    pub(super) fn read_word(&self, data: &[u8], offset: usize) -> Result<u64, ElfError> {
+        self.read(data, offset, self.word_size())
+    }
+
+    pub(super) fn write_word(&self, data: &mut [u8], offset: usize, value: u64) {
+        self.write(data, offset, self.word_size(), value)
+    }
+}
+
+/// The section header fields that matter for stripping
+struct Section {
+    name: u32,
+    kind: u32,
+    flags: u64,
+    offset: u64,
+    size: u64,
+    info: u32,
+    align: u64,
+}
+
+/// Byte range `[start, start + len)` of `data`, rejecting overflow and
+/// anything past the end of the file
+pub(super) fn span(
+    data: &[u8],
+    start: u64,
+    len: u64,
+    what: &str,
+) -> Result<(usize, usize), ElfError> {
+    let out_of_bounds = || ElfError::InvalidElf(format!("{} out of bounds", what));
+    let start = usize::try_from(start).map_err(|_| out_of_bounds())?;
+    let len = usize::try_from(len).map_err(|_| out_of_bounds())?;
+    let end = start.checked_add(len).ok_or_else(out_of_bounds)?;
+    if end > data.len() {
+        return Err(out_of_bounds());
+    }
+    Ok((start, end))
+}
+
+/// Field offsets within a section header: (flags, offset, size, info, align)
+fn section_fields(layout: &Layout) -> (usize, usize, usize, usize, usize) {
+    if layout.is_64 {
+        (8, 24, 32, 44, 48)
+    } else {
+        (8, 16, 20, 28, 32)
+    }
+}
+
+/// Remove `.debug_*` and `.zdebug_*` sections from an ELF file
+///
+/// Debug sections that lie after every loaded segment have their bytes
+/// dropped; the remaining non-loaded sections (symbol tables, `.shstrtab`,
+/// ...) and the section header table are packed after the last segment.
+/// Stripped sections, and relocation sections that apply to them, are
+/// turned into `SHT_NULL` entries rather than deleted, so section indices
+/// (referenced from symbol tables and `sh_link`) stay valid.
+///
+/// Returns the input unchanged if there is nothing to strip. Debug
+/// sections that sit in the middle of loaded data can't be removed without
+/// relinking and are left as they are.
+pub fn strip_debug_sections(data: &[u8]) -> Result<Vec<u8>, ElfError> {
+    let layout = Layout::detect(data)?;
+
+    // ELF header fields: (e_phoff, e_shoff, e_phentsize) offsets
+    let (phoff_at, shoff_at, phentsize_at) = if layout.is_64 {
+        (32, 40, 54)
+    } else {
+        (28, 32, 42)
+    };
+    let phoff = layout.read_word(data, phoff_at)?;
+    let shoff = layout.read_word(data, shoff_at)?;
+    let phentsize = layout.read(data, phentsize_at, 2)? as usize;
+    let phnum = layout.read(data, phentsize_at + 2, 2)? as usize;
+    let shentsize = layout.read(data, phentsize_at + 4, 2)? as usize;
+    let shnum = layout.read(data, phentsize_at + 6, 2)? as usize;
+    let shstrndx = layout.read(data, phentsize_at + 8, 2)? as usize;
+
+    // The fixed field offsets below assume the standard header sizes
+    let (expected_phentsize, expected_shentsize) = if layout.is_64 { (56, 64) } else { (32, 40) };
+    if phnum != 0 && phentsize != expected_phentsize {
+        return Err(ElfError::InvalidElf(format!(
+            "unexpected program header size {}",
+            phentsize
+        )));
+    }
+    if shnum != 0 && shentsize != expected_shentsize {
+        return Err(ElfError::InvalidElf(format!(
+            "unexpected section header size {}",
+            shentsize
+        )));
+    }
+    if shnum == 0 || shstrndx >= shnum {
+        return Ok(data.to_vec());
+    }
+
+    // Everything up to the end of the last segment (and the headers) stays put
+    let (p_offset_at, p_filesz_at) = if layout.is_64 { (8, 32) } else { (4, 16) };
+    let (phoff, mut fixed_end) = span(
+        data,
+        phoff,
+        (phnum * phentsize) as u64,
+        "program header table",
+    )?;
+    for i in 0..phnum {
+        let header = phoff + i * phentsize;
+        let offset = layout.read_word(data, header + p_offset_at)?;
+        let filesz = layout.read_word(data, header + p_filesz_at)?;
+        let (_, end) = span(data, offset, filesz, "segment")?;
+        fixed_end = fixed_end.max(end);
+    }
+    let (shoff, shend) = span(
+        data,
+        shoff,
+        (shnum * shentsize) as u64,
+        "section header table",
+    )?;
+    if shoff < fixed_end {
+        return Ok(data.to_vec());
+    }
+
+    let (flags_at, offset_at, size_at, info_at, align_at) = section_fields(&layout);
+    let mut sections = Vec::with_capacity(shnum);
+    for i in 0..shnum {
+        let header = shoff + i * shentsize;
+        sections.push(Section {
+            name: layout.read(data, header, 4)? as u32,
+            kind: layout.read(data, header + 4, 4)? as u32,
+            flags: layout.read_word(data, header + flags_at)?,
+            offset: layout.read_word(data, header + offset_at)?,
+            size: layout.read_word(data, header + size_at)?,
+            info: layout.read(data, header + info_at, 4)? as u32,
+            align: layout.read_word(data, header + align_at)?,
+        });
+    }
+
+    let strtab = &sections[shstrndx];
+    let (strtab_start, strtab_end) = span(data, strtab.offset, strtab.size, "section name table")?;
+    let names = &data[strtab_start..strtab_end];
+    let section_name = |section: &Section| {
+        let start = (section.name as usize).min(names.len());
+        let end = names[start..]
+            .iter()
+            .position(|&b| b == 0)
+            .map_or(names.len(), |p| start + p);
+        &names[start..end]
+    };
+
+    let movable = |section: &Section| {
+        section.kind != SHT_NULL
+            && section.kind != SHT_NOBITS
+            && section.flags & SHF_ALLOC == 0
+            && section.offset as usize >= fixed_end
+    };
+    let mut stripped: Vec<bool> = sections
+        .iter()
+        .map(|s| {
+            let name = section_name(s);
+            movable(s) && (name.starts_with(b".debug") || name.starts_with(b".zdebug"))
+        })
+        .collect();
+    for (i, section) in sections.iter().enumerate() {
+        if (section.kind == SHT_REL || section.kind == SHT_RELA)
+            && movable(section)
+            && stripped.get(section.info as usize) == Some(&true)
+        {
+            stripped[i] = true;
+        }
+    }
+    if !stripped.contains(&true) {
+        return Ok(data.to_vec());
+    }
+
+    // Pack the surviving non-loaded sections after the fixed prefix
+    let mut output = data[..fixed_end].to_vec();
+    let mut order: Vec<usize> = (0..shnum)
+        .filter(|&i| !stripped[i] && movable(&sections[i]))
+        .collect();
+    order.sort_by_key(|&i| sections[i].offset);
+    let mut new_offsets = vec![None; shnum];
+    for i in order {
+        let section = &sections[i];
+        let (start, end) = span(data, section.offset, section.size, "section data")?;
+        let align = usize::try_from(section.align.max(1))
+            .ok()
+            .filter(|&align| align <= data.len())
+            .ok_or_else(|| {
+                ElfError::InvalidElf(format!("section alignment {} too large", section.align))
+            })?;
+        output.resize(output.len().div_ceil(align) * align, 0);
+        new_offsets[i] = Some(output.len() as u64);
+        output.extend_from_slice(&data[start..end]);
+    }
+
+    // Rewrite the section header table
+    let word = layout.word_size();
+    output.resize(output.len().div_ceil(word) * word, 0);
+    let new_shoff = output.len();
+    output.extend_from_slice(&data[shoff..shend]);
+    for (i, &is_stripped) in stripped.iter().enumerate() {
+        let header = new_shoff + i * shentsize;
+        if is_stripped {
+            output[header..header + shentsize].fill(0);
+            layout.write(&mut output, header + 4, 4, SHT_NULL as u64);
+        } else if let Some(offset) = new_offsets[i] {
+            layout.write_word(&mut output, header + offset_at, offset);
+        }
+    }
+    layout.write_word(&mut output, shoff_at, new_shoff as u64);
+
+    Ok(output)
+}
+
+/// Build a minimal little-endian ELF64 file: one PT_LOAD segment covering
+/// `.text`, then `.debug_info` (64 bytes) and `.shstrtab` after it
+#[cfg(test)]
+pub(crate) fn synthetic_elf_with_debug() -> Vec<u8> {
+    let mut elf = vec![0u8; 0x100];
+    elf[0..4].copy_from_slice(b"\x7FELF");
+    elf[4] = 2; // ELFCLASS64
+    elf[5] = 1; // ELFDATA2LSB
+    elf[6] = 1; // EV_CURRENT
+    elf[16..18].copy_from_slice(&3u16.to_le_bytes()); // ET_DYN
+    elf[18..20].copy_from_slice(&62u16.to_le_bytes()); // EM_X86_64
+    elf[32..40].copy_from_slice(&64u64.to_le_bytes()); // e_phoff
+    elf[52..54].copy_from_slice(&64u16.to_le_bytes()); // e_ehsize
+    elf[54..56].copy_from_slice(&56u16.to_le_bytes()); // e_phentsize
+    elf[56..58].copy_from_slice(&1u16.to_le_bytes()); // e_phnum
+    elf[58..60].copy_from_slice(&64u16.to_le_bytes()); // e_shentsize
+    elf[60..62].copy_from_slice(&4u16.to_le_bytes()); // e_shnum
+    elf[62..64].copy_from_slice(&3u16.to_le_bytes()); // e_shstrndx
+
+    // PT_LOAD covering [0, 0x100)
+    elf[64..68].copy_from_slice(&1u32.to_le_bytes());
+    elf[64 + 32..64 + 40].copy_from_slice(&0x100u64.to_le_bytes()); // p_filesz
+    elf[64 + 40..64 + 48].copy_from_slice(&0x100u64.to_le_bytes()); // p_memsz
+
+    // .text contents
+    elf[0xf0..0xf8].copy_from_slice(b"\xc3TEXT\x90\x90\x90");
+
+    // .debug_info at 0x100, .shstrtab right after
+    elf.resize(0x140, 0xdb);
+    let shstrtab = b"\0.text\0.debug_info\0.shstrtab\0";
+    let shstrtab_offset = elf.len();
+    elf.extend_from_slice(shstrtab);
+    elf.resize(elf.len().div_ceil(8) * 8, 0);
+    let shoff = elf.len();
+    elf[40..48].copy_from_slice(&(shoff as u64).to_le_bytes());
+
+    let mut header = |name: u32, kind: u32, flags: u64, offset: u64, size: u64, align: u64| {
+        let mut sh = [0u8; 64];
+        sh[0..4].copy_from_slice(&name.to_le_bytes());
+        sh[4..8].copy_from_slice(&kind.to_le_bytes());
+        sh[8..16].copy_from_slice(&flags.to_le_bytes());
+        sh[24..32].copy_from_slice(&offset.to_le_bytes());
+        sh[32..40].copy_from_slice(&size.to_le_bytes());
+        sh[48..56].copy_from_slice(&align.to_le_bytes());
+        elf.extend_from_slice(&sh);
+    };
+    header(0, SHT_NULL, 0, 0, 0, 0);
+    header(1, 1, SHF_ALLOC | 0x4, 0xf0, 8, 16);
+    header(7, 1, 0, 0x100, 64, 1);
+    header(19, 3, 0, shstrtab_offset as u64, shstrtab.len() as u64, 1);
+    elf
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_strip_removes_debug_section() {
+        let elf = synthetic_elf_with_debug();
+        let stripped = strip_debug_sections(&elf).unwrap();
+        assert!(elf.len() - stripped.len() >= 64);
+
+        // The loaded segment is byte-identical
+        assert_eq!(&stripped[..0x100], &elf[..0x100]);
+
+        let layout = Layout {
+            is_64: true,
+            little_endian: true,
+        };
+        let shoff = layout.read_word(&stripped, 40).unwrap() as usize;
+        let debug = shoff + 2 * 64;
+        assert_eq!(
+            layout.read(&stripped, debug + 4, 4).unwrap(),
+            SHT_NULL as u64
+        );
+
+        // .shstrtab moved down and still holds the names
+        let strtab = shoff + 3 * 64;
+        let offset = layout.read_word(&stripped, strtab + 24).unwrap() as usize;
+        assert_eq!(offset, 0x100);
+        assert_eq!(&stripped[offset + 1..offset + 6], b".text");
+    }
+
+    #[test]
+    fn test_strip_without_debug_is_a_no_op() {
+        let elf = synthetic_elf_with_debug();
+        let stripped = strip_debug_sections(&elf).unwrap();
+        assert_eq!(strip_debug_sections(&stripped).unwrap(), stripped);
+    }
+
+    #[test]
+    fn test_strip_rejects_non_elf() {
+        assert!(strip_debug_sections(b"not an elf file").is_err());
+    }
+
+    #[test]
+    fn test_strip_rejects_bad_header_sizes() {
+        let mut elf = synthetic_elf_with_debug();
+        elf[58..60].copy_from_slice(&8u16.to_le_bytes()); // e_shentsize
+        assert!(matches!(
+            strip_debug_sections(&elf),
+            Err(ElfError::InvalidElf(_))
+        ));
+
+        let mut elf = synthetic_elf_with_debug();
+        elf[54..56].copy_from_slice(&0xffffu16.to_le_bytes()); // e_phentsize
+        assert!(matches!(
+            strip_debug_sections(&elf),
+            Err(ElfError::InvalidElf(_))
+        ));
+    }
+
+    #[test]
+    fn test_strip_rejects_out_of_range_offsets() {
+        let mut elf = synthetic_elf_with_debug();
+        elf[40..48].copy_from_slice(&u64::MAX.to_le_bytes()); // e_shoff
+        assert!(matches!(
+            strip_debug_sections(&elf),
+            Err(ElfError::InvalidElf(_))
+        ));
+
+        let mut elf = synthetic_elf_with_debug();
+        elf[64 + 8..64 + 16].copy_from_slice(&u64::MAX.to_le_bytes()); // p_offset
+        assert!(matches!(
+            strip_debug_sections(&elf),
+            Err(ElfError::InvalidElf(_))
+        ));
+    }
+}
@@ -1,5 +1,7 @@
 //! Types for ELF file information and modifications
 
+use std::collections::BTreeMap;
+
 /// Information extracted from an ELF file
 #[derive(Debug, Clone, Default)]
 pub struct ElfInfo {
@@ -11,6 +13,8 @@ pub struct ElfInfo {
     pub needed: Vec<String>,
     /// SONAME of the library
     pub soname: Option<String>,
+    /// Program interpreter (PT_INTERP), set on dynamically linked executables
+    pub interpreter: Option<String>,
 }
 
 /// Represents a modification to be applied to an ELF file
@@ -20,4 +24,74 @@ pub enum ElfModification {
     SetRpath(String),
     /// Set the RUNPATH (DT_RUNPATH) - preferred over RPATH
     SetRunpath(String),
+    /// Set the SONAME (DT_SONAME)
+    SetSoname(String),
+    /// Set the program interpreter (PT_INTERP); ignored on files without one,
+    /// which `WheelEditor::apply_elf_rules` reports as skipped
+    SetInterpreter(String),
+    /// Delete the RPATH (DT_RPATH) entry
+    RemoveRpath,
+    /// Delete the RUNPATH (DT_RUNPATH) entry
+    RemoveRunpath,
+    /// Remove `.debug_*` sections (see `strip_debug_sections`)
+    StripDebug,
+}
+
+impl ElfModification {
+    /// The entry or part of the file this modification changes; modifications
+    /// with the same target override each other
+    fn target(&self) -> u8 {
+        match self {
+            ElfModification::SetRpath(_) | ElfModification::RemoveRpath => 0,
+            ElfModification::SetRunpath(_) | ElfModification::RemoveRunpath => 1,
+            ElfModification::SetSoname(_) => 2,
+            ElfModification::SetInterpreter(_) => 3,
+            ElfModification::StripDebug => 4,
+        }
+    }
+}
+
+/// Add `modifications` to a file's queue, to be applied in one `modify_elf`
+/// call
+///
+/// `modify_elf` applies sets before removals regardless of list order, so
+/// a queued modification is dropped when a later one has the same target
+/// (e.g. `RemoveRunpath` then `SetRunpath`): the last edit to each entry
+/// wins, as it would if they were applied one call at a time.
+pub fn queue_modifications(queue: &mut Vec<ElfModification>, modifications: &[ElfModification]) {
+    for modification in modifications {
+        queue.retain(|queued| queued.target() != modification.target());
+        queue.push(modification.clone());
+    }
+}
+
+/// Outcome of `WheelEditor::set_rpath_checked`
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RpathResult {
+    /// Number of ELF files whose RPATH was set
+    pub modified: usize,
+    /// `$ORIGIN`-relative entries that point at no members of the wheel
+    pub warnings: Vec<String>,
+}
+
+/// Outcome of `WheelEditor::apply_elf_rules`
+#[derive(Debug, Clone, Default)]
+pub struct ElfEditReport {
+    /// Modifications applied to each file, in rule order
+    pub applied: BTreeMap<String, Vec<ElfModification>>,
+    /// Files a rule matched but couldn't modify, with the error message
+    pub failures: Vec<(String, String)>,
+    /// Modifications left out for a file they don't apply to, such as
+    /// `SetInterpreter` on a shared library without `PT_INTERP`; the rule's
+    /// other modifications still apply
+    pub skipped: Vec<(String, ElfModification)>,
+}
+
+/// An ELF file that an edit skipped because it couldn't be modified
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ElfWarning {
+    /// Archive path of the file
+    pub path: String,
+    /// Why the file couldn't be modified
+    pub error: String,
 }
@@ -0,0 +1,360 @@
+//! Parsing and serialization of the dist-info `entry_points.txt`
+//!
+//! The file is INI-like: `[group]` headers followed by `name = value` lines,
+//! where the value is an object reference such as `pkg.cli:main`. The
+//! well-known groups are `console_scripts` and `gui_scripts`, but any group
+//! name is allowed. Groups that aren't changed are written back exactly as
+//! they were read, comments and spacing included.
+
+use crate::error::EntryPointsError;
+
+/// One `name = value` line of `entry_points.txt`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EntryPoint {
+    pub name: String,
+    /// Object reference, e.g. `pkg.cli:main` or `pkg.cli:main [extra]`
+    pub value: String,
+}
+
+impl EntryPoint {
+    pub fn new(name: impl Into<String>, value: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            value: value.into(),
+        }
+    }
+}
+
+/// A `[group]` section and its entry points
+#[derive(Debug, Clone)]
+pub struct EntryPointGroup {
+    pub name: String,
+    pub entries: Vec<EntryPoint>,
+    /// The section's text as read, header included, with the entries it
+    /// parsed to; `serialize` reuses the text while both still match
+    original: Option<(String, String, Vec<EntryPoint>)>,
+}
+
+impl EntryPointGroup {
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            entries: Vec::new(),
+            original: None,
+        }
+    }
+
+    /// The value of the entry point called `name`
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.entries
+            .iter()
+            .find(|entry| entry.name == name)
+            .map(|entry| entry.value.as_str())
+    }
+
+    /// Whether the group differs from what was parsed (always true for a
+    /// group built with `new`)
+    fn is_modified(&self) -> bool {
+        match &self.original {
+            Some((name, _, entries)) => *name != self.name || *entries != self.entries,
+            None => true,
+        }
+    }
+}
+
+/// Two groups are equal if their names and entries are, however they were
+/// written
+impl PartialEq for EntryPointGroup {
+    fn eq(&self, other: &Self) -> bool {
+        self.name == other.name && self.entries == other.entries
+    }
+}
+
+impl Eq for EntryPointGroup {}
+
+/// Parsed `entry_points.txt`
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct EntryPoints {
+    /// Groups in file order
+    pub groups: Vec<EntryPointGroup>,
+}
+
+impl EntryPoints {
+    /// Parse `entry_points.txt` content
+    ///
+    /// Blank lines and lines starting with `#` or `;` are comments. Any other
+    /// line must be a `[group]` header or a `name = value` entry under one.
+    pub fn parse(content: &str) -> Result<Self, EntryPointsError> {
+        let mut groups: Vec<EntryPointGroup> = Vec::new();
+        // Text of the group being read, and where its last header or entry
+        // line ends: comments after that lead into the next group instead
+        let mut text = String::new();
+        let mut content_end = 0;
+
+        for (index, line) in content.split_inclusive('\n').enumerate() {
+            let trimmed = line.trim();
+            if let Some(header) = trimmed.strip_prefix('[') {
+                let name = header
+                    .strip_suffix(']')
+                    .ok_or_else(|| EntryPointsError::Parse {
+                        line: index + 1,
+                        reason: format!("unterminated group header '{}'", trimmed),
+                    })?;
+                if let Some(group) = groups.last_mut() {
+                    let leading = text.split_off(content_end);
+                    group.original = Some((
+                        group.name.clone(),
+                        std::mem::replace(&mut text, leading),
+                        group.entries.clone(),
+                    ));
+                }
+                groups.push(EntryPointGroup::new(name.trim()));
+                text.push_str(line);
+                content_end = text.len();
+                continue;
+            }
+
+            text.push_str(line);
+            if trimmed.is_empty() || trimmed.starts_with('#') || trimmed.starts_with(';') {
+                continue;
+            }
+            let Some(group) = groups.last_mut() else {
+                return Err(EntryPointsError::Parse {
+                    line: index + 1,
+                    reason: "entry point before the first [group] header".to_string(),
+                });
+            };
+            let (name, value) = trimmed
+                .split_once('=')
+                .ok_or_else(|| EntryPointsError::Parse {
+                    line: index + 1,
+                    reason: format!("expected 'name = value', got '{}'", trimmed),
+                })?;
+            group
+                .entries
+                .push(EntryPoint::new(name.trim(), value.trim()));
+            content_end = text.len();
+        }
+
+        // A file of only comments has no group to keep them with
+        if let Some(group) = groups.last_mut() {
+            group.original = Some((group.name.clone(), text, group.entries.clone()));
+        }
+        Ok(Self { groups })
+    }
+
+    /// Serialize to `entry_points.txt` content
+    ///
+    /// Unchanged groups are written exactly as parsed. Changed and new
+    /// groups are written as `name = value` lines, and a changed group left
+    /// with no entries is dropped.
+    pub fn serialize(&self) -> String {
+        let mut output = String::new();
+        for group in &self.groups {
+            if !group.is_modified() {
+                if let Some((_, text, _)) = &group.original {
+                    if !output.is_empty() && !output.ends_with('\n') {
+                        output.push('\n');
+                    }
+                    output.push_str(text);
+                }
+                continue;
+            }
+            if group.entries.is_empty() {
+                continue;
+            }
+            if !output.is_empty() {
+                if !output.ends_with('\n') {
+                    output.push('\n');
+                }
+                if !output.ends_with("\n\n") {
+                    output.push('\n');
+                }
+            }
+            output.push_str(&format!("[{}]\n", group.name));
+            for entry in &group.entries {
+                output.push_str(&format!("{} = {}\n", entry.name, entry.value));
+            }
+        }
+        output
+    }
+
+    /// The first group called `name`
+    pub fn group(&self, name: &str) -> Option<&EntryPointGroup> {
+        self.groups.iter().find(|group| group.name == name)
+    }
+
+    /// The first group called `name`, appended empty if there is none
+    pub fn group_mut(&mut self, name: &str) -> &mut EntryPointGroup {
+        let index = match self.groups.iter().position(|group| group.name == name) {
+            Some(index) => index,
+            None => {
+                self.groups.push(EntryPointGroup::new(name));
+                self.groups.len() - 1
+            }
+        };
+        &mut self.groups[index]
+    }
+
+    /// Remove every group called `name`, returning whether there was one
+    pub fn remove_group(&mut self, name: &str) -> bool {
+        let before = self.groups.len();
+        self.groups.retain(|group| group.name != name);
+        self.groups.len() != before
+    }
+
+    /// The value of entry point `name` in `group`
+    pub fn get(&self, group: &str, name: &str) -> Option<&str> {
+        self.group(group).and_then(|group| group.get(name))
+    }
+
+    /// Set entry point `name` in `group` to `value`, replacing its value if
+    /// it exists and appending it (and the group) otherwise
+    pub fn set(&mut self, group: &str, name: &str, value: impl Into<String>) {
+        let group = self.group_mut(group);
+        match group.entries.iter_mut().find(|entry| entry.name == name) {
+            Some(entry) => entry.value = value.into(),
+            None => group.entries.push(EntryPoint::new(name, value)),
+        }
+    }
+
+    /// Remove entry point `name` from `group`, returning whether it existed
+    pub fn remove(&mut self, group: &str, name: &str) -> bool {
+        let Some(group) = self.groups.iter_mut().find(|g| g.name == group) else {
+            return false;
+        };
+        let before = group.entries.len();
+        group.entries.retain(|entry| entry.name != name);
+        group.entries.len() != before
+    }
+
+    /// Rename entry point `old` in `group` to `new`, keeping its value and
+    /// position; returns whether `old` existed
+    ///
+    /// Fails with `EntryPointsError::Exists`, changing nothing, if `group`
+    /// already has a different entry point named `new`.
+    pub fn rename(&mut self, group: &str, old: &str, new: &str) -> Result<bool, EntryPointsError> {
+        let Some(group) = self.groups.iter_mut().find(|g| g.name == group) else {
+            return Ok(false);
+        };
+        let Some(index) = group.entries.iter().position(|entry| entry.name == old) else {
+            return Ok(false);
+        };
+        if old != new && group.entries.iter().any(|entry| entry.name == new) {
+            return Err(EntryPointsError::Exists {
+                group: group.name.clone(),
+                name: new.to_string(),
+            });
+        }
+        group.entries[index].name = new.to_string();
+        Ok(true)
+    }
+
+    /// Entry points of the `console_scripts` group
+    pub fn console_scripts(&self) -> &[EntryPoint] {
+        self.group("console_scripts")
+            .map_or(&[], |group| group.entries.as_slice())
+    }
+
+    /// Entry points of the `gui_scripts` group
+    pub fn gui_scripts(&self) -> &[EntryPoint] {
+        self.group("gui_scripts")
+            .map_or(&[], |group| group.entries.as_slice())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONTENT: &str = "\
+[console_scripts]
+old-tool = old_pkg.cli:main
+
+# Plugins are discovered by the host application
+[myapp.plugins]
+fancy=old_pkg.plugins:Fancy [extra]
+";
+
+    #[test]
+    fn test_parse_and_round_trip() {
+        let entry_points = EntryPoints::parse(CONTENT).unwrap();
+        assert_eq!(
+            entry_points.console_scripts(),
+            &[EntryPoint::new("old-tool", "old_pkg.cli:main")]
+        );
+        assert!(entry_points.gui_scripts().is_empty());
+        assert_eq!(
+            entry_points.get("myapp.plugins", "fancy"),
+            Some("old_pkg.plugins:Fancy [extra]")
+        );
+        assert_eq!(entry_points.serialize(), CONTENT);
+    }
+
+    #[test]
+    fn test_modify_keeps_other_groups_verbatim() {
+        let mut entry_points = EntryPoints::parse(CONTENT).unwrap();
+        assert!(
+            entry_points
+                .rename("console_scripts", "old-tool", "new-tool")
+                .unwrap()
+        );
+        entry_points.set("console_scripts", "new-tool", "new_pkg.cli:main");
+        entry_points.set("gui_scripts", "new-gui", "new_pkg.gui:main");
+        assert!(!entry_points.remove("console_scripts", "missing"));
+
+        assert_eq!(
+            entry_points.serialize(),
+            "\
+[console_scripts]
+new-tool = new_pkg.cli:main
+
+# Plugins are discovered by the host application
+[myapp.plugins]
+fancy=old_pkg.plugins:Fancy [extra]
+
+[gui_scripts]
+new-gui = new_pkg.gui:main
+"
+        );
+
+        assert!(entry_points.remove("gui_scripts", "new-gui"));
+        assert!(!entry_points.serialize().contains("gui_scripts"));
+    }
+
+    #[test]
+    fn test_rename_onto_existing_name() {
+        let mut entry_points = EntryPoints::parse(CONTENT).unwrap();
+        entry_points.set("console_scripts", "other-tool", "old_pkg.other:main");
+        let before = entry_points.clone();
+
+        let err = entry_points
+            .rename("console_scripts", "old-tool", "other-tool")
+            .unwrap_err();
+        assert!(matches!(err, EntryPointsError::Exists { .. }));
+        assert_eq!(entry_points, before);
+
+        // Renaming to the same name is a no-op, and a missing group or name
+        // is reported as before
+        assert!(
+            entry_points
+                .rename("console_scripts", "old-tool", "old-tool")
+                .unwrap()
+        );
+        assert!(!entry_points.rename("gui_scripts", "old-tool", "x").unwrap());
+        assert!(
+            !entry_points
+                .rename("console_scripts", "missing", "other-tool")
+                .unwrap()
+        );
+        assert_eq!(entry_points, before);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let err = EntryPoints::parse("tool = pkg:main\n").unwrap_err();
+        assert!(matches!(err, EntryPointsError::Parse { line: 1, .. }));
+        let err = EntryPoints::parse("[console_scripts]\n\ntool pkg:main\n").unwrap_err();
+        assert!(matches!(err, EntryPointsError::Parse { line: 3, .. }));
+    }
+}
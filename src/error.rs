@@ -26,8 +26,14 @@ pub enum WheelError {
     #[error("WHEEL info error: {0}")]
     WheelInfo(#[from] WheelInfoError),
 
+    #[error("Entry points error: {0}")]
+    EntryPoints(#[from] EntryPointsError),
+
     #[error("Glob pattern error: {0}")]
     GlobPattern(#[from] glob::PatternError),
+
+    #[error("Archive exceeds {limit}: {reason}")]
+    LimitExceeded { limit: &'static str, reason: String },
 }
 
 /// Errors related to METADATA parsing
@@ -38,6 +44,28 @@ pub enum MetadataError {
 
     #[error("Parse error: {0}")]
     Parse(String),
+
+    #[error("Invalid {field}: {reason}")]
+    InvalidValue { field: String, reason: String },
+}
+
+/// Errors from parsing a PEP 508 dependency specifier
+#[derive(Error, Debug)]
+pub enum RequirementError {
+    #[error("Invalid name: {0}")]
+    InvalidName(String),
+
+    #[error("Invalid extras: {0}")]
+    InvalidExtras(String),
+
+    #[error("Invalid version specifier: {0}")]
+    InvalidSpecifier(String),
+
+    #[error("Invalid URL: {0}")]
+    InvalidUrl(String),
+
+    #[error("Invalid marker: {0}")]
+    InvalidMarker(String),
 }
 
 /// Errors related to RECORD file
@@ -46,12 +74,21 @@ pub enum RecordError {
     #[error("Invalid CSV: {0}")]
     InvalidCsv(String),
 
+    #[error("Invalid size for {path}: '{value}' is not a non-negative integer")]
+    InvalidSize { path: String, value: String },
+
     #[error("Hash mismatch for {path}: expected {expected}, got {actual}")]
     HashMismatch {
         path: String,
         expected: String,
         actual: String,
     },
+
+    #[error("{path} must be hashed in RECORD, but matches hashless pattern '{pattern}'")]
+    HashRequired { path: String, pattern: String },
+
+    #[error("Signing RECORD failed: {0}")]
+    Signing(String),
 }
 
 /// Errors related to ELF file operations
@@ -79,10 +116,28 @@ pub enum WheelInfoError {
     #[error("Invalid tag format: {0}")]
     InvalidTag(String),
 
+    #[error("Platform tag {new} requires libc {new_version} but {old} requires {old_version}; refusing to claim compatibility with an older libc")]
+    LibcDowngrade {
+        old: String,
+        old_version: String,
+        new: String,
+        new_version: String,
+    },
+
     #[error("Parse error: {0}")]
     Parse(String),
 }
 
+/// Errors from parsing or editing `entry_points.txt`
+#[derive(Error, Debug)]
+pub enum EntryPointsError {
+    #[error("line {line}: {reason}")]
+    Parse { line: usize, reason: String },
+
+    #[error("entry point [{group}] {name} already exists")]
+    Exists { group: String, name: String },
+}
+
 /// Result of validating a wheel
 #[derive(Debug, Default)]
 pub struct ValidationResult {
@@ -96,17 +151,36 @@ impl ValidationResult {
 }
 
 /// Individual validation error
-#[derive(Debug)]
+///
+/// Displays as a single human-readable line, as printed by the CLI.
+#[derive(Error, Debug)]
 pub enum ValidationError {
+    #[error("hash mismatch for {path}: expected {expected}, got {actual}")]
     HashMismatch {
         path: String,
         expected: String,
         actual: String,
     },
-    MissingFile {
-        path: String,
-    },
-    ExtraFile {
-        path: String,
+    #[error("missing file (in RECORD but not in archive): {path}")]
+    MissingFile { path: String },
+    #[error("extra file (in archive but not in RECORD): {path}")]
+    ExtraFile { path: String },
+    /// A RECORD size field is present but doesn't parse as a u64
+    #[error("invalid RECORD size for {path}: '{value}'")]
+    InvalidRecordSize { path: String, value: String },
+    /// A required dist-info file (METADATA or WHEEL) has no hashed RECORD entry
+    #[error("missing RECORD entry with hash for required file: {path}")]
+    MissingRecordEntry { path: String },
+    /// The version in the dist-info directory name disagrees with METADATA
+    #[error(
+        "dist-info directory version {dist_info_version} does not match METADATA version {metadata_version}"
+    )]
+    DistInfoVersionMismatch {
+        dist_info_version: String,
+        metadata_version: String,
     },
+    /// A RECORD hash names an algorithm that can't be checked (see
+    /// `HashAlgorithm`)
+    #[error("unsupported RECORD hash algorithm for {path}: {hash}")]
+    UnsupportedHashAlgorithm { path: String, hash: String },
 }
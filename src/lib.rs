@@ -19,47 +19,113 @@
 //! editor.save("package-1.0.1-py3-none-any.whl").unwrap();
 //! ```
 
+pub mod changes;
 pub mod elf;
+pub mod entry_points;
 pub mod error;
+pub mod lint;
+pub mod members;
 pub mod metadata;
 pub mod name;
+pub mod plan;
 pub mod record;
 pub mod wheel;
 pub mod wheel_info;
 
+#[cfg(feature = "async")]
+mod async_editor;
 #[cfg(feature = "python")]
 mod python;
 
+use std::borrow::Cow;
 use std::collections::HashMap;
 use std::collections::HashSet;
 use std::fs::File;
 use std::io::BufReader;
+use std::io::Cursor;
 use std::io::Read;
+use std::io::Seek;
+use std::io::Write;
+use std::ops::Deref;
+use std::ops::DerefMut;
 use std::path::Path;
 use std::path::PathBuf;
+use std::sync::Mutex;
 
+#[cfg(feature = "async")]
+pub use async_editor::AsyncWheelEditor;
+pub use changes::FieldChange;
+pub use changes::metadata_diff;
+pub use changes::metadata_equal_ignoring;
+pub use elf::ElfEditReport;
 pub use elf::ElfInfo;
 pub use elf::ElfModification;
+pub use elf::ElfWarning;
+pub use elf::RpathResult;
+pub use entry_points::EntryPoint;
+pub use entry_points::EntryPointGroup;
+pub use entry_points::EntryPoints;
 pub use error::ElfError;
+pub use error::EntryPointsError;
 pub use error::MetadataError;
 pub use error::RecordError;
+pub use error::RequirementError;
 pub use error::ValidationError;
 pub use error::ValidationResult;
 pub use error::WheelError;
 pub use error::WheelInfoError;
+pub use lint::Lint;
+pub use lint::Severity;
+pub use members::MemberInfo;
+pub use metadata::Contributor;
 pub use metadata::Metadata;
+pub use metadata::MetadataValue;
+pub use metadata::Requirement;
+pub use metadata::Role;
+pub use metadata::VersionSpecifier;
+pub use metadata::format_address_list;
+pub use metadata::parse_address_list;
+pub use metadata::validate_description_content_type;
+pub use name::WheelFilename;
 pub use name::data_dir_name;
 pub use name::dist_info_name;
+pub use name::dist_info_version;
+pub use name::escape_version;
 pub use name::normalize_dist_info_name;
+pub use name::normalize_project_name;
+pub use plan::RewriteReason;
+pub use plan::SavePlan;
+pub use record::HashAlgorithm;
 pub use record::Record;
 pub use record::RecordEntry;
 pub use record::hash_content;
+pub use record::hash_content_with;
+pub use wheel::DEFLATE_LEVELS;
+pub use wheel::DOS_EPOCH;
+pub use wheel::OpenLimits;
+pub use wheel::RECORD_SIGNATURES;
+pub use wheel::RecordSigner;
 pub use wheel::WheelReader;
+pub use wheel::WriteOptions;
+pub use wheel::check_install_layout;
+pub use wheel::check_limits;
+pub use wheel::check_safe_paths;
+pub use wheel::metadata_differences;
+pub use wheel::metadata_matches;
+pub use wheel::project_record;
+pub use wheel::validate_record_control_files;
 pub use wheel::validate_wheel;
+pub use wheel::validate_wheel_against;
 pub use wheel::write_modified;
 pub use wheel::write_modified_extended;
+pub use wheel::write_modified_with_options;
+pub use wheel_info::BuildTag;
+pub use wheel_info::Libc;
 pub use wheel_info::WheelInfo;
 pub use wheel_info::WheelTag;
+pub use wheel_info::compress_tags;
+pub use wheel_info::platform_libc;
+pub use wheel_info::validate_python_tag;
 
 /// Dot-join unique values from an iterator, preserving first-occurrence order.
 fn dedup_join<'a>(iter: impl Iterator<Item = &'a str>) -> String {
@@ -73,6 +139,169 @@ fn dedup_join<'a>(iter: impl Iterator<Item = &'a str>) -> String {
     parts.join(".")
 }
 
+/// Map `f` over `items`, on rayon's thread pool if the `rayon` feature is
+/// enabled. Results come back in the order of `items` either way.
+fn par_map<T: Send, U: Send>(items: Vec<T>, f: impl Fn(T) -> U + Sync + Send) -> Vec<U> {
+    #[cfg(feature = "rayon")]
+    {
+        use rayon::prelude::*;
+        items.into_par_iter().map(f).collect()
+    }
+    #[cfg(not(feature = "rayon"))]
+    {
+        items.into_iter().map(f).collect()
+    }
+}
+
+/// Size of `content` once Deflate-compressed the way the writer does it
+fn deflated_size(content: &[u8]) -> Result<u64, WheelError> {
+    let mut writer = zip::ZipWriter::new(Cursor::new(Vec::new()));
+    let options = zip::write::SimpleFileOptions::default()
+        .compression_method(zip::CompressionMethod::Deflated);
+    writer.start_file("probe", options)?;
+    writer.write_all(content)?;
+    let mut archive = zip::ZipArchive::new(writer.finish()?)?;
+    let size = archive.by_index_raw(0)?.compressed_size();
+    Ok(size)
+}
+
+/// Fsync the directory containing `path`, so a newly created or renamed
+/// entry in it survives a crash. A no-op on non-Unix platforms, where
+/// directories can't be opened for syncing.
+fn sync_parent_dir(path: &Path) -> Result<(), WheelError> {
+    #[cfg(unix)]
+    {
+        let parent = match path.parent() {
+            Some(parent) if !parent.as_os_str().is_empty() => parent,
+            _ => Path::new("."),
+        };
+        File::open(parent)?.sync_all()?;
+    }
+    #[cfg(not(unix))]
+    let _ = path;
+    Ok(())
+}
+
+/// Object-safe `Read + Seek`, so the source wheel can be read through one
+/// type whether it comes from a file, a mapped slice or memory.
+trait ReadSeek: Read + Seek + Send {}
+
+impl<T: Read + Seek + Send> ReadSeek for T {}
+
+/// The source wheel opened as a ZIP archive
+type SourceArchive = zip::ZipArchive<Box<dyn ReadSeek>>;
+
+/// Where the source wheel's bytes are read from
+enum SourceData {
+    /// Open the file at `WheelEditor::path`
+    File,
+    /// Memory-mapped view of the file (see `WheelEditor::open_mmap`)
+    #[cfg(feature = "mmap")]
+    Mmap(std::sync::Arc<memmap2::Mmap>),
+    /// The whole wheel held in memory (see `WheelEditor::from_bytes`)
+    Bytes(std::sync::Arc<[u8]>),
+}
+
+/// A shared handle to a mapping, so a reader over it can outlive the borrow
+/// of `SourceData`
+#[cfg(feature = "mmap")]
+struct SharedMmap(std::sync::Arc<memmap2::Mmap>);
+
+#[cfg(feature = "mmap")]
+impl AsRef<[u8]> for SharedMmap {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl SourceData {
+    /// Get a fresh reader positioned at the start of the wheel
+    fn reader(&self, path: &Path) -> Result<Box<dyn ReadSeek>, WheelError> {
+        match self {
+            SourceData::File => Ok(Box::new(BufReader::new(File::open(path)?))),
+            #[cfg(feature = "mmap")]
+            SourceData::Mmap(mmap) => Ok(Box::new(Cursor::new(SharedMmap(mmap.clone())))),
+            SourceData::Bytes(bytes) => Ok(Box::new(Cursor::new(bytes.clone()))),
+        }
+    }
+
+    /// Open the wheel as a ZIP archive
+    fn archive(&self, path: &Path) -> Result<SourceArchive, WheelError> {
+        Ok(zip::ZipArchive::new(self.reader(path)?)?)
+    }
+}
+
+/// The source archive, opened on first use and kept for later reads
+///
+/// Opening the wheel means parsing its whole central directory, so doing it
+/// for every member read (e.g. `get_rpath` over hundreds of libraries) made
+/// per-file loops quadratic. `checkout` hands the cached archive out and the
+/// guard puts it back when dropped; a nested checkout while it is out just
+/// opens a second archive.
+#[derive(Default)]
+struct ArchiveCache(Mutex<Option<SourceArchive>>);
+
+impl ArchiveCache {
+    /// Take the cached archive, opening `path` if there is none
+    fn checkout(&self, source: &SourceData, path: &Path) -> Result<CachedArchive<'_>, WheelError> {
+        let cached = self.slot().take();
+        let archive = match cached {
+            Some(archive) => archive,
+            None => source.archive(path)?,
+        };
+        Ok(CachedArchive {
+            cache: self,
+            archive: Some(archive),
+        })
+    }
+
+    /// Close the cached archive, so the next checkout reopens the file
+    fn clear(&self) {
+        self.slot().take();
+    }
+
+    fn slot(&self) -> std::sync::MutexGuard<'_, Option<SourceArchive>> {
+        // The slot holds no invariant a panicking holder could break
+        self.0
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+/// An archive checked out of an `ArchiveCache`, returned to it on drop
+struct CachedArchive<'a> {
+    cache: &'a ArchiveCache,
+    /// Always `Some` until dropped
+    archive: Option<SourceArchive>,
+}
+
+impl Deref for CachedArchive<'_> {
+    type Target = SourceArchive;
+
+    fn deref(&self) -> &SourceArchive {
+        self.archive
+            .as_ref()
+            .expect("archive is present until drop")
+    }
+}
+
+impl DerefMut for CachedArchive<'_> {
+    fn deref_mut(&mut self) -> &mut SourceArchive {
+        self.archive
+            .as_mut()
+            .expect("archive is present until drop")
+    }
+}
+
+impl Drop for CachedArchive<'_> {
+    fn drop(&mut self) {
+        let mut slot = self.cache.slot();
+        if slot.is_none() {
+            *slot = self.archive.take();
+        }
+    }
+}
+
 /// High-level API for editing Python wheel files
 ///
 /// This struct provides a convenient interface for reading, modifying,
@@ -85,41 +314,215 @@ pub struct WheelEditor {
     wheel_info: WheelInfo,
     /// Files that have been modified (path -> new content)
     modified_files: HashMap<String, Vec<u8>>,
+    /// ELF modifications queued per file, applied on top of the file's
+    /// content (from `modified_files` or the source) in one pass at save
+    elf_modifications: HashMap<String, Vec<ElfModification>>,
     /// Files added to the archive (path -> content). Path is the full archive
     /// path; if `dist_info_prefix` is renamed at save time (because name or
     /// version changed), entries whose path begins with the old prefix are
     /// rewritten to the new prefix.
     added_files: HashMap<String, Vec<u8>>,
+    /// Source archive paths left out of the output (and its RECORD) at save
+    removed_files: HashSet<String>,
+    /// Every member name in the source archive's central directory, read at
+    /// open so `add_file` can tell replacements from additions infallibly
+    source_names: HashSet<String>,
     /// Whether the wheel_info has been modified (e.g., platform tag changed)
     wheel_info_modified: bool,
+    /// How the output archive is encoded on save
+    write_options: WriteOptions,
+    /// Where the source wheel's bytes are read from
+    source: SourceData,
+    /// The source archive, kept open between reads
+    archive: ArchiveCache,
+    /// Metadata, WHEEL info and RECORD as read at open, for `changes`
+    original_metadata: Metadata,
+    original_wheel_info: WheelInfo,
+    original_record: Record,
+    /// METADATA fields that may differ from the original at save time, as
+    /// lowercased header names (None allows every field)
+    editable_fields: Option<HashSet<String>>,
+    /// Whether saving fails when `validate_metadata` finds a problem
+    strict_metadata: bool,
+    /// Limits the wheel was opened with, reapplied when `save_in_place`
+    /// reopens it
+    limits: OpenLimits,
+    /// ELF files that `set_rpath` and friends skipped because modifying
+    /// them failed
+    warnings: Vec<ElfWarning>,
 }
 
 impl WheelEditor {
     /// Open a wheel file for editing
     pub fn open(path: impl AsRef<Path>) -> Result<Self, WheelError> {
+        Self::open_source(
+            path.as_ref().to_path_buf(),
+            SourceData::File,
+            &OpenLimits::default(),
+        )
+    }
+
+    /// Open a wheel file for editing, rejecting archives that exceed `limits`
+    ///
+    /// Meant for services handling untrusted uploads: the entry count,
+    /// total declared uncompressed size, and per-entry compression ratio are
+    /// checked from the central directory before anything is decompressed.
+    /// Returns `WheelError::LimitExceeded` naming the limit that was hit.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use editwheel::{OpenLimits, WheelEditor};
+    ///
+    /// let limits = OpenLimits {
+    ///     max_entries: Some(100_000),
+    ///     max_total_uncompressed: Some(10 << 30),
+    ///     max_ratio: Some(200.0),
+    /// };
+    /// let editor = WheelEditor::open_with_limits("upload.whl", limits).unwrap();
+    /// ```
+    pub fn open_with_limits(
+        path: impl AsRef<Path>,
+        limits: OpenLimits,
+    ) -> Result<Self, WheelError> {
+        Self::open_source(path.as_ref().to_path_buf(), SourceData::File, &limits)
+    }
+
+    /// Turn strict RECORD mode on or off for a freshly opened editor
+    ///
+    /// With `strict` set, a source wheel whose RECORD doesn't list every
+    /// member is rejected right away with `WheelError::InvalidWheel` naming
+    /// the unlisted paths, and `save` keeps failing on such members (see
+    /// `set_strict_record`). Without it, the default, those members are
+    /// hashed at save time; `unrecorded_files` lists them.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use editwheel::WheelEditor;
+    ///
+    /// let editor = WheelEditor::open("pkg-1.0-py3-none-any.whl")?.strict(true)?;
+    /// # Ok::<(), editwheel::WheelError>(())
+    /// ```
+    pub fn strict(mut self, strict: bool) -> Result<Self, WheelError> {
+        if strict {
+            let unrecorded = self.unrecorded_files()?;
+            if !unrecorded.is_empty() {
+                return Err(WheelError::InvalidWheel(format!(
+                    "{} member(s) not listed in RECORD: {}",
+                    unrecorded.len(),
+                    unrecorded.join(", ")
+                )));
+            }
+        }
+        self.write_options.strict_record = strict;
+        Ok(self)
+    }
+
+    /// Open a wheel file for editing, reading it through a memory map.
+    ///
+    /// Random access to member data (e.g. `get_rpath` across many libraries)
+    /// is served straight from the page cache instead of seeking a buffered
+    /// file handle, which is faster for read-heavy audits of large wheels.
+    ///
+    /// # Safety considerations
+    ///
+    /// The file must not be modified or truncated by anyone (including this
+    /// process) while the editor is alive: the mapping would observe the
+    /// change, and truncation can crash the process with `SIGBUS`. In
+    /// particular, never `save` to the path the editor was opened from;
+    /// write elsewhere and rename over it instead.
+    #[cfg(feature = "mmap")]
+    pub fn open_mmap(path: impl AsRef<Path>) -> Result<Self, WheelError> {
         let path = path.as_ref().to_path_buf();
         let file = File::open(&path)?;
-        let reader = BufReader::new(file);
-        let mut wheel_reader = WheelReader::new(reader)?;
+        // SAFETY: the caller upholds the no-concurrent-modification contract
+        // documented above; the mapping is read-only.
+        let mmap = unsafe { memmap2::Mmap::map(&file)? };
+        let source = SourceData::Mmap(std::sync::Arc::new(mmap));
+        Self::open_source(path, source, &OpenLimits::default())
+    }
+
+    /// Open a wheel held in memory for editing
+    ///
+    /// The bytes are copied, so the editor doesn't borrow `data`. Everything
+    /// that reads the source (`set_rpath`, `validate`, `save`, ...) reads
+    /// this copy. There is no file behind the editor, so `path` is empty and
+    /// `save_in_place` fails; save with `save` or `save_to_writer` instead.
+    pub fn from_bytes(data: &[u8]) -> Result<Self, WheelError> {
+        Self::open_source(
+            PathBuf::new(),
+            SourceData::Bytes(data.into()),
+            &OpenLimits::default(),
+        )
+    }
+
+    /// Open a wheel from any reader for editing
+    ///
+    /// The reader is read to the end up front and the wheel kept in memory,
+    /// exactly like `from_bytes`.
+    pub fn from_reader<R: Read + Seek>(mut reader: R) -> Result<Self, WheelError> {
+        let mut data = Vec::new();
+        reader.rewind()?;
+        reader.read_to_end(&mut data)?;
+        Self::open_source(
+            PathBuf::new(),
+            SourceData::Bytes(data.into()),
+            &OpenLimits::default(),
+        )
+    }
+
+    fn open_source(
+        path: PathBuf,
+        source: SourceData,
+        limits: &OpenLimits,
+    ) -> Result<Self, WheelError> {
+        let mut wheel_reader = WheelReader::new(source.reader(&path)?)?;
+        wheel_reader.check_limits(limits)?;
 
         let metadata = wheel_reader.read_metadata()?;
         let record = wheel_reader.read_record()?;
         let wheel_info = wheel_reader.read_wheel_info()?;
         let dist_info_prefix = wheel_reader.dist_info_prefix().to_string();
+        let source_names = wheel_reader
+            .archive()
+            .file_names()
+            .map(str::to_string)
+            .collect();
+        drop(wheel_reader);
 
         Ok(Self {
             path,
+            original_metadata: metadata.clone(),
+            original_wheel_info: wheel_info.clone(),
+            original_record: record.clone(),
             metadata,
             record,
             dist_info_prefix,
             wheel_info,
             modified_files: HashMap::new(),
+            elf_modifications: HashMap::new(),
             added_files: HashMap::new(),
+            removed_files: HashSet::new(),
+            source_names,
             wheel_info_modified: false,
+            write_options: WriteOptions::default(),
+            source,
+            archive: ArchiveCache::default(),
+            editable_fields: None,
+            strict_metadata: false,
+            limits: limits.clone(),
+            warnings: Vec::new(),
         })
     }
 
+    /// Get the source wheel as a ZIP archive, reusing the one opened by an
+    /// earlier call
+    fn source_archive(&self) -> Result<CachedArchive<'_>, WheelError> {
+        self.archive.checkout(&self.source, &self.path)
+    }
+
     /// Get the path to the wheel file
+    ///
+    /// Empty for a wheel opened with `from_bytes` or `from_reader`.
     pub fn path(&self) -> &Path {
         &self.path
     }
@@ -133,6 +536,17 @@ impl WheelEditor {
         dist_info_name(&self.metadata.name, &self.metadata.version)
     }
 
+    /// Get the version embedded in the source wheel's dist-info directory name.
+    ///
+    /// This is parsed from the directory found in the archive (e.g.
+    /// `pkg-1.0.0.dist-info` yields `1.0.0`), not from METADATA, so in a
+    /// malformed wheel it can disagree with the METADATA version. `validate`
+    /// reports such a disagreement as
+    /// `ValidationError::DistInfoVersionMismatch`.
+    pub fn dist_info_version(&self) -> Option<&str> {
+        name::dist_info_version(&self.dist_info_prefix)
+    }
+
     /// Add a new file to the archive.
     ///
     /// `path` is the full archive path (e.g.
@@ -140,11 +554,25 @@ impl WheelEditor {
     /// directory is renamed at save time because of a name/version change,
     /// paths under the old prefix are rewritten to the new prefix.
     ///
-    /// Adding a file whose path collides with an existing entry replaces the
-    /// added-file content for that path; collisions with files in the source
-    /// archive are rejected at save time with `WheelError::InvalidWheel`.
+    /// Adding a path that is already in the source archive replaces that
+    /// file, exactly like a modification: later edits such as `set_rpath`
+    /// apply on top of the new content, and earlier ones are discarded.
+    /// Adding a path twice keeps the last content, and adding a path passed
+    /// to `remove_file` brings it back. METADATA, RECORD and WHEEL are
+    /// generated, so adding them is rejected at save time with
+    /// `WheelError::InvalidWheel`.
     pub fn add_file(&mut self, path: impl Into<String>, content: Vec<u8>) {
-        self.added_files.insert(path.into(), content);
+        let path = path.into();
+        self.removed_files.remove(&path);
+        let is_control_file = ["METADATA", "RECORD", "WHEEL"]
+            .iter()
+            .any(|name| path == format!("{}/{}", self.dist_info_prefix, name));
+        if self.source_names.contains(&path) && !is_control_file {
+            self.elf_modifications.remove(&path);
+            self.modified_files.insert(path, content);
+        } else {
+            self.added_files.insert(path, content);
+        }
     }
 
     /// True if any new files have been queued via `add_file`.
@@ -152,13 +580,51 @@ impl WheelEditor {
         !self.added_files.is_empty()
     }
 
+    /// Remove a file from the archive.
+    ///
+    /// `path` is the full archive path, as in the source wheel. The file is
+    /// left out of the output on save, along with its RECORD entry, and any
+    /// pending modifications to it are discarded. Removing a file queued
+    /// with `add_file` simply unqueues it.
+    ///
+    /// METADATA, RECORD and WHEEL are structural and can't be removed;
+    /// trying returns `WheelError::InvalidWheel`.
+    ///
+    /// # Returns
+    /// True if the path was in the wheel, false if there was nothing to
+    /// remove (including a path that was already removed)
+    pub fn remove_file(&mut self, path: &str) -> Result<bool, WheelError> {
+        let new_dist_info = dist_info_name(&self.metadata.name, &self.metadata.version);
+        let is_control_file = ["METADATA", "RECORD", "WHEEL"].iter().any(|name| {
+            path == format!("{}/{}", self.dist_info_prefix, name)
+                || path == format!("{}/{}", new_dist_info, name)
+        });
+        if is_control_file {
+            return Err(WheelError::InvalidWheel(format!(
+                "cannot remove '{}': METADATA, RECORD and WHEEL are required",
+                path
+            )));
+        }
+
+        let was_added = self.added_files.remove(path).is_some();
+        let in_source = self.source_archive()?.index_for_name(path).is_some();
+        if !in_source {
+            return Ok(was_added);
+        }
+
+        self.modified_files.remove(path);
+        self.elf_modifications.remove(path);
+        Ok(self.removed_files.insert(path.to_string()) || was_added)
+    }
+
     /// Compute the PEP 427 wheel filename from current metadata and tags.
     ///
     /// Format: `{name}-{version}(-{build})?-{python}-{abi}-{platform}.whl`
-    /// where each tag component is dot-joined across unique values.
-    pub fn filename(&self) -> String {
+    /// where each tag component is dot-joined across unique values. The name
+    /// is normalized and the version escaped (see `escape_version`).
+    pub fn wheel_filename(&self) -> String {
         let name = normalize_dist_info_name(&self.metadata.name);
-        let version = &self.metadata.version;
+        let version = escape_version(&self.metadata.version);
 
         let python = dedup_join(self.wheel_info.tags.iter().map(|t| t.python.as_str()));
         let abi = dedup_join(self.wheel_info.tags.iter().map(|t| t.abi.as_str()));
@@ -170,6 +636,29 @@ impl WheelEditor {
         }
     }
 
+    /// Same as `wheel_filename`
+    pub fn filename(&self) -> String {
+        self.wheel_filename()
+    }
+
+    /// The dedup key for this wheel's filename (see `WheelFilename::canonical`)
+    pub fn canonical_filename(&self) -> String {
+        self.filename_parts().canonical()
+    }
+
+    /// The filename components for the current metadata and tags
+    fn filename_parts(&self) -> WheelFilename {
+        let tags = &self.wheel_info.tags;
+        WheelFilename {
+            name: self.metadata.name.clone(),
+            version: self.metadata.version.clone(),
+            build: self.wheel_info.build.clone(),
+            python: tags.iter().map(|t| t.python.clone()).collect(),
+            abi: tags.iter().map(|t| t.abi.clone()).collect(),
+            platform: tags.iter().map(|t| t.platform.clone()).collect(),
+        }
+    }
+
     /// Get the package name
     pub fn name(&self) -> &str {
         &self.metadata.name
@@ -210,6 +699,56 @@ impl WheelEditor {
         self.metadata.description = Some(description.into());
     }
 
+    /// Get the description content type (e.g. "text/markdown")
+    pub fn description_content_type(&self) -> Option<&str> {
+        self.metadata.description_content_type.as_deref()
+    }
+
+    /// Set the description content type without validation
+    pub fn set_description_content_type(&mut self, content_type: impl Into<String>) {
+        self.metadata.description_content_type = Some(content_type.into());
+    }
+
+    /// Set the description content type after validating it.
+    ///
+    /// Rejects anything other than `text/plain`, `text/x-rst`, or
+    /// `text/markdown` with optional `charset=UTF-8` and (markdown only)
+    /// `variant=GFM|CommonMark` parameters, so typos like `text/markdwon`
+    /// don't silently make PyPI render the description as plain text.
+    pub fn set_description_content_type_checked(
+        &mut self,
+        content_type: &str,
+    ) -> Result<(), WheelError> {
+        validate_description_content_type(content_type)?;
+        self.metadata.description_content_type = Some(content_type.to_string());
+        Ok(())
+    }
+
+    /// Check that the long description fits within `max` bytes
+    ///
+    /// This is a pre-upload lint for index size limits (e.g. PyPI's), kept
+    /// separate from `validate`. The error names the description's content
+    /// type so the format can be taken into account when trimming it.
+    pub fn check_description_size(&self, max: usize) -> Result<(), WheelError> {
+        let length = self.metadata.description_byte_length();
+        if length <= max {
+            return Ok(());
+        }
+        let format = self
+            .metadata
+            .description_content_type
+            .as_deref()
+            .unwrap_or("unspecified content type");
+        Err(MetadataError::InvalidValue {
+            field: "Description".to_string(),
+            reason: format!(
+                "{} description is {} bytes, exceeding the {} byte limit",
+                format, length, max
+            ),
+        }
+        .into())
+    }
+
     /// Get the package author
     pub fn author(&self) -> Option<&str> {
         self.metadata.author.as_deref()
@@ -230,6 +769,23 @@ impl WheelEditor {
         self.metadata.author_email = Some(email.into());
     }
 
+    /// Get the authors and maintainers as one list (see
+    /// `Metadata::contributors`)
+    pub fn contributors(&self) -> Vec<Contributor> {
+        self.metadata.contributors()
+    }
+
+    /// Parse `Author-email` into `(display name, address)` pairs (see
+    /// `Metadata::parsed_author_emails`)
+    pub fn parsed_author_emails(&self) -> Vec<(Option<String>, String)> {
+        self.metadata.parsed_author_emails()
+    }
+
+    /// Parse `Maintainer-email` into `(display name, address)` pairs
+    pub fn parsed_maintainer_emails(&self) -> Vec<(Option<String>, String)> {
+        self.metadata.parsed_maintainer_emails()
+    }
+
     /// Get the package license
     pub fn license(&self) -> Option<&str> {
         self.metadata.license.as_deref()
@@ -240,6 +796,41 @@ impl WheelEditor {
         self.metadata.license = Some(license.into());
     }
 
+    /// Get the raw Keywords field
+    pub fn keywords(&self) -> Option<&str> {
+        self.metadata.keywords.as_deref()
+    }
+
+    /// Set the raw Keywords field
+    pub fn set_keywords(&mut self, keywords: impl Into<String>) {
+        self.metadata.keywords = Some(keywords.into());
+    }
+
+    /// Get the keywords as a list, splitting on commas or whitespace
+    ///
+    /// See `Metadata::keywords_list`.
+    pub fn keywords_list(&self) -> Vec<String> {
+        self.metadata.keywords_list()
+    }
+
+    /// Set the keywords from a list, written comma-separated
+    pub fn set_keywords_list(&mut self, keywords: &[String]) {
+        self.metadata.set_keywords_list(keywords);
+    }
+
+    /// Append a keyword unless it is already listed, returning whether it
+    /// was added
+    ///
+    /// See `Metadata::add_keyword`.
+    pub fn add_keyword(&mut self, keyword: &str) -> bool {
+        self.metadata.add_keyword(keyword)
+    }
+
+    /// Rewrite space-separated keywords in the comma-separated form
+    pub fn normalize_keywords(&mut self) {
+        self.metadata.normalize_keywords();
+    }
+
     /// Get the Python version requirement
     pub fn requires_python(&self) -> Option<&str> {
         self.metadata.requires_python.as_deref()
@@ -280,6 +871,68 @@ impl WheelEditor {
         self.metadata.requires_dist.push(dep.into());
     }
 
+    /// Get the fields declared `Dynamic` (PEP 643)
+    pub fn dynamic(&self) -> &[String] {
+        &self.metadata.dynamic
+    }
+
+    /// Set the fields declared `Dynamic`
+    pub fn set_dynamic(&mut self, fields: Vec<String>) {
+        self.metadata.dynamic = fields;
+    }
+
+    /// Get the distributions this one renders obsolete (Obsoletes-Dist)
+    pub fn obsoletes_dist(&self) -> &[String] {
+        &self.metadata.obsoletes_dist
+    }
+
+    /// Set the Obsoletes-Dist entries
+    pub fn set_obsoletes_dist(&mut self, entries: Vec<String>) {
+        self.metadata.obsoletes_dist = entries;
+    }
+
+    /// Add an Obsoletes-Dist entry
+    pub fn add_obsoletes_dist(&mut self, entry: impl Into<String>) {
+        self.metadata.obsoletes_dist.push(entry.into());
+    }
+
+    /// Remove the Obsoletes-Dist entries for a project, returning the number
+    /// removed
+    ///
+    /// See `Metadata::remove_obsoletes_dist`.
+    pub fn remove_obsoletes_dist(&mut self, name: &str) -> usize {
+        self.metadata.remove_obsoletes_dist(name)
+    }
+
+    /// Append classifiers from another wheel's metadata that aren't already
+    /// present (exact comparison), returning the number added
+    ///
+    /// See `Metadata::merge_classifiers_from`.
+    pub fn merge_classifiers_from(&mut self, other: &Metadata) -> usize {
+        self.metadata.merge_classifiers_from(other)
+    }
+
+    /// Append Requires-Dist entries from another wheel's metadata that aren't
+    /// already present (compared by normalized name and marker), returning
+    /// the number added
+    ///
+    /// See `Metadata::merge_requires_dist_from`.
+    pub fn merge_requires_dist_from(&mut self, other: &Metadata) -> usize {
+        self.metadata.merge_requires_dist_from(other)
+    }
+
+    /// Rewrite METADATA for an older Metadata-Version (2.1 to 2.4)
+    ///
+    /// Fields the target can't express are dropped or translated (e.g.
+    /// `License-Expression` becomes `License`), and a description of each
+    /// change is returned. See `Metadata::downgrade_metadata_version`.
+    pub fn downgrade_metadata_version(
+        &mut self,
+        target: (u8, u8),
+    ) -> Result<Vec<String>, WheelError> {
+        Ok(self.metadata.downgrade_metadata_version(target)?)
+    }
+
     /// Get the project URLs
     pub fn project_urls(&self) -> &[String] {
         &self.metadata.project_url
@@ -305,6 +958,69 @@ impl WheelEditor {
         &mut self.metadata
     }
 
+    /// Get the exact METADATA bytes `save` will write into the archive
+    ///
+    /// Index tooling can write these as the PEP 658 `.metadata` sidecar
+    /// served next to the wheel, which must byte-match the wheel's METADATA.
+    /// Reflects pending edits.
+    pub fn extract_metadata_bytes(&self) -> Result<Vec<u8>, WheelError> {
+        Ok(self.metadata.serialize().into_bytes())
+    }
+
+    /// Get access to the RECORD used when saving
+    pub fn record(&self) -> &Record {
+        &self.record
+    }
+
+    /// Get mutable access to the RECORD used when saving
+    ///
+    /// Hashes in this RECORD are carried over verbatim for raw-copied files,
+    /// so use `refresh_record_hash` after changing a file's content.
+    pub fn record_mut(&mut self) -> &mut Record {
+        &mut self.record
+    }
+
+    /// Recompute the RECORD hash and size for a single file.
+    ///
+    /// The content is taken from the pending modifications if the file has
+    /// been modified, otherwise from the source archive. The existing RECORD
+    /// entry is updated, or a new one appended if the file has none.
+    /// Returns `WheelError::InvalidWheel` if the file doesn't exist in
+    /// either place.
+    pub fn refresh_record_hash(&mut self, path: &str) -> Result<(), WheelError> {
+        let (hash, size) = match self.pending_content(path)? {
+            Some(content) => (hash_content(&content), content.len() as u64),
+            None => {
+                let mut archive = self.source_archive()?;
+                let mut entry = match archive.by_name(path) {
+                    Ok(entry) => entry,
+                    Err(zip::result::ZipError::FileNotFound) => {
+                        return Err(WheelError::InvalidWheel(format!(
+                            "cannot refresh RECORD hash: '{}' not found in wheel",
+                            path
+                        )));
+                    }
+                    Err(e) => return Err(e.into()),
+                };
+                let size = entry.size();
+                let hash = record::hash_reader(&mut entry)?;
+                (hash, size)
+            }
+        };
+
+        match self.record.find_mut(path) {
+            Some(entry) => {
+                entry.hash = Some(hash);
+                entry.size = Some(size);
+            }
+            None => self
+                .record
+                .entries
+                .push(RecordEntry::new(path.to_string(), Some(hash), Some(size))),
+        }
+        Ok(())
+    }
+
     /// Get access to the wheel info (WHEEL file)
     pub fn wheel_info(&self) -> &WheelInfo {
         &self.wheel_info
@@ -315,6 +1031,172 @@ impl WheelEditor {
         &mut self.wheel_info
     }
 
+    /// Get the WHEEL `Build` field split into number and suffix
+    ///
+    /// `None` if there is no build tag or it doesn't start with a number;
+    /// such a tag is kept verbatim (see `WheelInfo::build`).
+    pub fn build_tag(&self) -> Option<BuildTag> {
+        self.wheel_info.build_tag()
+    }
+
+    /// Set the build number, keeping the suffix of the current build tag
+    ///
+    /// The build tag also appears in `wheel_filename`. A missing or
+    /// unparseable build tag is replaced by the bare number.
+    pub fn set_build_number(&mut self, number: u32) {
+        self.wheel_info.set_build_number(number);
+        self.wheel_info_modified = true;
+    }
+
+    /// Get the WHEEL `Generator` field
+    pub fn generator(&self) -> Option<&str> {
+        self.wheel_info.generator.as_deref()
+    }
+
+    /// Append this library's name and version to the WHEEL `Generator`.
+    ///
+    /// The original generator is preserved, e.g. `bdist_wheel (0.40.0)`
+    /// becomes `bdist_wheel (0.40.0); editwheel-rs 0.3.0`. Stamping twice
+    /// with the same version is a no-op.
+    pub fn stamp_generator(&mut self) {
+        let stamp = concat!("editwheel-rs ", env!("CARGO_PKG_VERSION"));
+        let generator = match self.wheel_info.generator.take() {
+            Some(original) if original.split("; ").any(|part| part == stamp) => original,
+            Some(original) if !original.is_empty() => format!("{}; {}", original, stamp),
+            _ => stamp.to_string(),
+        };
+        self.wheel_info.generator = Some(generator);
+        self.wheel_info_modified = true;
+    }
+
+    /// Replace the WHEEL `Generator` field verbatim
+    pub fn set_generator_exact(&mut self, generator: impl Into<String>) {
+        self.wheel_info.generator = Some(generator.into());
+        self.wheel_info_modified = true;
+    }
+
+    /// Get the output encoding settings used by `save`
+    pub fn write_options(&self) -> &WriteOptions {
+        &self.write_options
+    }
+
+    /// Get mutable access to the output encoding settings
+    pub fn write_options_mut(&mut self) -> &mut WriteOptions {
+        &mut self.write_options
+    }
+
+    /// Write the output for maximum compatibility with very old extractors.
+    ///
+    /// See `WriteOptions::max_compatibility` for exactly what this changes.
+    pub fn set_max_compatibility(&mut self, enabled: bool) {
+        self.write_options.max_compatibility = enabled;
+    }
+
+    /// Make `save` fail if a member is missing from RECORD.
+    ///
+    /// By default such members are silently decompressed and hashed. See
+    /// `WriteOptions::strict_record`.
+    pub fn set_strict_record(&mut self, strict: bool) {
+        self.write_options.strict_record = strict;
+    }
+
+    /// Make `save` fail if `validate_metadata` finds a problem.
+    ///
+    /// Off by default, so metadata is written as given. When on, every save
+    /// checks first, so a wheel whose Metadata-Version predates one of its
+    /// fields (or with malformed dependencies) is never produced.
+    pub fn set_strict_metadata(&mut self, strict: bool) {
+        self.strict_metadata = strict;
+    }
+
+    /// Whether strict metadata mode is on (see `set_strict_metadata`)
+    pub fn strict_metadata(&self) -> bool {
+        self.strict_metadata
+    }
+
+    /// Make `save` check every copied member against its RECORD hash.
+    ///
+    /// Catches a corrupted source wheel instead of reproducing it with a
+    /// RECORD that no longer matches, at the cost of reading the whole
+    /// wheel: saving becomes O(wheel size). See
+    /// `WriteOptions::verify_hashes`.
+    pub fn set_verify_hashes(&mut self, verify: bool) {
+        self.write_options.verify_hashes = verify;
+    }
+
+    /// Sign the RECORD `save` writes with `signer`, or stop signing with
+    /// `None`
+    ///
+    /// The signature is written as `RECORD.jws` in the output's dist-info
+    /// directory. See `WriteOptions::record_signer`.
+    pub fn set_record_signer(&mut self, signer: Option<RecordSigner>) {
+        self.write_options.record_signer = signer;
+    }
+
+    /// Compress every entry `save` generates with `method`, or go back to
+    /// keeping each rewritten member's Stored/Deflate choice with `None`.
+    ///
+    /// See `WriteOptions::compression`.
+    pub fn set_compression(&mut self, method: Option<zip::CompressionMethod>) {
+        self.write_options.compression = method;
+    }
+
+    /// Deflate every entry `save` generates and compresses at `level`
+    /// (within `DEFLATE_LEVELS`, 9 for the smallest output), or at the zip
+    /// crate's default with `None`.
+    ///
+    /// An out-of-range level is rejected here with
+    /// `WheelError::InvalidWheel`. See `WriteOptions::compression_level`.
+    pub fn set_compression_level(&mut self, level: Option<i64>) -> Result<(), WheelError> {
+        wheel::check_compression_level(level)?;
+        self.write_options.compression_level = level;
+        Ok(())
+    }
+
+    /// Stamp every entry `save` generates with a fixed timestamp (seconds
+    /// since the Unix epoch) instead of the current time, or go back to the
+    /// current time with `None`.
+    ///
+    /// Makes saving the same edit twice produce identical bytes. See
+    /// `WriteOptions::source_date_epoch`.
+    pub fn set_source_date_epoch(&mut self, epoch: Option<u64>) {
+        self.write_options.source_date_epoch = epoch;
+    }
+
+    /// Hash rewritten and added files with `algorithm` in the output
+    /// RECORD, instead of SHA-256.
+    ///
+    /// See `WriteOptions::hash_algorithm`.
+    pub fn set_hash_algorithm(&mut self, algorithm: HashAlgorithm) {
+        self.write_options.hash_algorithm = algorithm;
+    }
+
+    /// Write RECORD entries without a hash or size for members matching
+    /// any of `patterns`, replacing previously set patterns.
+    ///
+    /// Mirrors backends that ship generated files (e.g. `*.pyc`) unhashed.
+    /// A pattern that matches the dist-info `METADATA` or `WHEEL` is
+    /// rejected with `RecordError::HashRequired`, since those must always
+    /// be hashed. See `WriteOptions::hashless_patterns`.
+    pub fn set_hashless_patterns(&mut self, patterns: &[&str]) -> Result<(), WheelError> {
+        let patterns = patterns
+            .iter()
+            .map(|pattern| glob::Pattern::new(pattern))
+            .collect::<Result<Vec<_>, _>>()?;
+        for file in ["METADATA", "WHEEL"] {
+            let path = format!("{}/{}", self.dist_info_prefix, file);
+            if let Some(pattern) = patterns.iter().find(|pattern| pattern.matches(&path)) {
+                return Err(RecordError::HashRequired {
+                    path,
+                    pattern: pattern.to_string(),
+                }
+                .into());
+            }
+        }
+        self.write_options.hashless_patterns = patterns;
+        Ok(())
+    }
+
     /// Get the primary python tag (e.g., "cp312", "py3")
     pub fn python_tag(&self) -> Option<&str> {
         self.wheel_info.python()
@@ -326,6 +1208,18 @@ impl WheelEditor {
         self.wheel_info_modified = true;
     }
 
+    /// Set the Python tag for all tags after checking it is plausible
+    ///
+    /// Rewrites the Python component of every WHEEL `Tag` line, which also
+    /// changes `wheel_filename`. The tag must look like `py3`, `cp311`, etc.
+    /// (see `validate_python_tag`); otherwise nothing changes and
+    /// `WheelInfoError::InvalidTag` is returned.
+    pub fn set_python_tag_checked(&mut self, python: &str) -> Result<(), WheelError> {
+        validate_python_tag(python)?;
+        self.set_python_tag(python);
+        Ok(())
+    }
+
     /// Get the primary ABI tag (e.g., "cp312", "none")
     pub fn abi_tag(&self) -> Option<&str> {
         self.wheel_info.abi()
@@ -351,26 +1245,193 @@ impl WheelEditor {
         self.wheel_info_modified = true;
     }
 
-    /// Get the RPATH of a specific file in the wheel
+    /// Set any of the python, ABI and platform components of every tag in
+    /// one go
     ///
-    /// Returns the effective RPATH (prefers RUNPATH over RPATH).
-    /// Returns an error if the file is not found or is not a valid ELF.
-    pub fn get_rpath(&self, path: &str) -> Result<Option<String>, WheelError> {
-        let file = File::open(&self.path)?;
-        let reader = BufReader::new(file);
-        let mut archive = zip::ZipArchive::new(reader)?;
-
-        let mut entry = archive
-            .by_name(path)
-            .map_err(|_| WheelError::Elf(error::ElfError::FileNotFound(path.to_string())))?;
-
-        let mut content = Vec::new();
-        entry.read_to_end(&mut content)?;
+    /// `None` leaves that component alone, so
+    /// `retag(None, None, Some("manylinux_2_28_x86_64"))` only changes the
+    /// platform. Tags that become identical are collapsed, and
+    /// `wheel_filename` gives the matching output name. The python tag must
+    /// pass `validate_python_tag`, and the ABI and platform must be non-empty
+    /// with no `-` or whitespace; otherwise nothing changes and
+    /// `WheelInfoError::InvalidTag` is returned. Nothing here checks that the
+    /// wheel's binaries are actually compatible with the new tags; use
+    /// `set_platform_tag_checked` to guard against a libc downgrade.
+    pub fn retag(
+        &mut self,
+        python: Option<&str>,
+        abi: Option<&str>,
+        platform: Option<&str>,
+    ) -> Result<(), WheelError> {
+        if let Some(python) = python {
+            validate_python_tag(python)?;
+        }
+        if let Some(abi) = abi {
+            wheel_info::validate_tag_component("ABI", abi)?;
+        }
+        if let Some(platform) = platform {
+            wheel_info::validate_tag_component("platform", platform)?;
+        }
 
-        elf::get_rpath(&content).map_err(WheelError::from)
+        if let Some(python) = python {
+            self.wheel_info.set_python(python);
+        }
+        if let Some(abi) = abi {
+            self.wheel_info.set_abi(abi);
+        }
+        if let Some(platform) = platform {
+            self.wheel_info.set_platform(platform);
+        }
+        self.wheel_info.dedup_tags();
+        self.wheel_info_modified = true;
+        Ok(())
     }
 
-    /// Set the RPATH for files matching a glob pattern
+    /// Collapse exact duplicate WHEEL `Tag` lines, keeping first-occurrence
+    /// order
+    ///
+    /// Returns the number of tags removed. The WHEEL file is only rewritten
+    /// on save if something was removed.
+    pub fn dedup_tags(&mut self) -> usize {
+        let removed = self.wheel_info.dedup_tags();
+        if removed > 0 {
+            self.wheel_info_modified = true;
+        }
+        removed
+    }
+
+    /// Add a WHEEL `Tag` line, e.g. `py2-none-any` to make a `py3` wheel
+    /// universal
+    ///
+    /// The tag also appears in `wheel_filename`. Returns `false`, changing
+    /// nothing, if the wheel already has this tag.
+    pub fn add_tag(&mut self, tag: WheelTag) -> bool {
+        let added = self.wheel_info.add_tag(tag);
+        if added {
+            self.wheel_info_modified = true;
+        }
+        added
+    }
+
+    /// Remove a WHEEL `Tag` line
+    ///
+    /// Returns `false` if the wheel doesn't have this tag. Removing the last
+    /// tag is allowed here, but saving then fails with
+    /// `WheelInfoError::MissingField`, since a wheel needs at least one.
+    pub fn remove_tag(&mut self, tag: &WheelTag) -> bool {
+        let removed = self.wheel_info.remove_tag(tag);
+        if removed {
+            self.wheel_info_modified = true;
+        }
+        removed
+    }
+
+    /// Set the platform tag, refusing an empty tag or a lower required libc
+    /// version
+    ///
+    /// An empty platform is rejected with `WheelInfoError::InvalidTag`.
+    /// If both the current and new platforms are manylinux (or both
+    /// musllinux) and the new one requires an older libc, the tag is left
+    /// unchanged and `WheelInfoError::LibcDowngrade` is returned: the bundled
+    /// binaries were built against the current version, so claiming an
+    /// older one would be a false compatibility promise.
+    pub fn set_platform_tag_checked(&mut self, platform: &str) -> Result<(), WheelError> {
+        if platform.is_empty() {
+            return Err(
+                WheelInfoError::InvalidTag("platform tag cannot be empty".to_string()).into(),
+            );
+        }
+        if let Some(current) = self.wheel_info.platform() {
+            if let (Some((old_libc, old_version)), Some((new_libc, new_version))) =
+                (platform_libc(current), platform_libc(platform))
+            {
+                if old_libc == new_libc && new_version < old_version {
+                    return Err(WheelInfoError::LibcDowngrade {
+                        old: current.to_string(),
+                        old_version: format!("{}.{}", old_version.0, old_version.1),
+                        new: platform.to_string(),
+                        new_version: format!("{}.{}", new_version.0, new_version.1),
+                    }
+                    .into());
+                }
+            }
+        }
+        self.set_platform_tag(platform);
+        Ok(())
+    }
+
+    /// Get the libc `(major, minor)` version required by the primary
+    /// platform tag, if it is a manylinux or musllinux tag
+    pub fn libc_version(&self) -> Option<(u32, u32)> {
+        self.wheel_info.tags.first().and_then(WheelTag::libc_version)
+    }
+
+    /// Get the RPATH of a specific file in the wheel
+    ///
+    /// Returns the effective RPATH (prefers RUNPATH over RPATH).
+    /// Returns an error if the file is not found or is not a valid ELF.
+    pub fn get_rpath(&self, path: &str) -> Result<Option<String>, WheelError> {
+        let content = self.read_elf_member(path)?;
+        elf::get_rpath(&content).map_err(WheelError::from)
+    }
+
+    /// Get everything `parse_elf` reports about a specific file in the wheel
+    ///
+    /// The member is read and parsed once, so this is cheaper than calling
+    /// `get_rpath` and `needed_libraries` separately. Like them, it inspects
+    /// the source wheel, not pending edits. A member that isn't an ELF file
+    /// gives `ElfError::InvalidElf`; a missing one `ElfError::FileNotFound`.
+    pub fn elf_info(&self, path: &str) -> Result<ElfInfo, WheelError> {
+        let content = self.read_elf_member(path)?;
+        if !content.starts_with(b"\x7FELF") {
+            return Err(
+                error::ElfError::InvalidElf(format!("'{}' is not an ELF file", path)).into(),
+            );
+        }
+        elf::parse_elf(&content).map_err(WheelError::from)
+    }
+
+    /// Get the libraries a specific file in the wheel links against
+    ///
+    /// Returns the `DT_NEEDED` entries (e.g. `libc.so.6`) in the order the
+    /// dynamic linker loads them. Returns an error if the file is not found
+    /// or is not a valid ELF.
+    pub fn needed_libraries(&self, path: &str) -> Result<Vec<String>, WheelError> {
+        let content = self.read_elf_member(path)?;
+        elf::needed_libraries(&content).map_err(WheelError::from)
+    }
+
+    /// Read a member of the source wheel for ELF inspection
+    ///
+    /// Only a member that is genuinely absent is reported as
+    /// `ElfError::FileNotFound`; other ZIP failures (unsupported compression,
+    /// corrupt data, CRC mismatch) come back as `WheelError::Zip` naming the
+    /// member.
+    fn read_elf_member(&self, path: &str) -> Result<Vec<u8>, WheelError> {
+        let mut archive = self.source_archive()?;
+
+        let with_path = |e: zip::result::ZipError| {
+            WheelError::Zip(zip::result::ZipError::Io(std::io::Error::other(format!(
+                "reading '{}': {}",
+                path, e
+            ))))
+        };
+        let mut entry = match archive.by_name(path) {
+            Ok(entry) => entry,
+            Err(zip::result::ZipError::FileNotFound) => {
+                return Err(error::ElfError::FileNotFound(path.to_string()).into());
+            }
+            Err(e) => return Err(with_path(e)),
+        };
+
+        let mut content = Vec::new();
+        entry
+            .read_to_end(&mut content)
+            .map_err(|e| with_path(zip::result::ZipError::Io(e)))?;
+        Ok(content)
+    }
+
+    /// Set the RPATH for files matching a glob pattern
     ///
     /// This modifies all ELF files in the wheel that match the given glob pattern.
     /// Returns the number of files modified.
@@ -384,471 +1445,4954 @@ impl WheelEditor {
     /// println!("Modified {} files", count);
     /// ```
     pub fn set_rpath(&mut self, pattern: &str, rpath: &str) -> Result<usize, WheelError> {
+        Ok(self.apply_rpath(pattern, rpath)?.len())
+    }
+
+    /// Set the RPATH like `set_rpath`, then sanity-check where it points
+    ///
+    /// Every `$ORIGIN`-relative entry is resolved against each modified
+    /// library's directory, and the result carries a warning for each entry
+    /// whose directory has no members in the wheel (including pending
+    /// `add_file` additions) or that climbs out of it. This is best-effort:
+    /// system paths can't be checked and are skipped. Warnings don't stop
+    /// the edit.
+    pub fn set_rpath_checked(
+        &mut self,
+        pattern: &str,
+        rpath: &str,
+    ) -> Result<RpathResult, WheelError> {
+        let modified = self.apply_rpath(pattern, rpath)?;
+
+        let mut members = self.file_names()?;
+        members.extend(self.added_files.keys().cloned());
+        let warnings = modified
+            .iter()
+            .flat_map(|library| {
+                elf::check_rpath_targets(library, rpath, members.iter().map(String::as_str))
+            })
+            .collect();
+
+        Ok(RpathResult {
+            modified: modified.len(),
+            warnings,
+        })
+    }
+
+    /// Remove the RPATH and RUNPATH from files matching a glob pattern
+    ///
+    /// The dynamic entries are deleted outright rather than set to an empty
+    /// string (which would leave an empty `DT_RUNPATH` behind), e.g. to drop
+    /// absolute build-sandbox paths. Returns the number of ELF files
+    /// modified, like `set_rpath`.
+    pub fn remove_rpath(&mut self, pattern: &str) -> Result<usize, WheelError> {
+        let modifications = [ElfModification::RemoveRpath, ElfModification::RemoveRunpath];
+        Ok(self.apply_elf_modifications(pattern, &modifications)?.len())
+    }
+
+    /// Set the SONAME of ELF files matching a glob pattern
+    ///
+    /// Use this when vendoring a private copy of a library, so it can't
+    /// clash with a system library of the same SONAME at load time. Other
+    /// bundled libraries that list the old name in `DT_NEEDED` are not
+    /// updated. Returns the number of files modified.
+    pub fn set_soname(&mut self, pattern: &str, soname: &str) -> Result<usize, WheelError> {
+        let modifications = [ElfModification::SetSoname(soname.to_string())];
+        Ok(self.apply_elf_modifications(pattern, &modifications)?.len())
+    }
+
+    /// Set the program interpreter of ELF executables matching a glob pattern
+    ///
+    /// Meant for entry points (e.g. under `.data/scripts`) that should run
+    /// against a relocated glibc. Files without a `PT_INTERP` segment, such
+    /// as shared libraries, and non-ELF files are skipped. Returns the number
+    /// of files modified.
+    pub fn set_interpreter(
+        &mut self,
+        pattern: &str,
+        interpreter: &str,
+    ) -> Result<usize, WheelError> {
+        let modifications = [ElfModification::SetInterpreter(interpreter.to_string())];
+        let mut count = 0;
+        let results = self.try_apply_elf_modifications(pattern, &modifications, |content| {
+            Ok(elf::parse_elf(content)?.interpreter.is_some())
+        })?;
+        for (path, result) in results {
+            match result {
+                Ok(true) => count += 1,
+                Ok(false) => {}
+                Err(e) => self.push_warning(ElfWarning {
+                    path,
+                    error: e.to_string(),
+                }),
+            }
+        }
+        Ok(count)
+    }
+
+    /// Set the RUNPATH on every ELF file matching `pattern`, returning the
+    /// paths that were modified
+    fn apply_rpath(&mut self, pattern: &str, rpath: &str) -> Result<Vec<String>, WheelError> {
+        // Use RUNPATH (preferred over RPATH)
+        self.apply_elf_modifications(pattern, &[ElfModification::SetRunpath(rpath.to_string())])
+    }
+
+    /// Apply ELF modification rules in order, as a declarative recipe
+    ///
+    /// Each rule is a glob pattern and the modifications to apply to the
+    /// ELF files it matches. Rules compose: a file matched by several rules
+    /// gets each rule's modifications on top of the previous ones (and on
+    /// top of earlier `set_rpath`/`strip_debug` calls). Non-ELF files are
+    /// skipped. A file that fails to modify is reported and keeps its
+    /// content from before the failing rule; the remaining rules still run.
+    /// `SetInterpreter` only applies to files with a `PT_INTERP` segment:
+    /// on anything else, such as a shared library, it is reported in
+    /// `ElfEditReport::skipped` instead, and only the rule's other
+    /// modifications are queued.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use editwheel::{ElfModification, WheelEditor};
+    ///
+    /// let mut editor = WheelEditor::open("torch-2.0.0-cp311-cp311-linux_x86_64.whl").unwrap();
+    /// let report = editor
+    ///     .apply_elf_rules(&[
+    ///         ("torch/lib/*.so".to_string(), vec![ElfModification::RemoveRpath]),
+    ///         (
+    ///             "torch/lib/*.so".to_string(),
+    ///             vec![ElfModification::SetRunpath("$ORIGIN".to_string())],
+    ///         ),
+    ///     ])
+    ///     .unwrap();
+    /// println!("Modified {} files", report.applied.len());
+    /// ```
+    pub fn apply_elf_rules(
+        &mut self,
+        rules: &[(String, Vec<ElfModification>)],
+    ) -> Result<ElfEditReport, WheelError> {
+        let is_interpreter = |m: &ElfModification| matches!(m, ElfModification::SetInterpreter(_));
+        let mut report = ElfEditReport::default();
+        for (pattern, modifications) in rules {
+            let sets_interpreter = modifications.iter().any(is_interpreter);
+            let results = self.try_apply_elf_modifications(pattern, modifications, |content| {
+                Ok(!sets_interpreter || elf::parse_elf(content)?.interpreter.is_some())
+            })?;
+            let mut without_interpreter = Vec::new();
+            for (path, result) in results {
+                match result {
+                    Ok(true) => report
+                        .applied
+                        .entry(path)
+                        .or_default()
+                        .extend(modifications.iter().cloned()),
+                    Ok(false) => without_interpreter.push(path),
+                    Err(e) => report.failures.push((path, e.to_string())),
+                }
+            }
+            if without_interpreter.is_empty() {
+                continue;
+            }
+
+            // The files without PT_INTERP still get everything else
+            let rest: Vec<ElfModification> = modifications
+                .iter()
+                .filter(|m| !is_interpreter(*m))
+                .cloned()
+                .collect();
+            if !rest.is_empty() {
+                let results = self.try_apply_elf_modifications(pattern, &rest, |content| {
+                    Ok(elf::parse_elf(content).is_ok_and(|elf| elf.interpreter.is_none()))
+                })?;
+                for (path, result) in results {
+                    match result {
+                        Ok(true) => report
+                            .applied
+                            .entry(path)
+                            .or_default()
+                            .extend(rest.iter().cloned()),
+                        Ok(false) => {}
+                        Err(e) => report.failures.push((path, e.to_string())),
+                    }
+                }
+            }
+            for path in without_interpreter {
+                for modification in modifications.iter().filter(|m| is_interpreter(*m)) {
+                    report.skipped.push((path.clone(), modification.clone()));
+                }
+            }
+        }
+        Ok(report)
+    }
+
+    /// Apply `modifications` to every ELF file matching `pattern`, returning
+    /// the paths that were modified
+    ///
+    /// Files that can't be modified are skipped and added to `warnings`.
+    fn apply_elf_modifications(
+        &mut self,
+        pattern: &str,
+        modifications: &[ElfModification],
+    ) -> Result<Vec<String>, WheelError> {
+        let mut modified = Vec::new();
+        let results = self.try_apply_elf_modifications(pattern, modifications, |_| Ok(true))?;
+        for (file_path, result) in results {
+            match result {
+                Ok(true) => modified.push(file_path),
+                Ok(false) => {}
+                Err(e) => self.push_warning(ElfWarning {
+                    path: file_path,
+                    error: e.to_string(),
+                }),
+            }
+        }
+        Ok(modified)
+    }
+
+    /// ELF files skipped by `set_rpath`, `remove_rpath`, `set_soname`,
+    /// `set_interpreter` and `strip_debug` because modifying them failed
+    ///
+    /// Warnings accumulate across calls, in the order they occurred, until
+    /// `clear_warnings` is called, and are also logged with `log::warn!`
+    /// when the `logging` feature is on. `apply_elf_rules` reports its
+    /// failures in its return value instead.
+    pub fn warnings(&self) -> &[ElfWarning] {
+        &self.warnings
+    }
+
+    /// Discard the warnings collected so far
+    pub fn clear_warnings(&mut self) {
+        self.warnings.clear();
+    }
+
+    /// Collect a skipped ELF file for `warnings`, also logging it at warn
+    /// level when the `logging` feature is on
+    fn push_warning(&mut self, warning: ElfWarning) {
+        #[cfg(feature = "logging")]
+        log::warn!("skipped ELF file {}: {}", warning.path, warning.error);
+        self.warnings.push(warning);
+    }
+
+    /// Queue `modifications` for every ELF file matching `pattern` that
+    /// `applies` accepts, returning the outcome for each one: `Ok(true)` if
+    /// queued, `Ok(false)` if `applies` turned it down
+    ///
+    /// Each file is checked to take `modifications` on its own, so failures
+    /// are reported here rather than at save. The queued modifications of a
+    /// file are applied together at save (see `elf::queue_modifications`).
+    /// With the `rayon` feature, files are checked in parallel.
+    fn try_apply_elf_modifications(
+        &mut self,
+        pattern: &str,
+        modifications: &[ElfModification],
+        applies: impl Fn(&[u8]) -> Result<bool, error::ElfError> + Sync,
+    ) -> Result<Vec<(String, Result<bool, error::ElfError>)>, WheelError> {
         let glob_pattern = glob::Pattern::new(pattern)?;
 
-        // Open the archive to find matching files
-        let file = File::open(&self.path)?;
-        let reader = BufReader::new(file);
-        let mut archive = zip::ZipArchive::new(reader)?;
+        // Open the archive to find matching files. Borrow only the source so
+        // the queue can be updated while the archive is open.
+        let mut archive = self.archive.checkout(&self.source, &self.path)?;
 
         // Find all files matching the pattern
         let mut matching_files = Vec::new();
         for i in 0..archive.len() {
             let entry = archive.by_index(i)?;
             let name = entry.name().to_string();
-            if glob_pattern.matches(&name) {
+            if glob_pattern.matches(&name) && !self.removed_files.contains(&name) {
                 matching_files.push(name);
             }
         }
 
-        // Modify each matching file
-        let mut modified_count = 0;
+        // Read serially, since the archive can't be shared between threads.
+        // Check against the content the queue will be applied to.
+        let mut candidates = Vec::new();
         for file_path in matching_files {
-            // Read the file content
-            let mut entry = archive.by_name(&file_path)?;
-            let mut content = Vec::new();
-            entry.read_to_end(&mut content)?;
-            drop(entry); // Release borrow
+            let content = match self.modified_files.get(&file_path) {
+                Some(content) => Cow::Borrowed(content.as_slice()),
+                None => {
+                    let mut content = Vec::new();
+                    archive.by_name(&file_path)?.read_to_end(&mut content)?;
+                    Cow::Owned(content)
+                }
+            };
 
             // Check if it's an ELF file (magic bytes: 0x7F 'E' 'L' 'F')
             if content.len() < 4 || &content[0..4] != b"\x7FELF" {
                 continue; // Skip non-ELF files
             }
+            candidates.push((file_path, content));
+        }
+
+        let checked = par_map(candidates, |(file_path, content)| {
+            let result = match applies(&content) {
+                Ok(true) => elf::modify_elf(&content, modifications).map(|_| true),
+                other => other,
+            };
+            (file_path, result)
+        });
+
+        for (file_path, result) in &checked {
+            if let Ok(true) = result {
+                let queue = self.elf_modifications.entry(file_path.clone()).or_default();
+                elf::queue_modifications(queue, modifications);
+            }
+        }
 
-            // Modify the ELF file - use RUNPATH (preferred over RPATH)
-            let modifications = vec![ElfModification::SetRunpath(rpath.to_string())];
-            match elf::modify_elf(&content, &modifications) {
-                Ok(modified_content) => {
-                    self.modified_files.insert(file_path, modified_content);
-                    modified_count += 1;
+        Ok(checked)
+    }
+
+    /// Strip `.debug_*` sections from ELF files matching a glob pattern
+    ///
+    /// Each matched ELF file that gets smaller is recorded as modified (with
+    /// its RECORD hash and size updated); non-ELF files and libraries with
+    /// nothing to strip are left alone. Pending modifications (e.g. from
+    /// `set_rpath`) are stripped too. See `elf::strip_debug_sections` for
+    /// what can and can't be removed.
+    ///
+    /// # Returns
+    /// The total number of bytes saved across all matched files
+    pub fn strip_debug(&mut self, pattern: &str) -> Result<usize, WheelError> {
+        let saved = std::cell::Cell::new(0);
+        let warnings = std::cell::RefCell::new(Vec::new());
+        self.modify_matching(pattern, |path, content| {
+            if content.len() < 4 || &content[0..4] != b"\x7FELF" {
+                return None;
+            }
+            match elf::modify_elf(content, &[ElfModification::StripDebug]) {
+                Ok(stripped) if stripped.len() < content.len() => {
+                    saved.set(saved.get() + content.len() - stripped.len());
+                    Some(stripped)
                 }
+                Ok(_) => None,
                 Err(e) => {
-                    // Log or handle error - for now, skip files that can't be modified
-                    eprintln!("Warning: Failed to modify {}: {}", file_path, e);
+                    warnings.borrow_mut().push(ElfWarning {
+                        path: path.to_string(),
+                        error: e.to_string(),
+                    });
+                    None
+                }
+            }
+        })?;
+        for warning in warnings.into_inner() {
+            self.push_warning(warning);
+        }
+        Ok(saved.get())
+    }
+
+    /// Apply a content transform to every file matching a glob pattern
+    ///
+    /// Each matching file is read once and passed to `f` along with its
+    /// path. Returning `Some(new_bytes)` replaces the file's content (and
+    /// updates its RECORD hash and size); returning `None` leaves it as-is.
+    /// Files that were already modified are passed their pending content, so
+    /// transforms compose. Directory entries are skipped.
+    ///
+    /// # Returns
+    /// The number of files that were modified
+    pub fn modify_matching(
+        &mut self,
+        pattern: &str,
+        f: impl Fn(&str, &[u8]) -> Option<Vec<u8>>,
+    ) -> Result<usize, WheelError> {
+        let glob_pattern = glob::Pattern::new(pattern)?;
+        let mut archive = self.archive.checkout(&self.source, &self.path)?;
+
+        let mut modified_count = 0;
+        for i in 0..archive.len() {
+            let mut entry = archive.by_index(i)?;
+            let name = entry.name().to_string();
+            if entry.is_dir() || !glob_pattern.matches(&name) || self.removed_files.contains(&name)
+            {
+                continue;
+            }
+
+            let mut source = Vec::new();
+            let content = match self.modified_files.get(&name) {
+                Some(content) => content,
+                None => {
+                    entry.read_to_end(&mut source)?;
+                    &source
                 }
+            };
+            // Queued ELF modifications go first, so `f` sees them
+            let new_content = match self.elf_modifications.get(&name) {
+                Some(modifications) => f(&name, &elf::modify_elf(content, modifications)?),
+                None => f(&name, content),
+            };
+            drop(entry);
+
+            if let Some(content) = new_content {
+                if let Some(record_entry) = self.record.find_mut(&name) {
+                    record_entry.hash = Some(hash_content(&content));
+                    record_entry.size = Some(content.len() as u64);
+                }
+                self.elf_modifications.remove(&name);
+                self.modified_files.insert(name, content);
+                modified_count += 1;
             }
         }
 
         Ok(modified_count)
     }
 
-    /// Check if any files have been modified
-    pub fn has_modified_files(&self) -> bool {
-        !self.modified_files.is_empty()
+    /// Move the top-level package directory `old_top` to `new_top`
+    ///
+    /// For forks, where the importable package should follow a `set_name`:
+    /// on save, every member under `old_top/`, at the archive root or under
+    /// `.data/purelib/` or `.data/platlib/`, is written under `new_top/`
+    /// with its RECORD line renamed to match. Members are still raw-copied,
+    /// so the save stays constant-time. A dist-info `top_level.txt` line
+    /// naming `old_top` is updated too.
+    ///
+    /// Only paths change. Imports of `old_top` (including the package's own
+    /// absolute imports), `.pth` files, entry point values (see
+    /// `modify_entry_points`), and extension modules, whose `PyInit_`
+    /// symbols name the module they were built as, aren't rewritten, so the
+    /// result only works for packages that never refer to themselves by
+    /// name. A top-level module (`old_top.py`) isn't moved. Other methods
+    /// still take source paths, e.g. `read_file("old_top/mod.py")`.
+    ///
+    /// # Returns
+    /// The number of files moved; nothing is queued if it's zero. Fails with
+    /// `WheelError::InvalidWheel` if either name isn't a plain directory
+    /// name or the output would already have a `new_top` package.
+    pub fn rename_package(&mut self, old_top: &str, new_top: &str) -> Result<usize, WheelError> {
+        for name in [old_top, new_top] {
+            if name.is_empty()
+                || name.contains(['/', '\\'])
+                || name == "."
+                || name == ".."
+                || name.ends_with(".dist-info")
+                || name.ends_with(".data")
+            {
+                return Err(WheelError::InvalidWheel(format!(
+                    "'{}' is not a top-level package directory name",
+                    name
+                )));
+            }
+        }
+
+        fn package_of(path: &str) -> Option<&str> {
+            wheel::split_package_path(path).map(|(_, package, _)| package)
+        }
+
+        // Compare against output paths, so earlier renames are accounted for
+        let renames = &self.write_options.package_renames;
+        let paths: HashSet<String> = self
+            .file_names()?
+            .into_iter()
+            .filter(|path| !self.removed_files.contains(path))
+            .chain(self.added_files.keys().cloned())
+            .map(|path| wheel::rename_package_path(path, renames))
+            .collect();
+        if paths.iter().any(|path| package_of(path) == Some(new_top)) {
+            return Err(WheelError::InvalidWheel(format!(
+                "cannot rename package '{}' to '{}': the wheel already has a '{}' package",
+                old_top, new_top, new_top
+            )));
+        }
+        let moved = paths
+            .iter()
+            .filter(|path| package_of(path) == Some(old_top))
+            .count();
+        if moved == 0 {
+            return Ok(0);
+        }
+
+        // A package renamed before is renamed again from its source name
+        let renames = &mut self.write_options.package_renames;
+        match renames.iter_mut().find(|(_, new)| new == old_top) {
+            Some((_, new)) => *new = new_top.to_string(),
+            None => renames.push((old_top.to_string(), new_top.to_string())),
+        }
+
+        let top_level_path = format!("{}/top_level.txt", self.dist_info_prefix);
+        if let Ok(content) = self.read_file(&top_level_path) {
+            let content = String::from_utf8_lossy(&content);
+            if content.lines().any(|line| line.trim() == old_top) {
+                let updated: String = content
+                    .lines()
+                    .map(|line| {
+                        if line.trim() == old_top {
+                            new_top
+                        } else {
+                            line
+                        }
+                    })
+                    .map(|line| format!("{}\n", line))
+                    .collect();
+                if let Some(record_entry) = self.record.find_mut(&top_level_path) {
+                    record_entry.hash = Some(hash_content(updated.as_bytes()));
+                    record_entry.size = Some(updated.len() as u64);
+                }
+                self.add_file(top_level_path, updated.into_bytes());
+            }
+        }
+        Ok(moved)
     }
 
-    /// Get the paths of all modified files
-    pub fn modified_file_paths(&self) -> Vec<&str> {
-        self.modified_files.keys().map(|s| s.as_str()).collect()
+    /// Archive path of `entry_points.txt` in the source dist-info directory
+    fn entry_points_path(&self) -> String {
+        format!("{}/entry_points.txt", self.dist_info_prefix)
     }
 
-    /// Validate all file hashes in the wheel
+    /// Parse the wheel's `entry_points.txt`
     ///
-    /// This reads and hashes every file in the wheel to verify integrity.
-    /// Note: This is NOT constant-time - it's O(wheel_size).
-    pub fn validate(&self) -> Result<ValidationResult, WheelError> {
-        let file = File::open(&self.path)?;
-        let reader = BufReader::new(file);
-        let mut archive = zip::ZipArchive::new(reader)?;
-        validate_wheel(&mut archive, &self.record)
+    /// Like `read_file`, this sees pending edits to the file. Returns
+    /// `None` if the wheel has no `entry_points.txt`.
+    pub fn entry_points(&self) -> Result<Option<EntryPoints>, WheelError> {
+        let path = self.entry_points_path();
+        let exists = !self.removed_files.contains(&path)
+            && (self.added_files.contains_key(&path)
+                || self.source_archive()?.index_for_name(&path).is_some());
+        if !exists {
+            return Ok(None);
+        }
+        let content = self.read_file(&path)?;
+        let content = String::from_utf8(content)
+            .map_err(|_| WheelError::InvalidWheel(format!("'{}' is not valid UTF-8", path)))?;
+        Ok(Some(EntryPoints::parse(&content)?))
     }
 
-    /// Save the modified wheel to a new file
+    /// Replace the wheel's `entry_points.txt` with `entry_points`
     ///
-    /// This achieves constant-time performance by copying unchanged files
-    /// as raw compressed bytes. Modified files (METADATA, RECORD, and any
-    /// ELF files with changed RPATH) are rewritten with new content.
-    pub fn save(&self, output_path: impl AsRef<Path>) -> Result<(), WheelError> {
-        let output_path = output_path.as_ref();
-
-        // Compute new dist-info prefix if name or version changed
-        let new_dist_info = dist_info_name(&self.metadata.name, &self.metadata.version);
+    /// The file is created if the wheel has none. On save it is written
+    /// under the output's dist-info directory with a fresh RECORD hash;
+    /// groups that weren't changed keep their original text.
+    pub fn set_entry_points(&mut self, entry_points: &EntryPoints) {
+        let path = self.entry_points_path();
+        let content = entry_points.serialize().into_bytes();
+        if let Some(record_entry) = self.record.find_mut(&path) {
+            record_entry.hash = Some(hash_content(&content));
+            record_entry.size = Some(content.len() as u64);
+        }
+        self.add_file(path, content);
+    }
 
-        // Open source for reading
-        let source_file = File::open(&self.path)?;
-        let source_reader = BufReader::new(source_file);
-        let mut source_archive = zip::ZipArchive::new(source_reader)?;
+    /// Edit the wheel's `entry_points.txt` with `f`
+    ///
+    /// `f` gets the parsed file, or an empty one if the wheel has none, and
+    /// the result is stored with `set_entry_points` if `f` changed it.
+    /// Returns what `f` returns.
+    pub fn modify_entry_points<T>(
+        &mut self,
+        f: impl FnOnce(&mut EntryPoints) -> T,
+    ) -> Result<T, WheelError> {
+        let original = self.entry_points()?.unwrap_or_default();
+        let mut entry_points = original.clone();
+        let result = f(&mut entry_points);
+        if entry_points != original {
+            self.set_entry_points(&entry_points);
+        }
+        Ok(result)
+    }
 
-        // Create output file
-        let output_file = File::create(output_path)?;
+    /// List every change made since the wheel was opened
+    ///
+    /// Reports METADATA fields first, then WHEEL fields (prefixed `WHEEL:`),
+    /// then modified, added and removed files (as `file:<path>`, sorted by
+    /// path) with their old and new hashes; a removed file has no new hash.
+    /// Changes are computed by diffing against the
+    /// state at open, so setting a field back to its original value reports
+    /// nothing. Queued ELF modifications are applied to hash the files they
+    /// touch, so this fails if they can't be applied, as `save` would.
+    pub fn changes(&self) -> Result<Vec<FieldChange>, WheelError> {
+        let mut changes = Vec::new();
+        changes::diff_fields(
+            "",
+            &self.original_metadata.fields(),
+            &self.metadata.fields(),
+            &mut changes,
+        );
+        changes::diff_fields(
+            "WHEEL:",
+            &self.original_wheel_info.fields(),
+            &self.wheel_info.fields(),
+            &mut changes,
+        );
 
-        // Use extended writer if we have modified files, added files, or
-        // wheel info changes.
-        if !self.modified_files.is_empty()
-            || !self.added_files.is_empty()
-            || self.wheel_info_modified
-        {
-            write_modified_extended(
-                &mut source_archive,
-                output_file,
-                &self.metadata,
-                &self.record,
-                &self.dist_info_prefix,
-                &new_dist_info,
-                &self.modified_files,
-                &self.added_files,
-                Some(&self.wheel_info),
-            )?;
-        } else {
-            // Use the original writer for backward compatibility
-            write_modified(
-                &mut source_archive,
-                output_file,
-                &self.metadata,
-                &self.record,
-                &self.dist_info_prefix,
-                &new_dist_info,
-            )?;
+        let mut paths: Vec<&String> = self
+            .modified_files
+            .keys()
+            .chain(self.elf_modifications.keys())
+            .chain(self.added_files.keys())
+            .chain(self.removed_files.iter())
+            .collect();
+        paths.sort();
+        paths.dedup();
+        for path in paths {
+            let old = self
+                .original_record
+                .find(path)
+                .and_then(|entry| entry.hash.clone());
+            // Hash with the algorithm RECORD used, so unchanged content
+            // compares equal whatever the wheel was hashed with
+            let algorithm = old
+                .as_deref()
+                .and_then(HashAlgorithm::of_hash)
+                .unwrap_or_default();
+            let new = match self.added_files.get(path) {
+                Some(content) => Some(hash_content_with(content, algorithm)),
+                None => self
+                    .pending_content(path)?
+                    .map(|c| hash_content_with(&c, algorithm)),
+            };
+            if old != new {
+                changes.push(FieldChange {
+                    field: format!("file:{}", path),
+                    old,
+                    new,
+                });
+            }
         }
 
-        Ok(())
+        Ok(changes)
     }
-}
-
-#[cfg(test)]
-mod tests {
-    use std::io::Write;
 
-    use tempfile::TempDir;
-    use zip::ZipWriter;
-    use zip::write::SimpleFileOptions;
+    /// Restrict which METADATA fields may change before saving
+    ///
+    /// `allowed` holds header names (e.g. `"Summary"`, `"Classifier"`),
+    /// matched case-insensitively. Setters stay infallible; instead every
+    /// save runs `enforce_editable_fields` and fails if any other field
+    /// differs from the wheel as opened. WHEEL fields and files are not
+    /// covered.
+    pub fn set_editable_fields(&mut self, allowed: &[&str]) {
+        self.editable_fields = Some(allowed.iter().map(|f| f.to_ascii_lowercase()).collect());
+    }
 
-    use super::*;
+    /// Check that only fields allowed by `set_editable_fields` have changed
+    ///
+    /// Returns `MetadataError::InvalidValue` naming every frozen field that
+    /// was edited. Always succeeds if no allowlist was set.
+    pub fn enforce_editable_fields(&self) -> Result<(), WheelError> {
+        let Some(allowed) = &self.editable_fields else {
+            return Ok(());
+        };
 
-    fn create_test_wheel(dir: &Path) -> PathBuf {
-        let wheel_path = dir.join("test_pkg-1.0.0-py3-none-any.whl");
-        let file = File::create(&wheel_path).unwrap();
-        let mut zip = ZipWriter::new(file);
-        let options = SimpleFileOptions::default();
+        let mut changes = Vec::new();
+        changes::diff_fields(
+            "",
+            &self.original_metadata.fields(),
+            &self.metadata.fields(),
+            &mut changes,
+        );
+        let frozen: Vec<String> = changes
+            .into_iter()
+            .map(|c| c.field)
+            .filter(|field| !allowed.contains(&field.to_ascii_lowercase()))
+            .collect();
+        if frozen.is_empty() {
+            return Ok(());
+        }
 
-        // Package file
-        let init_content = b"__version__ = '1.0.0'\n";
-        zip.start_file("test_pkg/__init__.py", options).unwrap();
-        zip.write_all(init_content).unwrap();
-        let init_hash = hash_content(init_content);
+        Err(MetadataError::InvalidValue {
+            field: frozen.join(", "),
+            reason: "field is frozen (not in the editable-fields allowlist)".to_string(),
+        }
+        .into())
+    }
 
-        // METADATA
-        let metadata =
-            "Metadata-Version: 2.1\nName: test-pkg\nVersion: 1.0.0\nSummary: Test package\n";
-        zip.start_file("test_pkg-1.0.0.dist-info/METADATA", options)
-            .unwrap();
-        zip.write_all(metadata.as_bytes()).unwrap();
-        let metadata_hash = hash_content(metadata.as_bytes());
+    /// Checks every save runs before writing: the editable-fields allowlist,
+    /// and `validate_metadata` in strict metadata mode
+    fn check_before_save(&self) -> Result<(), WheelError> {
+        self.enforce_editable_fields()?;
+        if self.strict_metadata {
+            self.validate_metadata().map_err(|problems| {
+                WheelError::InvalidWheel(format!(
+                    "invalid metadata (strict metadata mode is enabled): {}",
+                    problems.join("; ")
+                ))
+            })?;
+        }
+        Ok(())
+    }
 
-        // WHEEL
-        let wheel_info =
-            "Wheel-Version: 1.0\nGenerator: test\nRoot-Is-Purelib: true\nTag: py3-none-any\n";
-        zip.start_file("test_pkg-1.0.0.dist-info/WHEEL", options)
-            .unwrap();
-        zip.write_all(wheel_info.as_bytes()).unwrap();
-        let wheel_hash = hash_content(wheel_info.as_bytes());
+    /// Compute the RECORD that `save` would write, without writing anything
+    ///
+    /// Only members missing from RECORD are read from the source archive.
+    pub fn projected_record(&self) -> Result<Record, WheelError> {
+        let new_dist_info = dist_info_name(&self.metadata.name, &self.metadata.version);
+        let modified_files = self.pending_files()?;
+        let mut source_archive = self.source_archive()?;
+        let wheel_info = if self.uses_extended_writer() {
+            Some(&self.wheel_info)
+        } else {
+            None
+        };
 
-        // RECORD
-        let record = format!(
-            "test_pkg/__init__.py,{},{}\ntest_pkg-1.0.0.dist-info/METADATA,{},{}\ntest_pkg-1.0.0.dist-info/WHEEL,{},{}\ntest_pkg-1.0.0.dist-info/RECORD,,\n",
-            init_hash,
-            init_content.len(),
-            metadata_hash,
-            metadata.len(),
-            wheel_hash,
-            wheel_info.len()
+        let mut record = project_record(
+            &mut *source_archive,
+            &self.metadata,
+            &self.record,
+            &self.dist_info_prefix,
+            &new_dist_info,
+            &modified_files,
+            &self.added_files,
+            &self.removed_files,
+            wheel_info,
+            self.write_options.hash_algorithm,
+        )?;
+        for entry in &mut record.entries {
+            let path = std::mem::take(&mut entry.path);
+            entry.path = wheel::rename_package_path(path, &self.write_options.package_renames);
+        }
+        wheel::clear_hashless_entries(
+            &mut record.entries,
+            &self.write_options.hashless_patterns,
+            &new_dist_info,
         );
-        zip.start_file("test_pkg-1.0.0.dist-info/RECORD", options)
-            .unwrap();
-        zip.write_all(record.as_bytes()).unwrap();
-
-        zip.finish().unwrap();
-        wheel_path
+        Ok(record)
     }
 
-    #[test]
-    fn test_open_wheel() {
-        let temp_dir = TempDir::new().unwrap();
-        let wheel_path = create_test_wheel(temp_dir.path());
+    /// Dry-run the RECORD rewrite: which entries `save` would change
+    ///
+    /// Returns `(path, old_hash, new_hash)` for every entry of the projected
+    /// RECORD whose hash differs from the source RECORD, plus entries that
+    /// would be added (`old_hash` is `None`) or removed (`new_hash` is
+    /// `None`). Renamed paths show up as a removal and an addition. Entries
+    /// come out in projected RECORD order, followed by removals in source
+    /// RECORD order.
+    pub fn record_hash_changes(
+        &self,
+    ) -> Result<Vec<(String, Option<String>, Option<String>)>, WheelError> {
+        let projected = self.projected_record()?;
+        let mut changes = Vec::new();
+
+        for entry in &projected.entries {
+            match self.original_record.find(&entry.path) {
+                Some(old) if old.hash == entry.hash => {}
+                Some(old) => {
+                    changes.push((entry.path.clone(), old.hash.clone(), entry.hash.clone()))
+                }
+                None => changes.push((entry.path.clone(), None, entry.hash.clone())),
+            }
+        }
+        for entry in &self.original_record.entries {
+            if projected.find(&entry.path).is_none() {
+                changes.push((entry.path.clone(), entry.hash.clone(), None));
+            }
+        }
+
+        Ok(changes)
+    }
+
+    /// Check if any files have been modified
+    pub fn has_modified_files(&self) -> bool {
+        !self.modified_files.is_empty() || !self.elf_modifications.is_empty()
+    }
+
+    /// Get the paths of all modified files, including files with queued
+    /// ELF modifications
+    pub fn modified_file_paths(&self) -> Vec<&str> {
+        let mut paths: Vec<&str> = self.modified_files.keys().map(|s| s.as_str()).collect();
+        paths.extend(
+            self.elf_modifications
+                .keys()
+                .map(|s| s.as_str())
+                .filter(|path| !self.modified_files.contains_key(*path)),
+        );
+        paths
+    }
+
+    /// The content `save` writes for a modified file, with its queued ELF
+    /// modifications applied, or `None` if the file isn't modified
+    fn pending_content(&self, path: &str) -> Result<Option<Cow<'_, [u8]>>, WheelError> {
+        let Some(modifications) = self.elf_modifications.get(path) else {
+            return Ok(self
+                .modified_files
+                .get(path)
+                .map(|c| Cow::Borrowed(c.as_slice())));
+        };
+        let content = match self.modified_files.get(path) {
+            Some(content) => elf::modify_elf(content, modifications)?,
+            None => {
+                let mut archive = self.source_archive()?;
+                let mut content = Vec::new();
+                archive.by_name(path)?.read_to_end(&mut content)?;
+                elf::modify_elf(&content, modifications)?
+            }
+        };
+        Ok(Some(Cow::Owned(content)))
+    }
+
+    /// Every modified file with the content `save` writes for it
+    ///
+    /// This is where queued ELF modifications are applied, each file's in a
+    /// single `modify_elf` call. With the `rayon` feature, files are patched
+    /// in parallel.
+    fn pending_files(&self) -> Result<Cow<'_, HashMap<String, Vec<u8>>>, WheelError> {
+        if self.elf_modifications.is_empty() {
+            return Ok(Cow::Borrowed(&self.modified_files));
+        }
+        let mut files = self.modified_files.clone();
+
+        let mut archive = self.source_archive()?;
+        let mut queued = Vec::with_capacity(self.elf_modifications.len());
+        for (path, modifications) in &self.elf_modifications {
+            let content = match files.remove(path) {
+                Some(content) => content,
+                None => {
+                    let mut content = Vec::new();
+                    archive.by_name(path)?.read_to_end(&mut content)?;
+                    content
+                }
+            };
+            queued.push((path, content, modifications));
+        }
+        drop(archive);
+
+        let patched = par_map(queued, |(path, content, modifications)| {
+            elf::modify_elf(&content, modifications).map(|patched| (path.clone(), patched))
+        });
+        for result in patched {
+            let (path, content) = result?;
+            files.insert(path, content);
+        }
+        Ok(Cow::Owned(files))
+    }
+
+    /// Read the decompressed content of a single file
+    ///
+    /// Returns what `save` would write for the file: its pending content if
+    /// it has been modified (including queued ELF modifications) or added,
+    /// otherwise its bytes in the source wheel. Returns
+    /// `WheelError::InvalidWheel` if the file isn't in the wheel or was
+    /// passed to `remove_file`.
+    pub fn read_file(&self, path: &str) -> Result<Vec<u8>, WheelError> {
+        let not_found = || WheelError::InvalidWheel(format!("'{}' not found in wheel", path));
+        if self.removed_files.contains(path) {
+            return Err(not_found());
+        }
+        if let Some(content) = self.added_files.get(path) {
+            return Ok(content.clone());
+        }
+        if let Some(content) = self.pending_content(path)? {
+            return Ok(content.into_owned());
+        }
+
+        let mut archive = self.source_archive()?;
+        let mut entry = match archive.by_name(path) {
+            Ok(entry) => entry,
+            Err(zip::result::ZipError::FileNotFound) => return Err(not_found()),
+            Err(e) => return Err(e.into()),
+        };
+        let mut content = Vec::new();
+        entry.read_to_end(&mut content)?;
+        Ok(content)
+    }
+
+    /// Get the names of every file in the source wheel, in archive order
+    ///
+    /// Directory entries (names ending in `/`) are left out; `members`
+    /// lists them along with sizes and compression methods.
+    pub fn file_names(&self) -> Result<Vec<String>, WheelError> {
+        let archive = self.source_archive()?;
+        Ok(archive
+            .file_names()
+            .filter(|name| !name.ends_with('/'))
+            .map(|s| s.to_string())
+            .collect())
+    }
+
+    /// List every member of the source wheel with its sizes, compression
+    /// method and CRC-32, in archive order
+    ///
+    /// Everything is read from the central directory in a single pass;
+    /// nothing is decompressed, so this is cheap even for large wheels.
+    /// Pending edits are not reflected. Use `file_names` when only the
+    /// names are needed.
+    pub fn members(&self) -> Result<Vec<MemberInfo>, WheelError> {
+        let mut archive = self.source_archive()?;
+
+        let mut members = Vec::with_capacity(archive.len());
+        for i in 0..archive.len() {
+            let entry = archive.by_index_raw(i)?;
+            members.push(MemberInfo {
+                name: entry.name().to_string(),
+                compressed_size: entry.compressed_size(),
+                uncompressed_size: entry.size(),
+                method: entry.compression(),
+                crc32: entry.crc32(),
+                is_dir: entry.is_dir(),
+            });
+        }
+        Ok(members)
+    }
+
+    /// Check every Requires-Dist, Obsoletes-Dist and Provides-Dist entry
+    /// against the PEP 508 grammar
+    ///
+    /// All failures are collected, one message per malformed entry, so a
+    /// typo like `my pkg>=1` is caught before the wheel reaches pip.
+    pub fn validate_dependencies(&self) -> Result<(), Vec<String>> {
+        let problems = self.metadata.validate_dependencies();
+        if problems.is_empty() {
+            Ok(())
+        } else {
+            Err(problems)
+        }
+    }
+
+    /// Lint the current metadata for values that would make the wheel
+    /// unusable or rejected on upload
+    ///
+    /// Checks that Name and Version are set, that Metadata-Version supports
+    /// every field in use (see `Metadata::validate`), that
+    /// Description-Content-Type (if any) is valid, that every
+    /// dependency-family entry parses
+    /// (see `validate_dependencies`), and that no `Dynamic` field also has a
+    /// value (see `validate_dynamic`). Unlike `validate`, this inspects pending
+    /// edits rather than the wheel on disk, and reads no file content.
+    pub fn validate_metadata(&self) -> Result<(), Vec<String>> {
+        let mut problems = Vec::new();
+        if self.metadata.name.trim().is_empty() {
+            problems.push(MetadataError::MissingField("Name".to_string()).to_string());
+        }
+        if self.metadata.version.trim().is_empty() {
+            problems.push(MetadataError::MissingField("Version".to_string()).to_string());
+        }
+        if let Some(content_type) = &self.metadata.description_content_type {
+            if let Err(e) = validate_description_content_type(content_type) {
+                problems.push(e.to_string());
+            }
+        }
+        if let Err(errors) = self.metadata.validate() {
+            problems.extend(errors.iter().map(ToString::to_string));
+        }
+        problems.extend(self.metadata.validate_dependencies());
+        problems.extend(self.metadata.validate_dynamic());
+
+        if problems.is_empty() {
+            Ok(())
+        } else {
+            Err(problems)
+        }
+    }
+
+    /// Validate all file hashes in the wheel
+    ///
+    /// This reads and hashes every file in the wheel to verify integrity,
+    /// checks that RECORD has hashed entries for METADATA and WHEEL and no
+    /// malformed size fields, and checks that the dist-info directory name
+    /// agrees with the METADATA version on disk.
+    /// Note: This is NOT constant-time - it's O(wheel_size).
+    pub fn validate(&self) -> Result<ValidationResult, WheelError> {
+        let mut archive = self.source_archive()?;
+        let mut read_control_file = |name: &str| -> Result<String, WheelError> {
+            let path = format!("{}/{}", self.dist_info_prefix, name);
+            let mut content = String::new();
+            archive.by_name(&path)?.read_to_string(&mut content)?;
+            Ok(content)
+        };
+        let source_metadata = Metadata::parse(&read_control_file("METADATA")?)?;
+        let size_errors: Vec<_> = Record::invalid_sizes(&read_control_file("RECORD")?)?
+            .into_iter()
+            .filter_map(|e| match e {
+                RecordError::InvalidSize { path, value } => {
+                    Some(ValidationError::InvalidRecordSize { path, value })
+                }
+                _ => None,
+            })
+            .collect();
+
+        let mut result = validate_wheel(&mut *archive, &self.record)?;
+        result
+            .errors
+            .extend(validate_record_control_files(&self.record, &self.dist_info_prefix));
+        result.errors.extend(size_errors);
+
+        if let Some(dir_version) = self.dist_info_version() {
+            if dir_version != source_metadata.version {
+                result.errors.push(ValidationError::DistInfoVersionMismatch {
+                    dist_info_version: dir_version.to_string(),
+                    metadata_version: source_metadata.version,
+                });
+            }
+        }
+
+        Ok(result)
+    }
+
+    /// Validate the wheel's contents against an externally-provided RECORD
+    ///
+    /// Unlike `validate`, the wheel's own RECORD is ignored: every hashed
+    /// entry in `record` must exist with a matching hash, and every member
+    /// must be listed in `record`. Use this with a RECORD from a trusted
+    /// source when the embedded one can't be trusted. Like `validate`, this
+    /// checks the source wheel on disk, not pending edits, and is
+    /// O(wheel_size).
+    pub fn validate_against(&self, record: &Record) -> Result<ValidationResult, WheelError> {
+        let mut archive = self.source_archive()?;
+        validate_wheel_against(&mut *archive, record)
+    }
+
+    /// List members that don't map cleanly to an install location.
+    ///
+    /// Every file should live in the dist-info directory, a `.data/<scheme>/`
+    /// directory with a known scheme, a package directory, or be a top-level
+    /// module. Stray files at the archive root (e.g. `README.md`) and other
+    /// oddities that installers handle inconsistently are returned. Files
+    /// queued via `add_file` are included. This only inspects member names.
+    pub fn check_install_layout(&self) -> Result<Vec<String>, WheelError> {
+        let mut archive = self.source_archive()?;
+
+        let mut names = Vec::with_capacity(archive.len() + self.added_files.len());
+        for i in 0..archive.len() {
+            names.push(archive.by_index_raw(i)?.name().to_string());
+        }
+        let mut added: Vec<&String> = self.added_files.keys().collect();
+        added.sort();
+        names.extend(added.into_iter().cloned());
+
+        Ok(check_install_layout(
+            names.iter().map(|s| s.as_str()),
+            &self.dist_info_prefix,
+        ))
+    }
+
+    /// List members that would be unsafe to extract.
+    ///
+    /// Flags member names that are absolute or contain `..` segments, and
+    /// members whose Unix mode (from the ZIP external attributes) marks them
+    /// as symlinks. `unpack` refuses wheels with any such member, and
+    /// installers and other downstream tools extract wheels too, so run this
+    /// as a pre-flight check before handing an untrusted wheel on. Only
+    /// member names and external attributes of the source wheel are read.
+    pub fn check_safe_paths(&self) -> Result<Vec<String>, WheelError> {
+        let mut archive = self.source_archive()?;
+
+        let mut members = Vec::with_capacity(archive.len());
+        for i in 0..archive.len() {
+            let entry = archive.by_index_raw(i)?;
+            members.push((entry.name().to_string(), entry.unix_mode()));
+        }
+
+        Ok(check_safe_paths(
+            members.iter().map(|(name, mode)| (name.as_str(), *mode)),
+        ))
+    }
+
+    /// List non-fatal problems with the wheel, most of which installers
+    /// tolerate but indexes or downstream tools may not.
+    ///
+    /// Checks for a purelib wheel with platform tags, a filename whose name,
+    /// version or tags disagree with METADATA and WHEEL, python tags ruled
+    /// out by `Requires-Python`, RECORD entries missing for METADATA or
+    /// WHEEL, RECORD hashes weaker than sha256, and members that are unsafe
+    /// to extract. Metadata and tag edits are taken into account; the
+    /// filename is the source wheel's. Each finding has a stable code (see
+    /// the constants in `lint`). Only member names are read from the
+    /// archive.
+    pub fn lint(&self) -> Result<Vec<Lint>, WheelError> {
+        let mut lints = Vec::new();
+        let tags = &self.wheel_info.tags;
+
+        if self.wheel_info.root_is_purelib {
+            let platforms: Vec<&str> = tags
+                .iter()
+                .map(|t| t.platform.as_str())
+                .filter(|p| *p != "any")
+                .collect();
+            if !platforms.is_empty() {
+                lints.push(Lint::new(
+                    lint::PURELIB_PLATFORM,
+                    Severity::Warning,
+                    format!(
+                        "Root-Is-Purelib is true but the wheel is tagged for platform {}",
+                        dedup_join(platforms.into_iter())
+                    ),
+                ));
+            }
+        }
+
+        let filename = self
+            .path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        match WheelFilename::parse(&filename) {
+            Ok(parsed) => {
+                if normalize_project_name(&parsed.name)
+                    != normalize_project_name(&self.metadata.name)
+                    || parsed.version != self.metadata.version
+                {
+                    lints.push(Lint::new(
+                        lint::NAME_MISMATCH,
+                        Severity::Warning,
+                        format!(
+                            "filename is for {} {} but METADATA is for {} {}",
+                            parsed.name, parsed.version, self.metadata.name, self.metadata.version
+                        ),
+                    ));
+                }
+                // Compare only the tags and build; a name mismatch is reported above
+                let expected = WheelFilename {
+                    name: parsed.name.clone(),
+                    version: parsed.version.clone(),
+                    ..self.filename_parts()
+                };
+                if expected.canonical() != parsed.canonical() {
+                    lints.push(Lint::new(
+                        lint::TAG_MISMATCH,
+                        Severity::Warning,
+                        format!(
+                            "filename {} does not match WHEEL, expected {}",
+                            filename,
+                            self.wheel_filename()
+                        ),
+                    ));
+                }
+            }
+            Err(e) => lints.push(Lint::new(
+                lint::INVALID_FILENAME,
+                Severity::Warning,
+                e.to_string(),
+            )),
+        }
+
+        if let Some(requires_python) = &self.metadata.requires_python {
+            for python in lint::requires_python_conflicts(requires_python, tags) {
+                lints.push(Lint::new(
+                    lint::REQUIRES_PYTHON_TAG,
+                    Severity::Warning,
+                    format!(
+                        "python tag {} is excluded by Requires-Python {}",
+                        python, requires_python
+                    ),
+                ));
+            }
+        }
+
+        for error in validate_record_control_files(&self.record, &self.dist_info_prefix) {
+            if let ValidationError::MissingRecordEntry { path } = error {
+                lints.push(Lint::new(
+                    lint::MISSING_RECORD_ENTRY,
+                    Severity::Error,
+                    format!("RECORD has no hashed entry for {}", path),
+                ));
+            }
+        }
+
+        for (path, algorithm) in lint::weak_hashes(&self.record) {
+            lints.push(Lint::new(
+                lint::WEAK_HASH,
+                Severity::Error,
+                format!("RECORD hash for {} uses {}", path, algorithm),
+            ));
+        }
+
+        for path in self.check_safe_paths()? {
+            lints.push(Lint::new(
+                lint::UNSAFE_PATH,
+                Severity::Error,
+                format!("{} is unsafe to extract", path),
+            ));
+        }
+
+        Ok(lints)
+    }
+
+    /// Extract every member of the source wheel into `dest`
+    ///
+    /// Paths are preserved and intermediate directories created as needed;
+    /// on Unix, the read/write/execute bits stored in the archive are
+    /// restored, while setuid, setgid and sticky bits are dropped. Existing
+    /// files are overwritten. Pending edits are not applied: `save` first to
+    /// unpack the edited wheel. Nothing is written if `check_safe_paths`
+    /// reports any member, so a malicious wheel can't escape `dest`.
+    pub fn unpack(&self, dest: impl AsRef<Path>) -> Result<(), WheelError> {
+        let dest = dest.as_ref();
+        let unsafe_members = self.check_safe_paths()?;
+        if !unsafe_members.is_empty() {
+            return Err(WheelError::InvalidWheel(format!(
+                "refusing to unpack unsafe members: {}",
+                unsafe_members.join(", ")
+            )));
+        }
+
+        let mut archive = self.source_archive()?;
+        for i in 0..archive.len() {
+            let mut entry = archive.by_index(i)?;
+            let target = dest.join(entry.name());
+            if entry.is_dir() {
+                std::fs::create_dir_all(&target)?;
+                continue;
+            }
+            if let Some(parent) = target.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            let mut file = File::create(&target)?;
+            std::io::copy(&mut entry, &mut file)?;
+
+            #[cfg(unix)]
+            if let Some(mode) = entry.unix_mode() {
+                use std::os::unix::fs::PermissionsExt;
+                std::fs::set_permissions(&target, std::fs::Permissions::from_mode(mode & 0o777))?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Source members that RECORD doesn't list, in archive order
+    ///
+    /// These are the files `save` recovers by decompressing and hashing them
+    /// so the output RECORD is complete (or rejects, in strict RECORD mode).
+    /// Directories, the dist-info control files, and members that are
+    /// modified or removed are skipped since they never need recovering.
+    pub fn unrecorded_files(&self) -> Result<Vec<String>, WheelError> {
+        let mut archive = self.source_archive()?;
+
+        let control_files = ["METADATA", "RECORD", "WHEEL"]
+            .map(|name| format!("{}/{}", self.dist_info_prefix, name));
+        let mut unrecorded = Vec::new();
+        for i in 0..archive.len() {
+            let entry = archive.by_index_raw(i)?;
+            let name = entry.name();
+            if name.ends_with('/')
+                || control_files.iter().any(|c| c == name)
+                || self.modified_files.contains_key(name)
+                || self.elf_modifications.contains_key(name)
+                || self.removed_files.contains(name)
+                || self.record.find(name).is_some()
+            {
+                continue;
+            }
+            unrecorded.push(name.to_string());
+        }
+        Ok(unrecorded)
+    }
+
+    /// RECORD signature files (`RECORD.jws`, `RECORD.p7s`) that `save` will
+    /// leave out, in archive order
+    ///
+    /// They sign the RECORD the wheel was built with, and every save
+    /// regenerates RECORD, so shipping them would give a wheel that strict
+    /// installers reject. Set a `RecordSigner` with `set_record_signer` to
+    /// write a fresh `RECORD.jws` instead. Files already passed to
+    /// `remove_file` aren't listed.
+    pub fn dropped_signatures(&self) -> Result<Vec<String>, WheelError> {
+        let mut archive = self.source_archive()?;
+        let mut dropped = Vec::new();
+        for i in 0..archive.len() {
+            let name = archive.by_index_raw(i)?.name().to_string();
+            if wheel::is_record_signature(&name, &self.dist_info_prefix)
+                && !self.removed_files.contains(&name)
+            {
+                dropped.push(name);
+            }
+        }
+        Ok(dropped)
+    }
+
+    /// Check whether `save` can run in constant time with the current edits.
+    ///
+    /// True iff no payload files are modified or added and every source
+    /// member can be raw-copied, i.e. only METADATA, WHEEL, and RECORD are
+    /// rewritten. See `constant_time_blockers` for the reasons when false.
+    pub fn is_constant_time(&self) -> Result<bool, WheelError> {
+        Ok(self.constant_time_blockers()?.is_empty())
+    }
+
+    /// Explain why `save` would not run in constant time.
+    ///
+    /// Returns one human-readable line per kind of work that scales with
+    /// content size (e.g. "2 modified file(s) will be rewritten"); empty if
+    /// the save is constant-time.
+    pub fn constant_time_blockers(&self) -> Result<Vec<String>, WheelError> {
+        let mut blockers = Vec::new();
+
+        let modified = self.modified_file_paths().len();
+        if modified > 0 {
+            blockers.push(format!("{} modified file(s) will be rewritten", modified));
+        }
+        if !self.added_files.is_empty() {
+            blockers.push(format!(
+                "{} added file(s) will be written",
+                self.added_files.len()
+            ));
+        }
+
+        let mut archive = self.source_archive()?;
+
+        let control_files = ["METADATA", "RECORD", "WHEEL"]
+            .map(|name| format!("{}/{}", self.dist_info_prefix, name));
+        let mut missing_from_record = 0;
+        let mut needs_reencode = 0;
+        let mut to_verify = 0;
+        for i in 0..archive.len() {
+            let entry = archive.by_index_raw(i)?;
+            let name = entry.name();
+            if name.ends_with('/')
+                || control_files.iter().any(|c| c == name)
+                || self.modified_files.contains_key(name)
+                || self.elf_modifications.contains_key(name)
+                || self.removed_files.contains(name)
+            {
+                continue;
+            }
+            let record_entry = self.record.find(name);
+            if self.write_options.verify_hashes && record_entry.is_some_and(|e| e.hash.is_some()) {
+                to_verify += 1;
+            }
+            if record_entry.is_none() {
+                missing_from_record += 1;
+            } else if self.write_options.max_compatibility
+                && !matches!(
+                    entry.compression(),
+                    zip::CompressionMethod::Stored | zip::CompressionMethod::Deflated
+                )
+            {
+                needs_reencode += 1;
+            }
+        }
+
+        if missing_from_record > 0 {
+            blockers.push(format!(
+                "{} member(s) missing from RECORD will be decompressed and hashed",
+                missing_from_record
+            ));
+        }
+        if needs_reencode > 0 {
+            blockers.push(format!(
+                "{} member(s) will be re-encoded with Deflate for max compatibility",
+                needs_reencode
+            ));
+        }
+        if to_verify > 0 {
+            blockers.push(format!(
+                "{} member(s) will be decompressed to verify their RECORD hash",
+                to_verify
+            ));
+        }
+
+        Ok(blockers)
+    }
+
+    /// Dry-run `save`: which members would be raw-copied, which rewritten
+    /// and why, and the output's dist-info directory
+    ///
+    /// Only the central directory is read and nothing is written, so this is
+    /// cheap even for large wheels. A plan where `is_constant_time` holds
+    /// rewrites nothing but METADATA, RECORD and WHEEL.
+    pub fn plan(&self) -> Result<SavePlan, WheelError> {
+        let new_dist_info = dist_info_name(&self.metadata.name, &self.metadata.version);
+        let new_data_dir = data_dir_name(&self.metadata.name, &self.metadata.version);
+        let rename = |path: &str| -> Result<String, WheelError> {
+            let path =
+                wheel::renamed_path(path, &self.dist_info_prefix, &new_dist_info, &new_data_dir)?;
+            Ok(wheel::rename_package_path(
+                path,
+                &self.write_options.package_renames,
+            ))
+        };
+        let extended = self.uses_extended_writer();
+        let old_metadata = format!("{}/METADATA", self.dist_info_prefix);
+        let old_record = format!("{}/RECORD", self.dist_info_prefix);
+        let old_wheel = format!("{}/WHEEL", self.dist_info_prefix);
+
+        let mut added: Vec<String> = self
+            .added_files
+            .keys()
+            .map(|path| rename(path))
+            .collect::<Result<_, _>>()?;
+        added.sort();
+        let mut plan = SavePlan {
+            dist_info: new_dist_info.clone(),
+            ..SavePlan::default()
+        };
+
+        let mut archive = self.source_archive()?;
+        for i in 0..archive.len() {
+            let entry = archive.by_index_raw(i)?;
+            let name = entry.name();
+            if name == old_metadata || name == old_record || (extended && name == old_wheel) {
+                continue;
+            }
+            if self.removed_files.contains(name)
+                || wheel::is_record_signature(name, &self.dist_info_prefix)
+            {
+                plan.removed.push(name.to_string());
+                continue;
+            }
+
+            let new_name = rename(name)?;
+            let replaced = match added.iter().position(|path| *path == new_name) {
+                Some(index) => {
+                    added.remove(index);
+                    true
+                }
+                None => false,
+            };
+            let reason = if replaced || self.modified_files.contains_key(name) {
+                Some(RewriteReason::Modified)
+            } else if self.elf_modifications.contains_key(name) {
+                Some(RewriteReason::Elf)
+            } else if !extended {
+                None
+            } else if self.record.find(name).is_none() {
+                Some(RewriteReason::Unrecorded)
+            } else if self.write_options.max_compatibility
+                && !matches!(
+                    entry.compression(),
+                    zip::CompressionMethod::Stored | zip::CompressionMethod::Deflated
+                )
+            {
+                Some(RewriteReason::Reencoded)
+            } else {
+                None
+            };
+            match reason {
+                Some(reason) => plan.rewritten.push((new_name, reason)),
+                None => plan.copied.push((name.to_string(), new_name)),
+            }
+        }
+
+        if extended {
+            plan.rewritten
+                .push((format!("{}/WHEEL", new_dist_info), RewriteReason::WheelInfo));
+        }
+        plan.rewritten.push((
+            format!("{}/METADATA", new_dist_info),
+            RewriteReason::Metadata,
+        ));
+        plan.rewritten
+            .extend(added.into_iter().map(|path| (path, RewriteReason::Added)));
+        plan.rewritten
+            .push((format!("{}/RECORD", new_dist_info), RewriteReason::Record));
+        if self.write_options.record_signer.is_some() {
+            plan.rewritten.push((
+                format!("{}/RECORD.jws", new_dist_info),
+                RewriteReason::Signature,
+            ));
+        }
+        Ok(plan)
+    }
+
+    /// Whether every pending rewrite would fit in the slot of the member it
+    /// replaces (experimental)
+    ///
+    /// This is the precondition for patching the archive in place instead of
+    /// rewriting it: the new METADATA, RECORD, WHEEL (if it changes) and every
+    /// modified file must compress, with the original member's method, to no
+    /// more than the original compressed size. Added files, a renamed
+    /// dist-info directory or package, or a failure to read the source all
+    /// give `false`. Sizes are estimated with this crate's Deflate settings,
+    /// which may differ from the tool that built the wheel. In-place
+    /// patching itself is not implemented yet.
+    pub fn can_patch_in_place(&self) -> bool {
+        self.pending_rewrites_fit().unwrap_or(false)
+    }
+
+    fn pending_rewrites_fit(&self) -> Result<bool, WheelError> {
+        let new_dist_info = dist_info_name(&self.metadata.name, &self.metadata.version);
+        if !self.added_files.is_empty()
+            || !self.removed_files.is_empty()
+            || !self.write_options.package_renames.is_empty()
+            || new_dist_info != self.dist_info_prefix
+        {
+            return Ok(false);
+        }
+
+        let mut archive = self.source_archive()?;
+        let mut slots = HashMap::new();
+        for i in 0..archive.len() {
+            let entry = archive.by_index_raw(i)?;
+            slots.insert(
+                entry.name().to_string(),
+                (entry.compression(), entry.compressed_size()),
+            );
+        }
+        let fits = |path: &str, content: &[u8]| -> Result<bool, WheelError> {
+            let Some(&(method, slot)) = slots.get(path) else {
+                return Ok(false);
+            };
+            let size = if method == zip::CompressionMethod::Stored {
+                content.len() as u64
+            } else {
+                deflated_size(content)?
+            };
+            Ok(size <= slot)
+        };
+
+        drop(archive);
+
+        let prefix = &self.dist_info_prefix;
+        let metadata = self.metadata.serialize();
+        let record = self.projected_record()?.serialize();
+        if !fits(&format!("{}/METADATA", prefix), metadata.as_bytes())?
+            || !fits(&format!("{}/RECORD", prefix), record.as_bytes())?
+        {
+            return Ok(false);
+        }
+        if self.uses_extended_writer()
+            && !fits(
+                &format!("{}/WHEEL", prefix),
+                self.wheel_info.serialize().as_bytes(),
+            )?
+        {
+            return Ok(false);
+        }
+        for (path, content) in self.pending_files()?.iter() {
+            if !fits(path, content)? {
+                return Ok(false);
+            }
+        }
+
+        Ok(true)
+    }
+
+    /// Save the modified wheel to a new file
+    ///
+    /// This achieves constant-time performance by copying unchanged files
+    /// as raw compressed bytes. Modified files (METADATA, RECORD, and any
+    /// ELF files with changed RPATH) are rewritten with new content.
+    ///
+    /// If `output_path` is the wheel being edited, this fails without
+    /// writing anything: truncating the source while reading from it would
+    /// corrupt both. Use `save_in_place` to overwrite the source.
+    pub fn save(&self, output_path: impl AsRef<Path>) -> Result<(), WheelError> {
+        let output_path = output_path.as_ref();
+        self.check_not_source_path(output_path)?;
+        // Checked before the file is created, so a rejected save leaves
+        // whatever was at `output_path` alone
+        self.check_before_save()?;
+        self.write_to(File::create(output_path)?)
+    }
+
+    /// Save the modified wheel into `dir`, named after its current metadata
+    /// and tags (see `wheel_filename`)
+    ///
+    /// Returns the path written, so a version bump or retag can't end up in
+    /// a file whose name still describes the old wheel.
+    pub fn save_with_generated_name(&self, dir: impl AsRef<Path>) -> Result<PathBuf, WheelError> {
+        let output_path = dir.as_ref().join(self.wheel_filename());
+        self.save(&output_path)?;
+        Ok(output_path)
+    }
+
+    /// Save the modified wheel to any seekable writer
+    ///
+    /// Works like `save`, unchanged files included, but the output can be
+    /// an in-memory buffer (`Cursor<Vec<u8>>`) or a writer of the caller's
+    /// choosing, so a wheel can be edited without touching disk.
+    pub fn save_to_writer<W: Write + Seek>(&self, output: W) -> Result<(), WheelError> {
+        self.check_before_save()?;
+        self.write_to(output)
+    }
+
+    /// Save the modified wheel and make sure it has reached stable storage
+    ///
+    /// Like `save`, but the file's data and metadata are fsynced before
+    /// returning, and on Unix so is the containing directory (making the new
+    /// directory entry durable too). Use this when the wheel's existence is
+    /// treated as a completion signal, so a crash can't leave a truncated
+    /// wheel behind that looks finished.
+    ///
+    /// Each fsync blocks until the device acknowledges the write, which can
+    /// cost anywhere from milliseconds to far more than the save itself on
+    /// slow or network filesystems, so prefer `save` when durability isn't
+    /// required.
+    pub fn save_durable(&self, output_path: impl AsRef<Path>) -> Result<(), WheelError> {
+        let output_path = output_path.as_ref();
+        self.check_not_source_path(output_path)?;
+        self.check_before_save()?;
+        let output_file = File::create(output_path)?;
+        self.write_to(&output_file)?;
+        output_file.sync_all()?;
+        sync_parent_dir(output_path)?;
+        Ok(())
+    }
+
+    /// Save the modified wheel over the file it was opened from
+    ///
+    /// The wheel is written to a temporary file next to the original, which
+    /// is then renamed over it, so readers never see a partial file. The
+    /// original's permission bits are applied to the new file and, on Unix,
+    /// its owner and group where the process is allowed to set them
+    /// (best-effort), so shared caches keep their access rules.
+    ///
+    /// The editor is then reloaded from the new file: pending edits are part
+    /// of it now and are cleared, and `changes` compares against it, while
+    /// write options, editable fields, strict metadata mode, the limits it
+    /// was opened with and warnings carry over. Further edits and saves
+    /// start from the saved wheel.
+    pub fn save_in_place(&mut self) -> Result<(), WheelError> {
+        self.replace_original(false)
+    }
+
+    /// Like `save_in_place`, but fsync the new file and its directory before
+    /// returning (see `save_durable`)
+    pub fn save_in_place_durable(&mut self) -> Result<(), WheelError> {
+        self.replace_original(true)
+    }
+
+    /// Whether `path` names the file this editor reads from
+    pub(crate) fn is_source_path(&self, path: &Path) -> bool {
+        if matches!(self.source, SourceData::Bytes(_)) {
+            return false;
+        }
+        match (
+            std::fs::canonicalize(&self.path),
+            std::fs::canonicalize(path),
+        ) {
+            (Ok(source), Ok(path)) => source == path,
+            _ => false,
+        }
+    }
+
+    /// Refuse to `save` over the wheel being read from
+    fn check_not_source_path(&self, path: &Path) -> Result<(), WheelError> {
+        if self.is_source_path(path) {
+            return Err(WheelError::Io(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                format!(
+                    "'{}' is the wheel being edited; use save_in_place to overwrite it",
+                    path.display()
+                ),
+            )));
+        }
+        Ok(())
+    }
+
+    fn replace_original(&mut self, durable: bool) -> Result<(), WheelError> {
+        if matches!(self.source, SourceData::Bytes(_)) {
+            return Err(WheelError::Io(std::io::Error::new(
+                std::io::ErrorKind::Unsupported,
+                "wheel was opened from memory, so there is no file to save in place",
+            )));
+        }
+        self.check_before_save()?;
+        let original = std::fs::metadata(&self.path)?;
+        // A uniquely named file in the same directory, so the rename stays on
+        // one filesystem and concurrent saves can't write over each other's
+        // temp file; it is removed when dropped on any error path
+        let parent = match self.path.parent() {
+            Some(parent) if !parent.as_os_str().is_empty() => parent,
+            _ => Path::new("."),
+        };
+        let temp_file = tempfile::NamedTempFile::new_in(parent)?;
+        let file = temp_file.as_file();
+        self.write_to(file)?;
+        file.set_permissions(original.permissions())?;
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::MetadataExt;
+            // Only root may give a file away, and other users may only
+            // pick a group they belong to, so failure is expected here
+            let _ = std::os::unix::fs::fchown(file, Some(original.uid()), Some(original.gid()));
+        }
+        if durable {
+            file.sync_all()?;
+        }
+
+        // Reads after this must see the new file, and Windows can't rename
+        // over a file that is still open
+        self.archive.clear();
+        temp_file.persist(&self.path).map_err(|e| e.error)?;
+        if durable {
+            sync_parent_dir(&self.path)?;
+        }
+        self.reload()
+    }
+
+    /// Reopen the wheel at `path` after `replace_original` wrote over it
+    ///
+    /// Everything pending was just written, so the editor starts over from
+    /// the new file; settings and warnings are kept. Package renames are
+    /// edits rather than settings and are dropped with the rest.
+    fn reload(&mut self) -> Result<(), WheelError> {
+        let source = match &self.source {
+            #[cfg(feature = "mmap")]
+            SourceData::Mmap(_) => {
+                let file = File::open(&self.path)?;
+                // SAFETY: same contract as `open_mmap`; the mapping is
+                // read-only.
+                let mmap = unsafe { memmap2::Mmap::map(&file)? };
+                SourceData::Mmap(std::sync::Arc::new(mmap))
+            }
+            _ => SourceData::File,
+        };
+        let mut reopened = Self::open_source(self.path.clone(), source, &self.limits)?;
+        reopened.write_options = std::mem::take(&mut self.write_options);
+        reopened.write_options.package_renames.clear();
+        reopened.editable_fields = self.editable_fields.take();
+        reopened.strict_metadata = self.strict_metadata;
+        reopened.warnings = std::mem::take(&mut self.warnings);
+        *self = reopened;
+        Ok(())
+    }
+
+    /// Whether saving goes through the extended writer, which rewrites WHEEL
+    ///
+    /// That's the case when we have modified, added or removed files, wheel
+    /// info changes, or non-default output settings.
+    fn uses_extended_writer(&self) -> bool {
+        self.has_modified_files()
+            || !self.added_files.is_empty()
+            || !self.removed_files.is_empty()
+            || self.wheel_info_modified
+            || self.write_options != WriteOptions::default()
+    }
+
+    /// Write the output wheel to an already-created file or buffer
+    fn write_to<W: Write + Seek>(&self, output: W) -> Result<(), WheelError> {
+        // PEP 427 requires at least one Tag line
+        if self.wheel_info.tags.is_empty() {
+            return Err(WheelInfoError::MissingField("Tag".to_string()).into());
+        }
+
+        // Compute new dist-info prefix if name or version changed
+        let new_dist_info = dist_info_name(&self.metadata.name, &self.metadata.version);
+
+        // Apply queued ELF modifications before the source is checked out
+        let modified_files = self.pending_files()?;
+
+        // Open source for reading
+        let mut source_archive = self.source_archive()?;
+
+        if self.uses_extended_writer() {
+            write_modified_with_options(
+                &mut *source_archive,
+                output,
+                &self.metadata,
+                &self.record,
+                &self.dist_info_prefix,
+                &new_dist_info,
+                &modified_files,
+                &self.added_files,
+                &self.removed_files,
+                Some(&self.wheel_info),
+                &self.write_options,
+            )?;
+        } else {
+            // Use the original writer for backward compatibility
+            write_modified(
+                &mut *source_archive,
+                output,
+                &self.metadata,
+                &self.record,
+                &self.dist_info_prefix,
+                &new_dist_info,
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use tempfile::TempDir;
+    use zip::ZipWriter;
+    use zip::write::SimpleFileOptions;
+
+    use super::*;
+
+    pub(crate) fn create_test_wheel(dir: &Path) -> PathBuf {
+        let wheel_path = dir.join("test_pkg-1.0.0-py3-none-any.whl");
+        let file = File::create(&wheel_path).unwrap();
+        let mut zip = ZipWriter::new(file);
+        let options = SimpleFileOptions::default();
+
+        // Package file
+        let init_content = b"__version__ = '1.0.0'\n";
+        zip.start_file("test_pkg/__init__.py", options).unwrap();
+        zip.write_all(init_content).unwrap();
+        let init_hash = hash_content(init_content);
+
+        // METADATA
+        let metadata =
+            "Metadata-Version: 2.1\nName: test-pkg\nVersion: 1.0.0\nSummary: Test package\n";
+        zip.start_file("test_pkg-1.0.0.dist-info/METADATA", options)
+            .unwrap();
+        zip.write_all(metadata.as_bytes()).unwrap();
+        let metadata_hash = hash_content(metadata.as_bytes());
+
+        // WHEEL
+        let wheel_info =
+            "Wheel-Version: 1.0\nGenerator: test\nRoot-Is-Purelib: true\nTag: py3-none-any\n";
+        zip.start_file("test_pkg-1.0.0.dist-info/WHEEL", options)
+            .unwrap();
+        zip.write_all(wheel_info.as_bytes()).unwrap();
+        let wheel_hash = hash_content(wheel_info.as_bytes());
+
+        // RECORD
+        let record = format!(
+            "test_pkg/__init__.py,{},{}\ntest_pkg-1.0.0.dist-info/METADATA,{},{}\ntest_pkg-1.0.0.dist-info/WHEEL,{},{}\ntest_pkg-1.0.0.dist-info/RECORD,,\n",
+            init_hash,
+            init_content.len(),
+            metadata_hash,
+            metadata.len(),
+            wheel_hash,
+            wheel_info.len()
+        );
+        zip.start_file("test_pkg-1.0.0.dist-info/RECORD", options)
+            .unwrap();
+        zip.write_all(record.as_bytes()).unwrap();
+
+        zip.finish().unwrap();
+        wheel_path
+    }
+
+    /// Write a wheel containing `files` plus a RECORD (under `dist_info`)
+    /// with correct hashes for every entry.
+    fn create_wheel_with_files(path: &Path, dist_info: &str, files: &[(&str, &[u8])]) {
+        let file = File::create(path).unwrap();
+        let mut zip = ZipWriter::new(file);
+        let options = SimpleFileOptions::default();
+
+        let mut record = String::new();
+        for (name, content) in files {
+            zip.start_file(*name, options).unwrap();
+            zip.write_all(content).unwrap();
+            record.push_str(&format!(
+                "{},{},{}\n",
+                name,
+                hash_content(content),
+                content.len()
+            ));
+        }
+        let record_path = format!("{dist_info}/RECORD");
+        record.push_str(&format!("{record_path},,\n"));
+        zip.start_file(record_path, options).unwrap();
+        zip.write_all(record.as_bytes()).unwrap();
+
+        zip.finish().unwrap();
+    }
+
+    #[test]
+    fn test_record_order_is_preserved() {
+        let temp_dir = TempDir::new().unwrap();
+        let wheel_path = temp_dir.path().join("test_pkg-1.0.0-py3-none-any.whl");
+        let output_path = temp_dir.path().join("out.whl");
+        create_wheel_with_files(
+            &wheel_path,
+            "test_pkg-1.0.0.dist-info",
+            &[
+                (
+                    "test_pkg-1.0.0.dist-info/METADATA",
+                    b"Metadata-Version: 2.1\nName: test-pkg\nVersion: 1.0.0\n",
+                ),
+                (
+                    "test_pkg-1.0.0.dist-info/WHEEL",
+                    b"Wheel-Version: 1.0\nRoot-Is-Purelib: true\nTag: py3-none-any\n",
+                ),
+                ("test_pkg/b.py", b"b = 1\n"),
+                ("test_pkg/a.py", b"a = 1\n"),
+            ],
+        );
+
+        let mut editor = WheelEditor::open(&wheel_path).unwrap();
+        editor.set_version("1.0.1");
+        editor.set_platform_tag("linux_x86_64");
+        editor.add_file("test_pkg/a.py", b"a = 2\n".to_vec());
+        let projected = editor.projected_record().unwrap();
+        editor.save(&output_path).unwrap();
+
+        let record = read_archive_entry(&output_path, "test_pkg-1.0.1.dist-info/RECORD").unwrap();
+        let paths: Vec<&str> = std::str::from_utf8(&record)
+            .unwrap()
+            .lines()
+            .map(|line| line.split(',').next().unwrap())
+            .collect();
+        assert_eq!(
+            paths,
+            vec![
+                "test_pkg-1.0.1.dist-info/METADATA",
+                "test_pkg-1.0.1.dist-info/WHEEL",
+                "test_pkg/b.py",
+                "test_pkg/a.py",
+                "test_pkg-1.0.1.dist-info/RECORD",
+            ]
+        );
+        assert_eq!(projected.serialize().as_bytes(), record.as_slice());
+    }
+
+    #[test]
+    fn test_open_with_limits() {
+        let temp_dir = TempDir::new().unwrap();
+        let wheel_path = temp_dir.path().join("test_pkg-1.0.0-py3-none-any.whl");
+        let zeros = vec![0u8; 1 << 20];
+        create_wheel_with_files(
+            &wheel_path,
+            "test_pkg-1.0.0.dist-info",
+            &[
+                ("test_pkg/zeros.bin", zeros.as_slice()),
+                (
+                    "test_pkg-1.0.0.dist-info/METADATA",
+                    b"Metadata-Version: 2.1\nName: test-pkg\nVersion: 1.0.0\n",
+                ),
+                (
+                    "test_pkg-1.0.0.dist-info/WHEEL",
+                    b"Wheel-Version: 1.0\nRoot-Is-Purelib: true\nTag: py3-none-any\n",
+                ),
+            ],
+        );
+
+        let open = |limits: OpenLimits| WheelEditor::open_with_limits(&wheel_path, limits);
+        let exceeded = |limits: OpenLimits| match open(limits) {
+            Err(WheelError::LimitExceeded { limit, .. }) => Some(limit),
+            Err(e) => panic!("unexpected error: {}", e),
+            Ok(_) => None,
+        };
+        assert_eq!(
+            exceeded(OpenLimits {
+                max_entries: Some(3),
+                ..Default::default()
+            }),
+            Some("max_entries")
+        );
+        assert_eq!(
+            exceeded(OpenLimits {
+                max_total_uncompressed: Some(1 << 20),
+                ..Default::default()
+            }),
+            Some("max_total_uncompressed")
+        );
+        assert_eq!(
+            exceeded(OpenLimits {
+                max_ratio: Some(100.0),
+                ..Default::default()
+            }),
+            Some("max_ratio")
+        );
+
+        // Generous limits, and no limits at all, both open fine
+        let generous = OpenLimits {
+            max_entries: Some(4),
+            max_total_uncompressed: Some(2 << 20),
+            max_ratio: Some(10_000.0),
+        };
+        assert_eq!(exceeded(generous), None);
+        assert_eq!(exceeded(OpenLimits::default()), None);
+    }
+
+    #[test]
+    fn test_open_wheel() {
+        let temp_dir = TempDir::new().unwrap();
+        let wheel_path = create_test_wheel(temp_dir.path());
+
+        let editor = WheelEditor::open(&wheel_path).unwrap();
+        assert_eq!(editor.name(), "test-pkg");
+        assert_eq!(editor.version(), "1.0.0");
+        assert_eq!(editor.summary(), Some("Test package"));
+    }
+
+    #[test]
+    fn test_modify_and_save() {
+        let temp_dir = TempDir::new().unwrap();
+        let wheel_path = create_test_wheel(temp_dir.path());
+        let output_path = temp_dir.path().join("test_pkg-1.0.1-py3-none-any.whl");
+
+        let mut editor = WheelEditor::open(&wheel_path).unwrap();
+        editor.set_version("1.0.1");
+        editor.set_summary("Updated summary");
+        editor.save(&output_path).unwrap();
+
+        // Verify the output
+        let new_editor = WheelEditor::open(&output_path).unwrap();
+        assert_eq!(new_editor.version(), "1.0.1");
+        assert_eq!(new_editor.summary(), Some("Updated summary"));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_save_in_place_preserves_permissions() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let temp_dir = TempDir::new().unwrap();
+        let wheel_path = create_test_wheel(temp_dir.path());
+        std::fs::set_permissions(&wheel_path, std::fs::Permissions::from_mode(0o664)).unwrap();
+
+        let mut editor = WheelEditor::open(&wheel_path).unwrap();
+        editor.set_summary("Edited in place");
+        editor.save_in_place().unwrap();
+
+        let mode = std::fs::metadata(&wheel_path).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o664);
+        // No temp file is left behind
+        assert_eq!(std::fs::read_dir(temp_dir.path()).unwrap().count(), 1);
+        let reopened = WheelEditor::open(&wheel_path).unwrap();
+        assert_eq!(reopened.summary(), Some("Edited in place"));
+    }
+
+    #[test]
+    fn test_save_in_place_leaves_other_temp_files_alone() {
+        let temp_dir = TempDir::new().unwrap();
+        let wheel_path = create_test_wheel(temp_dir.path());
+        // What another save of the same wheel might be writing
+        let other = temp_dir.path().join("test_pkg-1.0.0-py3-none-any.whl.tmp");
+        std::fs::write(&other, b"someone else's save").unwrap();
+
+        let mut editor = WheelEditor::open(&wheel_path).unwrap();
+        editor.set_summary("Edited in place");
+        editor.save_in_place().unwrap();
+
+        assert_eq!(std::fs::read(&other).unwrap(), b"someone else's save");
+        assert_eq!(std::fs::read_dir(temp_dir.path()).unwrap().count(), 2);
+        let reopened = WheelEditor::open(&wheel_path).unwrap();
+        assert_eq!(reopened.summary(), Some("Edited in place"));
+    }
+
+    #[test]
+    fn test_save_to_source_path_is_refused() {
+        let temp_dir = TempDir::new().unwrap();
+        let wheel_path = create_test_wheel(temp_dir.path());
+        let refused = |result: Result<(), WheelError>| match result {
+            Err(WheelError::Io(e)) => e.kind() == std::io::ErrorKind::InvalidInput,
+            _ => false,
+        };
+
+        let mut editor = WheelEditor::open(&wheel_path).unwrap();
+        editor.set_summary("Saved over the source");
+        assert!(refused(editor.save(&wheel_path)));
+
+        // A version bump renames dist-info, so every member is rewritten
+        // from the source; a path spelled differently is still the source
+        editor.set_version("1.0.1");
+        let same_path = temp_dir
+            .path()
+            .join(".")
+            .join("test_pkg-1.0.0-py3-none-any.whl");
+        assert!(refused(editor.save_durable(&same_path)));
+
+        // Nothing was written over the source
+        let reopened = WheelEditor::open(&wheel_path).unwrap();
+        assert_eq!(reopened.version(), "1.0.0");
+        assert!(reopened.validate().unwrap().is_valid());
+
+        editor.save_in_place().unwrap();
+        let reopened = WheelEditor::open(&wheel_path).unwrap();
+        assert_eq!(reopened.version(), "1.0.1");
+        assert_eq!(reopened.summary(), Some("Saved over the source"));
+        assert!(reopened.validate().unwrap().is_valid());
+        assert!(read_archive_entry(&wheel_path, "test_pkg-1.0.1.dist-info/METADATA").is_some());
+    }
+
+    #[test]
+    fn test_save_in_place_keeps_open_limits() {
+        let temp_dir = TempDir::new().unwrap();
+        let wheel_path = create_test_wheel(temp_dir.path());
+        let entries = WheelEditor::open(&wheel_path)
+            .unwrap()
+            .members()
+            .unwrap()
+            .len();
+
+        let limits = OpenLimits {
+            max_entries: Some(entries),
+            ..OpenLimits::default()
+        };
+        let mut editor = WheelEditor::open_with_limits(&wheel_path, limits).unwrap();
+        editor.set_summary("Still within the limits");
+        editor.save_in_place().unwrap();
+
+        // The reopened wheel is checked against the same limits
+        editor.add_file("test_pkg/extra.py", b"x = 1\n".to_vec());
+        assert!(matches!(
+            editor.save_in_place(),
+            Err(WheelError::LimitExceeded {
+                limit: "max_entries",
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn test_save_in_place_twice_after_version_bump() {
+        let temp_dir = TempDir::new().unwrap();
+        let wheel_path = create_test_wheel(temp_dir.path());
+
+        let mut editor = WheelEditor::open(&wheel_path).unwrap();
+        editor.set_version("1.0.1");
+        editor.save_in_place().unwrap();
+        assert_eq!(editor.dist_info_version(), Some("1.0.1"));
+        assert!(editor.changes().unwrap().is_empty());
+
+        editor.set_summary("Second save");
+        editor.save_in_place().unwrap();
+        assert!(editor.save(&wheel_path).is_err());
+
+        let reopened = WheelEditor::open(&wheel_path).unwrap();
+        assert_eq!(reopened.version(), "1.0.1");
+        assert_eq!(reopened.summary(), Some("Second save"));
+        assert!(reopened.validate().unwrap().is_valid());
+        let names = reopened.file_names().unwrap();
+        assert_eq!(
+            names
+                .iter()
+                .filter(|name| name.ends_with("/METADATA"))
+                .count(),
+            1,
+            "{names:?}"
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_source_archive_stays_open_until_save_in_place() {
+        let temp_dir = TempDir::new().unwrap();
+        let wheel_path = create_test_wheel(temp_dir.path());
+        let moved_path = temp_dir.path().join("moved.whl");
+
+        let mut editor = WheelEditor::open(&wheel_path).unwrap();
+        let names = editor.file_names().unwrap();
+
+        // The cached archive keeps reading the file it opened, even once the
+        // path points elsewhere
+        std::fs::rename(&wheel_path, &moved_path).unwrap();
+        assert_eq!(editor.file_names().unwrap(), names);
+        assert!(editor.validate().unwrap().is_valid());
+
+        // Saving in place drops it, so later reads see the new file
+        std::fs::copy(&moved_path, &wheel_path).unwrap();
+        editor.set_summary("Edited in place");
+        editor.save_in_place().unwrap();
+        std::fs::remove_file(&wheel_path).unwrap();
+        assert!(editor.file_names().is_err());
+    }
+
+    #[test]
+    fn test_save_durable() {
+        let temp_dir = TempDir::new().unwrap();
+        let wheel_path = create_test_wheel(temp_dir.path());
+        let output_path = temp_dir.path().join("test_pkg-1.0.1-py3-none-any.whl");
+
+        let mut editor = WheelEditor::open(&wheel_path).unwrap();
+        editor.set_version("1.0.1");
+        editor.save_durable(&output_path).unwrap();
+
+        let new_editor = WheelEditor::open(&output_path).unwrap();
+        assert_eq!(new_editor.version(), "1.0.1");
+        assert!(new_editor.validate().unwrap().is_valid());
+    }
+
+    #[test]
+    fn test_from_bytes_and_reader() {
+        let temp_dir = TempDir::new().unwrap();
+        let wheel_path = create_test_wheel(temp_dir.path());
+        let output_path = temp_dir.path().join("from_memory.whl");
+        let data = std::fs::read(&wheel_path).unwrap();
+
+        let mut editor = WheelEditor::from_bytes(&data).unwrap();
+        assert_eq!(editor.name(), "test-pkg");
+        assert_eq!(editor.path(), Path::new(""));
+        assert!(editor.validate().unwrap().is_valid());
+        assert_eq!(
+            editor.read_file("test_pkg/__init__.py").unwrap(),
+            read_archive_entry(&wheel_path, "test_pkg/__init__.py").unwrap()
+        );
+
+        editor.set_summary("From memory");
+        assert!(matches!(editor.save_in_place(), Err(WheelError::Io(_))));
+        editor.save(&output_path).unwrap();
+        let saved = WheelEditor::open(&output_path).unwrap();
+        assert_eq!(saved.summary(), Some("From memory"));
+        assert!(saved.validate().unwrap().is_valid());
+
+        let mut reader = Cursor::new(data);
+        reader.seek(std::io::SeekFrom::End(0)).unwrap();
+        let editor = WheelEditor::from_reader(reader).unwrap();
+        assert_eq!(editor.file_names().unwrap(), saved.file_names().unwrap());
+    }
+
+    #[test]
+    fn test_save_to_writer() {
+        let temp_dir = TempDir::new().unwrap();
+        let wheel_path = create_test_wheel(temp_dir.path());
+        let output_path = temp_dir.path().join("test_pkg-1.0.1-py3-none-any.whl");
+
+        let mut editor = WheelEditor::open(&wheel_path).unwrap();
+        editor.set_version("1.0.1");
+        let mut buffer = Cursor::new(Vec::new());
+        editor.save_to_writer(&mut buffer).unwrap();
+        std::fs::write(&output_path, buffer.into_inner()).unwrap();
+
+        let new_editor = WheelEditor::open(&output_path).unwrap();
+        assert_eq!(new_editor.version(), "1.0.1");
+        assert!(new_editor.validate().unwrap().is_valid());
+    }
+
+    #[test]
+    fn test_validate() {
+        let temp_dir = TempDir::new().unwrap();
+        let wheel_path = create_test_wheel(temp_dir.path());
+
+        let editor = WheelEditor::open(&wheel_path).unwrap();
+        let result = editor.validate().unwrap();
+        assert!(result.is_valid());
+    }
+
+    #[test]
+    fn test_python_tag_get() {
+        let temp_dir = TempDir::new().unwrap();
+        let wheel_path = create_test_wheel(temp_dir.path());
+
+        let editor = WheelEditor::open(&wheel_path).unwrap();
+        assert_eq!(
+            editor.python_tag(),
+            Some("py3"),
+            "test wheel should have python tag 'py3'"
+        );
+    }
+
+    #[test]
+    fn test_abi_tag_get() {
+        let temp_dir = TempDir::new().unwrap();
+        let wheel_path = create_test_wheel(temp_dir.path());
+
+        let editor = WheelEditor::open(&wheel_path).unwrap();
+        assert_eq!(
+            editor.abi_tag(),
+            Some("none"),
+            "test wheel should have abi tag 'none'"
+        );
+    }
+
+    #[test]
+    fn test_python_tag_set_and_persist() {
+        let temp_dir = TempDir::new().unwrap();
+        let wheel_path = create_test_wheel(temp_dir.path());
+        let output_path = temp_dir.path().join("output.whl");
+
+        let mut editor = WheelEditor::open(&wheel_path).unwrap();
+        editor.set_python_tag("cp312");
+        editor.save(&output_path).unwrap();
+
+        let new_editor = WheelEditor::open(&output_path).unwrap();
+        assert_eq!(
+            new_editor.python_tag(),
+            Some("cp312"),
+            "set_python_tag should persist through save/reload"
+        );
+        // abi and platform should be unchanged
+        assert_eq!(
+            new_editor.abi_tag(),
+            Some("none"),
+            "abi tag should be unchanged after setting python tag"
+        );
+        assert_eq!(
+            new_editor.platform_tag(),
+            Some("any"),
+            "platform tag should be unchanged after setting python tag"
+        );
+    }
+
+    #[test]
+    fn test_save_with_generated_name() {
+        let temp_dir = TempDir::new().unwrap();
+        let wheel_path = create_test_wheel(temp_dir.path());
+        let output_dir = temp_dir.path().join("dist");
+        std::fs::create_dir(&output_dir).unwrap();
+
+        let mut editor = WheelEditor::open(&wheel_path).unwrap();
+        editor.set_version("1.0.1");
+        let output_path = editor.save_with_generated_name(&output_dir).unwrap();
+        assert_eq!(
+            output_path,
+            output_dir.join("test_pkg-1.0.1-py3-none-any.whl")
+        );
+
+        let saved = WheelEditor::open(&output_path).unwrap();
+        assert_eq!(saved.version(), "1.0.1");
+    }
+
+    #[test]
+    fn test_filename() {
+        let temp_dir = TempDir::new().unwrap();
+        let wheel_path = create_test_wheel(temp_dir.path());
+
+        let editor = WheelEditor::open(&wheel_path).unwrap();
+        assert_eq!(
+            editor.filename(),
+            "test_pkg-1.0.0-py3-none-any.whl",
+            "filename should match PEP 427 format"
+        );
+    }
+
+    #[test]
+    fn test_filename_after_tag_change() {
+        let temp_dir = TempDir::new().unwrap();
+        let wheel_path = create_test_wheel(temp_dir.path());
+
+        let mut editor = WheelEditor::open(&wheel_path).unwrap();
+        editor.set_python_tag("cp312");
+        editor.set_abi_tag("cp312");
+        editor.set_platform_tag("linux_x86_64");
+        assert_eq!(
+            editor.filename(),
+            "test_pkg-1.0.0-cp312-cp312-linux_x86_64.whl",
+            "filename should reflect updated tags"
+        );
+    }
+
+    #[test]
+    fn test_filename_after_name_version_change() {
+        let temp_dir = TempDir::new().unwrap();
+        let wheel_path = create_test_wheel(temp_dir.path());
+
+        let mut editor = WheelEditor::open(&wheel_path).unwrap();
+        editor.set_name("my-new-package");
+        editor.set_version("2.0.0");
+        assert_eq!(
+            editor.filename(),
+            "my_new_package-2.0.0-py3-none-any.whl",
+            "filename should reflect updated name and version"
+        );
+    }
+
+    #[test]
+    fn test_filename_multi_tag_dedup() {
+        let temp_dir = TempDir::new().unwrap();
+        let wheel_path = create_test_wheel(temp_dir.path());
+
+        let mut editor = WheelEditor::open(&wheel_path).unwrap();
+        // Simulate a wheel with two tags that share python/abi but differ in platform
+        editor.wheel_info_mut().tags = vec![
+            WheelTag::parse("cp311-cp311-manylinux_2_17_x86_64").unwrap(),
+            WheelTag::parse("cp311-cp311-manylinux2014_x86_64").unwrap(),
+        ];
+        assert_eq!(
+            editor.filename(),
+            "test_pkg-1.0.0-cp311-cp311-manylinux_2_17_x86_64.manylinux2014_x86_64.whl",
+            "filename should dot-join unique platform values and dedup python/abi"
+        );
+    }
+
+    #[test]
+    fn test_filename_multi_tag_all_different() {
+        let temp_dir = TempDir::new().unwrap();
+        let wheel_path = create_test_wheel(temp_dir.path());
+
+        let mut editor = WheelEditor::open(&wheel_path).unwrap();
+        // Simulate a wheel with two tags that differ in all components
+        editor.wheel_info_mut().tags = vec![
+            WheelTag::parse("py2-none-any").unwrap(),
+            WheelTag::parse("py3-none-any").unwrap(),
+        ];
+        assert_eq!(
+            editor.filename(),
+            "test_pkg-1.0.0-py2.py3-none-any.whl",
+            "filename should dot-join unique python values"
+        );
+    }
+
+    #[test]
+    fn test_retag() {
+        let temp_dir = TempDir::new().unwrap();
+        let wheel_path = create_test_wheel(temp_dir.path());
+        let output_path = temp_dir.path().join("out.whl");
+
+        let mut editor = WheelEditor::open(&wheel_path).unwrap();
+        editor.wheel_info_mut().tags = vec![
+            WheelTag::parse("cp311-cp311-linux_x86_64").unwrap(),
+            WheelTag::parse("cp311-cp311-linux_aarch64").unwrap(),
+        ];
+        editor
+            .retag(None, None, Some("manylinux_2_28_x86_64"))
+            .unwrap();
+        assert_eq!(
+            editor.filename(),
+            "test_pkg-1.0.0-cp311-cp311-manylinux_2_28_x86_64.whl"
+        );
+        editor.retag(Some("cp312"), Some("abi3"), None).unwrap();
+        assert_eq!(
+            editor.filename(),
+            "test_pkg-1.0.0-cp312-abi3-manylinux_2_28_x86_64.whl"
+        );
+
+        // A bad component rejects the whole call
+        for (python, abi, platform) in [
+            (Some("py-3"), None, None),
+            (Some("cp313"), Some(""), None),
+            (None, Some("abi3"), Some("linux-x86_64")),
+            (None, None, Some("linux x86_64")),
+        ] {
+            assert!(matches!(
+                editor.retag(python, abi, platform),
+                Err(WheelError::WheelInfo(WheelInfoError::InvalidTag(_)))
+            ));
+        }
+        assert_eq!(
+            editor.filename(),
+            "test_pkg-1.0.0-cp312-abi3-manylinux_2_28_x86_64.whl"
+        );
+        editor.save(&output_path).unwrap();
+
+        let wheel = read_archive_entry(&output_path, "test_pkg-1.0.0.dist-info/WHEEL").unwrap();
+        let wheel = String::from_utf8(wheel).unwrap();
+        assert_eq!(wheel.matches("Tag: ").count(), 1, "{wheel}");
+        assert!(
+            wheel.contains("Tag: cp312-abi3-manylinux_2_28_x86_64\n"),
+            "{wheel}"
+        );
+    }
+
+    #[test]
+    fn test_abi_tag_set_and_persist() {
+        let temp_dir = TempDir::new().unwrap();
+        let wheel_path = create_test_wheel(temp_dir.path());
+        let output_path = temp_dir.path().join("output.whl");
+
+        let mut editor = WheelEditor::open(&wheel_path).unwrap();
+        editor.set_abi_tag("cp312");
+        editor.save(&output_path).unwrap();
+
+        let new_editor = WheelEditor::open(&output_path).unwrap();
+        assert_eq!(
+            new_editor.abi_tag(),
+            Some("cp312"),
+            "set_abi_tag should persist through save/reload"
+        );
+        // python and platform should be unchanged
+        assert_eq!(
+            new_editor.python_tag(),
+            Some("py3"),
+            "python tag should be unchanged after setting abi tag"
+        );
+        assert_eq!(
+            new_editor.platform_tag(),
+            Some("any"),
+            "platform tag should be unchanged after setting abi tag"
+        );
+    }
+
+    fn read_archive_entry(path: &Path, entry_name: &str) -> Option<Vec<u8>> {
+        let file = File::open(path).unwrap();
+        let mut archive = zip::ZipArchive::new(file).unwrap();
+        let mut entry = archive.by_name(entry_name).ok()?;
+        let mut buf = Vec::new();
+        std::io::Read::read_to_end(&mut entry, &mut buf).unwrap();
+        Some(buf)
+    }
+
+    #[test]
+    fn test_extract_metadata_bytes_matches_saved_metadata() {
+        let temp_dir = TempDir::new().unwrap();
+        let wheel_path = create_test_wheel(temp_dir.path());
+        let output_path = temp_dir.path().join("test_pkg-1.0.1-py3-none-any.whl");
+
+        let mut editor = WheelEditor::open(&wheel_path).unwrap();
+        editor.set_version("1.0.1");
+        editor.add_classifier("Programming Language :: Python :: 3");
+        let sidecar = editor.extract_metadata_bytes().unwrap();
+        editor.save(&output_path).unwrap();
+
+        let saved = read_archive_entry(&output_path, "test_pkg-1.0.1.dist-info/METADATA").unwrap();
+        assert_eq!(sidecar, saved);
+    }
+
+    #[test]
+    fn test_strip_debug_on_synthetic_elf() {
+        let temp_dir = TempDir::new().unwrap();
+        let wheel_path = temp_dir.path().join("test_pkg-1.0.0-py3-none-any.whl");
+        let output_path = temp_dir.path().join("stripped.whl");
+        let elf = elf::synthetic_elf_with_debug();
+        create_wheel_with_files(
+            &wheel_path,
+            "test_pkg-1.0.0.dist-info",
+            &[
+                ("test_pkg/_native.so", elf.as_slice()),
+                ("test_pkg/__init__.py", b""),
+                (
+                    "test_pkg-1.0.0.dist-info/METADATA",
+                    b"Metadata-Version: 2.1\nName: test-pkg\nVersion: 1.0.0\n",
+                ),
+                (
+                    "test_pkg-1.0.0.dist-info/WHEEL",
+                    b"Wheel-Version: 1.0\nRoot-Is-Purelib: false\nTag: py3-none-any\n",
+                ),
+            ],
+        );
+
+        let mut editor = WheelEditor::open(&wheel_path).unwrap();
+        let saved = editor.strip_debug("**/*").unwrap();
+        assert!(saved >= 64);
+        assert_eq!(editor.modified_file_paths(), vec!["test_pkg/_native.so"]);
+        editor.save(&output_path).unwrap();
+
+        let stripped = read_archive_entry(&output_path, "test_pkg/_native.so").unwrap();
+        assert_eq!(elf.len() - stripped.len(), saved);
+        let result = WheelEditor::open(&output_path).unwrap().validate().unwrap();
+        assert!(result.is_valid(), "{:?}", result.errors);
+    }
+
+    #[test]
+    fn test_needed_libraries_from_fixture() {
+        let temp_dir = TempDir::new().unwrap();
+        let wheel_path = temp_dir.path().join("test_pkg-1.0.0-py3-none-any.whl");
+        let library = std::fs::read(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/tests/fixtures/librpath.so"
+        ))
+        .unwrap();
+        create_wheel_with_files(
+            &wheel_path,
+            "test_pkg-1.0.0.dist-info",
+            &[
+                ("test_pkg/lib/librpath.so", library.as_slice()),
+                (
+                    "test_pkg-1.0.0.dist-info/METADATA",
+                    b"Metadata-Version: 2.1\nName: test-pkg\nVersion: 1.0.0\n",
+                ),
+                (
+                    "test_pkg-1.0.0.dist-info/WHEEL",
+                    b"Wheel-Version: 1.0\nRoot-Is-Purelib: false\nTag: py3-none-any\n",
+                ),
+            ],
+        );
+
+        let editor = WheelEditor::open(&wheel_path).unwrap();
+        assert_eq!(
+            editor.needed_libraries("test_pkg/lib/librpath.so").unwrap(),
+            vec!["libc.so.6".to_string()]
+        );
+        assert!(matches!(
+            editor.needed_libraries("test_pkg/missing.so"),
+            Err(WheelError::Elf(error::ElfError::FileNotFound(_)))
+        ));
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn test_parallel_save_matches_serial_save() {
+        let temp_dir = TempDir::new().unwrap();
+        let wheel_path = temp_dir.path().join("test_pkg-1.0.0-py3-none-any.whl");
+        let library = std::fs::read(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/tests/fixtures/librpath.so"
+        ))
+        .unwrap();
+        let names: Vec<String> = (0..8).map(|i| format!("test_pkg/lib/lib{i}.so")).collect();
+        let mut files: Vec<(&str, &[u8])> = names
+            .iter()
+            .map(|name| (name.as_str(), library.as_slice()))
+            .collect();
+        files.extend([
+            ("test_pkg/__init__.py", b"x = 1\n".as_slice()),
+            (
+                "test_pkg-1.0.0.dist-info/METADATA",
+                b"Metadata-Version: 2.1\nName: test-pkg\nVersion: 1.0.0\n",
+            ),
+            (
+                "test_pkg-1.0.0.dist-info/WHEEL",
+                b"Wheel-Version: 1.0\nRoot-Is-Purelib: false\nTag: py3-none-any\n",
+            ),
+        ]);
+        create_wheel_with_files(&wheel_path, "test_pkg-1.0.0.dist-info", &files);
+
+        let mut editor = WheelEditor::open(&wheel_path).unwrap();
+        for (i, name) in names.iter().enumerate() {
+            let rpath = format!("$ORIGIN/../deps{i}");
+            assert_eq!(editor.set_rpath(name, &rpath).unwrap(), 1);
+        }
+        editor
+            .modify_matching("test_pkg/*.py", |_, _| Some(b"x = 2\n".to_vec()))
+            .unwrap();
+
+        let save_with_threads = |threads: usize| {
+            let pool = rayon::ThreadPoolBuilder::new()
+                .num_threads(threads)
+                .build()
+                .unwrap();
+            let mut output = Cursor::new(Vec::new());
+            pool.install(|| editor.save_to_writer(&mut output)).unwrap();
+            output.into_inner()
+        };
+        let serial = save_with_threads(1);
+        assert_eq!(save_with_threads(4), serial);
+
+        let saved = WheelEditor::from_bytes(&serial).unwrap();
+        assert!(saved.validate().unwrap().is_valid());
+        assert_eq!(
+            saved.get_rpath("test_pkg/lib/lib7.so").unwrap().as_deref(),
+            Some("$ORIGIN/../deps7")
+        );
+    }
+
+    #[test]
+    fn test_remove_rpath() {
+        let temp_dir = TempDir::new().unwrap();
+        let wheel_path = temp_dir.path().join("test_pkg-1.0.0-py3-none-any.whl");
+        let output_path = temp_dir.path().join("output.whl");
+        let library = std::fs::read(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/tests/fixtures/librpath.so"
+        ))
+        .unwrap();
+        create_wheel_with_files(
+            &wheel_path,
+            "test_pkg-1.0.0.dist-info",
+            &[
+                ("test_pkg/lib/librpath.so", library.as_slice()),
+                (
+                    "test_pkg-1.0.0.dist-info/METADATA",
+                    b"Metadata-Version: 2.1\nName: test-pkg\nVersion: 1.0.0\n",
+                ),
+                (
+                    "test_pkg-1.0.0.dist-info/WHEEL",
+                    b"Wheel-Version: 1.0\nRoot-Is-Purelib: false\nTag: py3-none-any\n",
+                ),
+            ],
+        );
+
+        let mut editor = WheelEditor::open(&wheel_path).unwrap();
+        assert_eq!(editor.remove_rpath("test_pkg/lib/*.so").unwrap(), 1);
+        editor.save(&output_path).unwrap();
+
+        let saved = WheelEditor::open(&output_path).unwrap();
+        assert_eq!(saved.get_rpath("test_pkg/lib/librpath.so").unwrap(), None);
+        assert_eq!(
+            saved.needed_libraries("test_pkg/lib/librpath.so").unwrap(),
+            vec!["libc.so.6".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_set_rpath_collects_warnings() {
+        let temp_dir = TempDir::new().unwrap();
+        let wheel_path = temp_dir.path().join("test_pkg-1.0.0-py3-none-any.whl");
+        let library = std::fs::read(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/tests/fixtures/librpath.so"
+        ))
+        .unwrap();
+        create_wheel_with_files(
+            &wheel_path,
+            "test_pkg-1.0.0.dist-info",
+            &[
+                ("test_pkg/lib/librpath.so", library.as_slice()),
+                ("test_pkg/lib/libbroken.so", b"\x7FELF truncated"),
+                (
+                    "test_pkg-1.0.0.dist-info/METADATA",
+                    b"Metadata-Version: 2.1\nName: test-pkg\nVersion: 1.0.0\n",
+                ),
+                (
+                    "test_pkg-1.0.0.dist-info/WHEEL",
+                    b"Wheel-Version: 1.0\nRoot-Is-Purelib: false\nTag: py3-none-any\n",
+                ),
+            ],
+        );
+
+        let mut editor = WheelEditor::open(&wheel_path).unwrap();
+        assert_eq!(editor.set_rpath("test_pkg/lib/*.so", "$ORIGIN").unwrap(), 1);
+        assert_eq!(editor.strip_debug("test_pkg/lib/*.so").unwrap(), 0);
+
+        let paths: Vec<&str> = editor
+            .warnings()
+            .iter()
+            .map(|warning| warning.path.as_str())
+            .collect();
+        assert_eq!(
+            paths,
+            vec!["test_pkg/lib/libbroken.so", "test_pkg/lib/libbroken.so"]
+        );
+        assert!(!editor.warnings()[0].error.is_empty());
+
+        editor.clear_warnings();
+        assert!(editor.warnings().is_empty());
+    }
+
+    #[test]
+    fn test_set_rpath_checked_warns_on_empty_directory() {
+        let temp_dir = TempDir::new().unwrap();
+        let wheel_path = temp_dir.path().join("test_pkg-1.0.0-py3-none-any.whl");
+        let library = std::fs::read(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/tests/fixtures/librpath.so"
+        ))
+        .unwrap();
+        create_wheel_with_files(
+            &wheel_path,
+            "test_pkg-1.0.0.dist-info",
+            &[
+                ("test_pkg/lib/librpath.so", library.as_slice()),
+                (
+                    "test_pkg-1.0.0.dist-info/METADATA",
+                    b"Metadata-Version: 2.1\nName: test-pkg\nVersion: 1.0.0\n",
+                ),
+                (
+                    "test_pkg-1.0.0.dist-info/WHEEL",
+                    b"Wheel-Version: 1.0\nRoot-Is-Purelib: false\nTag: py3-none-any\n",
+                ),
+            ],
+        );
+
+        let mut editor = WheelEditor::open(&wheel_path).unwrap();
+        let result = editor
+            .set_rpath_checked("test_pkg/lib/*.so", "$ORIGIN:$ORIGIN/../deps")
+            .unwrap();
+        assert_eq!(result.modified, 1);
+        assert_eq!(result.warnings.len(), 1, "{:?}", result.warnings);
+        assert!(
+            result.warnings[0].contains("test_pkg/deps"),
+            "{}",
+            result.warnings[0]
+        );
+
+        // A pending addition fills the directory
+        editor.add_file("test_pkg/deps/libdep.so", Vec::new());
+        let result = editor
+            .set_rpath_checked("test_pkg/lib/*.so", "$ORIGIN:$ORIGIN/../deps")
+            .unwrap();
+        assert!(result.warnings.is_empty(), "{:?}", result.warnings);
+    }
+
+    #[test]
+    fn test_apply_elf_rules_composes_across_rules() {
+        let temp_dir = TempDir::new().unwrap();
+        let wheel_path = temp_dir.path().join("test_pkg-1.0.0-py3-none-any.whl");
+        let output_path = temp_dir.path().join("output.whl");
+        let library = std::fs::read(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/tests/fixtures/librpath.so"
+        ))
+        .unwrap();
+        let debug = elf::synthetic_elf_with_debug();
+        create_wheel_with_files(
+            &wheel_path,
+            "test_pkg-1.0.0.dist-info",
+            &[
+                ("test_pkg/liba.so", library.as_slice()),
+                ("test_pkg/libb.so", library.as_slice()),
+                ("test_pkg/libbroken.so", b"\x7FELF\x02\x01\x01"),
+                ("test_pkg/_debug.so", debug.as_slice()),
+                ("test_pkg/__init__.py", b""),
+                (
+                    "test_pkg-1.0.0.dist-info/METADATA",
+                    b"Metadata-Version: 2.1\nName: test-pkg\nVersion: 1.0.0\n",
+                ),
+                (
+                    "test_pkg-1.0.0.dist-info/WHEEL",
+                    b"Wheel-Version: 1.0\nRoot-Is-Purelib: false\nTag: py3-none-any\n",
+                ),
+            ],
+        );
+
+        let mut editor = WheelEditor::open(&wheel_path).unwrap();
+        let report = editor
+            .apply_elf_rules(&[
+                (
+                    "test_pkg/lib*.so".to_string(),
+                    vec![ElfModification::RemoveRunpath],
+                ),
+                ("test_pkg/*".to_string(), vec![ElfModification::StripDebug]),
+            ])
+            .unwrap();
+
+        let applied: Vec<_> = report.applied.keys().map(String::as_str).collect();
+        assert_eq!(
+            applied,
+            vec!["test_pkg/_debug.so", "test_pkg/liba.so", "test_pkg/libb.so"]
+        );
+        assert!(matches!(
+            report.applied["test_pkg/liba.so"].as_slice(),
+            [ElfModification::RemoveRunpath, ElfModification::StripDebug]
+        ));
+        let failed: Vec<_> = report.failures.iter().map(|(p, _)| p.as_str()).collect();
+        assert_eq!(
+            failed,
+            vec!["test_pkg/libbroken.so", "test_pkg/libbroken.so"]
+        );
+
+        // The strip rule ran on the output of the remove rule
+        editor.save(&output_path).unwrap();
+        let saved = WheelEditor::open(&output_path).unwrap();
+        assert_eq!(saved.get_rpath("test_pkg/liba.so").unwrap(), None);
+        let stripped = read_archive_entry(&output_path, "test_pkg/_debug.so").unwrap();
+        assert!(stripped.len() < debug.len());
+    }
+
+    #[test]
+    fn test_set_soname() {
+        let temp_dir = TempDir::new().unwrap();
+        let wheel_path = temp_dir.path().join("test_pkg-1.0.0-py3-none-any.whl");
+        let output_path = temp_dir.path().join("output.whl");
+        let library = std::fs::read(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/tests/fixtures/librpath.so"
+        ))
+        .unwrap();
+        create_wheel_with_files(
+            &wheel_path,
+            "test_pkg-1.0.0.dist-info",
+            &[
+                ("test_pkg.libs/librpath.so", library.as_slice()),
+                (
+                    "test_pkg-1.0.0.dist-info/METADATA",
+                    b"Metadata-Version: 2.1\nName: test-pkg\nVersion: 1.0.0\n",
+                ),
+                (
+                    "test_pkg-1.0.0.dist-info/WHEEL",
+                    b"Wheel-Version: 1.0\nRoot-Is-Purelib: false\nTag: py3-none-any\n",
+                ),
+            ],
+        );
+
+        let mut editor = WheelEditor::open(&wheel_path).unwrap();
+        let count = editor
+            .set_soname("test_pkg.libs/*.so", "librpath-3f2a1b.so.1")
+            .unwrap();
+        assert_eq!(count, 1);
+        editor.save(&output_path).unwrap();
+
+        let saved = read_archive_entry(&output_path, "test_pkg.libs/librpath.so").unwrap();
+        let info = elf::parse_elf(&saved).unwrap();
+        assert_eq!(info.soname.as_deref(), Some("librpath-3f2a1b.so.1"));
+    }
+
+    #[test]
+    fn test_elf_modifications_compose_on_the_same_file() {
+        let temp_dir = TempDir::new().unwrap();
+        let wheel_path = temp_dir.path().join("test_pkg-1.0.0-py3-none-any.whl");
+        let output_path = temp_dir.path().join("output.whl");
+        let library = std::fs::read(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/tests/fixtures/librpath.so"
+        ))
+        .unwrap();
+        create_wheel_with_files(
+            &wheel_path,
+            "test_pkg-1.0.0.dist-info",
+            &[
+                ("test_pkg.libs/librpath.so", library.as_slice()),
+                (
+                    "test_pkg-1.0.0.dist-info/METADATA",
+                    b"Metadata-Version: 2.1\nName: test-pkg\nVersion: 1.0.0\n",
+                ),
+                (
+                    "test_pkg-1.0.0.dist-info/WHEEL",
+                    b"Wheel-Version: 1.0\nRoot-Is-Purelib: false\nTag: py3-none-any\n",
+                ),
+            ],
+        );
+
+        let mut editor = WheelEditor::open(&wheel_path).unwrap();
+        assert_eq!(editor.remove_rpath("test_pkg.libs/*.so").unwrap(), 1);
+        assert_eq!(
+            editor.set_rpath("test_pkg.libs/*.so", "$ORIGIN").unwrap(),
+            1
+        );
+        assert_eq!(
+            editor
+                .set_soname("test_pkg.libs/*.so", "librpath-3f2a1b.so.1")
+                .unwrap(),
+            1
+        );
+        assert!(matches!(
+            editor.elf_modifications["test_pkg.libs/librpath.so"].as_slice(),
+            [
+                ElfModification::RemoveRpath,
+                ElfModification::SetRunpath(_),
+                ElfModification::SetSoname(_)
+            ]
+        ));
+        assert_eq!(
+            editor.modified_file_paths(),
+            vec!["test_pkg.libs/librpath.so"]
+        );
+        editor.save(&output_path).unwrap();
+
+        let saved = read_archive_entry(&output_path, "test_pkg.libs/librpath.so").unwrap();
+        let info = elf::parse_elf(&saved).unwrap();
+        assert_eq!(info.runpath.as_deref(), Some("$ORIGIN"));
+        assert_eq!(info.soname.as_deref(), Some("librpath-3f2a1b.so.1"));
+        assert_eq!(info.needed, vec!["libc.so.6"]);
+        let result = WheelEditor::open(&output_path).unwrap().validate().unwrap();
+        assert!(result.is_valid(), "{:?}", result);
+    }
+
+    #[test]
+    fn test_set_interpreter_skips_shared_libraries() {
+        let temp_dir = TempDir::new().unwrap();
+        let wheel_path = temp_dir.path().join("test_pkg-1.0.0-py3-none-any.whl");
+        let output_path = temp_dir.path().join("output.whl");
+        let fixture = |name: &str| {
+            std::fs::read(
+                Path::new(env!("CARGO_MANIFEST_DIR"))
+                    .join("tests/fixtures")
+                    .join(name),
+            )
+            .unwrap()
+        };
+        let executable = fixture("hello");
+        let library = fixture("librpath.so");
+        create_wheel_with_files(
+            &wheel_path,
+            "test_pkg-1.0.0.dist-info",
+            &[
+                ("test_pkg-1.0.0.data/scripts/hello", executable.as_slice()),
+                (
+                    "test_pkg-1.0.0.data/scripts/librpath.so",
+                    library.as_slice(),
+                ),
+                ("test_pkg-1.0.0.data/scripts/tool.py", b"print('hi')\n"),
+                (
+                    "test_pkg-1.0.0.dist-info/METADATA",
+                    b"Metadata-Version: 2.1\nName: test-pkg\nVersion: 1.0.0\n",
+                ),
+                (
+                    "test_pkg-1.0.0.dist-info/WHEEL",
+                    b"Wheel-Version: 1.0\nRoot-Is-Purelib: false\nTag: py3-none-any\n",
+                ),
+            ],
+        );
+
+        let interp = "/opt/glibc/lib/ld-linux-x86-64.so.2";
+        let mut editor = WheelEditor::open(&wheel_path).unwrap();
+        let count = editor
+            .set_interpreter("test_pkg-1.0.0.data/scripts/*", interp)
+            .unwrap();
+        assert_eq!(count, 1);
+        assert_eq!(
+            editor.modified_file_paths(),
+            vec!["test_pkg-1.0.0.data/scripts/hello"]
+        );
+        editor.save(&output_path).unwrap();
+
+        let saved = read_archive_entry(&output_path, "test_pkg-1.0.0.data/scripts/hello").unwrap();
+        assert_eq!(
+            elf::parse_elf(&saved).unwrap().interpreter.as_deref(),
+            Some(interp)
+        );
+
+        // As a rule, the library is reported as skipped instead of queued,
+        // and the rule's other modifications still apply to it
+        let library_path = "test_pkg-1.0.0.data/scripts/librpath.so";
+        let mut editor = WheelEditor::open(&wheel_path).unwrap();
+        let report = editor
+            .apply_elf_rules(&[
+                (
+                    "test_pkg-1.0.0.data/scripts/*".to_string(),
+                    vec![ElfModification::SetInterpreter(interp.to_string())],
+                ),
+                (
+                    library_path.to_string(),
+                    vec![
+                        ElfModification::SetInterpreter(interp.to_string()),
+                        ElfModification::SetSoname("libvendored.so.1".to_string()),
+                    ],
+                ),
+            ])
+            .unwrap();
+        assert!(report.failures.is_empty(), "{:?}", report.failures);
+        assert!(matches!(
+            report.applied["test_pkg-1.0.0.data/scripts/hello"].as_slice(),
+            [ElfModification::SetInterpreter(_)]
+        ));
+        assert!(matches!(
+            report.applied[library_path].as_slice(),
+            [ElfModification::SetSoname(_)]
+        ));
+        assert_eq!(report.skipped.len(), 2);
+        for (path, modification) in &report.skipped {
+            assert_eq!(path, library_path);
+            assert!(matches!(modification, ElfModification::SetInterpreter(_)));
+        }
+        assert!(matches!(
+            editor.elf_modifications[library_path].as_slice(),
+            [ElfModification::SetSoname(_)]
+        ));
+    }
+
+    #[test]
+    fn test_elf_info() {
+        let temp_dir = TempDir::new().unwrap();
+        let wheel_path = temp_dir.path().join("test_pkg-1.0.0-py3-none-any.whl");
+        let library = std::fs::read(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/tests/fixtures/librpath.so"
+        ))
+        .unwrap();
+        create_wheel_with_files(
+            &wheel_path,
+            "test_pkg-1.0.0.dist-info",
+            &[
+                ("test_pkg/lib/librpath.so", library.as_slice()),
+                ("test_pkg/__init__.py", b"x = 1\n"),
+                (
+                    "test_pkg-1.0.0.dist-info/METADATA",
+                    b"Metadata-Version: 2.1\nName: test-pkg\nVersion: 1.0.0\n",
+                ),
+                (
+                    "test_pkg-1.0.0.dist-info/WHEEL",
+                    b"Wheel-Version: 1.0\nRoot-Is-Purelib: false\nTag: py3-none-any\n",
+                ),
+            ],
+        );
+
+        let editor = WheelEditor::open(&wheel_path).unwrap();
+        let info = editor.elf_info("test_pkg/lib/librpath.so").unwrap();
+        assert_eq!(info.runpath.as_deref(), Some("$ORIGIN/../lib:$ORIGIN"));
+        assert_eq!(info.needed, ["libc.so.6"]);
+        assert_eq!(info.soname.as_deref(), Some("librpath.so.1"));
+        assert_eq!(info.interpreter, None);
+
+        assert!(matches!(
+            editor.elf_info("test_pkg/__init__.py"),
+            Err(WheelError::Elf(error::ElfError::InvalidElf(_)))
+        ));
+        assert!(matches!(
+            editor.elf_info("test_pkg/missing.so"),
+            Err(WheelError::Elf(error::ElfError::FileNotFound(_)))
+        ));
+    }
+
+    #[test]
+    fn test_get_rpath_corrupt_member_is_not_file_not_found() {
+        let temp_dir = TempDir::new().unwrap();
+        let wheel_path = temp_dir.path().join("test_pkg-1.0.0-py3-none-any.whl");
+        let library = elf::synthetic_elf_with_debug();
+        let stored =
+            SimpleFileOptions::default().compression_method(zip::CompressionMethod::Stored);
+        let mut zip = ZipWriter::new(File::create(&wheel_path).unwrap());
+        for (name, content) in [
+            ("test_pkg/_native.so", library.as_slice()),
+            (
+                "test_pkg-1.0.0.dist-info/METADATA",
+                b"Metadata-Version: 2.1\nName: test-pkg\nVersion: 1.0.0\n".as_slice(),
+            ),
+            (
+                "test_pkg-1.0.0.dist-info/WHEEL",
+                b"Wheel-Version: 1.0\nRoot-Is-Purelib: false\nTag: py3-none-any\n".as_slice(),
+            ),
+            ("test_pkg-1.0.0.dist-info/RECORD", b"".as_slice()),
+        ] {
+            zip.start_file(name, stored).unwrap();
+            zip.write_all(content).unwrap();
+        }
+        zip.finish().unwrap();
+
+        // Flip a byte of the stored library so its CRC no longer matches
+        let mut bytes = std::fs::read(&wheel_path).unwrap();
+        let at = bytes.windows(5).position(|w| w == b"\xc3TEXT").unwrap();
+        bytes[at + 1] ^= 0xff;
+        std::fs::write(&wheel_path, bytes).unwrap();
+
+        let editor = WheelEditor::open(&wheel_path).unwrap();
+        let err = editor.get_rpath("test_pkg/_native.so").unwrap_err();
+        assert!(matches!(err, WheelError::Zip(_)), "{}", err);
+        assert!(err.to_string().contains("test_pkg/_native.so"), "{}", err);
+        assert!(matches!(
+            editor.get_rpath("test_pkg/missing.so"),
+            Err(WheelError::Elf(error::ElfError::FileNotFound(_)))
+        ));
+    }
+
+    #[test]
+    fn test_canonical_filename() {
+        let temp_dir = TempDir::new().unwrap();
+        let wheel_path = create_test_wheel(temp_dir.path());
+
+        let mut editor = WheelEditor::open(&wheel_path).unwrap();
+        assert_eq!(
+            editor.canonical_filename(),
+            "test_pkg-1.0.0-py3-none-any.whl"
+        );
+        editor.set_name("Test.Pkg");
+        assert_eq!(
+            editor.canonical_filename(),
+            "test_pkg-1.0.0-py3-none-any.whl"
+        );
+    }
+
+    #[test]
+    fn test_editable_fields_rejects_frozen_version() {
+        let temp_dir = TempDir::new().unwrap();
+        let wheel_path = create_test_wheel(temp_dir.path());
+        let output_path = temp_dir.path().join("out.whl");
+
+        let mut editor = WheelEditor::open(&wheel_path).unwrap();
+        editor.set_editable_fields(&["summary", "Classifier"]);
+        editor.set_summary("Allowed change");
+        editor.add_classifier("Programming Language :: Python :: 3");
+        editor.enforce_editable_fields().unwrap();
+
+        editor.set_version("2.0.0");
+        let err = editor.save(&output_path).unwrap_err();
+        assert!(err.to_string().contains("Version"), "{}", err);
+        assert!(!output_path.exists());
+
+        // Reverting the frozen field makes the save go through again
+        editor.set_version("1.0.0");
+        editor.save(&output_path).unwrap();
+    }
+
+    #[test]
+    fn test_record_hash_changes_after_version_change() {
+        let temp_dir = TempDir::new().unwrap();
+        let wheel_path = create_test_wheel(temp_dir.path());
+        let output_path = temp_dir.path().join("test_pkg-1.0.1-py3-none-any.whl");
+
+        let mut editor = WheelEditor::open(&wheel_path).unwrap();
+        assert!(editor.record_hash_changes().unwrap().is_empty());
+
+        editor.set_summary("Changed");
+        let changes = editor.record_hash_changes().unwrap();
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].0, "test_pkg-1.0.0.dist-info/METADATA");
+        assert!(changes[0].1.is_some() && changes[0].2.is_some());
+        assert_ne!(changes[0].1, changes[0].2);
+
+        editor.set_version("1.0.1");
+        let changes = editor.record_hash_changes().unwrap();
+        let paths: Vec<&str> = changes.iter().map(|(path, _, _)| path.as_str()).collect();
+        assert!(paths.iter().all(|path| path.contains(".dist-info/")));
+        assert!(paths.contains(&"test_pkg-1.0.1.dist-info/METADATA"));
+        assert!(paths.contains(&"test_pkg-1.0.0.dist-info/METADATA"));
+        assert!(paths.contains(&"test_pkg-1.0.1.dist-info/WHEEL"));
+        assert!(!paths.contains(&"test_pkg/__init__.py"));
+
+        // The dry run matches what save actually writes
+        let projected = editor.projected_record().unwrap();
+        editor.save(&output_path).unwrap();
+        let saved = read_archive_entry(&output_path, "test_pkg-1.0.1.dist-info/RECORD").unwrap();
+        assert_eq!(projected.serialize(), String::from_utf8(saved).unwrap());
+    }
+
+    #[test]
+    fn test_add_file_to_dist_info() {
+        let temp_dir = TempDir::new().unwrap();
+        let wheel_path = create_test_wheel(temp_dir.path());
+        let output_path = temp_dir.path().join("with_extra.whl");
+
+        let mut editor = WheelEditor::open(&wheel_path).unwrap();
+        let dist_info = editor.dist_info_dir();
+        assert_eq!(dist_info, "test_pkg-1.0.0.dist-info");
+        let payload = br#"{"vcs_name":"git","vcs_ref":"deadbeef"}"#;
+        editor.add_file(format!("{dist_info}/build-details.json"), payload.to_vec());
+        editor.save(&output_path).unwrap();
+
+        // The added entry should be present and readable.
+        let got = read_archive_entry(&output_path, "test_pkg-1.0.0.dist-info/build-details.json")
+            .expect("build-details.json should be present in saved wheel");
+        assert_eq!(got, payload);
+
+        // The wheel should pass full validation: RECORD must contain a
+        // correct hash for the new file.
+        let result = WheelEditor::open(&output_path).unwrap().validate().unwrap();
+        assert!(
+            result.is_valid(),
+            "wheel with added file should validate: {:?}",
+            result
+        );
+    }
+
+    #[test]
+    fn test_add_file_renamed_when_version_changes() {
+        let temp_dir = TempDir::new().unwrap();
+        let wheel_path = create_test_wheel(temp_dir.path());
+        let output_path = temp_dir.path().join("renamed.whl");
+
+        let mut editor = WheelEditor::open(&wheel_path).unwrap();
+        // Add using the *old* dist-info prefix, then bump version. The writer
+        // should rewrite the path to land under the new dist-info dir.
+        editor.add_file(
+            "test_pkg-1.0.0.dist-info/build-details.json",
+            b"{}".to_vec(),
+        );
+        editor.set_version("1.0.1");
+        editor.save(&output_path).unwrap();
+
+        assert!(
+            read_archive_entry(
+                &output_path,
+                "test_pkg-1.0.1.dist-info/build-details.json",
+            )
+            .is_some(),
+            "added file should be rewritten to new dist-info prefix"
+        );
+        assert!(
+            read_archive_entry(
+                &output_path,
+                "test_pkg-1.0.0.dist-info/build-details.json",
+            )
+            .is_none(),
+            "added file should not appear under old dist-info prefix"
+        );
+
+        let result = WheelEditor::open(&output_path).unwrap().validate().unwrap();
+        assert!(result.is_valid());
+    }
+
+    #[test]
+    fn test_add_file_existing_path_replaces_it() {
+        let temp_dir = TempDir::new().unwrap();
+        let wheel_path = create_test_wheel(temp_dir.path());
+        let output_path = temp_dir.path().join("replaced.whl");
+
+        let mut editor = WheelEditor::open(&wheel_path).unwrap();
+        editor.add_file("test_pkg/__init__.py", b"x = 1\n".to_vec());
+        assert!(!editor.has_added_files());
+        assert_eq!(editor.modified_file_paths(), vec!["test_pkg/__init__.py"]);
+        editor.save(&output_path).unwrap();
+
+        assert_eq!(
+            read_archive_entry(&output_path, "test_pkg/__init__.py").unwrap(),
+            b"x = 1\n"
+        );
+        let saved = WheelEditor::open(&output_path).unwrap();
+        assert_eq!(saved.file_names().unwrap().len(), 4);
+        assert!(saved.validate().unwrap().is_valid());
+
+        // Replacements are told apart from the names read at open, so they
+        // don't turn into duplicate additions when the source can't be read
+        let mut editor = WheelEditor::open(&wheel_path).unwrap();
+        let moved = temp_dir.path().join("moved.whl");
+        std::fs::rename(&wheel_path, &moved).unwrap();
+        editor.add_file("test_pkg/__init__.py", b"x = 1\n".to_vec());
+        assert!(!editor.has_added_files());
+        std::fs::rename(&moved, &wheel_path).unwrap();
+
+        // Generated dist-info files can't be replaced this way
+        let mut editor = WheelEditor::open(&wheel_path).unwrap();
+        editor.add_file("test_pkg-1.0.0.dist-info/WHEEL", b"".to_vec());
+        match editor.save(temp_dir.path().join("collide.whl")) {
+            Err(WheelError::InvalidWheel(msg)) => assert!(msg.contains("WHEEL"), "{msg}"),
+            other => panic!("expected InvalidWheel, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_remove_file() {
+        let temp_dir = TempDir::new().unwrap();
+        let wheel_path = create_test_wheel(temp_dir.path());
+        let output_path = temp_dir.path().join("removed.whl");
+
+        let mut editor = WheelEditor::open(&wheel_path).unwrap();
+        editor.add_file("test_pkg/extra.py", b"y = 2\n".to_vec());
+        assert!(editor.remove_file("test_pkg/extra.py").unwrap());
+        assert!(!editor.has_added_files());
+
+        editor
+            .modify_matching("test_pkg/*.py", |_, _| Some(b"x = 1\n".to_vec()))
+            .unwrap();
+        assert!(editor.remove_file("test_pkg/__init__.py").unwrap());
+        assert!(!editor.remove_file("test_pkg/__init__.py").unwrap());
+        assert!(!editor.remove_file("test_pkg/missing.py").unwrap());
+        assert!(!editor.has_modified_files());
+        let change = editor.changes().unwrap().pop().unwrap();
+        assert_eq!(change.field, "file:test_pkg/__init__.py");
+        assert_eq!(change.new, None);
+
+        for name in ["METADATA", "RECORD", "WHEEL"] {
+            let path = format!("test_pkg-1.0.0.dist-info/{}", name);
+            match editor.remove_file(&path) {
+                Err(WheelError::InvalidWheel(msg)) => assert!(msg.contains(&path), "{msg}"),
+                other => panic!("expected InvalidWheel, got {other:?}"),
+            }
+        }
+
+        editor.save(&output_path).unwrap();
+        assert!(read_archive_entry(&output_path, "test_pkg/__init__.py").is_none());
+        let saved = WheelEditor::open(&output_path).unwrap();
+        assert_eq!(saved.file_names().unwrap().len(), 3);
+        assert!(saved.record().find("test_pkg/__init__.py").is_none());
+        assert!(saved.validate().unwrap().is_valid());
+    }
+
+    #[test]
+    fn test_dist_info_version() {
+        let temp_dir = TempDir::new().unwrap();
+        let wheel_path = create_test_wheel(temp_dir.path());
+
+        let mut editor = WheelEditor::open(&wheel_path).unwrap();
+        assert_eq!(editor.dist_info_version(), Some("1.0.0"));
+
+        // Reflects the source archive, not pending metadata edits.
+        editor.set_version("2.0.0");
+        assert_eq!(editor.dist_info_version(), Some("1.0.0"));
+        assert!(editor.validate().unwrap().is_valid());
+    }
+
+    #[test]
+    fn test_dist_info_version_mismatch_reported() {
+        let temp_dir = TempDir::new().unwrap();
+        let wheel_path = temp_dir.path().join("test_pkg-1.0.0-py3-none-any.whl");
+        create_wheel_with_files(
+            &wheel_path,
+            "test_pkg-1.0.0.dist-info",
+            &[
+                ("test_pkg/__init__.py", b"\n"),
+                (
+                    "test_pkg-1.0.0.dist-info/METADATA",
+                    b"Metadata-Version: 2.1\nName: test-pkg\nVersion: 1.0.1\n",
+                ),
+                (
+                    "test_pkg-1.0.0.dist-info/WHEEL",
+                    b"Wheel-Version: 1.0\nRoot-Is-Purelib: true\nTag: py3-none-any\n",
+                ),
+            ],
+        );
+
+        let editor = WheelEditor::open(&wheel_path).unwrap();
+        assert_eq!(editor.dist_info_version(), Some("1.0.0"));
+        assert_eq!(editor.version(), "1.0.1");
+
+        let result = editor.validate().unwrap();
+        assert!(!result.is_valid());
+        assert!(
+            result.errors.iter().any(|e| matches!(
+                e,
+                ValidationError::DistInfoVersionMismatch {
+                    dist_info_version,
+                    metadata_version,
+                } if dist_info_version == "1.0.0" && metadata_version == "1.0.1"
+            )),
+            "expected DistInfoVersionMismatch, got {:?}",
+            result.errors
+        );
+    }
+
+    #[test]
+    fn test_modify_matching() {
+        let temp_dir = TempDir::new().unwrap();
+        let wheel_path = temp_dir.path().join("test_pkg-1.0.0-py3-none-any.whl");
+        let output_path = temp_dir.path().join("rewritten.whl");
+        create_wheel_with_files(
+            &wheel_path,
+            "test_pkg-1.0.0.dist-info",
+            &[
+                ("test_pkg/__init__.py", b"BUILD = '/build/abc/src'\n"),
+                ("test_pkg/config.txt", b"root=/build/abc/src\n"),
+                ("test_pkg/other.txt", b"unrelated\n"),
+                (
+                    "test_pkg-1.0.0.dist-info/METADATA",
+                    b"Metadata-Version: 2.1\nName: test-pkg\nVersion: 1.0.0\n",
+                ),
+                (
+                    "test_pkg-1.0.0.dist-info/WHEEL",
+                    b"Wheel-Version: 1.0\nRoot-Is-Purelib: true\nTag: py3-none-any\n",
+                ),
+            ],
+        );
+
+        let mut editor = WheelEditor::open(&wheel_path).unwrap();
+        let count = editor
+            .modify_matching("test_pkg/*.txt", |_, content| {
+                let text = std::str::from_utf8(content).ok()?;
+                text.contains("/build/abc")
+                    .then(|| text.replace("/build/abc", "/opt").into_bytes())
+            })
+            .unwrap();
+        assert_eq!(count, 1);
+
+        let expected = b"root=/opt/src\n";
+        let entry = editor.record().find("test_pkg/config.txt").unwrap();
+        assert_eq!(entry.hash, Some(hash_content(expected)));
+        assert_eq!(entry.size, Some(expected.len() as u64));
+
+        editor.save(&output_path).unwrap();
+        assert_eq!(
+            read_archive_entry(&output_path, "test_pkg/config.txt").unwrap(),
+            expected
+        );
+        assert_eq!(
+            read_archive_entry(&output_path, "test_pkg/__init__.py").unwrap(),
+            b"BUILD = '/build/abc/src'\n"
+        );
+        let saved = WheelEditor::open(&output_path).unwrap();
+        assert_eq!(
+            saved.record().find("test_pkg/config.txt").unwrap().hash,
+            Some(hash_content(expected))
+        );
+        assert!(saved.validate().unwrap().is_valid());
+    }
+
+    #[test]
+    fn test_validate_against() {
+        let temp_dir = TempDir::new().unwrap();
+        let wheel_path = create_test_wheel(temp_dir.path());
+
+        let editor = WheelEditor::open(&wheel_path).unwrap();
+        let trusted = editor.record().clone();
+        assert!(editor.validate_against(&trusted).unwrap().is_valid());
+
+        let mut untrusted = trusted.clone();
+        untrusted.find_mut("test_pkg/__init__.py").unwrap().hash =
+            Some(hash_content(b"something else"));
+        let result = editor.validate_against(&untrusted).unwrap();
+        assert!(matches!(
+            &result.errors[..],
+            [ValidationError::HashMismatch { path, .. }] if path == "test_pkg/__init__.py"
+        ));
+    }
+
+    #[test]
+    fn test_changes_tracks_edited_fields() {
+        let temp_dir = TempDir::new().unwrap();
+        let wheel_path = create_test_wheel(temp_dir.path());
+
+        let mut editor = WheelEditor::open(&wheel_path).unwrap();
+        assert!(editor.changes().unwrap().is_empty());
+
+        editor.set_version("1.0.1");
+        editor.set_summary("Updated summary");
+        assert_eq!(
+            editor.changes().unwrap(),
+            vec![
+                FieldChange {
+                    field: "Version".to_string(),
+                    old: Some("1.0.0".to_string()),
+                    new: Some("1.0.1".to_string()),
+                },
+                FieldChange {
+                    field: "Summary".to_string(),
+                    old: Some("Test package".to_string()),
+                    new: Some("Updated summary".to_string()),
+                },
+            ]
+        );
+
+        // Reverting a field drops it from the report.
+        editor.set_summary("Test package");
+        assert_eq!(editor.changes().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_changes_tracks_wheel_info_and_files() {
+        let temp_dir = TempDir::new().unwrap();
+        let wheel_path = create_test_wheel(temp_dir.path());
+
+        let mut editor = WheelEditor::open(&wheel_path).unwrap();
+        editor.set_platform_tag("linux_x86_64");
+        editor.add_file("test_pkg/extra.py", b"x = 1\n".to_vec());
+
+        let changes = editor.changes().unwrap();
+        assert_eq!(changes.len(), 2, "{changes:?}");
+        assert_eq!(changes[0].field, "WHEEL:Tag");
+        assert_eq!(changes[0].old.as_deref(), Some("py3-none-any"));
+        assert_eq!(changes[0].new.as_deref(), Some("py3-none-linux_x86_64"));
+        assert_eq!(
+            changes[1],
+            FieldChange {
+                field: "file:test_pkg/extra.py".to_string(),
+                old: None,
+                new: Some(hash_content(b"x = 1\n")),
+            }
+        );
+    }
+
+    #[test]
+    fn test_check_description_size() {
+        let temp_dir = TempDir::new().unwrap();
+        let wheel_path = create_test_wheel(temp_dir.path());
+
+        let mut editor = WheelEditor::open(&wheel_path).unwrap();
+        editor.set_description("x".repeat(100));
+        editor.set_description_content_type("text/markdown");
+        assert!(editor.check_description_size(100).is_ok());
+
+        match editor.check_description_size(99) {
+            Err(WheelError::Metadata(MetadataError::InvalidValue { field, reason })) => {
+                assert_eq!(field, "Description");
+                assert!(reason.contains("text/markdown"), "{reason}");
+                assert!(reason.contains("100 bytes"), "{reason}");
+            }
+            other => panic!("expected InvalidValue, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_strict_record_rejects_missing_entry() {
+        let temp_dir = TempDir::new().unwrap();
+        let wheel_path = create_test_wheel(temp_dir.path());
+        let output_path = temp_dir.path().join("strict.whl");
+
+        let mut editor = WheelEditor::open(&wheel_path).unwrap();
+        editor
+            .record_mut()
+            .entries
+            .retain(|entry| entry.path != "test_pkg/__init__.py");
+        editor.set_strict_record(true);
+        match editor.save(&output_path) {
+            Err(WheelError::InvalidWheel(msg)) => {
+                assert!(msg.contains("test_pkg/__init__.py"), "{msg}")
+            }
+            other => panic!("expected InvalidWheel, got {other:?}"),
+        }
+
+        // Default mode falls back to hashing the member.
+        editor.set_strict_record(false);
+        editor.save(&output_path).unwrap();
+        let saved = WheelEditor::open(&output_path).unwrap();
+        assert!(saved.record().find("test_pkg/__init__.py").is_some());
+    }
+
+    #[test]
+    fn test_strict_open_rejects_unrecorded_members() {
+        let temp_dir = TempDir::new().unwrap();
+        let wheel_path = create_test_wheel(temp_dir.path());
+        let output_path = temp_dir.path().join("recovered.whl");
+
+        // Append a member after RECORD was written
+        let file = std::fs::OpenOptions::new()
+            .read(true)
+            .write(true)
+            .open(&wheel_path)
+            .unwrap();
+        let mut zip = ZipWriter::new_append(file).unwrap();
+        zip.start_file("test_pkg/extra.py", SimpleFileOptions::default())
+            .unwrap();
+        zip.write_all(b"x = 1\n").unwrap();
+        zip.finish().unwrap();
+
+        let editor = WheelEditor::open(&wheel_path).unwrap();
+        assert_eq!(
+            editor.unrecorded_files().unwrap(),
+            vec!["test_pkg/extra.py"]
+        );
+        editor.save(&output_path).unwrap();
+        let saved = WheelEditor::open(&output_path).unwrap();
+        assert!(saved.unrecorded_files().unwrap().is_empty());
+        assert!(saved.strict(true).is_ok());
+
+        match WheelEditor::open(&wheel_path).unwrap().strict(true).err() {
+            Some(WheelError::InvalidWheel(msg)) => {
+                assert!(msg.contains("test_pkg/extra.py"), "{msg}")
+            }
+            other => panic!("expected InvalidWheel, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_hashless_patterns() {
+        let temp_dir = TempDir::new().unwrap();
+        let wheel_path = temp_dir.path().join("test_pkg-1.0.0-py3-none-any.whl");
+        create_wheel_with_files(
+            &wheel_path,
+            "test_pkg-1.0.0.dist-info",
+            &[
+                (
+                    "test_pkg-1.0.0.dist-info/METADATA",
+                    b"Metadata-Version: 2.1\nName: test-pkg\nVersion: 1.0.0\n",
+                ),
+                (
+                    "test_pkg-1.0.0.dist-info/WHEEL",
+                    b"Wheel-Version: 1.0\nRoot-Is-Purelib: false\nTag: py3-none-any\n",
+                ),
+                ("test_pkg/__init__.py", b"x = 1\n"),
+                ("test_pkg/__pycache__/__init__.cpython-312.pyc", b"\x00\x01"),
+            ],
+        );
+        let output_path = temp_dir.path().join("hashless.whl");
+
+        let mut editor = WheelEditor::open(&wheel_path).unwrap();
+        match editor.set_hashless_patterns(&["*"]) {
+            Err(WheelError::Record(RecordError::HashRequired { path, .. })) => {
+                assert_eq!(path, "test_pkg-1.0.0.dist-info/METADATA")
+            }
+            other => panic!("expected HashRequired, got {other:?}"),
+        }
+
+        editor.set_hashless_patterns(&["*.pyc"]).unwrap();
+        let projected = editor.projected_record().unwrap();
+        editor.save(&output_path).unwrap();
+        let record = read_archive_entry(&output_path, "test_pkg-1.0.0.dist-info/RECORD").unwrap();
+        assert_eq!(projected.serialize(), String::from_utf8(record).unwrap());
+
+        let saved = WheelEditor::open(&output_path).unwrap();
+
+        let pyc = saved
+            .record()
+            .find("test_pkg/__pycache__/__init__.cpython-312.pyc")
+            .unwrap();
+        assert_eq!((pyc.hash.as_deref(), pyc.size), (None, None));
+        for path in ["test_pkg/__init__.py", "test_pkg-1.0.0.dist-info/METADATA"] {
+            assert!(saved.record().find(path).unwrap().hash.is_some(), "{path}");
+        }
+    }
+
+    #[test]
+    fn test_set_build_number() {
+        let temp_dir = TempDir::new().unwrap();
+        let wheel_path = create_test_wheel(temp_dir.path());
+        let output_path = temp_dir.path().join("rebuilt.whl");
+
+        let mut editor = WheelEditor::open(&wheel_path).unwrap();
+        assert_eq!(editor.build_tag(), None);
+        editor.set_build_number(1);
+        assert_eq!(editor.filename(), "test_pkg-1.0.0-1-py3-none-any.whl");
+        assert_eq!(editor.wheel_filename(), editor.filename());
+        editor.save(&output_path).unwrap();
+
+        let mut saved = WheelEditor::open(&output_path).unwrap();
+        assert_eq!(saved.build_tag().unwrap().number, 1);
+        let next = saved.build_tag().unwrap().number + 1;
+        saved.set_build_number(next);
+        assert_eq!(saved.wheel_info().build.as_deref(), Some("2"));
+    }
+
+    #[test]
+    fn test_verify_hashes() {
+        let temp_dir = TempDir::new().unwrap();
+        let wheel_path = create_test_wheel(temp_dir.path());
+        let output_path = temp_dir.path().join("verified.whl");
+
+        let mut editor = WheelEditor::open(&wheel_path).unwrap();
+        editor.set_summary("Verified");
+        editor.set_verify_hashes(true);
+        assert!(!editor.is_constant_time().unwrap());
+        editor.save(&output_path).unwrap();
+        let saved = WheelEditor::open(&output_path).unwrap();
+        assert!(saved.validate().unwrap().is_valid());
+
+        // A RECORD that no longer matches the content fails the save
+        let entry = editor
+            .record_mut()
+            .find_mut("test_pkg/__init__.py")
+            .unwrap();
+        entry.hash = Some(hash_content(b"something else"));
+        match editor.save(&output_path) {
+            Err(WheelError::Record(RecordError::HashMismatch { path, .. })) => {
+                assert_eq!(path, "test_pkg/__init__.py")
+            }
+            other => panic!("expected HashMismatch, got {other:?}"),
+        }
+
+        editor.set_verify_hashes(false);
+        editor.save(&output_path).unwrap();
+    }
+
+    #[test]
+    fn test_rewrites_keep_stored_compression() {
+        let temp_dir = TempDir::new().unwrap();
+        let wheel_path = temp_dir.path().join("test_pkg-1.0.0-py3-none-any.whl");
+        let output_path = temp_dir.path().join("out.whl");
+        let stored =
+            SimpleFileOptions::default().compression_method(zip::CompressionMethod::Stored);
+        let mut zip = ZipWriter::new(File::create(&wheel_path).unwrap());
+        for (name, content) in [
+            ("test_pkg/_native.so", b"not really a library".as_slice()),
+            (
+                "test_pkg-1.0.0.dist-info/METADATA",
+                b"Metadata-Version: 2.1\nName: test-pkg\nVersion: 1.0.0\n".as_slice(),
+            ),
+            (
+                "test_pkg-1.0.0.dist-info/WHEEL",
+                b"Wheel-Version: 1.0\nRoot-Is-Purelib: false\nTag: py3-none-any\n".as_slice(),
+            ),
+            ("test_pkg-1.0.0.dist-info/RECORD", b"".as_slice()),
+        ] {
+            zip.start_file(name, stored).unwrap();
+            zip.write_all(content).unwrap();
+        }
+        zip.finish().unwrap();
+
+        let methods = |path: &Path| {
+            let mut archive = zip::ZipArchive::new(File::open(path).unwrap()).unwrap();
+            (0..archive.len())
+                .map(|i| {
+                    let entry = archive.by_index_raw(i).unwrap();
+                    (entry.name().to_string(), entry.compression())
+                })
+                .collect::<HashMap<_, _>>()
+        };
+
+        let mut editor = WheelEditor::open(&wheel_path).unwrap();
+        editor.set_summary("Stored");
+        editor.set_platform_tag("linux_x86_64");
+        editor.add_file("test_pkg/_native.so", b"a patched library".to_vec());
+        editor.add_file("test_pkg/extra.py", b"y = 2\n".to_vec());
+        editor.save(&output_path).unwrap();
+
+        let saved = methods(&output_path);
+        for name in [
+            "test_pkg/_native.so",
+            "test_pkg-1.0.0.dist-info/METADATA",
+            "test_pkg-1.0.0.dist-info/WHEEL",
+            "test_pkg-1.0.0.dist-info/RECORD",
+        ] {
+            assert_eq!(saved[name], zip::CompressionMethod::Stored, "{name}");
+        }
+        assert_eq!(saved["test_pkg/extra.py"], zip::CompressionMethod::Deflated);
+
+        // An explicit method applies to every generated entry
+        editor.set_compression(Some(zip::CompressionMethod::Deflated));
+        editor.save(&output_path).unwrap();
+        assert!(
+            methods(&output_path)
+                .values()
+                .all(|&method| method == zip::CompressionMethod::Deflated)
+        );
+    }
+
+    #[test]
+    fn test_compression_level() {
+        let temp_dir = TempDir::new().unwrap();
+        let wheel_path = create_test_wheel(temp_dir.path());
+        let output_path = temp_dir.path().join("out.whl");
+        let content: Vec<u8> = (0..20_000u32)
+            .flat_map(|i| format!("line {} of {}\n", i % 97, i % 13).into_bytes())
+            .collect();
+
+        let mut editor = WheelEditor::open(&wheel_path).unwrap();
+        editor.add_file("test_pkg/data.txt", content.clone());
+        let mut compressed_size = |level| {
+            editor.set_compression_level(Some(level)).unwrap();
+            editor.save(&output_path).unwrap();
+            let mut archive = zip::ZipArchive::new(File::open(&output_path).unwrap()).unwrap();
+            let entry = archive.by_name("test_pkg/data.txt").unwrap();
+            entry.compressed_size()
+        };
+        let fast = compressed_size(1);
+        let best = compressed_size(9);
+        assert!(best < fast, "level 9: {best}, level 1: {fast}");
+        assert_eq!(
+            read_archive_entry(&output_path, "test_pkg/data.txt").unwrap(),
+            content
+        );
+
+        // Level 0 is still Deflate, just without compressing
+        let none = compressed_size(0);
+        assert!(none >= content.len() as u64, "level 0: {none}");
+        let mut archive = zip::ZipArchive::new(File::open(&output_path).unwrap()).unwrap();
+        assert_eq!(
+            archive.by_name("test_pkg/data.txt").unwrap().compression(),
+            zip::CompressionMethod::Deflated
+        );
+        assert_eq!(
+            read_archive_entry(&output_path, "test_pkg/data.txt").unwrap(),
+            content
+        );
+
+        editor.set_compression_level(Some(9)).unwrap();
+        for level in [10, -1] {
+            assert!(matches!(
+                editor.set_compression_level(Some(level)),
+                Err(WheelError::InvalidWheel(_))
+            ));
+        }
+        assert_eq!(editor.write_options().compression_level, Some(9));
+    }
+
+    #[test]
+    fn test_source_date_epoch() {
+        let temp_dir = TempDir::new().unwrap();
+        let wheel_path = create_test_wheel(temp_dir.path());
+        let first = temp_dir.path().join("first.whl");
+        let second = temp_dir.path().join("second.whl");
+
+        let mut editor = WheelEditor::open(&wheel_path).unwrap();
+        editor.set_summary("Reproducible");
+        editor.add_file("test_pkg/extra.py", b"y = 2\n".to_vec());
+        editor.set_source_date_epoch(Some(1_700_000_000));
+        editor.save(&first).unwrap();
+        editor.save(&second).unwrap();
+        assert_eq!(
+            std::fs::read(&first).unwrap(),
+            std::fs::read(&second).unwrap()
+        );
+
+        let mut archive = zip::ZipArchive::new(File::open(&first).unwrap()).unwrap();
+        for name in ["test_pkg-1.0.0.dist-info/METADATA", "test_pkg/extra.py"] {
+            let entry = archive.by_name(name).unwrap();
+            let modified = entry.last_modified().unwrap();
+            assert_eq!(
+                (modified.year(), modified.month(), modified.day()),
+                (2023, 11, 14),
+                "{name}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_hash_algorithm() {
+        let temp_dir = TempDir::new().unwrap();
+        let wheel_path = create_test_wheel(temp_dir.path());
+        let output_path = temp_dir.path().join("sha512.whl");
+
+        let mut editor = WheelEditor::open(&wheel_path).unwrap();
+        editor.set_hash_algorithm(HashAlgorithm::Sha512);
+        editor.add_file("test_pkg/extra.py", b"y = 2\n".to_vec());
+        let projected = editor.projected_record().unwrap();
+        editor.save(&output_path).unwrap();
+        let record = read_archive_entry(&output_path, "test_pkg-1.0.0.dist-info/RECORD").unwrap();
+        assert_eq!(projected.serialize(), String::from_utf8(record).unwrap());
+
+        let saved = WheelEditor::open(&output_path).unwrap();
+        let hash = |path: &str| saved.record().find(path).unwrap().hash.clone().unwrap();
+        assert!(hash("test_pkg/extra.py").starts_with("sha512="));
+        assert!(hash("test_pkg-1.0.0.dist-info/METADATA").starts_with("sha512="));
+        // Untouched members keep their original hash
+        assert!(hash("test_pkg/__init__.py").starts_with("sha256="));
+        assert!(saved.validate().unwrap().is_valid());
+
+        // Rewriting a file with its own content isn't a change, whatever
+        // algorithm its RECORD hash uses
+        let mut saved = saved;
+        saved.add_file("test_pkg/extra.py", b"y = 2\n".to_vec());
+        assert!(saved.changes().unwrap().is_empty());
+        saved.add_file("test_pkg/extra.py", b"y = 3\n".to_vec());
+        let changes = saved.changes().unwrap();
+        assert_eq!(changes.len(), 1);
+        assert!(changes[0].new.as_deref().unwrap().starts_with("sha512="));
+    }
+
+    #[test]
+    fn test_metadata_differences_ignoring_platform() {
+        let temp_dir = TempDir::new().unwrap();
+        let wheel = |platform: &str, tag: &str| {
+            let path = temp_dir
+                .path()
+                .join(format!("test_pkg-1.0.0-py3-none-{tag}.whl"));
+            let metadata = format!(
+                "Metadata-Version: 2.1\nName: test-pkg\nVersion: 1.0.0\nPlatform: {platform}\n"
+            );
+            let wheel =
+                format!("Wheel-Version: 1.0\nRoot-Is-Purelib: false\nTag: py3-none-{tag}\n");
+            create_wheel_with_files(
+                &path,
+                "test_pkg-1.0.0.dist-info",
+                &[
+                    ("test_pkg-1.0.0.dist-info/METADATA", metadata.as_bytes()),
+                    ("test_pkg-1.0.0.dist-info/WHEEL", wheel.as_bytes()),
+                ],
+            );
+            path
+        };
+        let linux = wheel("linux", "manylinux_2_17_x86_64");
+        let macos = wheel("macosx", "macosx_11_0_arm64");
+
+        let ignored = metadata_differences(&linux, &macos, &["Platform"]).unwrap();
+        assert!(ignored.is_empty(), "{ignored:?}");
+        assert!(metadata_matches(&linux, &macos, &["Platform"]).unwrap());
+        assert!(!metadata_matches(&linux, &macos, &[]).unwrap());
+        assert_eq!(
+            metadata_differences(&linux, &macos, &[]).unwrap(),
+            vec![FieldChange {
+                field: "Platform".to_string(),
+                old: Some("linux".to_string()),
+                new: Some("macosx".to_string()),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_set_python_tag_checked_updates_wheel_and_filename() {
+        let temp_dir = TempDir::new().unwrap();
+        let wheel_path = create_test_wheel(temp_dir.path());
+        let output_path = temp_dir.path().join("out.whl");
+
+        let mut editor = WheelEditor::open(&wheel_path).unwrap();
+        assert!(editor.set_python_tag_checked("python3").is_err());
+        assert!(editor.set_python_tag_checked("py2.py3").is_err());
+        assert_eq!(editor.python_tag(), Some("py3"));
+
+        editor.set_python_tag_checked("cp310").unwrap();
+        assert_eq!(editor.filename(), "test_pkg-1.0.0-cp310-none-any.whl");
+        editor.save(&output_path).unwrap();
+
+        let wheel = read_archive_entry(&output_path, "test_pkg-1.0.0.dist-info/WHEEL").unwrap();
+        let wheel = String::from_utf8(wheel).unwrap();
+        assert!(wheel.contains("Tag: cp310-none-any\n"), "{wheel}");
+        assert!(!wheel.contains("py3-none-any"), "{wheel}");
+    }
+
+    #[test]
+    fn test_unpack_extracts_every_member() {
+        let temp_dir = TempDir::new().unwrap();
+        let wheel_path = create_test_wheel(temp_dir.path());
+        let dest = temp_dir.path().join("unpacked");
+
+        let editor = WheelEditor::open(&wheel_path).unwrap();
+        editor.unpack(&dest).unwrap();
+
+        let names = editor.file_names().unwrap();
+        for name in &names {
+            let on_disk = std::fs::read(dest.join(name)).unwrap();
+            assert_eq!(
+                Some(on_disk),
+                read_archive_entry(&wheel_path, name),
+                "{name}"
+            );
+        }
+        let mut extracted = 0;
+        let mut dirs = vec![dest.clone()];
+        while let Some(dir) = dirs.pop() {
+            for entry in std::fs::read_dir(dir).unwrap() {
+                let path = entry.unwrap().path();
+                if path.is_dir() {
+                    dirs.push(path);
+                } else {
+                    extracted += 1;
+                }
+            }
+        }
+        assert_eq!(extracted, names.len());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_unpack_drops_setuid_bits() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let temp_dir = TempDir::new().unwrap();
+        let wheel_path = temp_dir.path().join("test_pkg-1.0.0-py3-none-any.whl");
+        let mut zip = ZipWriter::new(File::create(&wheel_path).unwrap());
+        for (name, mode, content) in [
+            ("test_pkg/__init__.py", 0o644, &b""[..]),
+            ("test_pkg/tool", 0o4755, &b"#!/bin/sh\n"[..]),
+            (
+                "test_pkg-1.0.0.dist-info/METADATA",
+                0o644,
+                &b"Metadata-Version: 2.1\nName: test-pkg\nVersion: 1.0.0\n"[..],
+            ),
+            (
+                "test_pkg-1.0.0.dist-info/WHEEL",
+                0o644,
+                &b"Wheel-Version: 1.0\nRoot-Is-Purelib: true\nTag: py3-none-any\n"[..],
+            ),
+            ("test_pkg-1.0.0.dist-info/RECORD", 0o644, &b""[..]),
+        ] {
+            let options = SimpleFileOptions::default().unix_permissions(mode);
+            zip.start_file(name, options).unwrap();
+            zip.write_all(content).unwrap();
+        }
+        zip.finish().unwrap();
+        let dest = temp_dir.path().join("unpacked");
+
+        let editor = WheelEditor::open(&wheel_path).unwrap();
+        editor.unpack(&dest).unwrap();
+
+        let mode = |name: &str| {
+            let permissions = std::fs::metadata(dest.join(name)).unwrap().permissions();
+            permissions.mode() & 0o7777
+        };
+        assert_eq!(mode("test_pkg/tool"), 0o755);
+        assert_eq!(mode("test_pkg/__init__.py"), 0o644);
+    }
+
+    #[test]
+    fn test_unpack_refuses_unsafe_paths() {
+        let temp_dir = TempDir::new().unwrap();
+        let wheel_path = temp_dir.path().join("test_pkg-1.0.0-py3-none-any.whl");
+        create_wheel_with_files(
+            &wheel_path,
+            "test_pkg-1.0.0.dist-info",
+            &[
+                ("test_pkg/__init__.py", b""),
+                ("../evil.txt", b"gotcha"),
+                (
+                    "test_pkg-1.0.0.dist-info/METADATA",
+                    b"Metadata-Version: 2.1\nName: test-pkg\nVersion: 1.0.0\n",
+                ),
+                (
+                    "test_pkg-1.0.0.dist-info/WHEEL",
+                    b"Wheel-Version: 1.0\nRoot-Is-Purelib: true\nTag: py3-none-any\n",
+                ),
+            ],
+        );
+        let dest = temp_dir.path().join("a").join("unpacked");
+
+        let editor = WheelEditor::open(&wheel_path).unwrap();
+        let err = editor.unpack(&dest).unwrap_err();
+        assert!(err.to_string().contains("../evil.txt"), "{err}");
+        assert!(!dest.exists());
+        assert!(!temp_dir.path().join("a").join("evil.txt").exists());
+    }
+
+    #[test]
+    fn test_can_patch_in_place_rejects_grown_metadata() {
+        let temp_dir = TempDir::new().unwrap();
+        let wheel_path = create_test_wheel(temp_dir.path());
+
+        let mut editor = WheelEditor::open(&wheel_path).unwrap();
+        editor.set_description(
+            (0..200)
+                .map(|i| format!("Line {i} of a much longer description."))
+                .collect::<Vec<_>>()
+                .join("\n"),
+        );
+        assert!(!editor.can_patch_in_place());
+
+        // Renaming the dist-info directory changes member names
+        let mut editor = WheelEditor::open(&wheel_path).unwrap();
+        editor.set_version("1.0.1");
+        assert!(!editor.can_patch_in_place());
+    }
+
+    #[test]
+    fn test_can_patch_in_place_accepts_shrunk_metadata() {
+        let temp_dir = TempDir::new().unwrap();
+        let wheel_path = temp_dir.path().join("test_pkg-1.0.0-py3-none-any.whl");
+        let description = (0..200)
+            .map(|i| format!("Line {i} of a much longer description."))
+            .collect::<Vec<_>>()
+            .join("\n");
+        let metadata =
+            format!("Metadata-Version: 2.1\nName: test-pkg\nVersion: 1.0.0\n\n{description}\n");
+        let wheel = b"Wheel-Version: 1.0\nRoot-Is-Purelib: true\nTag: py3-none-any\n";
+        let record = format!(
+            "test_pkg-1.0.0.dist-info/METADATA,{},{}\ntest_pkg-1.0.0.dist-info/WHEEL,{},{}\ntest_pkg-1.0.0.dist-info/RECORD,,\n",
+            hash_content(metadata.as_bytes()),
+            metadata.len(),
+            hash_content(wheel),
+            wheel.len(),
+        );
+
+        // Stored members, so each slot is exactly the original length
+        let stored =
+            SimpleFileOptions::default().compression_method(zip::CompressionMethod::Stored);
+        let mut zip = ZipWriter::new(File::create(&wheel_path).unwrap());
+        for (name, content) in [
+            ("test_pkg-1.0.0.dist-info/METADATA", metadata.as_bytes()),
+            ("test_pkg-1.0.0.dist-info/WHEEL", wheel.as_slice()),
+            ("test_pkg-1.0.0.dist-info/RECORD", record.as_bytes()),
+        ] {
+            zip.start_file(name, stored).unwrap();
+            zip.write_all(content).unwrap();
+        }
+        zip.finish().unwrap();
+
+        let mut editor = WheelEditor::open(&wheel_path).unwrap();
+        editor.set_description("A short description.");
+        assert!(editor.can_patch_in_place());
+    }
+
+    #[test]
+    fn test_dedup_tags_collapses_repeated_tag() {
+        let temp_dir = TempDir::new().unwrap();
+        let wheel_path = temp_dir.path().join("test_pkg-1.0.0-py3-none-any.whl");
+        let output_path = temp_dir.path().join("out.whl");
+        create_wheel_with_files(
+            &wheel_path,
+            "test_pkg-1.0.0.dist-info",
+            &[
+                ("test_pkg/__init__.py", b""),
+                (
+                    "test_pkg-1.0.0.dist-info/METADATA",
+                    b"Metadata-Version: 2.1\nName: test-pkg\nVersion: 1.0.0\n",
+                ),
+                (
+                    "test_pkg-1.0.0.dist-info/WHEEL",
+                    b"Wheel-Version: 1.0\nRoot-Is-Purelib: true\nTag: py3-none-any\nTag: py3-none-any\n",
+                ),
+            ],
+        );
+
+        let mut editor = WheelEditor::open(&wheel_path).unwrap();
+        assert_eq!(editor.dedup_tags(), 1);
+        assert_eq!(editor.dedup_tags(), 0);
+        editor.save(&output_path).unwrap();
+
+        let wheel = read_archive_entry(&output_path, "test_pkg-1.0.0.dist-info/WHEEL").unwrap();
+        let wheel = String::from_utf8(wheel).unwrap();
+        assert_eq!(wheel.matches("Tag: py3-none-any").count(), 1, "{wheel}");
+    }
+
+    #[test]
+    fn test_add_and_remove_tag() {
+        let temp_dir = TempDir::new().unwrap();
+        let wheel_path = create_test_wheel(temp_dir.path());
+        let output_path = temp_dir.path().join("out.whl");
+
+        let mut editor = WheelEditor::open(&wheel_path).unwrap();
+        assert!(editor.add_tag(WheelTag::parse("py2-none-any").unwrap()));
+        assert!(!editor.add_tag(WheelTag::parse("py2-none-any").unwrap()));
+        assert_eq!(editor.filename(), "test_pkg-1.0.0-py3.py2-none-any.whl");
+        editor.save(&output_path).unwrap();
+
+        let wheel = read_archive_entry(&output_path, "test_pkg-1.0.0.dist-info/WHEEL").unwrap();
+        let wheel = String::from_utf8(wheel).unwrap();
+        assert!(
+            wheel.contains("Tag: py3-none-any\nTag: py2-none-any\n"),
+            "{wheel}"
+        );
+
+        // A wheel with no tags can't be saved
+        let mut editor = WheelEditor::open(&output_path).unwrap();
+        assert!(editor.remove_tag(&WheelTag::parse("py3-none-any").unwrap()));
+        assert!(editor.remove_tag(&WheelTag::parse("py2-none-any").unwrap()));
+        assert!(!editor.remove_tag(&WheelTag::parse("py2-none-any").unwrap()));
+        assert!(matches!(
+            editor.save(temp_dir.path().join("empty.whl")),
+            Err(WheelError::WheelInfo(WheelInfoError::MissingField(_)))
+        ));
+    }
+
+    #[test]
+    fn test_set_platform_tag_checked_rejects_libc_downgrade() {
+        let temp_dir = TempDir::new().unwrap();
+        let wheel_path = create_test_wheel(temp_dir.path());
+
+        let mut editor = WheelEditor::open(&wheel_path).unwrap();
+        assert_eq!(editor.libc_version(), None);
+        editor
+            .set_platform_tag_checked("manylinux_2_28_x86_64")
+            .unwrap();
+        assert_eq!(editor.libc_version(), Some((2, 28)));
+
+        match editor.set_platform_tag_checked("manylinux2014_x86_64") {
+            Err(WheelError::WheelInfo(WheelInfoError::LibcDowngrade {
+                old_version,
+                new_version,
+                ..
+            })) => {
+                assert_eq!(old_version, "2.28");
+                assert_eq!(new_version, "2.17");
+            }
+            other => panic!("expected LibcDowngrade, got {other:?}"),
+        }
+        assert_eq!(editor.platform_tag(), Some("manylinux_2_28_x86_64"));
+
+        // Upgrades and switching libc family are allowed.
+        editor
+            .set_platform_tag_checked("manylinux_2_34_x86_64")
+            .unwrap();
+        editor
+            .set_platform_tag_checked("musllinux_1_1_x86_64")
+            .unwrap();
+        assert_eq!(editor.libc_version(), Some((1, 1)));
+
+        assert!(matches!(
+            editor.set_platform_tag_checked(""),
+            Err(WheelError::WheelInfo(WheelInfoError::InvalidTag(_)))
+        ));
+        assert_eq!(editor.platform_tag(), Some("musllinux_1_1_x86_64"));
+    }
+
+    #[test]
+    fn test_refresh_record_hash() {
+        let temp_dir = TempDir::new().unwrap();
+        let wheel_path = create_test_wheel(temp_dir.path());
+        let output_path = temp_dir.path().join("refreshed.whl");
+
+        let mut editor = WheelEditor::open(&wheel_path).unwrap();
+        let expected = hash_content(b"__version__ = '1.0.0'\n");
+
+        // Leave a stale hash behind, then repair just that entry.
+        let entry = editor.record_mut().find_mut("test_pkg/__init__.py").unwrap();
+        entry.hash = Some("sha256=stale".to_string());
+        entry.size = Some(1);
+        editor.refresh_record_hash("test_pkg/__init__.py").unwrap();
+
+        let entry = editor.record().find("test_pkg/__init__.py").unwrap();
+        assert_eq!(entry.hash.as_deref(), Some(expected.as_str()));
+        assert_eq!(entry.size, Some(22));
+
+        editor.save(&output_path).unwrap();
+        let result = WheelEditor::open(&output_path).unwrap().validate().unwrap();
+        assert!(result.is_valid(), "{:?}", result);
+    }
+
+    #[test]
+    fn test_refresh_record_hash_uses_modified_content() {
+        let temp_dir = TempDir::new().unwrap();
+        let wheel_path = create_test_wheel(temp_dir.path());
+
+        let mut editor = WheelEditor::open(&wheel_path).unwrap();
+        editor
+            .modified_files
+            .insert("test_pkg/__init__.py".to_string(), b"x = 2\n".to_vec());
+        editor.refresh_record_hash("test_pkg/__init__.py").unwrap();
+
+        let entry = editor.record().find("test_pkg/__init__.py").unwrap();
+        assert_eq!(entry.hash, Some(hash_content(b"x = 2\n")));
+        assert_eq!(entry.size, Some(6));
+    }
+
+    #[test]
+    fn test_refresh_record_hash_missing_file_errors() {
+        let temp_dir = TempDir::new().unwrap();
+        let wheel_path = create_test_wheel(temp_dir.path());
+
+        let mut editor = WheelEditor::open(&wheel_path).unwrap();
+        match editor.refresh_record_hash("test_pkg/missing.py") {
+            Err(WheelError::InvalidWheel(msg)) => assert!(msg.contains("not found"), "{msg}"),
+            other => panic!("expected InvalidWheel, got {other:?}"),
+        }
+        assert!(editor.record().find("test_pkg/missing.py").is_none());
+    }
+
+    #[test]
+    fn test_check_install_layout() {
+        let temp_dir = TempDir::new().unwrap();
+        let wheel_path = create_test_wheel(temp_dir.path());
+
+        let mut editor = WheelEditor::open(&wheel_path).unwrap();
+        assert!(editor.check_install_layout().unwrap().is_empty());
+
+        editor.add_file("NOTES.txt", b"stray".to_vec());
+        assert_eq!(editor.check_install_layout().unwrap(), vec!["NOTES.txt"]);
+    }
+
+    #[test]
+    fn test_validate_metadata() {
+        let temp_dir = TempDir::new().unwrap();
+        let wheel_path = create_test_wheel(temp_dir.path());
+
+        let mut editor = WheelEditor::open(&wheel_path).unwrap();
+        assert!(editor.validate_metadata().is_ok());
+
+        editor.add_requires_dist("my pkg>=1");
+        editor.set_description_content_type("text/markdwon");
+        assert_eq!(editor.validate_dependencies().unwrap_err().len(), 1);
+        assert_eq!(editor.validate_metadata().unwrap_err().len(), 2);
+
+        editor.add_obsoletes_dist("Gorgon (<3.0); os_name == \"posix\"");
+        editor.add_obsoletes_dist("Gorgon 3");
+        assert_eq!(editor.validate_dependencies().unwrap_err().len(), 2);
+        assert_eq!(editor.remove_obsoletes_dist("gorgon"), 2);
+        assert!(editor.obsoletes_dist().is_empty());
+    }
+
+    #[test]
+    fn test_strict_metadata_rejects_save() {
+        let temp_dir = TempDir::new().unwrap();
+        let wheel_path = create_test_wheel(temp_dir.path());
+        let output_path = temp_dir.path().join("strict.whl");
+
+        let mut editor = WheelEditor::open(&wheel_path).unwrap();
+        editor
+            .metadata_mut()
+            .set_values("License-Expression", vec!["MIT".to_string()])
+            .unwrap();
+        let problems = editor.validate_metadata().unwrap_err();
+        assert_eq!(problems.len(), 1);
+        assert!(problems[0].contains("License-Expression"), "{problems:?}");
+
+        // Not strict by default
+        editor.save(&output_path).unwrap();
+
+        editor.set_strict_metadata(true);
+        assert!(editor.strict_metadata());
+        // A check before writing, not a write option, so a METADATA-only
+        // save keeps the fast path
+        assert!(!editor.uses_extended_writer());
+        match editor.save(&output_path) {
+            Err(WheelError::InvalidWheel(msg)) => assert!(msg.contains("2.4"), "{msg}"),
+            other => panic!("expected InvalidWheel, got {other:?}"),
+        }
+        // The rejected save left the earlier output untouched
+        assert!(WheelEditor::open(&output_path).is_ok());
+
+        editor.metadata_mut().metadata_version = "2.4".to_string();
+        editor.save(&output_path).unwrap();
+    }
+
+    #[test]
+    fn test_members() {
+        let temp_dir = TempDir::new().unwrap();
+        let wheel_path = create_test_wheel(temp_dir.path());
+
+        let editor = WheelEditor::open(&wheel_path).unwrap();
+        let members = editor.members().unwrap();
+        assert_eq!(members.len(), 4);
+        assert_eq!(
+            members.iter().map(|m| m.name.as_str()).collect::<Vec<_>>(),
+            editor.file_names().unwrap()
+        );
+
+        let metadata = members
+            .iter()
+            .find(|m| m.name == "test_pkg-1.0.0.dist-info/METADATA")
+            .unwrap();
+        assert_eq!(
+            metadata.uncompressed_size,
+            editor.record().find(&metadata.name).unwrap().size.unwrap()
+        );
+        let wheel = members
+            .iter()
+            .find(|m| m.name == "test_pkg-1.0.0.dist-info/WHEEL")
+            .unwrap();
+        assert_eq!(
+            wheel.uncompressed_size,
+            editor.record().find(&wheel.name).unwrap().size.unwrap()
+        );
+        assert!(members.iter().all(|m| !m.is_dir && m.compressed_size > 0));
+    }
+
+    #[test]
+    fn test_read_file() {
+        let temp_dir = TempDir::new().unwrap();
+        let wheel_path = create_test_wheel(temp_dir.path());
+
+        let mut editor = WheelEditor::open(&wheel_path).unwrap();
+        let original = read_archive_entry(&wheel_path, "test_pkg/__init__.py").unwrap();
+        assert_eq!(editor.read_file("test_pkg/__init__.py").unwrap(), original);
+
+        // Pending content wins over the source
+        editor
+            .modify_matching("test_pkg/*.py", |_, _| Some(b"x = 1\n".to_vec()))
+            .unwrap();
+        assert_eq!(
+            editor.read_file("test_pkg/__init__.py").unwrap(),
+            b"x = 1\n"
+        );
+        editor.add_file("test_pkg/extra.py", b"y = 2\n".to_vec());
+        assert_eq!(editor.read_file("test_pkg/extra.py").unwrap(), b"y = 2\n");
+
+        editor.remove_file("test_pkg/__init__.py").unwrap();
+        for missing in ["test_pkg/missing.py", "test_pkg/__init__.py"] {
+            match editor.read_file(missing) {
+                Err(WheelError::InvalidWheel(msg)) => assert!(msg.contains(missing), "{msg}"),
+                other => panic!("expected InvalidWheel, got {other:?}"),
+            }
+        }
+    }
+
+    #[test]
+    fn test_file_names_skip_directories() {
+        let temp_dir = TempDir::new().unwrap();
+        let wheel_path = temp_dir.path().join("test_pkg-1.0.0-py3-none-any.whl");
+        create_wheel_with_files(
+            &wheel_path,
+            "test_pkg-1.0.0.dist-info",
+            &[
+                ("test_pkg/", b""),
+                ("test_pkg/__init__.py", b"\n"),
+                (
+                    "test_pkg-1.0.0.dist-info/METADATA",
+                    b"Metadata-Version: 2.1\nName: test-pkg\nVersion: 1.0.0\n",
+                ),
+                (
+                    "test_pkg-1.0.0.dist-info/WHEEL",
+                    b"Wheel-Version: 1.0\nRoot-Is-Purelib: true\nTag: py3-none-any\n",
+                ),
+            ],
+        );
 
         let editor = WheelEditor::open(&wheel_path).unwrap();
-        assert_eq!(editor.name(), "test-pkg");
-        assert_eq!(editor.version(), "1.0.0");
-        assert_eq!(editor.summary(), Some("Test package"));
+        assert_eq!(
+            editor.file_names().unwrap(),
+            vec![
+                "test_pkg/__init__.py",
+                "test_pkg-1.0.0.dist-info/METADATA",
+                "test_pkg-1.0.0.dist-info/WHEEL",
+                "test_pkg-1.0.0.dist-info/RECORD",
+            ]
+        );
+        let members = editor.members().unwrap();
+        assert_eq!(members.len(), 5);
+        assert!(members[0].is_dir);
     }
 
     #[test]
-    fn test_modify_and_save() {
+    fn test_downgrade_metadata_version() {
         let temp_dir = TempDir::new().unwrap();
-        let wheel_path = create_test_wheel(temp_dir.path());
-        let output_path = temp_dir.path().join("test_pkg-1.0.1-py3-none-any.whl");
+        let wheel_path = temp_dir.path().join("test_pkg-1.0.0-py3-none-any.whl");
+        let output_path = temp_dir.path().join("out.whl");
+        create_wheel_with_files(
+            &wheel_path,
+            "test_pkg-1.0.0.dist-info",
+            &[
+                ("test_pkg/__init__.py", b""),
+                (
+                    "test_pkg-1.0.0.dist-info/METADATA",
+                    b"Metadata-Version: 2.4\nName: test-pkg\nVersion: 1.0.0\nLicense-Expression: MIT\nLicense-File: LICENSE\n",
+                ),
+                (
+                    "test_pkg-1.0.0.dist-info/WHEEL",
+                    b"Wheel-Version: 1.0\nRoot-Is-Purelib: true\nTag: py3-none-any\n",
+                ),
+            ],
+        );
 
         let mut editor = WheelEditor::open(&wheel_path).unwrap();
-        editor.set_version("1.0.1");
-        editor.set_summary("Updated summary");
+        let changes = editor.downgrade_metadata_version((2, 1)).unwrap();
+        assert_eq!(changes.len(), 3, "{changes:?}");
         editor.save(&output_path).unwrap();
 
-        // Verify the output
-        let new_editor = WheelEditor::open(&output_path).unwrap();
-        assert_eq!(new_editor.version(), "1.0.1");
-        assert_eq!(new_editor.summary(), Some("Updated summary"));
+        let reopened = WheelEditor::open(&output_path).unwrap();
+        assert_eq!(reopened.metadata().metadata_version, "2.1");
+        assert_eq!(reopened.license(), Some("MIT"));
+        assert!(reopened.metadata().extra_headers.is_empty());
     }
 
     #[test]
-    fn test_validate() {
+    fn test_check_safe_paths() {
         let temp_dir = TempDir::new().unwrap();
         let wheel_path = create_test_wheel(temp_dir.path());
+        assert!(
+            WheelEditor::open(&wheel_path)
+                .unwrap()
+                .check_safe_paths()
+                .unwrap()
+                .is_empty()
+        );
 
+        let wheel_path = temp_dir.path().join("evil-1.0.0-py3-none-any.whl");
+        create_wheel_with_files(
+            &wheel_path,
+            "evil-1.0.0.dist-info",
+            &[
+                ("evil/__init__.py", b""),
+                ("../evil", b"payload"),
+                (
+                    "evil-1.0.0.dist-info/METADATA",
+                    b"Metadata-Version: 2.1\nName: evil\nVersion: 1.0.0\n",
+                ),
+                (
+                    "evil-1.0.0.dist-info/WHEEL",
+                    b"Wheel-Version: 1.0\nRoot-Is-Purelib: true\nTag: py3-none-any\n",
+                ),
+            ],
+        );
         let editor = WheelEditor::open(&wheel_path).unwrap();
-        let result = editor.validate().unwrap();
-        assert!(result.is_valid());
+        assert_eq!(editor.check_safe_paths().unwrap(), vec!["../evil"]);
     }
 
     #[test]
-    fn test_python_tag_get() {
+    fn test_lint_messy_wheel() {
         let temp_dir = TempDir::new().unwrap();
         let wheel_path = create_test_wheel(temp_dir.path());
+        assert!(
+            WheelEditor::open(&wheel_path)
+                .unwrap()
+                .lint()
+                .unwrap()
+                .is_empty()
+        );
 
-        let editor = WheelEditor::open(&wheel_path).unwrap();
+        // Filename says py3-none-any for "messy", the wheel is a purelib
+        // linux build of test-pkg that also claims py2
+        let wheel_path = temp_dir.path().join("messy-1.0.0-py3-none-any.whl");
+        create_wheel_with_files(
+            &wheel_path,
+            "test_pkg-1.0.0.dist-info",
+            &[
+                ("test_pkg/__init__.py", b""),
+                ("../escape.py", b"payload"),
+                (
+                    "test_pkg-1.0.0.dist-info/METADATA",
+                    b"Metadata-Version: 2.1\nName: test-pkg\nVersion: 1.0.0\nRequires-Python: >=3.8\n",
+                ),
+                (
+                    "test_pkg-1.0.0.dist-info/WHEEL",
+                    b"Wheel-Version: 1.0\nRoot-Is-Purelib: true\nTag: py2-none-linux_x86_64\nTag: py3-none-linux_x86_64\n",
+                ),
+            ],
+        );
+        let mut editor = WheelEditor::open(&wheel_path).unwrap();
+        editor
+            .record
+            .find_mut("test_pkg-1.0.0.dist-info/WHEEL")
+            .unwrap()
+            .hash = None;
+        editor.record.find_mut("test_pkg/__init__.py").unwrap().hash =
+            Some("md5=1B2M2Y8AsgTpgAmY7PhCfg".to_string());
+
+        let lints = editor.lint().unwrap();
+        let codes: Vec<&str> = lints.iter().map(|l| l.code).collect();
         assert_eq!(
-            editor.python_tag(),
-            Some("py3"),
-            "test wheel should have python tag 'py3'"
+            codes,
+            vec![
+                lint::PURELIB_PLATFORM,
+                lint::NAME_MISMATCH,
+                lint::TAG_MISMATCH,
+                lint::REQUIRES_PYTHON_TAG,
+                lint::MISSING_RECORD_ENTRY,
+                lint::WEAK_HASH,
+                lint::UNSAFE_PATH,
+            ]
         );
+        assert_eq!(lints[0].severity, Severity::Warning);
+        assert!(lints[3].message.contains("py2"), "{}", lints[3].message);
+        assert_eq!(lints[6].severity, Severity::Error);
+        assert!(lints[6].message.contains("../escape.py"));
+    }
+
+    /// Collect the version-needed-to-extract field of every central
+    /// directory record in a ZIP file.
+    fn central_dir_versions_needed(data: &[u8]) -> Vec<u16> {
+        const CENTRAL_DIR_SIGNATURE: &[u8] = &[0x50, 0x4b, 0x01, 0x02];
+        data.windows(4)
+            .enumerate()
+            .filter(|(_, w)| *w == CENTRAL_DIR_SIGNATURE)
+            .map(|(i, _)| u16::from_le_bytes([data[i + 6], data[i + 7]]))
+            .collect()
     }
 
     #[test]
-    fn test_abi_tag_get() {
+    fn test_max_compatibility_minimal_version_needed() {
         let temp_dir = TempDir::new().unwrap();
         let wheel_path = create_test_wheel(temp_dir.path());
+        let output_path = temp_dir.path().join("compat.whl");
 
-        let editor = WheelEditor::open(&wheel_path).unwrap();
-        assert_eq!(
-            editor.abi_tag(),
-            Some("none"),
-            "test wheel should have abi tag 'none'"
+        let mut editor = WheelEditor::open(&wheel_path).unwrap();
+        editor.set_max_compatibility(true);
+        assert!(editor.write_options().max_compatibility);
+        editor.save(&output_path).unwrap();
+
+        let file = File::open(&output_path).unwrap();
+        let mut archive = zip::ZipArchive::new(file).unwrap();
+        for i in 0..archive.len() {
+            let entry = archive.by_index(i).unwrap();
+            assert!(
+                matches!(
+                    entry.compression(),
+                    zip::CompressionMethod::Stored | zip::CompressionMethod::Deflated
+                ),
+                "{} uses {:?}",
+                entry.name(),
+                entry.compression()
+            );
+        }
+
+        let versions = central_dir_versions_needed(&std::fs::read(&output_path).unwrap());
+        assert_eq!(versions.len(), archive.len());
+        assert!(
+            versions.iter().all(|v| *v <= 20),
+            "expected version-needed <= 2.0, got {:?}",
+            versions
         );
+
+        let result = WheelEditor::open(&output_path).unwrap().validate().unwrap();
+        assert!(result.is_valid(), "{:?}", result);
     }
 
     #[test]
-    fn test_python_tag_set_and_persist() {
+    fn test_max_compatibility_reencodes_bzip2_member() {
         let temp_dir = TempDir::new().unwrap();
-        let wheel_path = create_test_wheel(temp_dir.path());
-        let output_path = temp_dir.path().join("output.whl");
+        let wheel_path = temp_dir.path().join("test_pkg-1.0.0-py3-none-any.whl");
+        let output_path = temp_dir.path().join("compat.whl");
+        let module = b"x = 1\n".repeat(100);
+        let metadata = b"Metadata-Version: 2.1\nName: test-pkg\nVersion: 1.0.0\n";
+        let wheel = b"Wheel-Version: 1.0\nRoot-Is-Purelib: true\nTag: py3-none-any\n";
+        let record = format!(
+            "test_pkg/__init__.py,{},{}\ntest_pkg-1.0.0.dist-info/METADATA,{},{}\ntest_pkg-1.0.0.dist-info/WHEEL,{},{}\ntest_pkg-1.0.0.dist-info/RECORD,,\n",
+            hash_content(&module),
+            module.len(),
+            hash_content(metadata),
+            metadata.len(),
+            hash_content(wheel),
+            wheel.len(),
+        );
+
+        let bzip2 = SimpleFileOptions::default().compression_method(zip::CompressionMethod::Bzip2);
+        let mut zip = ZipWriter::new(File::create(&wheel_path).unwrap());
+        zip.start_file("test_pkg/__init__.py", bzip2).unwrap();
+        zip.write_all(&module).unwrap();
+        for (name, content) in [
+            ("test_pkg-1.0.0.dist-info/METADATA", metadata.as_slice()),
+            ("test_pkg-1.0.0.dist-info/WHEEL", wheel.as_slice()),
+            ("test_pkg-1.0.0.dist-info/RECORD", record.as_bytes()),
+        ] {
+            zip.start_file(name, SimpleFileOptions::default()).unwrap();
+            zip.write_all(content).unwrap();
+        }
+        zip.finish().unwrap();
 
         let mut editor = WheelEditor::open(&wheel_path).unwrap();
-        editor.set_python_tag("cp312");
+        editor.set_max_compatibility(true);
         editor.save(&output_path).unwrap();
 
-        let new_editor = WheelEditor::open(&output_path).unwrap();
-        assert_eq!(
-            new_editor.python_tag(),
-            Some("cp312"),
-            "set_python_tag should persist through save/reload"
+        let mut archive = zip::ZipArchive::new(File::open(&output_path).unwrap()).unwrap();
+        let mut entry = archive.by_name("test_pkg/__init__.py").unwrap();
+        assert_eq!(entry.compression(), zip::CompressionMethod::Deflated);
+        let mut content = Vec::new();
+        entry.read_to_end(&mut content).unwrap();
+        assert_eq!(content, module);
+        drop(entry);
+
+        let versions = central_dir_versions_needed(&std::fs::read(&output_path).unwrap());
+        assert_eq!(versions.len(), archive.len());
+        assert!(
+            versions.iter().all(|v| *v <= 20),
+            "expected version-needed <= 2.0, got {:?}",
+            versions
         );
-        // abi and platform should be unchanged
+
+        let result = WheelEditor::open(&output_path).unwrap().validate().unwrap();
+        assert!(result.is_valid(), "{:?}", result);
+    }
+
+    #[test]
+    fn test_set_description_content_type_checked() {
+        let temp_dir = TempDir::new().unwrap();
+        let wheel_path = create_test_wheel(temp_dir.path());
+
+        let mut editor = WheelEditor::open(&wheel_path).unwrap();
+        editor
+            .set_description_content_type_checked("text/markdown; charset=UTF-8")
+            .unwrap();
         assert_eq!(
-            new_editor.abi_tag(),
-            Some("none"),
-            "abi tag should be unchanged after setting python tag"
+            editor.description_content_type(),
+            Some("text/markdown; charset=UTF-8")
+        );
+
+        let err = editor
+            .set_description_content_type_checked("text/markdwon")
+            .unwrap_err();
+        assert!(
+            matches!(err, WheelError::Metadata(MetadataError::InvalidValue { .. })),
+            "got {err:?}"
         );
+        // A rejected value leaves the previous one in place.
         assert_eq!(
-            new_editor.platform_tag(),
-            Some("any"),
-            "platform tag should be unchanged after setting python tag"
+            editor.description_content_type(),
+            Some("text/markdown; charset=UTF-8")
         );
     }
 
     #[test]
-    fn test_filename() {
+    fn test_constant_time_metadata_only() {
         let temp_dir = TempDir::new().unwrap();
         let wheel_path = create_test_wheel(temp_dir.path());
 
-        let editor = WheelEditor::open(&wheel_path).unwrap();
-        assert_eq!(
-            editor.filename(),
-            "test_pkg-1.0.0-py3-none-any.whl",
-            "filename should match PEP 427 format"
-        );
+        let mut editor = WheelEditor::open(&wheel_path).unwrap();
+        editor.set_version("1.0.1");
+        editor.set_platform_tag("linux_x86_64");
+        assert!(editor.is_constant_time().unwrap());
+        assert!(editor.constant_time_blockers().unwrap().is_empty());
     }
 
     #[test]
-    fn test_filename_after_tag_change() {
+    fn test_constant_time_blockers() {
         let temp_dir = TempDir::new().unwrap();
         let wheel_path = create_test_wheel(temp_dir.path());
 
         let mut editor = WheelEditor::open(&wheel_path).unwrap();
-        editor.set_python_tag("cp312");
-        editor.set_abi_tag("cp312");
-        editor.set_platform_tag("linux_x86_64");
+        editor
+            .record_mut()
+            .entries
+            .retain(|e| e.path != "test_pkg/__init__.py");
+        editor.add_file("test_pkg/extra.py", b"".to_vec());
+
+        assert!(!editor.is_constant_time().unwrap());
         assert_eq!(
-            editor.filename(),
-            "test_pkg-1.0.0-cp312-cp312-linux_x86_64.whl",
-            "filename should reflect updated tags"
+            editor.constant_time_blockers().unwrap(),
+            vec![
+                "1 added file(s) will be written",
+                "1 member(s) missing from RECORD will be decompressed and hashed",
+            ]
         );
     }
 
     #[test]
-    fn test_filename_after_name_version_change() {
+    fn test_plan_metadata_only() {
         let temp_dir = TempDir::new().unwrap();
         let wheel_path = create_test_wheel(temp_dir.path());
 
         let mut editor = WheelEditor::open(&wheel_path).unwrap();
-        editor.set_name("my-new-package");
         editor.set_version("2.0.0");
+        let plan = editor.plan().unwrap();
+
+        assert_eq!(plan.dist_info, "test_pkg-2.0.0.dist-info");
         assert_eq!(
-            editor.filename(),
-            "my_new_package-2.0.0-py3-none-any.whl",
-            "filename should reflect updated name and version"
+            plan.copied,
+            vec![
+                (
+                    "test_pkg/__init__.py".to_string(),
+                    "test_pkg/__init__.py".to_string()
+                ),
+                (
+                    "test_pkg-1.0.0.dist-info/WHEEL".to_string(),
+                    "test_pkg-2.0.0.dist-info/WHEEL".to_string()
+                ),
+            ]
         );
+        assert_eq!(
+            plan.rewritten,
+            vec![
+                (
+                    "test_pkg-2.0.0.dist-info/METADATA".to_string(),
+                    RewriteReason::Metadata
+                ),
+                (
+                    "test_pkg-2.0.0.dist-info/RECORD".to_string(),
+                    RewriteReason::Record
+                ),
+            ]
+        );
+        assert!(plan.is_constant_time());
     }
 
     #[test]
-    fn test_filename_multi_tag_dedup() {
+    fn test_plan_lists_rewrite_reasons() {
         let temp_dir = TempDir::new().unwrap();
         let wheel_path = create_test_wheel(temp_dir.path());
 
         let mut editor = WheelEditor::open(&wheel_path).unwrap();
-        // Simulate a wheel with two tags that share python/abi but differ in platform
-        editor.wheel_info_mut().tags = vec![
-            WheelTag::parse("cp311-cp311-manylinux_2_17_x86_64").unwrap(),
-            WheelTag::parse("cp311-cp311-manylinux2014_x86_64").unwrap(),
-        ];
+        editor.add_file("test_pkg/__init__.py", b"# replaced\n".to_vec());
+        editor.add_file("test_pkg/extra.py", b"".to_vec());
+        let plan = editor.plan().unwrap();
+
+        assert!(plan.copied.is_empty());
+        let reasons: Vec<(&str, RewriteReason)> = plan
+            .rewritten
+            .iter()
+            .map(|(path, reason)| (path.as_str(), *reason))
+            .collect();
         assert_eq!(
-            editor.filename(),
-            "test_pkg-1.0.0-cp311-cp311-manylinux_2_17_x86_64.manylinux2014_x86_64.whl",
-            "filename should dot-join unique platform values and dedup python/abi"
+            reasons,
+            vec![
+                ("test_pkg/__init__.py", RewriteReason::Modified),
+                ("test_pkg-1.0.0.dist-info/WHEEL", RewriteReason::WheelInfo),
+                ("test_pkg-1.0.0.dist-info/METADATA", RewriteReason::Metadata),
+                ("test_pkg/extra.py", RewriteReason::Added),
+                ("test_pkg-1.0.0.dist-info/RECORD", RewriteReason::Record),
+            ]
         );
+        assert!(!plan.is_constant_time());
+        assert_eq!(plan.rewritten_payload().count(), 2);
     }
 
     #[test]
-    fn test_filename_multi_tag_all_different() {
+    fn test_record_signatures_dropped_or_resigned() {
         let temp_dir = TempDir::new().unwrap();
         let wheel_path = create_test_wheel(temp_dir.path());
+        let signed_path = temp_dir.path().join("signed.whl");
+        let jws = "test_pkg-1.0.0.dist-info/RECORD.jws";
 
         let mut editor = WheelEditor::open(&wheel_path).unwrap();
-        // Simulate a wheel with two tags that differ in all components
-        editor.wheel_info_mut().tags = vec![
-            WheelTag::parse("py2-none-any").unwrap(),
-            WheelTag::parse("py3-none-any").unwrap(),
-        ];
+        editor.add_file(jws, b"stale signature".to_vec());
+        editor.save(&signed_path).unwrap();
+
+        // Without a signer the stale signature is left out
+        let mut editor = WheelEditor::open(&signed_path).unwrap();
+        assert_eq!(editor.dropped_signatures().unwrap(), vec![jws]);
+        assert_eq!(editor.plan().unwrap().removed, vec![jws]);
+        editor.set_version("1.0.1");
+        let mut output = Cursor::new(Vec::new());
+        editor.save_to_writer(&mut output).unwrap();
+        let saved = WheelEditor::from_bytes(output.get_ref()).unwrap();
+        assert!(saved.dropped_signatures().unwrap().is_empty());
+        assert!(saved.record().find(jws).is_none());
+
+        // A signer gets the RECORD that was written
+        editor.set_record_signer(Some(RecordSigner::new(|record| {
+            Ok(hash_content(record).into_bytes())
+        })));
+        let plan = editor.plan().unwrap();
         assert_eq!(
-            editor.filename(),
-            "test_pkg-1.0.0-py2.py3-none-any.whl",
-            "filename should dot-join unique python values"
+            plan.rewritten.last().unwrap(),
+            &(
+                "test_pkg-1.0.1.dist-info/RECORD.jws".to_string(),
+                RewriteReason::Signature
+            )
+        );
+        let mut output = Cursor::new(Vec::new());
+        editor.save_to_writer(&mut output).unwrap();
+        let saved = WheelEditor::from_bytes(output.get_ref()).unwrap();
+        let record = saved.read_file("test_pkg-1.0.1.dist-info/RECORD").unwrap();
+        assert_eq!(
+            saved
+                .read_file("test_pkg-1.0.1.dist-info/RECORD.jws")
+                .unwrap(),
+            hash_content(&record).into_bytes()
         );
+        assert!(saved.validate().unwrap().is_valid());
+
+        editor.set_record_signer(Some(RecordSigner::new(|_| Err("no key".to_string()))));
+        let err = editor.save_to_writer(Cursor::new(Vec::new())).unwrap_err();
+        assert!(matches!(err, WheelError::Record(RecordError::Signing(_))));
     }
 
     #[test]
-    fn test_abi_tag_set_and_persist() {
+    fn test_edit_entry_points() {
         let temp_dir = TempDir::new().unwrap();
         let wheel_path = create_test_wheel(temp_dir.path());
-        let output_path = temp_dir.path().join("output.whl");
+        let with_entry_points = temp_dir.path().join("with_entry_points.whl");
+        let output_path = temp_dir.path().join("renamed.whl");
 
         let mut editor = WheelEditor::open(&wheel_path).unwrap();
-        editor.set_abi_tag("cp312");
+        assert_eq!(editor.entry_points().unwrap(), None);
+        editor.add_file(
+            "test_pkg-1.0.0.dist-info/entry_points.txt",
+            b"[console_scripts]\ntest-tool = test_pkg:main\n\n[plugins]\nx=test_pkg:X\n".to_vec(),
+        );
+        editor.save(&with_entry_points).unwrap();
+
+        let mut editor = WheelEditor::open(&with_entry_points).unwrap();
+        editor.set_name("new-pkg");
+        let renamed = editor
+            .modify_entry_points(|entry_points| {
+                entry_points.set("console_scripts", "test-tool", "new_pkg:main");
+                entry_points.rename("console_scripts", "test-tool", "new-tool")
+            })
+            .unwrap();
+        assert!(renamed.unwrap());
         editor.save(&output_path).unwrap();
 
-        let new_editor = WheelEditor::open(&output_path).unwrap();
+        let saved = WheelEditor::open(&output_path).unwrap();
         assert_eq!(
-            new_editor.abi_tag(),
-            Some("cp312"),
-            "set_abi_tag should persist through save/reload"
+            saved
+                .read_file("new_pkg-1.0.0.dist-info/entry_points.txt")
+                .unwrap(),
+            b"[console_scripts]\nnew-tool = new_pkg:main\n\n[plugins]\nx=test_pkg:X\n"
         );
-        // python and platform should be unchanged
+        assert!(saved.validate().unwrap().is_valid());
+
+        // Only a missing file is absent; a file that can't be read is an error
+        let mut editor = WheelEditor::open(&output_path).unwrap();
+        let path = "new_pkg-1.0.0.dist-info/entry_points.txt";
+        editor.add_file(path, vec![0xff]);
+        assert!(editor.entry_points().is_err());
+        editor.remove_file(path).unwrap();
+        assert_eq!(editor.entry_points().unwrap(), None);
+    }
+
+    #[test]
+    fn test_rename_package() {
+        let temp_dir = TempDir::new().unwrap();
+        let wheel_path = create_test_wheel(temp_dir.path());
+        let with_top_level = temp_dir.path().join("with_top_level.whl");
+        let output_path = temp_dir.path().join("renamed.whl");
+
+        let mut editor = WheelEditor::open(&wheel_path).unwrap();
+        editor.add_file(
+            "test_pkg-1.0.0.dist-info/top_level.txt",
+            b"test_pkg\n".to_vec(),
+        );
+        editor.save(&with_top_level).unwrap();
+
+        let mut editor = WheelEditor::open(&with_top_level).unwrap();
+        editor.set_name("new-pkg");
+        assert!(editor.rename_package("test_pkg", "a/b").is_err());
+        assert_eq!(editor.rename_package("missing", "other").unwrap(), 0);
+        assert_eq!(editor.rename_package("test_pkg", "new_pkg").unwrap(), 1);
+        assert!(editor.rename_package("missing", "new_pkg").is_err());
+
+        let plan = editor.plan().unwrap();
+        assert!(plan.copied.contains(&(
+            "test_pkg/__init__.py".to_string(),
+            "new_pkg/__init__.py".to_string()
+        )));
+        let projected = editor.projected_record().unwrap();
+        assert!(projected.find("new_pkg/__init__.py").is_some());
+        assert!(projected.find("test_pkg/__init__.py").is_none());
+        editor.save(&output_path).unwrap();
+
+        let saved = WheelEditor::open(&output_path).unwrap();
         assert_eq!(
-            new_editor.python_tag(),
-            Some("py3"),
-            "python tag should be unchanged after setting abi tag"
+            saved.read_file("new_pkg/__init__.py").unwrap(),
+            b"__version__ = '1.0.0'\n"
         );
+        assert!(saved.read_file("test_pkg/__init__.py").is_err());
         assert_eq!(
-            new_editor.platform_tag(),
-            Some("any"),
-            "platform tag should be unchanged after setting abi tag"
+            saved
+                .read_file("new_pkg-1.0.0.dist-info/top_level.txt")
+                .unwrap(),
+            b"new_pkg\n"
         );
+        assert_eq!(saved.record().serialize(), projected.serialize());
+        assert!(saved.validate().unwrap().is_valid());
     }
 
-    fn read_archive_entry(path: &Path, entry_name: &str) -> Option<Vec<u8>> {
-        let file = File::open(path).unwrap();
-        let mut archive = zip::ZipArchive::new(file).unwrap();
-        let mut entry = archive.by_name(entry_name).ok()?;
-        let mut buf = Vec::new();
-        std::io::Read::read_to_end(&mut entry, &mut buf).unwrap();
-        Some(buf)
+    #[cfg(feature = "mmap")]
+    #[test]
+    fn test_open_mmap() {
+        let temp_dir = TempDir::new().unwrap();
+        let wheel_path = create_test_wheel(temp_dir.path());
+        let output_path = temp_dir.path().join("mapped.whl");
+
+        let mut editor = WheelEditor::open_mmap(&wheel_path).unwrap();
+        assert_eq!(editor.name(), "test-pkg");
+        assert!(editor.validate().unwrap().is_valid());
+
+        editor.set_version("1.0.1");
+        editor.save(&output_path).unwrap();
+
+        let reopened = WheelEditor::open(&output_path).unwrap();
+        assert_eq!(reopened.version(), "1.0.1");
+        assert!(reopened.validate().unwrap().is_valid());
     }
 
     #[test]
-    fn test_add_file_to_dist_info() {
+    fn test_validate_reports_missing_wheel_record_entry() {
         let temp_dir = TempDir::new().unwrap();
         let wheel_path = create_test_wheel(temp_dir.path());
-        let output_path = temp_dir.path().join("with_extra.whl");
 
         let mut editor = WheelEditor::open(&wheel_path).unwrap();
-        let dist_info = editor.dist_info_dir();
-        assert_eq!(dist_info, "test_pkg-1.0.0.dist-info");
-        let payload = br#"{"vcs_name":"git","vcs_ref":"deadbeef"}"#;
-        editor.add_file(format!("{dist_info}/build-details.json"), payload.to_vec());
-        editor.save(&output_path).unwrap();
-
-        // The added entry should be present and readable.
-        let got = read_archive_entry(&output_path, "test_pkg-1.0.0.dist-info/build-details.json")
-            .expect("build-details.json should be present in saved wheel");
-        assert_eq!(got, payload);
+        editor
+            .record_mut()
+            .entries
+            .retain(|e| e.path != "test_pkg-1.0.0.dist-info/WHEEL");
 
-        // The wheel should pass full validation: RECORD must contain a
-        // correct hash for the new file.
-        let result = WheelEditor::open(&output_path).unwrap().validate().unwrap();
+        let result = editor.validate().unwrap();
         assert!(
-            result.is_valid(),
-            "wheel with added file should validate: {:?}",
-            result
+            result.errors.iter().any(|e| matches!(
+                e,
+                ValidationError::MissingRecordEntry { path }
+                    if path == "test_pkg-1.0.0.dist-info/WHEEL"
+            )),
+            "expected MissingRecordEntry for WHEEL, got {:?}",
+            result.errors
         );
     }
 
     #[test]
-    fn test_add_file_renamed_when_version_changes() {
+    fn test_stamp_generator() {
         let temp_dir = TempDir::new().unwrap();
         let wheel_path = create_test_wheel(temp_dir.path());
-        let output_path = temp_dir.path().join("renamed.whl");
+        let output_path = temp_dir.path().join("stamped.whl");
 
         let mut editor = WheelEditor::open(&wheel_path).unwrap();
-        // Add using the *old* dist-info prefix, then bump version. The writer
-        // should rewrite the path to land under the new dist-info dir.
-        editor.add_file(
-            "test_pkg-1.0.0.dist-info/build-details.json",
-            b"{}".to_vec(),
-        );
-        editor.set_version("1.0.1");
-        editor.save(&output_path).unwrap();
+        assert_eq!(editor.generator(), Some("test"));
+        editor.stamp_generator();
+        editor.stamp_generator();
 
-        assert!(
-            read_archive_entry(
-                &output_path,
-                "test_pkg-1.0.1.dist-info/build-details.json",
-            )
-            .is_some(),
-            "added file should be rewritten to new dist-info prefix"
-        );
-        assert!(
-            read_archive_entry(
-                &output_path,
-                "test_pkg-1.0.0.dist-info/build-details.json",
-            )
-            .is_none(),
-            "added file should not appear under old dist-info prefix"
-        );
+        let expected = format!("test; editwheel-rs {}", env!("CARGO_PKG_VERSION"));
+        assert_eq!(editor.generator(), Some(expected.as_str()));
 
-        let result = WheelEditor::open(&output_path).unwrap().validate().unwrap();
-        assert!(result.is_valid());
+        editor.save(&output_path).unwrap();
+        let reopened = WheelEditor::open(&output_path).unwrap();
+        let generator = reopened.generator().unwrap();
+        assert!(generator.contains("test"), "{generator}");
+        assert!(generator.contains(env!("CARGO_PKG_VERSION")), "{generator}");
     }
 
     #[test]
-    fn test_add_file_collision_with_source_errors() {
+    fn test_set_generator_exact() {
         let temp_dir = TempDir::new().unwrap();
         let wheel_path = create_test_wheel(temp_dir.path());
-        let output_path = temp_dir.path().join("collide.whl");
 
         let mut editor = WheelEditor::open(&wheel_path).unwrap();
-        editor.add_file("test_pkg/__init__.py", b"x = 1\n".to_vec());
-        let err = editor.save(&output_path).unwrap_err();
-        match err {
-            WheelError::InvalidWheel(_) => {}
-            other => panic!("expected InvalidWheel, got {other:?}"),
+        editor.set_generator_exact("my-builder 1.2");
+        assert_eq!(editor.generator(), Some("my-builder 1.2"));
+    }
+
+    #[test]
+    fn test_validate_reports_bogus_record_size() {
+        let temp_dir = TempDir::new().unwrap();
+        let wheel_path = temp_dir.path().join("test_pkg-1.0.0-py3-none-any.whl");
+        let metadata = b"Metadata-Version: 2.1\nName: test-pkg\nVersion: 1.0.0\n";
+        let wheel = b"Wheel-Version: 1.0\nRoot-Is-Purelib: true\nTag: py3-none-any\n";
+        let record = format!(
+            "test_pkg/__init__.py,{},-1\ntest_pkg-1.0.0.dist-info/METADATA,{},12abc\ntest_pkg-1.0.0.dist-info/WHEEL,{},{}\ntest_pkg-1.0.0.dist-info/RECORD,,\n",
+            hash_content(b""),
+            hash_content(metadata),
+            hash_content(wheel),
+            wheel.len(),
+        );
+
+        let file = File::create(&wheel_path).unwrap();
+        let mut zip = ZipWriter::new(file);
+        let options = SimpleFileOptions::default();
+        for (name, content) in [
+            ("test_pkg/__init__.py", &b""[..]),
+            ("test_pkg-1.0.0.dist-info/METADATA", metadata),
+            ("test_pkg-1.0.0.dist-info/WHEEL", wheel),
+            ("test_pkg-1.0.0.dist-info/RECORD", record.as_bytes()),
+        ] {
+            zip.start_file(name, options).unwrap();
+            zip.write_all(content).unwrap();
         }
+        zip.finish().unwrap();
+
+        // Every malformed size is reported, not just the first
+        let result = WheelEditor::open(&wheel_path).unwrap().validate().unwrap();
+        let invalid: Vec<_> = result
+            .errors
+            .iter()
+            .filter_map(|e| match e {
+                ValidationError::InvalidRecordSize { path, value } => {
+                    Some((path.as_str(), value.as_str()))
+                }
+                _ => None,
+            })
+            .collect();
+        assert_eq!(
+            invalid,
+            [
+                ("test_pkg/__init__.py", "-1"),
+                ("test_pkg-1.0.0.dist-info/METADATA", "12abc")
+            ],
+            "got {:?}",
+            result.errors
+        );
     }
 }
@@ -0,0 +1,233 @@
+//! Non-fatal checks - things that are questionable about a wheel but don't
+//! stop it from being read or edited
+//!
+//! `WheelEditor::lint` gathers these from the metadata, WHEEL tags, RECORD
+//! and member names. Each finding carries a stable code so callers can
+//! filter or suppress specific ones.
+
+use crate::record::Record;
+use crate::wheel_info::WheelTag;
+
+/// WHEEL says `Root-Is-Purelib: true` but a tag names a platform
+pub const PURELIB_PLATFORM: &str = "purelib-platform";
+/// The wheel's filename doesn't parse
+pub const INVALID_FILENAME: &str = "invalid-filename";
+/// The name or version in the filename differs from METADATA
+pub const NAME_MISMATCH: &str = "name-mismatch";
+/// The tags or build number in the filename differ from WHEEL
+pub const TAG_MISMATCH: &str = "tag-mismatch";
+/// A python tag can't satisfy `Requires-Python`
+pub const REQUIRES_PYTHON_TAG: &str = "requires-python-tag";
+/// RECORD has no hashed entry for METADATA or WHEEL
+pub const MISSING_RECORD_ENTRY: &str = "missing-record-entry";
+/// A RECORD hash uses an algorithm weaker than sha256
+pub const WEAK_HASH: &str = "weak-hash";
+/// A member is absolute, escapes the install root, or is a symlink
+pub const UNSAFE_PATH: &str = "unsafe-path";
+
+/// Hash algorithms PEP 427 rules out for RECORD
+const WEAK_HASH_ALGORITHMS: &[&str] = &["md5", "sha1", "sha224"];
+
+/// How serious a lint is
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    /// Unusual, but installers accept it
+    Warning,
+    /// Installers or indexes are likely to reject the wheel
+    Error,
+}
+
+impl Severity {
+    /// Lowercase name, as shown by the CLI
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Severity::Warning => "warning",
+            Severity::Error => "error",
+        }
+    }
+}
+
+/// A single finding from `WheelEditor::lint`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Lint {
+    /// Stable identifier, one of the constants in this module
+    pub code: &'static str,
+    pub severity: Severity,
+    pub message: String,
+}
+
+impl Lint {
+    pub fn new(code: &'static str, severity: Severity, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            severity,
+            message: message.into(),
+        }
+    }
+}
+
+/// RECORD entries hashed with an algorithm weaker than sha256, as
+/// `(path, algorithm)`
+pub fn weak_hashes(record: &Record) -> Vec<(&str, &str)> {
+    record
+        .entries
+        .iter()
+        .filter_map(|entry| {
+            let (algorithm, _) = entry.hash.as_deref()?.split_once('=')?;
+            WEAK_HASH_ALGORITHMS
+                .contains(&algorithm.to_ascii_lowercase().as_str())
+                .then_some((entry.path.as_str(), algorithm))
+        })
+        .collect()
+}
+
+/// Python tags that no interpreter allowed by `requires_python` matches
+///
+/// Only CPython-style version tags are understood: `py3`/`cp3` stand for
+/// every 3.x and `cp311`/`py38` for one minor version; other tags
+/// (`pp310`, `graalpy`) are skipped. Clauses with operators or versions
+/// this can't reason about (`!=`, `===`, pre-releases) never cause a
+/// conflict, so a tag is only reported when it is definitely unusable.
+pub fn requires_python_conflicts<'a>(requires_python: &str, tags: &'a [WheelTag]) -> Vec<&'a str> {
+    let clauses: Vec<(&str, Vec<u64>)> = requires_python
+        .split(',')
+        .filter_map(|clause| parse_clause(clause.trim()))
+        .collect();
+
+    let mut conflicts: Vec<&str> = Vec::new();
+    for tag in tags {
+        let python = tag.python.as_str();
+        if conflicts.contains(&python) {
+            continue;
+        }
+        let Some((major, minor)) = parse_python_tag(python) else {
+            continue;
+        };
+        if clauses
+            .iter()
+            .any(|(op, version)| excludes(op, version, major, minor))
+        {
+            conflicts.push(python);
+        }
+    }
+    conflicts
+}
+
+/// Split a specifier clause into its operator and numeric release segments
+fn parse_clause(clause: &str) -> Option<(&str, Vec<u64>)> {
+    let op_len = clause
+        .find(|c: char| !matches!(c, '<' | '>' | '=' | '!' | '~'))
+        .unwrap_or(clause.len());
+    let (op, version) = clause.split_at(op_len);
+    if !matches!(op, ">=" | ">" | "<=" | "<" | "==" | "~=") {
+        return None;
+    }
+
+    let version = version.trim();
+    let version = version.strip_suffix(".*").unwrap_or(version);
+    let release = version
+        .split('.')
+        .map(|part| part.parse().ok())
+        .collect::<Option<Vec<u64>>>()?;
+    Some((op, release))
+}
+
+/// Parse `py3`, `cp3`, `py38` or `cp311` into `(major, minor)`
+fn parse_python_tag(tag: &str) -> Option<(u64, Option<u64>)> {
+    let digits = tag.strip_prefix("py").or_else(|| tag.strip_prefix("cp"))?;
+    if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    let (major, minor) = digits.split_at(1);
+    let minor = (!minor.is_empty()).then(|| minor.parse().ok()).flatten();
+    Some((major.parse().ok()?, minor))
+}
+
+/// True if no Python `major.minor.*` (or `major.*` without a minor)
+/// satisfies `op version`
+fn excludes(op: &str, version: &[u64], major: u64, minor: Option<u64>) -> bool {
+    let segment = |i: usize| version.get(i).copied().unwrap_or(0);
+    let (want_major, want_minor, want_micro) = (segment(0), segment(1), segment(2));
+
+    match minor {
+        Some(minor) => {
+            let tag = (major, minor);
+            let want = (want_major, want_minor);
+            match op {
+                ">=" | ">" => tag < want,
+                "<=" => tag > want,
+                "<" => tag > want || (tag == want && want_micro == 0),
+                "==" if version.len() >= 2 => tag != want,
+                "==" => major != want_major,
+                "~=" => tag < want || major != want_major,
+                _ => false,
+            }
+        }
+        None => match op {
+            ">=" | ">" => major < want_major,
+            "<=" => major > want_major,
+            "<" => {
+                major > want_major || (major == want_major && want_minor == 0 && want_micro == 0)
+            }
+            "==" | "~=" => major != want_major,
+            _ => false,
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::record::RecordEntry;
+
+    fn tags(pythons: &[&str]) -> Vec<WheelTag> {
+        pythons
+            .iter()
+            .map(|python| WheelTag {
+                python: python.to_string(),
+                abi: "none".to_string(),
+                platform: "any".to_string(),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_requires_python_conflicts() {
+        let universal = tags(&["py2", "py3"]);
+        assert_eq!(requires_python_conflicts(">=3.8", &universal), vec!["py2"]);
+        assert!(requires_python_conflicts(">=3.8, <4", &universal[1..]).is_empty());
+
+        let cpython = tags(&["cp38", "cp311", "cp312"]);
+        assert_eq!(
+            requires_python_conflicts(">=3.9,<3.12", &cpython),
+            vec!["cp38", "cp312"]
+        );
+        assert!(requires_python_conflicts("<=3.11.4", &cpython[..2]).is_empty());
+        assert!(requires_python_conflicts("~=3.8", &cpython).is_empty());
+        assert_eq!(
+            requires_python_conflicts("==3.11.*", &cpython),
+            vec!["cp38", "cp312"]
+        );
+
+        // Unknown tags and clauses never conflict
+        assert!(requires_python_conflicts("!=3.8", &cpython).is_empty());
+        assert!(requires_python_conflicts(">=3.8", &tags(&["pp37"])).is_empty());
+        assert!(requires_python_conflicts(">=3.12.0rc1", &cpython).is_empty());
+    }
+
+    #[test]
+    fn test_weak_hashes() {
+        let record = Record {
+            entries: vec![
+                RecordEntry::new("a.py".into(), Some("sha256=abc".into()), Some(1)),
+                RecordEntry::new("b.py".into(), Some("md5=abc".into()), Some(1)),
+                RecordEntry::new("c.py".into(), Some("SHA1=abc".into()), Some(1)),
+                RecordEntry::new("pkg.dist-info/RECORD".into(), None, None),
+            ],
+        };
+        assert_eq!(
+            weak_hashes(&record),
+            vec![("b.py", "md5"), ("c.py", "SHA1")]
+        );
+    }
+}
@@ -0,0 +1,19 @@
+//! Archive member listing
+
+/// Central directory information for one archive member, as reported by
+/// `WheelEditor::members`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MemberInfo {
+    /// Full path of the member inside the archive
+    pub name: String,
+    /// Size of the stored (compressed) data in bytes
+    pub compressed_size: u64,
+    /// Size of the member once decompressed in bytes
+    pub uncompressed_size: u64,
+    /// Compression method the member is stored with
+    pub method: zip::CompressionMethod,
+    /// CRC-32 of the uncompressed data, as recorded in the archive
+    pub crc32: u32,
+    /// Whether the member is a directory entry
+    pub is_dir: bool,
+}
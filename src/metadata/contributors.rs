@@ -0,0 +1,280 @@
+//! Author and maintainer fields as a list of people
+//!
+//! `Author-email` and `Maintainer-email` hold RFC 5322 address lists
+//! (`"Doe, Jane" <jane@example.org>, team@example.org`), while `Author` and
+//! `Maintainer` are free-form names. `Metadata::contributors` pairs them up.
+
+use super::Metadata;
+
+/// Which field a contributor came from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Role {
+    Author,
+    Maintainer,
+}
+
+/// A person (or team) named in the author or maintainer fields
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Contributor {
+    pub name: Option<String>,
+    pub email: Option<String>,
+    pub role: Role,
+}
+
+impl Metadata {
+    /// Combine `Author`/`Author-email` and `Maintainer`/`Maintainer-email`
+    /// into one list, authors first
+    ///
+    /// Addresses with a display name (`Jane Doe <jane@example.org>`) carry
+    /// their own name. Bare addresses are paired in order with the
+    /// comma-separated names from the name field that aren't already used
+    /// as a display name; whatever is left over on either side becomes a
+    /// name-only or email-only contributor.
+    pub fn contributors(&self) -> Vec<Contributor> {
+        let mut contributors = Vec::new();
+        for (role, names, emails) in [
+            (Role::Author, &self.author, &self.author_email),
+            (Role::Maintainer, &self.maintainer, &self.maintainer_email),
+        ] {
+            let addresses = emails
+                .as_deref()
+                .map(parse_address_list)
+                .unwrap_or_default();
+            let named: Vec<&str> = addresses
+                .iter()
+                .filter_map(|(name, _)| name.as_deref())
+                .collect();
+            let mut names = names
+                .as_deref()
+                .unwrap_or("")
+                .split(',')
+                .map(str::trim)
+                .filter(|name| !name.is_empty() && !named.contains(name));
+
+            for (name, email) in &addresses {
+                let name = name.clone().or_else(|| names.next().map(String::from));
+                contributors.push(Contributor {
+                    name,
+                    email: Some(email.clone()),
+                    role,
+                });
+            }
+            contributors.extend(names.map(|name| Contributor {
+                name: Some(name.to_string()),
+                email: None,
+                role,
+            }));
+        }
+        contributors
+    }
+
+    /// Parse `Author-email` into `(display name, address)` pairs
+    ///
+    /// A read-side view of the raw field (see `parse_address_list`); empty
+    /// if the field is unset.
+    pub fn parsed_author_emails(&self) -> Vec<(Option<String>, String)> {
+        self.author_email
+            .as_deref()
+            .map(parse_address_list)
+            .unwrap_or_default()
+    }
+
+    /// Parse `Maintainer-email` into `(display name, address)` pairs
+    ///
+    /// See `parsed_author_emails`.
+    pub fn parsed_maintainer_emails(&self) -> Vec<(Option<String>, String)> {
+        self.maintainer_email
+            .as_deref()
+            .map(parse_address_list)
+            .unwrap_or_default()
+    }
+}
+
+/// Characters that force a display name to be quoted, as in Python's
+/// `email.utils.formataddr`
+const NAME_SPECIALS: &[char] = &[
+    '(', ')', '<', '>', '@', ',', ':', ';', '.', '"', '[', ']', '\\',
+];
+
+/// Build an RFC 5322 address list from `(display name, address)` pairs
+///
+/// The inverse of `parse_address_list`: names containing specials such as
+/// commas or dots are quoted, with `"` and `\` escaped, so the result
+/// parses back to the same pairs.
+pub fn format_address_list(addresses: &[(Option<String>, String)]) -> String {
+    addresses
+        .iter()
+        .map(|(name, address)| match name.as_deref().map(str::trim) {
+            Some(name) if !name.is_empty() => {
+                if name.contains(NAME_SPECIALS) {
+                    let escaped = name.replace('\\', "\\\\").replace('"', "\\\"");
+                    format!("\"{}\" <{}>", escaped, address)
+                } else {
+                    format!("{} <{}>", name, address)
+                }
+            }
+            _ => address.clone(),
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Parse an RFC 5322 address list into `(display name, address)` pairs
+///
+/// Handles bare addresses, `Name <address>`, quoted display names that
+/// contain commas or escaped quotes, and `(comments)`, which are dropped.
+/// Group syntax and obsolete forms aren't supported; anything unparseable
+/// is kept as a bare address so no input is silently lost.
+pub fn parse_address_list(value: &str) -> Vec<(Option<String>, String)> {
+    let mut addresses = Vec::new();
+    let mut display = String::new();
+    let mut angle: Option<String> = None;
+    let mut quoted = false;
+    let mut escaped = false;
+    let mut comment_depth = 0;
+
+    let mut finish = |display: &mut String, angle: &mut Option<String>| {
+        let name = display.trim();
+        match angle.take() {
+            Some(address) => {
+                let name = (!name.is_empty()).then(|| name.to_string());
+                addresses.push((name, address.trim().to_string()));
+            }
+            None if !name.is_empty() => addresses.push((None, name.to_string())),
+            None => {}
+        }
+        display.clear();
+    };
+
+    for c in value.chars() {
+        if escaped {
+            if comment_depth == 0 {
+                display.push(c);
+            }
+            escaped = false;
+            continue;
+        }
+        if comment_depth > 0 {
+            match c {
+                '(' => comment_depth += 1,
+                ')' => comment_depth -= 1,
+                '\\' => escaped = true,
+                _ => {}
+            }
+            continue;
+        }
+        if quoted {
+            match c {
+                '"' => quoted = false,
+                '\\' => escaped = true,
+                _ => display.push(c),
+            }
+            continue;
+        }
+        if let Some(address) = angle.as_mut().filter(|_| c != '>') {
+            address.push(c);
+            continue;
+        }
+        match c {
+            '"' => quoted = true,
+            '(' => comment_depth = 1,
+            '<' => angle = Some(String::new()),
+            '>' => {}
+            ',' => finish(&mut display, &mut angle),
+            _ => display.push(c),
+        }
+    }
+    finish(&mut display, &mut angle);
+
+    addresses
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_address_list() {
+        let addresses = parse_address_list(
+            r#""Doe, Jane" <jane@example.org>, team@example.org (mailing list), John <john@example.org>"#,
+        );
+        assert_eq!(
+            addresses,
+            vec![
+                (
+                    Some("Doe, Jane".to_string()),
+                    "jane@example.org".to_string()
+                ),
+                (None, "team@example.org".to_string()),
+                (Some("John".to_string()), "john@example.org".to_string()),
+            ]
+        );
+        assert!(parse_address_list(" , ").is_empty());
+    }
+
+    #[test]
+    fn test_format_address_list_round_trips() {
+        let addresses = vec![
+            (
+                Some("Doe, Jane".to_string()),
+                "jane@example.org".to_string(),
+            ),
+            (None, "team@example.org".to_string()),
+            (Some("John".to_string()), "john@example.org".to_string()),
+            (
+                Some(r#"J. "Jr" Roe"#.to_string()),
+                "roe@example.org".to_string(),
+            ),
+        ];
+        let formatted = format_address_list(&addresses);
+        assert_eq!(
+            formatted,
+            r#""Doe, Jane" <jane@example.org>, team@example.org, John <john@example.org>, "J. \"Jr\" Roe" <roe@example.org>"#
+        );
+        assert_eq!(parse_address_list(&formatted), addresses);
+    }
+
+    #[test]
+    fn test_parsed_author_and_maintainer_emails() {
+        let metadata = Metadata {
+            author_email: Some(r#"Jane Doe <jane@x.com>, "Roe, John" <john@x.com>"#.to_string()),
+            ..Default::default()
+        };
+        assert_eq!(
+            metadata.parsed_author_emails(),
+            vec![
+                (Some("Jane Doe".to_string()), "jane@x.com".to_string()),
+                (Some("Roe, John".to_string()), "john@x.com".to_string()),
+            ]
+        );
+        assert!(metadata.parsed_maintainer_emails().is_empty());
+    }
+
+    #[test]
+    fn test_contributors_merge_author_name_and_emails() {
+        let metadata = Metadata::parse(
+            "Metadata-Version: 2.1\n\
+             Name: pkg\n\
+             Version: 1.0\n\
+             Author: Jane Doe, John Roe\n\
+             Author-email: Jane Doe <jane@example.org>, john@example.org, team@example.org\n\
+             Maintainer: Bob\n",
+        )
+        .unwrap();
+
+        let contributor = |name: Option<&str>, email: Option<&str>, role| Contributor {
+            name: name.map(String::from),
+            email: email.map(String::from),
+            role,
+        };
+        assert_eq!(
+            metadata.contributors(),
+            vec![
+                contributor(Some("Jane Doe"), Some("jane@example.org"), Role::Author),
+                contributor(Some("John Roe"), Some("john@example.org"), Role::Author),
+                contributor(None, Some("team@example.org"), Role::Author),
+                contributor(Some("Bob"), None, Role::Maintainer),
+            ]
+        );
+    }
+}
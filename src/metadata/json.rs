@@ -0,0 +1,332 @@
+//! PEP 566 JSON representation of core metadata
+//!
+//! PEP 566 defines a canonical mapping from METADATA to JSON: keys are the
+//! lowercased field names with hyphens replaced by underscores, multiple-use
+//! fields are arrays keyed by the singular field name (`classifier`,
+//! `requires_dist`, ...), `keywords` is split into a list, and the message
+//! body becomes `description`.
+
+use serde_json::{Map, Value};
+
+use super::Metadata;
+use crate::error::MetadataError;
+
+impl Metadata {
+    /// Convert metadata to its PEP 566 JSON form
+    ///
+    /// Unknown headers are emitted under their transformed key as arrays,
+    /// like the known multiple-use fields, since any of them may repeat
+    /// (`License-File` is the common case).
+    pub fn to_pep566_json(&self) -> Value {
+        let mut map = Map::new();
+
+        map.insert("metadata_version".into(), self.metadata_version.clone().into());
+        map.insert("name".into(), self.name.clone().into());
+        map.insert("version".into(), self.version.clone().into());
+
+        for (key, value) in [
+            ("summary", &self.summary),
+            ("description", &self.description),
+            ("description_content_type", &self.description_content_type),
+            ("home_page", &self.home_page),
+            ("download_url", &self.download_url),
+            ("author", &self.author),
+            ("author_email", &self.author_email),
+            ("maintainer", &self.maintainer),
+            ("maintainer_email", &self.maintainer_email),
+            ("license", &self.license),
+            ("requires_python", &self.requires_python),
+        ] {
+            if let Some(v) = value {
+                map.insert(key.into(), v.clone().into());
+            }
+        }
+
+        // Split like `keywords_list`, so comma-separated keywords with
+        // spaces in them stay whole
+        if self.keywords.is_some() {
+            map.insert("keywords".into(), self.keywords_list().into());
+        }
+
+        for (key, values) in [
+            ("platform", &self.platform),
+            ("classifier", &self.classifiers),
+            ("requires_dist", &self.requires_dist),
+            ("requires_external", &self.requires_external),
+            ("project_url", &self.project_url),
+            ("provides_extra", &self.provides_extra),
+            ("provides_dist", &self.provides_dist),
+            ("obsoletes_dist", &self.obsoletes_dist),
+            ("dynamic", &self.dynamic),
+        ] {
+            if !values.is_empty() {
+                map.insert(key.into(), values.clone().into());
+            }
+        }
+
+        let mut extra: Vec<_> = self.extra_headers.iter().collect();
+        extra.sort_by(|a, b| a.0.cmp(b.0));
+        for (key, values) in extra {
+            map.insert(json_key(key), values.clone().into());
+        }
+
+        Value::Object(map)
+    }
+
+    /// Serialize metadata to a PEP 566 JSON string
+    ///
+    /// The string form of `to_pep566_json`, in the shape PyPI's JSON API
+    /// uses for a release's `info`.
+    pub fn to_json(&self) -> String {
+        self.to_pep566_json().to_string()
+    }
+
+    /// Parse metadata from a PEP 566 JSON string
+    ///
+    /// See `from_pep566_json` for the mapping. Returns
+    /// `MetadataError::Parse` if `json` isn't valid JSON.
+    pub fn from_json(json: &str) -> Result<Self, MetadataError> {
+        let value: Value = serde_json::from_str(json)
+            .map_err(|e| MetadataError::Parse(format!("invalid PEP 566 JSON: {}", e)))?;
+        Self::from_pep566_json(&value)
+    }
+
+    /// Build metadata from its PEP 566 JSON form
+    ///
+    /// This is the inverse of `to_pep566_json`. `keywords` is joined back
+    /// with commas, as `set_keywords_list` does, and unrecognized keys are kept as extra headers with
+    /// their field name restored (e.g. `license_file` -> `License-File`).
+    pub fn from_pep566_json(value: &Value) -> Result<Self, MetadataError> {
+        let map = value
+            .as_object()
+            .ok_or_else(|| MetadataError::Parse("PEP 566 JSON must be an object".to_string()))?;
+
+        let mut metadata = Metadata::default();
+        for (key, value) in map {
+            let values = json_strings(key, value)?;
+            match key.as_str() {
+                "keywords" => metadata.set_keywords_list(&values),
+                "classifier" => metadata.classifiers = values,
+                "platform" => metadata.platform = values,
+                "requires_dist" => metadata.requires_dist = values,
+                "requires_external" => metadata.requires_external = values,
+                "project_url" => metadata.project_url = values,
+                "provides_extra" => metadata.provides_extra = values,
+                "provides_dist" => metadata.provides_dist = values,
+                "obsoletes_dist" => metadata.obsoletes_dist = values,
+                "dynamic" => metadata.dynamic = values,
+                _ => {
+                    let field = header_name(key);
+                    for v in values {
+                        metadata.set_field(&field, &v)?;
+                    }
+                }
+            }
+        }
+
+        if metadata.name.is_empty() {
+            return Err(MetadataError::MissingField("Name".to_string()));
+        }
+        if metadata.version.is_empty() {
+            return Err(MetadataError::MissingField("Version".to_string()));
+        }
+
+        Ok(metadata)
+    }
+}
+
+/// Transform a METADATA field name into its PEP 566 JSON key
+fn json_key(field: &str) -> String {
+    field.to_ascii_lowercase().replace('-', "_")
+}
+
+/// Restore the METADATA field name for a PEP 566 JSON key
+fn header_name(key: &str) -> String {
+    match key {
+        "home_page" => "Home-page".to_string(),
+        "download_url" => "Download-URL".to_string(),
+        "project_url" => "Project-URL".to_string(),
+        "author_email" => "Author-email".to_string(),
+        "maintainer_email" => "Maintainer-email".to_string(),
+        _ => key
+            .split('_')
+            .map(|word| {
+                let mut chars = word.chars();
+                match chars.next() {
+                    Some(first) => first.to_ascii_uppercase().to_string() + chars.as_str(),
+                    None => String::new(),
+                }
+            })
+            .collect::<Vec<_>>()
+            .join("-"),
+    }
+}
+
+/// Read a JSON value as one or more strings
+fn json_strings(key: &str, value: &Value) -> Result<Vec<String>, MetadataError> {
+    let not_string = || MetadataError::InvalidValue {
+        field: key.to_string(),
+        reason: "expected a string or an array of strings".to_string(),
+    };
+    match value {
+        Value::String(s) => Ok(vec![s.clone()]),
+        Value::Array(items) => items
+            .iter()
+            .map(|item| item.as_str().map(str::to_string).ok_or_else(not_string))
+            .collect(),
+        _ => Err(not_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    const METADATA: &str = "Metadata-Version: 2.1
+Name: example-pkg
+Version: 1.2.0
+Summary: An example package
+Home-page: https://example.com
+Author-email: Jane <jane@example.com>
+Keywords: packaging metadata
+Requires-Python: >=3.8
+Classifier: Programming Language :: Python :: 3
+Classifier: License :: OSI Approved :: MIT License
+Requires-Dist: requests>=2.20
+Requires-Dist: click; extra == \"cli\"
+Project-URL: Source, https://example.com/src
+Provides-Extra: cli
+License-File: LICENSE
+
+A longer description.";
+
+    fn expected() -> Value {
+        json!({
+            "metadata_version": "2.1",
+            "name": "example-pkg",
+            "version": "1.2.0",
+            "summary": "An example package",
+            "home_page": "https://example.com",
+            "author_email": "Jane <jane@example.com>",
+            "keywords": ["packaging", "metadata"],
+            "requires_python": ">=3.8",
+            "classifier": [
+                "Programming Language :: Python :: 3",
+                "License :: OSI Approved :: MIT License"
+            ],
+            "requires_dist": ["requests>=2.20", "click; extra == \"cli\""],
+            "project_url": ["Source, https://example.com/src"],
+            "provides_extra": ["cli"],
+            "license_file": ["LICENSE"],
+            "description": "A longer description."
+        })
+    }
+
+    #[test]
+    fn test_to_pep566_json() {
+        let metadata = Metadata::parse(METADATA).unwrap();
+        assert_eq!(metadata.to_pep566_json(), expected());
+    }
+
+    #[test]
+    fn test_from_pep566_json_roundtrip() {
+        let metadata = Metadata::from_pep566_json(&expected()).unwrap();
+        assert_eq!(metadata.name, "example-pkg");
+        assert_eq!(metadata.home_page.as_deref(), Some("https://example.com"));
+        assert_eq!(metadata.keywords.as_deref(), Some("packaging,metadata"));
+        assert_eq!(metadata.classifiers.len(), 2);
+        assert_eq!(
+            metadata.extra_headers.get("License-File"),
+            Some(&vec!["LICENSE".to_string()])
+        );
+        assert_eq!(metadata.to_pep566_json(), expected());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_uses_pep566_keys() {
+        let metadata = Metadata::parse(METADATA).unwrap();
+        let value = serde_json::to_value(&metadata).unwrap();
+        let pep566 = expected();
+        for key in [
+            "metadata_version",
+            "home_page",
+            "classifier",
+            "requires_dist",
+        ] {
+            assert_eq!(value[key], pep566[key], "{key}");
+        }
+        assert_eq!(value["keywords"], "packaging metadata");
+
+        let restored: Metadata = serde_json::from_value(value).unwrap();
+        assert_eq!(restored.to_pep566_json(), pep566);
+
+        // Missing keys fall back to the defaults
+        let minimal: Metadata =
+            serde_json::from_value(json!({"name": "pkg", "version": "1.0"})).unwrap();
+        assert_eq!(minimal.name, "pkg");
+        assert!(minimal.classifiers.is_empty());
+    }
+
+    #[test]
+    fn test_comma_separated_keywords() {
+        let mut metadata = Metadata::parse(METADATA).unwrap();
+        metadata.keywords = Some("machine learning, deep learning".to_string());
+        let value = metadata.to_pep566_json();
+        assert_eq!(
+            value["keywords"],
+            json!(["machine learning", "deep learning"])
+        );
+
+        let restored = Metadata::from_pep566_json(&value).unwrap();
+        assert_eq!(
+            restored.keywords.as_deref(),
+            Some("machine learning,deep learning")
+        );
+        assert_eq!(restored.to_pep566_json(), value);
+    }
+
+    #[test]
+    fn test_json_roundtrip_through_metadata_file() {
+        let json = Metadata::parse(METADATA).unwrap().to_json();
+        let from_json = Metadata::from_json(&json).unwrap();
+        let reparsed = Metadata::parse(&from_json.serialize()).unwrap();
+        assert_eq!(reparsed.to_json(), json);
+        assert_eq!(
+            reparsed.description.as_deref(),
+            Some("A longer description.")
+        );
+
+        let with_content_type = Metadata::from_json(
+            r#"{"name": "pkg", "version": "1.0", "description_content_type": "text/markdown"}"#,
+        )
+        .unwrap();
+        assert!(
+            with_content_type
+                .serialize()
+                .contains("Description-Content-Type: text/markdown\n")
+        );
+
+        assert!(matches!(
+            Metadata::from_json("{not json"),
+            Err(MetadataError::Parse(_))
+        ));
+    }
+
+    #[test]
+    fn test_from_pep566_json_rejects_invalid() {
+        assert!(matches!(
+            Metadata::from_pep566_json(&json!(["not", "an", "object"])),
+            Err(MetadataError::Parse(_))
+        ));
+        assert!(matches!(
+            Metadata::from_pep566_json(&json!({"name": "pkg"})),
+            Err(MetadataError::MissingField(_))
+        ));
+        assert!(matches!(
+            Metadata::from_pep566_json(&json!({"name": "pkg", "version": 1})),
+            Err(MetadataError::InvalidValue { .. })
+        ));
+    }
+}
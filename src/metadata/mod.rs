@@ -1,5 +1,17 @@
 //! Metadata parsing and serialization for Python wheel METADATA files
 
+mod contributors;
+#[cfg(feature = "json")]
+mod json;
+mod requirement;
 mod types;
 
+pub use contributors::Contributor;
+pub use contributors::Role;
+pub use contributors::format_address_list;
+pub use contributors::parse_address_list;
+pub use requirement::Requirement;
+pub use requirement::VersionSpecifier;
 pub use types::Metadata;
+pub use types::MetadataValue;
+pub use types::validate_description_content_type;
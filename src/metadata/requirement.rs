@@ -0,0 +1,550 @@
+//! PEP 508 dependency specifier parsing
+
+use std::fmt;
+
+use crate::error::RequirementError;
+
+/// Version comparison operators, longest first so prefixes don't shadow
+/// them (e.g. `===` before `==`)
+const COMPARISON_OPERATORS: &[&str] = &["===", "~=", "==", "!=", "<=", ">=", "<", ">"];
+
+/// Environment marker variables defined by PEP 508
+const MARKER_VARIABLES: &[&str] = &[
+    "python_version",
+    "python_full_version",
+    "os_name",
+    "sys_platform",
+    "platform_release",
+    "platform_system",
+    "platform_version",
+    "platform_machine",
+    "platform_python_implementation",
+    "implementation_name",
+    "implementation_version",
+    "extra",
+];
+
+/// A single version clause such as `>=2.0`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VersionSpecifier {
+    pub operator: String,
+    pub version: String,
+}
+
+/// A parsed PEP 508 dependency specifier, as found in `Requires-Dist` and
+/// `Obsoletes-Dist` (or `Provides-Dist`, see `parse_provides`)
+///
+/// Parsing checks the grammar (names, extras, version clauses, and marker
+/// structure); markers are kept as text and not evaluated.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Requirement {
+    pub name: String,
+    pub extras: Vec<String>,
+    /// Version clauses; empty for URL requirements or unversioned names
+    pub specifiers: Vec<VersionSpecifier>,
+    /// Direct reference given with `name @ url`
+    pub url: Option<String>,
+    /// Environment marker after `;`, trimmed
+    pub marker: Option<String>,
+}
+
+impl Requirement {
+    /// Parse a dependency specifier like `requests[socks]>=2.20; python_version < "3.10"`
+    pub fn parse(input: &str) -> Result<Self, RequirementError> {
+        Self::parse_entry(input, false)
+    }
+
+    /// Parse a `Provides-Dist` entry like `OtherProject (3.1); os_name == "nt"`
+    ///
+    /// Same as `parse`, except that the version clause may be a bare version
+    /// naming the provided release, as the core metadata spec allows. It is
+    /// reported with the `==` operator.
+    pub fn parse_provides(input: &str) -> Result<Self, RequirementError> {
+        Self::parse_entry(input, true)
+    }
+
+    fn parse_entry(input: &str, bare_version: bool) -> Result<Self, RequirementError> {
+        let input = input.trim();
+        let name_len = input
+            .find(|c: char| !is_name_char(c))
+            .unwrap_or(input.len());
+        let name = &input[..name_len];
+        if name.is_empty() {
+            return Err(RequirementError::InvalidName(format!(
+                "'{}' does not start with a project name",
+                input
+            )));
+        }
+        if !is_valid_name(name) {
+            return Err(RequirementError::InvalidName(format!(
+                "'{}' must start and end with a letter or digit",
+                name
+            )));
+        }
+
+        let mut rest = input[name_len..].trim_start();
+        if rest.starts_with(|c: char| c.is_ascii_alphanumeric()) {
+            return Err(RequirementError::InvalidName(format!(
+                "unexpected '{}' after '{}'; names can't contain whitespace",
+                rest, name
+            )));
+        }
+
+        let mut extras = Vec::new();
+        if let Some(after) = rest.strip_prefix('[') {
+            let (list, after) = after.split_once(']').ok_or_else(|| {
+                RequirementError::InvalidExtras(format!("unclosed '[' in '{}'", input))
+            })?;
+            if !list.trim().is_empty() {
+                for extra in list.split(',').map(str::trim) {
+                    if !is_valid_name(extra) {
+                        return Err(RequirementError::InvalidExtras(format!(
+                            "'{}' is not a valid extra name",
+                            extra
+                        )));
+                    }
+                    extras.push(extra.to_string());
+                }
+            }
+            rest = after.trim_start();
+        }
+
+        let mut url = None;
+        let mut specifiers = Vec::new();
+        let marker = if let Some(after) = rest.strip_prefix('@') {
+            // The URL runs to the next whitespace, so a marker after it must
+            // be separated by whitespace (`;` is legal inside URLs)
+            let after = after.trim_start();
+            let url_len = after.find(char::is_whitespace).unwrap_or(after.len());
+            let value = &after[..url_len];
+            if !value.contains(':') {
+                return Err(RequirementError::InvalidUrl(format!(
+                    "'{}' is not a URL",
+                    value
+                )));
+            }
+            url = Some(value.to_string());
+            let after = after[url_len..].trim_start();
+            if after.is_empty() {
+                None
+            } else {
+                Some(after.strip_prefix(';').ok_or_else(|| {
+                    RequirementError::InvalidUrl(format!("unexpected '{}' after URL", after))
+                })?)
+            }
+        } else {
+            let (spec, marker) = match rest.split_once(';') {
+                Some((spec, marker)) => (spec, Some(marker)),
+                None => (rest, None),
+            };
+            specifiers = parse_specifiers(spec, bare_version)?;
+            marker
+        };
+
+        let marker = match marker.map(str::trim) {
+            Some(marker) => {
+                check_marker(marker).map_err(|reason| {
+                    RequirementError::InvalidMarker(format!("'{}': {}", marker, reason))
+                })?;
+                Some(marker.to_string())
+            }
+            None => None,
+        };
+
+        Ok(Self {
+            name: name.to_string(),
+            extras,
+            specifiers,
+            url,
+            marker,
+        })
+    }
+}
+
+impl fmt::Display for VersionSpecifier {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}{}", self.operator, self.version)
+    }
+}
+
+/// Writes the canonical form, e.g. `requests[socks]>=2.20,<3; python_version < "3.10"`
+///
+/// Whitespace and parentheses around version clauses are not preserved, so
+/// this may differ from the parsed input but always parses back to an equal
+/// `Requirement`.
+impl fmt::Display for Requirement {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.name)?;
+        if !self.extras.is_empty() {
+            write!(f, "[{}]", self.extras.join(","))?;
+        }
+        if let Some(ref url) = self.url {
+            // A marker after a URL must be separated by whitespace
+            write!(f, " @ {}", url)?;
+            if self.marker.is_some() {
+                f.write_str(" ")?;
+            }
+        } else {
+            for (i, specifier) in self.specifiers.iter().enumerate() {
+                if i > 0 {
+                    f.write_str(",")?;
+                }
+                write!(f, "{}", specifier)?;
+            }
+        }
+        if let Some(ref marker) = self.marker {
+            write!(f, "; {}", marker)?;
+        }
+        Ok(())
+    }
+}
+
+fn is_name_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.')
+}
+
+/// Check a project or extra name: letters, digits, `-`, `_` and `.`,
+/// starting and ending with a letter or digit
+fn is_valid_name(name: &str) -> bool {
+    !name.is_empty()
+        && name.chars().all(is_name_char)
+        && name.starts_with(|c: char| c.is_ascii_alphanumeric())
+        && name.ends_with(|c: char| c.is_ascii_alphanumeric())
+}
+
+/// Parse a comma-separated list of version clauses, optionally wrapped in
+/// parentheses
+///
+/// With `bare_version`, a lone version without an operator is accepted as
+/// `==version` (the `Provides-Dist` form).
+fn parse_specifiers(
+    spec: &str,
+    bare_version: bool,
+) -> Result<Vec<VersionSpecifier>, RequirementError> {
+    let mut spec = spec.trim();
+    if let Some(inner) = spec.strip_prefix('(') {
+        spec = inner
+            .strip_suffix(')')
+            .ok_or_else(|| {
+                RequirementError::InvalidSpecifier(format!("unclosed '(' in '{}'", spec))
+            })?
+            .trim();
+    }
+    if spec.is_empty() {
+        return Ok(Vec::new());
+    }
+    if bare_version && is_valid_version(spec) {
+        return Ok(vec![VersionSpecifier {
+            operator: "==".to_string(),
+            version: spec.to_string(),
+        }]);
+    }
+    spec.split(',')
+        .map(|clause| parse_specifier(clause.trim()))
+        .collect()
+}
+
+fn parse_specifier(clause: &str) -> Result<VersionSpecifier, RequirementError> {
+    let Some(operator) = COMPARISON_OPERATORS
+        .iter()
+        .find(|op| clause.starts_with(**op))
+    else {
+        return Err(RequirementError::InvalidSpecifier(format!(
+            "'{}' does not start with a comparison operator",
+            clause
+        )));
+    };
+    let version = clause[operator.len()..].trim();
+    let valid = match *operator {
+        // Arbitrary equality compares strings, so anything goes
+        "===" => !version.is_empty() && !version.contains(char::is_whitespace),
+        "==" | "!=" => is_valid_version(version.strip_suffix(".*").unwrap_or(version)),
+        _ => is_valid_version(version),
+    };
+    if !valid {
+        return Err(RequirementError::InvalidSpecifier(format!(
+            "'{}' is not a valid version for '{}'",
+            version, operator
+        )));
+    }
+    Ok(VersionSpecifier {
+        operator: operator.to_string(),
+        version: version.to_string(),
+    })
+}
+
+/// Loose PEP 440 check: starts with a letter or digit and uses only the
+/// characters a version can contain
+fn is_valid_version(version: &str) -> bool {
+    version.starts_with(|c: char| c.is_ascii_alphanumeric())
+        && version
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '_' | '+' | '!'))
+}
+
+/// Tokens of an environment marker
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum MarkerToken {
+    Open,
+    Close,
+    /// A marker variable or quoted string
+    Value,
+    /// A comparison operator, `in`, or `not in`
+    Operator,
+    And,
+    Or,
+}
+
+fn tokenize_marker(marker: &str) -> Result<Vec<MarkerToken>, String> {
+    let mut tokens = Vec::new();
+    let mut rest = marker.trim_start();
+    while !rest.is_empty() {
+        if let Some(after) = rest.strip_prefix('(') {
+            tokens.push(MarkerToken::Open);
+            rest = after;
+        } else if let Some(after) = rest.strip_prefix(')') {
+            tokens.push(MarkerToken::Close);
+            rest = after;
+        } else if let Some(quote) = rest.chars().next().filter(|c| matches!(c, '\'' | '"')) {
+            let body = &rest[1..];
+            let end = body
+                .find(quote)
+                .ok_or_else(|| format!("unterminated string {}", rest))?;
+            tokens.push(MarkerToken::Value);
+            rest = &body[end + 1..];
+        } else if let Some(op) = COMPARISON_OPERATORS
+            .iter()
+            .find(|op| rest.starts_with(**op))
+        {
+            tokens.push(MarkerToken::Operator);
+            rest = &rest[op.len()..];
+        } else {
+            let word_len = rest
+                .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+                .unwrap_or(rest.len());
+            let word = &rest[..word_len];
+            rest = &rest[word_len..];
+            match word {
+                "and" => tokens.push(MarkerToken::And),
+                "or" => tokens.push(MarkerToken::Or),
+                "in" => tokens.push(MarkerToken::Operator),
+                "not" => {
+                    let after = rest.trim_start();
+                    match after.strip_prefix("in") {
+                        Some(after_in) if !after_in.starts_with(is_name_char) => {
+                            tokens.push(MarkerToken::Operator);
+                            rest = after_in;
+                        }
+                        _ => return Err("expected 'in' after 'not'".to_string()),
+                    }
+                }
+                "" => return Err(format!("unexpected '{}'", rest)),
+                word if MARKER_VARIABLES.contains(&word) => tokens.push(MarkerToken::Value),
+                word => return Err(format!("unknown marker variable '{}'", word)),
+            }
+        }
+        rest = rest.trim_start();
+    }
+    Ok(tokens)
+}
+
+/// Check that an environment marker is well-formed: comparisons of
+/// variables and quoted strings, combined with `and`/`or` and parentheses
+fn check_marker(marker: &str) -> Result<(), String> {
+    let tokens = tokenize_marker(marker)?;
+    let mut parser = MarkerParser {
+        tokens: &tokens,
+        pos: 0,
+    };
+    parser.parse_or()?;
+    if parser.pos < tokens.len() {
+        return Err("unexpected trailing input".to_string());
+    }
+    Ok(())
+}
+
+/// Recursive-descent parser over marker tokens that only checks structure
+struct MarkerParser<'a> {
+    tokens: &'a [MarkerToken],
+    pos: usize,
+}
+
+impl MarkerParser<'_> {
+    fn eat(&mut self, token: MarkerToken) -> bool {
+        if self.tokens.get(self.pos) == Some(&token) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn parse_or(&mut self) -> Result<(), String> {
+        self.parse_and()?;
+        while self.eat(MarkerToken::Or) {
+            self.parse_and()?;
+        }
+        Ok(())
+    }
+
+    fn parse_and(&mut self) -> Result<(), String> {
+        self.parse_expression()?;
+        while self.eat(MarkerToken::And) {
+            self.parse_expression()?;
+        }
+        Ok(())
+    }
+
+    fn parse_expression(&mut self) -> Result<(), String> {
+        if self.eat(MarkerToken::Open) {
+            self.parse_or()?;
+            if !self.eat(MarkerToken::Close) {
+                return Err("missing ')'".to_string());
+            }
+            return Ok(());
+        }
+        if !self.eat(MarkerToken::Value) {
+            return Err("expected a marker variable or quoted string".to_string());
+        }
+        if !self.eat(MarkerToken::Operator) {
+            return Err("expected a comparison operator".to_string());
+        }
+        if !self.eat(MarkerToken::Value) {
+            return Err("expected a marker variable or quoted string after operator".to_string());
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_valid_requirements() {
+        let req =
+            Requirement::parse("requests[socks, security] >=2.20,<3 ; python_version < \"3.10\"")
+                .unwrap();
+        assert_eq!(req.name, "requests");
+        assert_eq!(req.extras, ["socks", "security"]);
+        assert_eq!(
+            req.specifiers,
+            [
+                VersionSpecifier {
+                    operator: ">=".to_string(),
+                    version: "2.20".to_string(),
+                },
+                VersionSpecifier {
+                    operator: "<".to_string(),
+                    version: "3".to_string(),
+                },
+            ]
+        );
+        assert_eq!(req.marker.as_deref(), Some("python_version < \"3.10\""));
+
+        for input in [
+            "click",
+            "foo.bar-baz_qux (==1.0.*)",
+            "torch===2.5.0+cu121",
+            "pkg @ https://example.com/pkg.whl ; os_name == 'posix'",
+            "pkg; (sys_platform == 'linux' or sys_platform == 'darwin') and extra == 'gpu'",
+            "pkg; 'linux' not in sys_platform",
+        ] {
+            assert!(Requirement::parse(input).is_ok(), "{input}");
+        }
+    }
+
+    #[test]
+    fn test_display_round_trips() {
+        for (input, canonical) in [
+            ("requests[socks]>=2.0", "requests[socks]>=2.0"),
+            (
+                "requests [socks, security] (>=2.20, <3) ; python_version < \"3.9\"",
+                "requests[socks,security]>=2.20,<3; python_version < \"3.9\"",
+            ),
+            ("click", "click"),
+            (
+                "pkg @ https://example.com/pkg.whl ; os_name == 'posix'",
+                "pkg @ https://example.com/pkg.whl ; os_name == 'posix'",
+            ),
+        ] {
+            let req = Requirement::parse(input).unwrap();
+            assert_eq!(req.to_string(), canonical);
+            assert_eq!(Requirement::parse(canonical).unwrap(), req);
+        }
+    }
+
+    #[test]
+    fn test_parse_obsoletes_and_provides() {
+        let req = Requirement::parse("Gorgon (<3.0); os_name == \"posix\"").unwrap();
+        assert_eq!(req.name, "Gorgon");
+        assert_eq!(
+            req.specifiers,
+            [VersionSpecifier {
+                operator: "<".to_string(),
+                version: "3.0".to_string(),
+            }]
+        );
+        assert_eq!(req.marker.as_deref(), Some("os_name == \"posix\""));
+
+        // Only Provides-Dist allows a bare version
+        assert!(Requirement::parse("OtherProject (3.1)").is_err());
+        let req =
+            Requirement::parse_provides("OtherProject (3.1); python_version >= '3.4'").unwrap();
+        assert_eq!(
+            req.specifiers,
+            [VersionSpecifier {
+                operator: "==".to_string(),
+                version: "3.1".to_string(),
+            }]
+        );
+        assert!(Requirement::parse_provides("virtual_package").is_ok());
+    }
+
+    #[test]
+    fn test_parse_invalid_name() {
+        assert!(matches!(
+            Requirement::parse("my pkg>=1"),
+            Err(RequirementError::InvalidName(_))
+        ));
+        assert!(matches!(
+            Requirement::parse("-pkg"),
+            Err(RequirementError::InvalidName(_))
+        ));
+        assert!(matches!(
+            Requirement::parse("pkg[bad extra]"),
+            Err(RequirementError::InvalidExtras(_))
+        ));
+    }
+
+    #[test]
+    fn test_parse_invalid_specifier() {
+        assert!(matches!(
+            Requirement::parse("requests=>2"),
+            Err(RequirementError::InvalidSpecifier(_))
+        ));
+        assert!(matches!(
+            Requirement::parse("requests>=2.*"),
+            Err(RequirementError::InvalidSpecifier(_))
+        ));
+    }
+
+    #[test]
+    fn test_parse_invalid_marker() {
+        for input in [
+            "pkg; python_version >=",
+            "pkg; python_vers == '3'",
+            "pkg; (os_name == 'nt'",
+            "pkg; os_name == 'nt",
+            "pkg;",
+        ] {
+            assert!(
+                matches!(
+                    Requirement::parse(input),
+                    Err(RequirementError::InvalidMarker(_))
+                ),
+                "{input}"
+            );
+        }
+    }
+}
@@ -1,11 +1,22 @@
 //! Metadata types for Python wheel METADATA file (PEP 566)
 
 use std::collections::HashMap;
+use std::collections::HashSet;
 
 use crate::error::MetadataError;
+use crate::metadata::Requirement;
+use crate::name::normalize_project_name;
 
 /// Core metadata per PEP 566/621
+///
+/// With the `serde` feature, fields serialize under their PEP 566 JSON keys
+/// (`classifier`, `requires_dist`, ...) and missing keys deserialize to the
+/// defaults. Unlike `to_pep566_json`, every field is always present,
+/// `keywords` stays a single string and unknown headers stay nested under
+/// `extra_headers`, so a round trip gives back the same struct.
 #[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
 pub struct Metadata {
     // Required fields
     pub metadata_version: String,
@@ -27,6 +38,7 @@ pub struct Metadata {
     pub requires_python: Option<String>,
 
     // Multi-value fields
+    #[cfg_attr(feature = "serde", serde(rename = "classifier"))]
     pub classifiers: Vec<String>,
     pub platform: Vec<String>,
     pub requires_dist: Vec<String>,
@@ -35,12 +47,405 @@ pub struct Metadata {
     pub provides_extra: Vec<String>,
     pub provides_dist: Vec<String>,
     pub obsoletes_dist: Vec<String>,
+    /// Fields declared `Dynamic` (PEP 643, Metadata-Version 2.2+)
+    pub dynamic: Vec<String>,
 
     // For preserving unknown headers
     pub extra_headers: HashMap<String, Vec<String>>,
 }
 
+/// Media types allowed in Description-Content-Type
+const DESCRIPTION_MEDIA_TYPES: &[&str] = &["text/plain", "text/x-rst", "text/markdown"];
+
+/// Markdown variants allowed in Description-Content-Type
+const MARKDOWN_VARIANTS: &[&str] = &["GFM", "CommonMark"];
+
+/// Validate a Description-Content-Type value.
+///
+/// The media type must be `text/plain`, `text/x-rst`, or `text/markdown`.
+/// The optional `charset` parameter must be `UTF-8`, and the optional
+/// `variant` parameter is only allowed for `text/markdown` and must be `GFM`
+/// or `CommonMark`. Media types and parameter names are case-insensitive.
+pub fn validate_description_content_type(value: &str) -> Result<(), MetadataError> {
+    let invalid = |reason: String| MetadataError::InvalidValue {
+        field: "Description-Content-Type".to_string(),
+        reason,
+    };
+
+    let mut parts = value.split(';');
+    let media_type = parts.next().unwrap_or("").trim().to_ascii_lowercase();
+    if !DESCRIPTION_MEDIA_TYPES.contains(&media_type.as_str()) {
+        return Err(invalid(format!(
+            "unsupported media type '{}' (expected one of {})",
+            media_type,
+            DESCRIPTION_MEDIA_TYPES.join(", ")
+        )));
+    }
+
+    for param in parts {
+        let param = param.trim();
+        if param.is_empty() {
+            continue;
+        }
+        let Some((key, val)) = param.split_once('=') else {
+            return Err(invalid(format!("malformed parameter '{}'", param)));
+        };
+        let key = key.trim().to_ascii_lowercase();
+        let val = val.trim().trim_matches('"');
+        match key.as_str() {
+            "charset" => {
+                if !val.eq_ignore_ascii_case("UTF-8") {
+                    return Err(invalid(format!(
+                        "unsupported charset '{}' (only UTF-8 is allowed)",
+                        val
+                    )));
+                }
+            }
+            "variant" => {
+                if media_type != "text/markdown" {
+                    return Err(invalid(format!(
+                        "variant is only allowed for text/markdown, not {}",
+                        media_type
+                    )));
+                }
+                if !MARKDOWN_VARIANTS.contains(&val) {
+                    return Err(invalid(format!(
+                        "unsupported markdown variant '{}' (expected one of {})",
+                        val,
+                        MARKDOWN_VARIANTS.join(", ")
+                    )));
+                }
+            }
+            _ => return Err(invalid(format!("unknown parameter '{}'", key))),
+        }
+    }
+
+    Ok(())
+}
+
+/// Known headers that may appear at most once
+const SINGLE_VALUE_FIELDS: &[&str] = &[
+    "Metadata-Version",
+    "Name",
+    "Version",
+    "Summary",
+    "Description",
+    "Description-Content-Type",
+    "Home-page",
+    "Download-URL",
+    "Author",
+    "Author-email",
+    "Maintainer",
+    "Maintainer-email",
+    "License",
+    "Keywords",
+    "Requires-Python",
+];
+
+/// Known headers that may be repeated
+const MULTI_VALUE_FIELDS: &[&str] = &[
+    "Classifier",
+    "Platform",
+    "Requires-Dist",
+    "Requires-External",
+    "Project-URL",
+    "Provides-Extra",
+    "Provides-Dist",
+    "Obsoletes-Dist",
+    "Dynamic",
+];
+
+/// Resolve a header name to the spelling used by the known-field tables
+///
+/// Header names are case-insensitive, so `Home-Page`, `home-page` and
+/// `Home-page` all resolve to `Home-page`. Returns `None` for headers that
+/// aren't known fields.
+fn canonical_field(key: &str) -> Option<&'static str> {
+    SINGLE_VALUE_FIELDS
+        .iter()
+        .chain(MULTI_VALUE_FIELDS)
+        .find(|field| field.eq_ignore_ascii_case(key))
+        .copied()
+}
+
+/// The value of a METADATA field, as returned by `Metadata::get`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MetadataValue {
+    /// A single-value field, or an unknown header that appears once
+    Single(Option<String>),
+    /// A multi-value field, or an unknown header that appears repeatedly
+    Multi(Vec<String>),
+}
+
+/// Fields introduced after Metadata-Version 1.0, with the version that
+/// added them
+const FIELD_VERSIONS: &[(&str, (u8, u8))] = &[
+    ("Classifier", (1, 1)),
+    ("Download-URL", (1, 1)),
+    ("Supported-Platform", (1, 1)),
+    ("Maintainer", (1, 2)),
+    ("Maintainer-email", (1, 2)),
+    ("Requires-Dist", (1, 2)),
+    ("Requires-Python", (1, 2)),
+    ("Requires-External", (1, 2)),
+    ("Project-URL", (1, 2)),
+    ("Provides-Dist", (1, 2)),
+    ("Obsoletes-Dist", (1, 2)),
+    ("Description-Content-Type", (2, 1)),
+    ("Provides-Extra", (2, 1)),
+    ("Dynamic", (2, 2)),
+    ("License-Expression", (2, 4)),
+    ("License-File", (2, 4)),
+];
+
+/// Metadata-Version values `Metadata::downgrade_metadata_version` can target
+const DOWNGRADE_TARGETS: &[(u8, u8)] = &[(2, 1), (2, 2), (2, 3), (2, 4)];
+
+/// Parse a Metadata-Version value like `2.4` into `(major, minor)`
+fn parse_metadata_version(value: &str) -> Option<(u8, u8)> {
+    let (major, minor) = value.trim().split_once('.')?;
+    Some((major.parse().ok()?, minor.parse().ok()?))
+}
+
+/// Key identifying a Requires-Dist entry for de-duplication: the normalized
+/// project name plus the marker with whitespace removed and quotes unified.
+/// Version specifiers and extras are ignored, so `requests>=2` and
+/// `requests>=2.31` are considered the same requirement.
+fn requirement_key(requirement: &str) -> (String, String) {
+    let (spec, marker) = requirement.split_once(';').unwrap_or((requirement, ""));
+    let spec = spec.trim();
+    let name_len = spec
+        .find(|c: char| !(c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.')))
+        .unwrap_or(spec.len());
+    let marker = marker
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .map(|c| if c == '\'' { '"' } else { c })
+        .collect();
+    (normalize_project_name(&spec[..name_len]), marker)
+}
+
+/// Header lines longer than this are folded; RFC 5322's hard limit, so
+/// ordinary METADATA is written exactly as before
+const MAX_HEADER_LINE: usize = 998;
+
+/// Width folded header lines are wrapped to, as `email.message` does
+const FOLD_WIDTH: usize = 78;
+
+/// Indentation for the lines of a multi-line value, as setuptools writes it
+const CONTINUATION_INDENT: &str = "        ";
+
+/// Write `key: value` followed by a newline
+///
+/// Each line after the first in a multi-line value (e.g. a License text) is
+/// indented as a continuation line. For fields that `is_foldable` accepts,
+/// a line that would exceed `MAX_HEADER_LINE` is folded at single spaces
+/// into lines of about `FOLD_WIDTH`, each fold starting with the space it
+/// broke at, which `Metadata::parse` joins back without adding a newline.
+/// Runs without such a space are left whole.
+fn write_header(output: &mut String, key: &str, value: &str) {
+    output.push_str(key);
+    output.push_str(": ");
+    let mut indent = key.len() + 2;
+    for (i, line) in value.split('\n').enumerate() {
+        if i > 0 {
+            output.push_str(CONTINUATION_INDENT);
+            indent = CONTINUATION_INDENT.len();
+        }
+        if indent + line.len() <= MAX_HEADER_LINE || !is_foldable(key) {
+            output.push_str(line);
+            output.push('\n');
+            continue;
+        }
+        for piece in fold(line, indent) {
+            output.push_str(piece);
+            output.push('\n');
+        }
+    }
+}
+
+/// Split `line` before single spaces so each piece fits `FOLD_WIDTH` where
+/// possible; the first piece shares its line with `indent` columns of prefix
+fn fold(line: &str, indent: usize) -> Vec<&str> {
+    let bytes = line.as_bytes();
+    let mut pieces = Vec::new();
+    let mut start = 0;
+    let mut width = indent;
+    let mut last_break = 0;
+    let breaks = (1..bytes.len().saturating_sub(1)).filter(|&i| {
+        bytes[i] == b' '
+            && !bytes[i - 1].is_ascii_whitespace()
+            && !bytes[i + 1].is_ascii_whitespace()
+    });
+    for i in breaks.chain([bytes.len()]) {
+        if width + (i - start) > FOLD_WIDTH && last_break > start {
+            pieces.push(&line[start..last_break]);
+            start = last_break;
+            width = 0;
+        }
+        last_break = i;
+    }
+    pieces.push(&line[start..]);
+    pieces
+}
+
+/// Whether a continuation line is a fold made by `write_header`: one space
+/// followed by text, using the same ASCII whitespace test as `fold`
+fn is_fold(line: &str) -> bool {
+    line.strip_prefix(' ')
+        .is_some_and(|rest| rest.starts_with(|c: char| !c.is_ascii_whitespace()))
+}
+
+/// Whether `key` holds single-line values that `write_header` may fold
+///
+/// License and Description keep their line structure, and unknown headers
+/// are passed through as found, so their continuation lines are never
+/// unfolded.
+fn is_foldable(key: &str) -> bool {
+    canonical_field(key).is_some_and(|field| !matches!(field, "License" | "Description"))
+}
+
 impl Metadata {
+    /// Append classifiers from `other` that aren't already present
+    ///
+    /// Classifiers are compared exactly. Existing entries keep their order,
+    /// followed by new ones in `other`'s order. Returns the number added.
+    pub fn merge_classifiers_from(&mut self, other: &Metadata) -> usize {
+        let before = self.classifiers.len();
+        for classifier in &other.classifiers {
+            if !self.classifiers.contains(classifier) {
+                self.classifiers.push(classifier.clone());
+            }
+        }
+        self.classifiers.len() - before
+    }
+
+    /// Append Requires-Dist entries from `other` that aren't already present
+    ///
+    /// Entries are compared by normalized project name and marker, so
+    /// `Foo_Bar>=1; python_version<'3.10'` duplicates
+    /// `foo-bar; python_version < "3.10"` and is skipped (the existing entry
+    /// wins). Existing entries keep their order, followed by new ones in
+    /// `other`'s order. Returns the number added.
+    pub fn merge_requires_dist_from(&mut self, other: &Metadata) -> usize {
+        let mut seen: HashSet<(String, String)> =
+            self.requires_dist.iter().map(|r| requirement_key(r)).collect();
+        let before = self.requires_dist.len();
+        for requirement in &other.requires_dist {
+            if seen.insert(requirement_key(requirement)) {
+                self.requires_dist.push(requirement.clone());
+            }
+        }
+        self.requires_dist.len() - before
+    }
+
+    /// Rewrite this metadata for an older Metadata-Version
+    ///
+    /// Fields the target version can't express are removed or translated:
+    /// below 2.4, `License-Expression` becomes the `License` value and
+    /// `License-File` entries are dropped (the files themselves stay in the
+    /// wheel); below 2.2, `Dynamic` is dropped. Returns a description of
+    /// every change.
+    ///
+    /// Only 2.1 through 2.4 can be targeted, since earlier versions can't
+    /// carry the description as the message body. Targeting a version newer
+    /// than the current one is an error, and so is a `License-Expression`
+    /// with nowhere to go because `License` is already set (or there is more
+    /// than one): the license would be silently lost, so the caller has to
+    /// remove one first. On error nothing is changed.
+    pub fn downgrade_metadata_version(
+        &mut self,
+        target: (u8, u8),
+    ) -> Result<Vec<String>, MetadataError> {
+        let invalid = |reason: String| MetadataError::InvalidValue {
+            field: "Metadata-Version".to_string(),
+            reason,
+        };
+        let current = parse_metadata_version(&self.metadata_version)
+            .ok_or_else(|| invalid(format!("cannot parse '{}'", self.metadata_version)))?;
+        if !DOWNGRADE_TARGETS.contains(&target) {
+            return Err(invalid(format!(
+                "cannot downgrade to {}.{}; supported targets are 2.1 to 2.4",
+                target.0, target.1
+            )));
+        }
+        if target > current {
+            return Err(invalid(format!(
+                "{}.{} is newer than the current {}",
+                target.0, target.1, self.metadata_version
+            )));
+        }
+
+        if target < (2, 4) {
+            let expressions: usize = self
+                .extra_headers
+                .iter()
+                .filter(|(key, _)| key.eq_ignore_ascii_case("License-Expression"))
+                .map(|(_, values)| values.len())
+                .sum();
+            if expressions > usize::from(self.license.is_none()) {
+                let reason = if self.license.is_some() {
+                    "License is already set"
+                } else {
+                    "it appears more than once"
+                };
+                return Err(MetadataError::InvalidValue {
+                    field: "License-Expression".to_string(),
+                    reason: format!(
+                        "{}, so it can't be moved to License for Metadata-Version {}.{}; \
+                         remove one first",
+                        reason, target.0, target.1
+                    ),
+                });
+            }
+        }
+
+        let mut changes = Vec::new();
+        if target < (2, 4) {
+            if let Some(expression) = self.take_extra_header("License-Expression").pop() {
+                changes.push(format!(
+                    "moved License-Expression to License: {}",
+                    expression
+                ));
+                self.license = Some(expression);
+            }
+            for file in self.take_extra_header("License-File") {
+                changes.push(format!("dropped License-File: {}", file));
+            }
+        }
+        if target < (2, 2) {
+            for field in std::mem::take(&mut self.dynamic) {
+                changes.push(format!("dropped Dynamic: {}", field));
+            }
+        }
+
+        let version = format!("{}.{}", target.0, target.1);
+        if current != target {
+            changes.push(format!(
+                "changed Metadata-Version: {} -> {}",
+                self.metadata_version, version
+            ));
+        }
+        self.metadata_version = version;
+        Ok(changes)
+    }
+
+    /// Remove an unknown header (matched case-insensitively), returning its
+    /// values
+    fn take_extra_header(&mut self, name: &str) -> Vec<String> {
+        let keys: Vec<String> = self
+            .extra_headers
+            .keys()
+            .filter(|key| key.eq_ignore_ascii_case(name))
+            .cloned()
+            .collect();
+        let mut values = Vec::new();
+        for key in keys {
+            values.extend(self.extra_headers.remove(&key).unwrap_or_default());
+        }
+        values
+    }
+
     /// Parse metadata from RFC822 format content
     pub fn parse(content: &str) -> Result<Self, MetadataError> {
         let mut metadata = Metadata::default();
@@ -65,10 +470,17 @@ impl Metadata {
 
                 // Check for continuation line (starts with whitespace)
                 if line.starts_with(' ') || line.starts_with('\t') {
-                    // Continuation of previous header
-                    if current_key.is_some() {
-                        current_value.push('\n');
-                        current_value.push_str(line.trim());
+                    // Continuation of previous header: in a single-line
+                    // field, a single space before text is a fold (see
+                    // `write_header`) and unfolds to that space; anything
+                    // else continues on a new line
+                    if let Some(key) = &current_key {
+                        if is_fold(line) && is_foldable(key) {
+                            current_value.push_str(line);
+                        } else {
+                            current_value.push('\n');
+                            current_value.push_str(line.trim());
+                        }
                     }
                     continue;
                 }
@@ -114,22 +526,23 @@ impl Metadata {
     }
 
     /// Set a metadata field by key
-    fn set_field(&mut self, key: &str, value: &str) -> Result<(), MetadataError> {
-        match key {
+    ///
+    /// Known fields are matched case-insensitively; unknown headers are
+    /// preserved with their original spelling.
+    pub(super) fn set_field(&mut self, key: &str, value: &str) -> Result<(), MetadataError> {
+        match canonical_field(key).unwrap_or(key) {
             "Metadata-Version" => self.metadata_version = value.to_string(),
             "Name" => self.name = value.to_string(),
             "Version" => self.version = value.to_string(),
             "Summary" => self.summary = Some(value.to_string()),
             "Description" => self.description = Some(value.to_string()),
             "Description-Content-Type" => self.description_content_type = Some(value.to_string()),
-            "Home-page" | "Home-Page" => self.home_page = Some(value.to_string()),
+            "Home-page" => self.home_page = Some(value.to_string()),
             "Download-URL" => self.download_url = Some(value.to_string()),
             "Author" => self.author = Some(value.to_string()),
-            "Author-email" | "Author-Email" => self.author_email = Some(value.to_string()),
+            "Author-email" => self.author_email = Some(value.to_string()),
             "Maintainer" => self.maintainer = Some(value.to_string()),
-            "Maintainer-email" | "Maintainer-Email" => {
-                self.maintainer_email = Some(value.to_string())
-            }
+            "Maintainer-email" => self.maintainer_email = Some(value.to_string()),
             "License" => self.license = Some(value.to_string()),
             "Keywords" => self.keywords = Some(value.to_string()),
             "Requires-Python" => self.requires_python = Some(value.to_string()),
@@ -141,6 +554,7 @@ impl Metadata {
             "Provides-Extra" => self.provides_extra.push(value.to_string()),
             "Provides-Dist" => self.provides_dist.push(value.to_string()),
             "Obsoletes-Dist" => self.obsoletes_dist.push(value.to_string()),
+            "Dynamic" => self.dynamic.push(value.to_string()),
             _ => {
                 // Preserve unknown headers
                 self.extra_headers
@@ -152,81 +566,482 @@ impl Metadata {
         Ok(())
     }
 
+    /// Replace every value of a field by header name
+    ///
+    /// Known multi-value fields (e.g. `Classifier`) take any number of
+    /// values, and unknown headers are stored as given. Known single-value
+    /// fields (e.g. `Summary`) must be given exactly one value; anything
+    /// else is rejected with `MetadataError::InvalidValue` rather than
+    /// written out as repeated headers.
+    pub fn set_values(&mut self, key: &str, values: Vec<String>) -> Result<(), MetadataError> {
+        if let Some(field) = self.multi_value_field_mut(key) {
+            *field = values;
+            return Ok(());
+        }
+        if canonical_field(key).is_some() {
+            let [value] =
+                <[String; 1]>::try_from(values).map_err(|values| MetadataError::InvalidValue {
+                    field: key.to_string(),
+                    reason: format!("single-value field cannot take {} values", values.len()),
+                })?;
+            return self.set_field(key, &value);
+        }
+        self.extra_headers.insert(key.to_string(), values);
+        Ok(())
+    }
+
+    /// Get a field by header name
+    ///
+    /// Known fields are matched case-insensitively (so `Home-Page` and
+    /// `home-page` both read `Home-page`) and come back as `Single` or
+    /// `Multi` according to their kind, even when unset. Unknown headers
+    /// are looked up in `extra_headers`, also ignoring case: one value is
+    /// returned as `Single`, several as `Multi`, and a missing header as
+    /// `Single(None)`.
+    pub fn get(&self, key: &str) -> MetadataValue {
+        let single = |value: &Option<String>| MetadataValue::Single(value.clone());
+        let multi = |values: &Vec<String>| MetadataValue::Multi(values.clone());
+        match canonical_field(key) {
+            Some("Metadata-Version") => MetadataValue::Single(Some(self.metadata_version.clone())),
+            Some("Name") => MetadataValue::Single(Some(self.name.clone())),
+            Some("Version") => MetadataValue::Single(Some(self.version.clone())),
+            Some("Summary") => single(&self.summary),
+            Some("Description") => single(&self.description),
+            Some("Description-Content-Type") => single(&self.description_content_type),
+            Some("Home-page") => single(&self.home_page),
+            Some("Download-URL") => single(&self.download_url),
+            Some("Author") => single(&self.author),
+            Some("Author-email") => single(&self.author_email),
+            Some("Maintainer") => single(&self.maintainer),
+            Some("Maintainer-email") => single(&self.maintainer_email),
+            Some("License") => single(&self.license),
+            Some("Keywords") => single(&self.keywords),
+            Some("Requires-Python") => single(&self.requires_python),
+            Some("Classifier") => multi(&self.classifiers),
+            Some("Platform") => multi(&self.platform),
+            Some("Requires-Dist") => multi(&self.requires_dist),
+            Some("Requires-External") => multi(&self.requires_external),
+            Some("Project-URL") => multi(&self.project_url),
+            Some("Provides-Extra") => multi(&self.provides_extra),
+            Some("Provides-Dist") => multi(&self.provides_dist),
+            Some("Obsoletes-Dist") => multi(&self.obsoletes_dist),
+            Some("Dynamic") => multi(&self.dynamic),
+            _ => match self
+                .extra_headers
+                .iter()
+                .find(|(name, _)| name.eq_ignore_ascii_case(key))
+            {
+                Some((_, values)) if values.len() == 1 => {
+                    MetadataValue::Single(Some(values[0].clone()))
+                }
+                Some((_, values)) => multi(values),
+                None => MetadataValue::Single(None),
+            },
+        }
+    }
+
+    /// Set a field by header name from a value shaped like `get` returns
+    ///
+    /// `Multi` replaces every value as in `set_values`, and `Single(Some(_))`
+    /// is the one-value case of it, so a single value given for a
+    /// multi-value field becomes a one-element list. `Single(None)` removes
+    /// the field as in `remove_field`.
+    pub fn set(&mut self, key: &str, value: MetadataValue) -> Result<(), MetadataError> {
+        match value {
+            MetadataValue::Single(Some(value)) => self.set_values(key, vec![value]),
+            MetadataValue::Single(None) => self.remove_field(key).map(|_| ()),
+            MetadataValue::Multi(values) => self.set_values(key, values),
+        }
+    }
+
+    /// Remove a field by header name, returning whether it had a value
+    ///
+    /// Known optional fields are matched case-insensitively and reset to
+    /// unset (or empty, for multi-value fields); unknown headers are removed
+    /// from `extra_headers`, also ignoring case. `Metadata-Version`, `Name`
+    /// and `Version` are required and can't be removed.
+    pub fn remove_field(&mut self, key: &str) -> Result<bool, MetadataError> {
+        if let Some(field @ ("Metadata-Version" | "Name" | "Version")) = canonical_field(key) {
+            return Err(MetadataError::InvalidValue {
+                field: field.to_string(),
+                reason: "required field cannot be removed".to_string(),
+            });
+        }
+        if let Some(values) = self.multi_value_field_mut(key) {
+            return Ok(!std::mem::take(values).is_empty());
+        }
+        if let Some(value) = self.optional_field_mut(key) {
+            return Ok(value.take().is_some());
+        }
+        Ok(!self.take_extra_header(key).is_empty())
+    }
+
+    /// Get the value backing a known optional single-value field
+    fn optional_field_mut(&mut self, key: &str) -> Option<&mut Option<String>> {
+        match canonical_field(key)? {
+            "Summary" => Some(&mut self.summary),
+            "Description" => Some(&mut self.description),
+            "Description-Content-Type" => Some(&mut self.description_content_type),
+            "Home-page" => Some(&mut self.home_page),
+            "Download-URL" => Some(&mut self.download_url),
+            "Author" => Some(&mut self.author),
+            "Author-email" => Some(&mut self.author_email),
+            "Maintainer" => Some(&mut self.maintainer),
+            "Maintainer-email" => Some(&mut self.maintainer_email),
+            "License" => Some(&mut self.license),
+            "Keywords" => Some(&mut self.keywords),
+            "Requires-Python" => Some(&mut self.requires_python),
+            _ => None,
+        }
+    }
+
+    /// Get the list backing a known multi-value field
+    fn multi_value_field_mut(&mut self, key: &str) -> Option<&mut Vec<String>> {
+        match canonical_field(key)? {
+            "Classifier" => Some(&mut self.classifiers),
+            "Platform" => Some(&mut self.platform),
+            "Requires-Dist" => Some(&mut self.requires_dist),
+            "Requires-External" => Some(&mut self.requires_external),
+            "Project-URL" => Some(&mut self.project_url),
+            "Provides-Extra" => Some(&mut self.provides_extra),
+            "Provides-Dist" => Some(&mut self.provides_dist),
+            "Obsoletes-Dist" => Some(&mut self.obsoletes_dist),
+            "Dynamic" => Some(&mut self.dynamic),
+            _ => None,
+        }
+    }
+
+    /// Length of the long description in bytes (0 if there is none)
+    ///
+    /// This is the raw UTF-8 size, which is what PyPI's upload size limit
+    /// applies to, not the rendered length.
+    pub fn description_byte_length(&self) -> usize {
+        self.description.as_ref().map_or(0, |d| d.len())
+    }
+
+    /// Check every Requires-Dist, Obsoletes-Dist and Provides-Dist entry
+    /// against the PEP 508 grammar
+    ///
+    /// Provides-Dist entries may also give a bare version (see
+    /// `Requirement::parse_provides`). Returns one message per entry that
+    /// fails to parse, naming the field, the entry and what is wrong with it
+    /// (name, extras, version specifier, URL, or marker); empty if all
+    /// entries are valid.
+    pub fn validate_dependencies(&self) -> Vec<String> {
+        let fields: [(&str, &[String], fn(&str) -> _); 3] = [
+            ("Requires-Dist", &self.requires_dist, Requirement::parse),
+            ("Obsoletes-Dist", &self.obsoletes_dist, Requirement::parse),
+            (
+                "Provides-Dist",
+                &self.provides_dist,
+                Requirement::parse_provides,
+            ),
+        ];
+        fields
+            .into_iter()
+            .flat_map(|(field, entries, parse)| {
+                entries.iter().filter_map(move |entry| {
+                    parse(entry)
+                        .err()
+                        .map(|e| format!("{} '{}': {}", field, entry, e))
+                })
+            })
+            .collect()
+    }
+
+    /// Check that the declared Metadata-Version supports every populated
+    /// field
+    ///
+    /// For example `Provides-Extra` needs 2.1 and `License-Expression` 2.4,
+    /// so declaring an older version makes installers misread the file.
+    /// Returns one `MetadataError::InvalidValue` per field that is too new,
+    /// or a single one for a Metadata-Version that doesn't parse.
+    pub fn validate(&self) -> Result<(), Vec<MetadataError>> {
+        let Some(declared) = parse_metadata_version(&self.metadata_version) else {
+            return Err(vec![MetadataError::InvalidValue {
+                field: "Metadata-Version".to_string(),
+                reason: format!("cannot parse '{}'", self.metadata_version),
+            }]);
+        };
+
+        let errors: Vec<MetadataError> = self
+            .fields()
+            .into_iter()
+            .filter_map(|(field, _)| {
+                let &(_, required) = FIELD_VERSIONS
+                    .iter()
+                    .find(|(name, _)| name.eq_ignore_ascii_case(&field))?;
+                (required > declared).then(|| MetadataError::InvalidValue {
+                    reason: format!(
+                        "requires Metadata-Version {}.{} or later, but {} is declared",
+                        required.0, required.1, self.metadata_version
+                    ),
+                    field,
+                })
+            })
+            .collect();
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+
+    /// Check the fields declared `Dynamic` against the rest of the metadata
+    ///
+    /// PEP 643 forbids `Name`, `Version` and `Metadata-Version` from being
+    /// dynamic, and a field that is dynamic yet has a concrete value is a
+    /// common build backend bug: tools can't tell which one to trust.
+    /// Returns one message per problem; empty if there are none.
+    pub fn validate_dynamic(&self) -> Vec<String> {
+        let mut problems = Vec::new();
+        for field in &self.dynamic {
+            let name = canonical_field(field).unwrap_or(field.as_str());
+            if matches!(name, "Name" | "Version" | "Metadata-Version") {
+                problems.push(format!("Dynamic: {} is not allowed to be dynamic", name));
+                continue;
+            }
+            let has_value = match self.get(name) {
+                MetadataValue::Single(value) => value.is_some(),
+                MetadataValue::Multi(values) => !values.is_empty(),
+            };
+            if has_value {
+                problems.push(format!(
+                    "Dynamic: {} is declared dynamic but has a value",
+                    name
+                ));
+            }
+        }
+        problems
+    }
+
+    /// Parse every Requires-Dist entry as a PEP 508 requirement
+    ///
+    /// `requires_dist` keeps the raw strings; this is a structured view of
+    /// them. Fails on the first entry that doesn't parse.
+    pub fn parsed_requires_dist(&self) -> Result<Vec<Requirement>, MetadataError> {
+        self.requires_dist
+            .iter()
+            .map(|entry| {
+                Requirement::parse(entry).map_err(|e| MetadataError::InvalidValue {
+                    field: "Requires-Dist".to_string(),
+                    reason: format!("'{}': {}", entry, e),
+                })
+            })
+            .collect()
+    }
+
+    /// Replace Requires-Dist with `requirements`, each written in its
+    /// canonical form
+    pub fn set_parsed_requires_dist(&mut self, requirements: &[Requirement]) {
+        self.requires_dist = requirements.iter().map(Requirement::to_string).collect();
+    }
+
+    /// Remove Obsoletes-Dist entries for a project, returning the number
+    /// removed
+    ///
+    /// Entries are matched by normalized project name, ignoring version
+    /// clauses and markers, so `Foo_Bar` removes `foo-bar (<2.0); os_name ==
+    /// "posix"`.
+    pub fn remove_obsoletes_dist(&mut self, name: &str) -> usize {
+        let name = normalize_project_name(name);
+        let before = self.obsoletes_dist.len();
+        self.obsoletes_dist
+            .retain(|entry| requirement_key(entry).0 != name);
+        before - self.obsoletes_dist.len()
+    }
+
+    /// Split the Keywords field into individual keywords
+    ///
+    /// Older tooling wrote keywords space-separated while the core metadata
+    /// spec recommends commas, so the delimiter is detected: if the value
+    /// contains a comma it is split on commas, otherwise on whitespace.
+    /// Keywords are trimmed and empty ones dropped.
+    pub fn keywords_list(&self) -> Vec<String> {
+        let Some(keywords) = self.keywords.as_deref() else {
+            return Vec::new();
+        };
+        let parts: Vec<&str> = if keywords.contains(',') {
+            keywords.split(',').collect()
+        } else {
+            keywords.split_whitespace().collect()
+        };
+        parts
+            .into_iter()
+            .map(str::trim)
+            .filter(|k| !k.is_empty())
+            .map(str::to_string)
+            .collect()
+    }
+
+    /// Set the Keywords field from a list, written comma-separated
+    ///
+    /// An empty list removes the field.
+    pub fn set_keywords_list(&mut self, keywords: &[String]) {
+        self.keywords = if keywords.is_empty() {
+            None
+        } else {
+            Some(keywords.join(","))
+        };
+    }
+
+    /// Append a keyword unless it is already listed, returning whether it
+    /// was added
+    ///
+    /// The field is rewritten comma-separated, like `set_keywords_list`.
+    pub fn add_keyword(&mut self, keyword: &str) -> bool {
+        let keyword = keyword.trim();
+        let mut keywords = self.keywords_list();
+        if keyword.is_empty() || keywords.iter().any(|k| k == keyword) {
+            return false;
+        }
+        keywords.push(keyword.to_string());
+        self.set_keywords_list(&keywords);
+        true
+    }
+
+    /// Rewrite the Keywords field in the comma-separated form
+    ///
+    /// The field is kept verbatim on parse; call this to turn e.g.
+    /// `packaging metadata wheel` into `packaging,metadata,wheel`.
+    pub fn normalize_keywords(&mut self) {
+        if self.keywords.is_some() {
+            let keywords = self.keywords_list();
+            self.set_keywords_list(&keywords);
+        }
+    }
+
+    /// List every populated field as `(header name, values)` pairs
+    ///
+    /// Fields are in serialization order, with unknown headers sorted by name
+    /// and the body last as `Description`. Single-value fields have exactly
+    /// one value; multi-value fields are omitted when empty.
+    pub fn fields(&self) -> Vec<(String, Vec<String>)> {
+        let mut fields = Vec::new();
+        for (key, value) in [
+            ("Metadata-Version", Some(&self.metadata_version)),
+            ("Name", Some(&self.name)),
+            ("Version", Some(&self.version)),
+            ("Summary", self.summary.as_ref()),
+            ("Description-Content-Type", self.description_content_type.as_ref()),
+            ("Home-page", self.home_page.as_ref()),
+            ("Download-URL", self.download_url.as_ref()),
+            ("Author", self.author.as_ref()),
+            ("Author-email", self.author_email.as_ref()),
+            ("Maintainer", self.maintainer.as_ref()),
+            ("Maintainer-email", self.maintainer_email.as_ref()),
+            ("License", self.license.as_ref()),
+            ("Keywords", self.keywords.as_ref()),
+            ("Requires-Python", self.requires_python.as_ref()),
+        ] {
+            if let Some(v) = value {
+                fields.push((key.to_string(), vec![v.clone()]));
+            }
+        }
+        for (key, values) in [
+            ("Platform", &self.platform),
+            ("Classifier", &self.classifiers),
+            ("Requires-Dist", &self.requires_dist),
+            ("Requires-External", &self.requires_external),
+            ("Project-URL", &self.project_url),
+            ("Provides-Extra", &self.provides_extra),
+            ("Provides-Dist", &self.provides_dist),
+            ("Obsoletes-Dist", &self.obsoletes_dist),
+            ("Dynamic", &self.dynamic),
+        ] {
+            if !values.is_empty() {
+                fields.push((key.to_string(), values.clone()));
+            }
+        }
+        let mut extra: Vec<_> = self.extra_headers.iter().collect();
+        extra.sort_by(|a, b| a.0.cmp(b.0));
+        for (key, values) in extra {
+            fields.push((key.clone(), values.clone()));
+        }
+        if let Some(ref desc) = self.description {
+            fields.push(("Description".to_string(), vec![desc.clone()]));
+        }
+        fields
+    }
+
     /// Serialize metadata back to RFC822 format
     pub fn serialize(&self) -> String {
         use std::fmt::Write;
         let mut output = String::new();
 
         // Required fields first
-        writeln!(output, "Metadata-Version: {}", self.metadata_version).unwrap();
-        writeln!(output, "Name: {}", self.name).unwrap();
-        writeln!(output, "Version: {}", self.version).unwrap();
+        write_header(&mut output, "Metadata-Version", &self.metadata_version);
+        write_header(&mut output, "Name", &self.name);
+        write_header(&mut output, "Version", &self.version);
 
         // Optional single-value fields
         if let Some(ref v) = self.summary {
-            writeln!(output, "Summary: {}", v).unwrap();
+            write_header(&mut output, "Summary", v);
         }
         if let Some(ref v) = self.description_content_type {
-            writeln!(output, "Description-Content-Type: {}", v).unwrap();
+            write_header(&mut output, "Description-Content-Type", v);
         }
         if let Some(ref v) = self.home_page {
-            writeln!(output, "Home-page: {}", v).unwrap();
+            write_header(&mut output, "Home-page", v);
         }
         if let Some(ref v) = self.download_url {
-            writeln!(output, "Download-URL: {}", v).unwrap();
+            write_header(&mut output, "Download-URL", v);
         }
         if let Some(ref v) = self.author {
-            writeln!(output, "Author: {}", v).unwrap();
+            write_header(&mut output, "Author", v);
         }
         if let Some(ref v) = self.author_email {
-            writeln!(output, "Author-email: {}", v).unwrap();
+            write_header(&mut output, "Author-email", v);
         }
         if let Some(ref v) = self.maintainer {
-            writeln!(output, "Maintainer: {}", v).unwrap();
+            write_header(&mut output, "Maintainer", v);
         }
         if let Some(ref v) = self.maintainer_email {
-            writeln!(output, "Maintainer-email: {}", v).unwrap();
+            write_header(&mut output, "Maintainer-email", v);
         }
         if let Some(ref v) = self.license {
-            writeln!(output, "License: {}", v).unwrap();
+            write_header(&mut output, "License", v);
         }
         if let Some(ref v) = self.keywords {
-            writeln!(output, "Keywords: {}", v).unwrap();
+            write_header(&mut output, "Keywords", v);
         }
         if let Some(ref v) = self.requires_python {
-            writeln!(output, "Requires-Python: {}", v).unwrap();
+            write_header(&mut output, "Requires-Python", v);
         }
 
         // Multi-value fields
         for v in &self.platform {
-            writeln!(output, "Platform: {}", v).unwrap();
+            write_header(&mut output, "Platform", v);
         }
         for v in &self.classifiers {
-            writeln!(output, "Classifier: {}", v).unwrap();
+            write_header(&mut output, "Classifier", v);
         }
         for v in &self.requires_dist {
-            writeln!(output, "Requires-Dist: {}", v).unwrap();
+            write_header(&mut output, "Requires-Dist", v);
         }
         for v in &self.requires_external {
-            writeln!(output, "Requires-External: {}", v).unwrap();
+            write_header(&mut output, "Requires-External", v);
         }
         for v in &self.project_url {
-            writeln!(output, "Project-URL: {}", v).unwrap();
+            write_header(&mut output, "Project-URL", v);
         }
         for v in &self.provides_extra {
-            writeln!(output, "Provides-Extra: {}", v).unwrap();
+            write_header(&mut output, "Provides-Extra", v);
         }
         for v in &self.provides_dist {
-            writeln!(output, "Provides-Dist: {}", v).unwrap();
+            write_header(&mut output, "Provides-Dist", v);
         }
         for v in &self.obsoletes_dist {
-            writeln!(output, "Obsoletes-Dist: {}", v).unwrap();
+            write_header(&mut output, "Obsoletes-Dist", v);
+        }
+        for v in &self.dynamic {
+            write_header(&mut output, "Dynamic", v);
         }
 
         // Extra headers
         for (key, values) in &self.extra_headers {
             for v in values {
-                writeln!(output, "{}: {}", key, v).unwrap();
+                write_header(&mut output, key, v);
             }
         }
 
@@ -280,6 +1095,577 @@ Requires-Dist: click"#;
         assert_eq!(metadata.requires_dist.len(), 2);
     }
 
+    #[test]
+    fn test_validate_description_content_type_valid() {
+        for ct in [
+            "text/plain",
+            "text/x-rst",
+            "text/markdown",
+            "text/markdown; charset=UTF-8",
+            "text/markdown; charset=utf-8; variant=GFM",
+            "Text/Markdown; variant=CommonMark",
+        ] {
+            assert!(
+                validate_description_content_type(ct).is_ok(),
+                "{ct} should be valid"
+            );
+        }
+    }
+
+    #[test]
+    fn test_validate_description_content_type_invalid() {
+        for ct in [
+            "text/markdwon",
+            "text/html",
+            "text/plain; charset=latin-1",
+            "text/plain; variant=GFM",
+            "text/markdown; variant=MultiMarkdown",
+            "text/markdown; flavor=GFM",
+            "text/markdown; charset",
+        ] {
+            match validate_description_content_type(ct) {
+                Err(MetadataError::InvalidValue { field, .. }) => {
+                    assert_eq!(field, "Description-Content-Type")
+                }
+                other => panic!("{ct} should be rejected, got {other:?}"),
+            }
+        }
+    }
+
+    #[test]
+    fn test_description_byte_length() {
+        let mut metadata = Metadata::default();
+        assert_eq!(metadata.description_byte_length(), 0);
+        metadata.description = Some("héllo".to_string());
+        assert_eq!(metadata.description_byte_length(), 6);
+    }
+
+    #[test]
+    fn test_keywords_list_detects_delimiter() {
+        let mut metadata = Metadata {
+            keywords: Some("packaging  metadata wheel".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(metadata.keywords_list(), ["packaging", "metadata", "wheel"]);
+
+        metadata.keywords = Some("python packaging, wheel ,".to_string());
+        assert_eq!(metadata.keywords_list(), ["python packaging", "wheel"]);
+    }
+
+    #[test]
+    fn test_normalize_keywords() {
+        let content =
+            "Metadata-Version: 2.1\nName: pkg\nVersion: 1.0\nKeywords: packaging metadata wheel\n";
+        let mut metadata = Metadata::parse(content).unwrap();
+        assert_eq!(
+            metadata.keywords.as_deref(),
+            Some("packaging metadata wheel")
+        );
+
+        metadata.normalize_keywords();
+        assert_eq!(
+            metadata.keywords.as_deref(),
+            Some("packaging,metadata,wheel")
+        );
+        assert!(
+            metadata
+                .serialize()
+                .contains("Keywords: packaging,metadata,wheel\n")
+        );
+    }
+
+    #[test]
+    fn test_set_keywords_list() {
+        let mut metadata = Metadata::default();
+        metadata.set_keywords_list(&["a".to_string(), "b c".to_string()]);
+        assert_eq!(metadata.keywords.as_deref(), Some("a,b c"));
+        metadata.set_keywords_list(&[]);
+        assert_eq!(metadata.keywords, None);
+    }
+
+    #[test]
+    fn test_add_keyword() {
+        let mut metadata = Metadata {
+            keywords: Some("packaging metadata".to_string()),
+            ..Default::default()
+        };
+        assert!(metadata.add_keyword("wheel"));
+        assert!(!metadata.add_keyword("metadata"));
+        assert!(!metadata.add_keyword(" "));
+        assert_eq!(
+            metadata.keywords.as_deref(),
+            Some("packaging,metadata,wheel")
+        );
+
+        let mut metadata = Metadata::default();
+        assert!(metadata.add_keyword("first"));
+        assert_eq!(metadata.keywords.as_deref(), Some("first"));
+    }
+
+    #[test]
+    fn test_downgrade_metadata_version() {
+        let content = r#"Metadata-Version: 2.4
+Name: pkg
+Version: 1.0
+License-Expression: MIT OR Apache-2.0
+License-File: LICENSE-MIT
+License-File: LICENSE-APACHE
+Dynamic: Requires-Dist
+
+Long description.
+"#;
+        let mut metadata = Metadata::parse(content).unwrap();
+        let changes = metadata.downgrade_metadata_version((2, 1)).unwrap();
+
+        assert_eq!(
+            changes,
+            [
+                "moved License-Expression to License: MIT OR Apache-2.0",
+                "dropped License-File: LICENSE-MIT",
+                "dropped License-File: LICENSE-APACHE",
+                "dropped Dynamic: Requires-Dist",
+                "changed Metadata-Version: 2.4 -> 2.1",
+            ]
+        );
+        assert_eq!(metadata.license.as_deref(), Some("MIT OR Apache-2.0"));
+        assert!(metadata.extra_headers.is_empty());
+        assert!(metadata.dynamic.is_empty());
+
+        let serialized = metadata.serialize();
+        assert!(serialized.starts_with("Metadata-Version: 2.1\n"));
+        assert!(!serialized.contains("License-File"));
+        assert!(serialized.contains("License: MIT OR Apache-2.0\n"));
+        assert!(serialized.ends_with("\n\nLong description."));
+    }
+
+    #[test]
+    fn test_downgrade_metadata_version_rejects_invalid_targets() {
+        let mut metadata = Metadata {
+            metadata_version: "2.2".to_string(),
+            dynamic: vec!["Requires-Dist".to_string()],
+            ..Default::default()
+        };
+
+        assert!(metadata.downgrade_metadata_version((2, 4)).is_err());
+        assert!(metadata.downgrade_metadata_version((1, 2)).is_err());
+        assert_eq!(metadata.metadata_version, "2.2");
+        assert_eq!(metadata.dynamic, ["Requires-Dist"]);
+    }
+
+    #[test]
+    fn test_downgrade_metadata_version_keeps_both_licenses() {
+        let content = "Metadata-Version: 2.4
+Name: pkg
+Version: 1.0
+License: MIT License text
+License-Expression: MIT
+";
+        let mut metadata = Metadata::parse(content).unwrap();
+        assert!(matches!(
+            metadata.downgrade_metadata_version((2, 3)),
+            Err(MetadataError::InvalidValue { field, .. }) if field == "License-Expression"
+        ));
+        assert_eq!(
+            metadata.serialize(),
+            Metadata::parse(content).unwrap().serialize()
+        );
+
+        // Staying on 2.4 has nothing to move
+        assert!(metadata.downgrade_metadata_version((2, 4)).is_ok());
+    }
+
+    #[test]
+    fn test_validate_metadata_version() {
+        let mut metadata = Metadata::parse(
+            "Metadata-Version: 1.0
+Name: pkg
+Version: 1.0
+Summary: fine in 1.0
+Requires-Dist: click
+Provides-Extra: cli
+License-Expression: MIT
+",
+        )
+        .unwrap();
+
+        let errors: Vec<String> = metadata
+            .validate()
+            .unwrap_err()
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+        assert_eq!(
+            errors,
+            [
+                "Invalid Requires-Dist: requires Metadata-Version 1.2 or later, but 1.0 is declared",
+                "Invalid Provides-Extra: requires Metadata-Version 2.1 or later, but 1.0 is declared",
+                "Invalid License-Expression: requires Metadata-Version 2.4 or later, but 1.0 is declared",
+            ]
+        );
+
+        metadata.metadata_version = "2.4".to_string();
+        assert!(metadata.validate().is_ok());
+        metadata.metadata_version = "two".to_string();
+        assert_eq!(metadata.validate().unwrap_err().len(), 1);
+    }
+
+    #[test]
+    fn test_long_values_fold_and_round_trip() {
+        let long_marker = (0..800)
+            .map(|i| format!("extra == 'e{}'", i % 10))
+            .collect::<Vec<_>>()
+            .join(" or ");
+        let requirement = format!("pkg; {}", long_marker);
+        assert!(requirement.len() > 8000);
+        let metadata = Metadata {
+            metadata_version: "2.1".to_string(),
+            name: "pkg".to_string(),
+            version: "1.0".to_string(),
+            license: Some("Line one\n\nLine three".to_string()),
+            requires_dist: vec![requirement.clone()],
+            ..Default::default()
+        };
+
+        let serialized = metadata.serialize();
+        assert!(serialized.lines().all(|line| line.len() <= FOLD_WIDTH));
+        assert!(serialized.contains("License: Line one\n        \n        Line three\n"));
+
+        let parsed = Metadata::parse(&serialized).unwrap();
+        assert_eq!(parsed.requires_dist, [requirement]);
+        assert_eq!(parsed.license.as_deref(), Some("Line one\n\nLine three"));
+        assert_eq!(parsed.serialize(), serialized);
+
+        // Ordinary lines are left alone
+        let summary = "x".repeat(500);
+        let metadata = Metadata {
+            summary: Some(summary.clone()),
+            ..metadata
+        };
+        assert!(
+            metadata
+                .serialize()
+                .contains(&format!("Summary: {}\n", summary))
+        );
+    }
+
+    #[test]
+    fn test_one_space_continuations_outside_single_line_fields() {
+        let content = "Metadata-Version: 2.1
+Name: pkg
+Version: 1.0
+Summary: A summary
+ folded by another tool
+License: Copyright (c) 2024 Someone
+ Permission is hereby granted, free of charge,
+ to any person obtaining a copy
+X-Notes: first
+ second
+";
+        let metadata = Metadata::parse(content).unwrap();
+        assert_eq!(
+            metadata.summary.as_deref(),
+            Some("A summary folded by another tool")
+        );
+        assert_eq!(
+            metadata.license.as_deref(),
+            Some(
+                "Copyright (c) 2024 Someone\nPermission is hereby granted, free of charge,\nto any person obtaining a copy"
+            )
+        );
+        assert_eq!(metadata.extra_headers["X-Notes"], ["first\nsecond"]);
+    }
+
+    #[test]
+    fn test_dynamic_fields() {
+        let content = "Metadata-Version: 2.2
+Name: pkg
+Version: 1.0
+Requires-Dist: click
+dynamic: Requires-Dist
+Dynamic: license
+Dynamic: Version
+X-Custom: kept
+";
+        let metadata = Metadata::parse(content).unwrap();
+        assert_eq!(metadata.dynamic, ["Requires-Dist", "license", "Version"]);
+        assert_eq!(metadata.extra_headers.len(), 1);
+        assert!(
+            metadata
+                .serialize()
+                .contains("Requires-Dist: click\nDynamic: Requires-Dist\nDynamic: license\n")
+        );
+
+        assert_eq!(
+            metadata.validate_dynamic(),
+            [
+                "Dynamic: Requires-Dist is declared dynamic but has a value",
+                "Dynamic: Version is not allowed to be dynamic",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_set_mirrors_get() {
+        let mut metadata =
+            Metadata::parse("Metadata-Version: 2.1\nName: pkg\nVersion: 1.0\n").unwrap();
+
+        metadata
+            .set("home-page", MetadataValue::Single(Some("https://a".into())))
+            .unwrap();
+        assert_eq!(
+            metadata.get("Home-Page"),
+            MetadataValue::Single(Some("https://a".into()))
+        );
+
+        metadata
+            .set("classifier", MetadataValue::Single(Some("A".into())))
+            .unwrap();
+        assert_eq!(
+            metadata.get("Classifier"),
+            MetadataValue::Multi(vec!["A".into()])
+        );
+
+        let tags = MetadataValue::Multi(vec!["a".into(), "b".into()]);
+        metadata.set("X-Tag", tags.clone()).unwrap();
+        assert_eq!(metadata.get("x-tag"), tags);
+
+        metadata
+            .set("Home-page", MetadataValue::Single(None))
+            .unwrap();
+        assert_eq!(metadata.home_page, None);
+        assert!(metadata.set("Name", MetadataValue::Single(None)).is_err());
+        assert!(
+            metadata
+                .set("Summary", MetadataValue::Multi(vec![]))
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_remove_field() {
+        let mut metadata = Metadata::parse(
+            "Metadata-Version: 2.1
+Name: pkg
+Version: 1.0
+Home-page: https://example.com
+Classifier: A
+X-Custom: kept
+x-custom: also
+",
+        )
+        .unwrap();
+
+        assert!(metadata.remove_field("home-page").unwrap());
+        assert_eq!(metadata.home_page, None);
+        assert!(!metadata.remove_field("Home-page").unwrap());
+        assert!(metadata.remove_field("Classifier").unwrap());
+        assert!(metadata.classifiers.is_empty());
+        assert!(metadata.remove_field("X-CUSTOM").unwrap());
+        assert!(metadata.extra_headers.is_empty());
+        assert!(!metadata.remove_field("License").unwrap());
+
+        assert!(matches!(
+            metadata.remove_field("name"),
+            Err(MetadataError::InvalidValue { .. })
+        ));
+        assert_eq!(metadata.name, "pkg");
+    }
+
+    #[test]
+    fn test_set_values() {
+        let mut metadata = Metadata::default();
+
+        metadata
+            .set_values("Classifier", vec!["A".to_string(), "B".to_string()])
+            .unwrap();
+        assert_eq!(metadata.classifiers, ["A", "B"]);
+        metadata
+            .set_values("Requires-Dist", vec!["click".to_string()])
+            .unwrap();
+        assert_eq!(metadata.requires_dist, ["click"]);
+        metadata
+            .set_values("Summary", vec!["one".to_string()])
+            .unwrap();
+        assert_eq!(metadata.summary.as_deref(), Some("one"));
+        metadata
+            .set_values("X-Custom", vec!["a".to_string(), "b".to_string()])
+            .unwrap();
+        assert_eq!(metadata.extra_headers["X-Custom"], ["a", "b"]);
+    }
+
+    #[test]
+    fn test_set_values_rejects_multiple_values_for_single_value_field() {
+        let mut metadata = Metadata::default();
+
+        let err = metadata
+            .set_values("Summary", vec!["a".to_string(), "b".to_string()])
+            .unwrap_err();
+        assert!(matches!(err, MetadataError::InvalidValue { ref field, .. } if field == "Summary"));
+        assert!(metadata.set_values("Author", Vec::new()).is_err());
+        assert_eq!(metadata.summary, None);
+        assert!(metadata.extra_headers.is_empty());
+    }
+
+    #[test]
+    fn test_get_matches_alternate_spellings() {
+        let mut metadata = Metadata {
+            home_page: Some("https://example.com".to_string()),
+            author_email: Some("dev@example.com".to_string()),
+            classifiers: vec!["A".to_string()],
+            ..Default::default()
+        };
+        metadata
+            .extra_headers
+            .insert("X-Custom".to_string(), vec!["x".to_string()]);
+
+        let home = MetadataValue::Single(Some("https://example.com".to_string()));
+        for key in ["Home-page", "Home-Page", "home-page", "HOME-PAGE"] {
+            assert_eq!(metadata.get(key), home, "{key}");
+        }
+        assert_eq!(metadata.get("Author-Email"), metadata.get("Author-email"));
+        assert_eq!(
+            metadata.get("classifier"),
+            MetadataValue::Multi(vec!["A".to_string()])
+        );
+        assert_eq!(metadata.get("Platform"), MetadataValue::Multi(Vec::new()));
+        assert_eq!(metadata.get("License"), MetadataValue::Single(None));
+        assert_eq!(
+            metadata.get("x-custom"),
+            MetadataValue::Single(Some("x".to_string()))
+        );
+        assert_eq!(metadata.get("X-Missing"), MetadataValue::Single(None));
+
+        metadata
+            .set_values("maintainer-EMAIL", vec!["m@example.com".to_string()])
+            .unwrap();
+        assert_eq!(metadata.maintainer_email.as_deref(), Some("m@example.com"));
+        assert!(!metadata.extra_headers.contains_key("maintainer-EMAIL"));
+    }
+
+    #[test]
+    fn test_validate_dependencies() {
+        let metadata = Metadata {
+            requires_dist: vec![
+                "requests>=2.20".to_string(),
+                "my pkg>=1".to_string(),
+                "click=>8".to_string(),
+                "numpy; python_version >=".to_string(),
+            ],
+            ..Default::default()
+        };
+
+        let problems = metadata.validate_dependencies();
+        assert_eq!(problems.len(), 3, "{problems:?}");
+        assert!(problems[0].starts_with("Requires-Dist 'my pkg>=1': Invalid name"));
+        assert!(problems[1].starts_with("Requires-Dist 'click=>8': Invalid version specifier"));
+        assert!(
+            problems[2].starts_with("Requires-Dist 'numpy; python_version >=': Invalid marker")
+        );
+
+        let metadata = Metadata {
+            obsoletes_dist: vec!["Gorgon (<3.0)".to_string(), "Gorgon (3.0)".to_string()],
+            provides_dist: vec!["OtherProject (3.1)".to_string()],
+            ..Default::default()
+        };
+        let problems = metadata.validate_dependencies();
+        assert_eq!(problems.len(), 1, "{problems:?}");
+        assert!(problems[0].starts_with("Obsoletes-Dist 'Gorgon (3.0)'"));
+    }
+
+    #[test]
+    fn test_parsed_requires_dist() {
+        let mut metadata = Metadata {
+            requires_dist: vec![
+                "requests[socks] >=2.0".to_string(),
+                "importlib-metadata; python_version < \"3.9\"".to_string(),
+            ],
+            ..Default::default()
+        };
+
+        let mut requirements = metadata.parsed_requires_dist().unwrap();
+        assert_eq!(requirements[0].extras, ["socks"]);
+        assert_eq!(
+            requirements[1].marker.as_deref(),
+            Some("python_version < \"3.9\"")
+        );
+
+        requirements[0].specifiers[0].version = "2.31".to_string();
+        requirements[1].marker = None;
+        metadata.set_parsed_requires_dist(&requirements);
+        assert_eq!(
+            metadata.requires_dist,
+            ["requests[socks]>=2.31", "importlib-metadata"]
+        );
+
+        metadata.requires_dist.push("my pkg".to_string());
+        assert!(matches!(
+            metadata.parsed_requires_dist(),
+            Err(MetadataError::InvalidValue { .. })
+        ));
+    }
+
+    #[test]
+    fn test_remove_obsoletes_dist() {
+        let mut metadata = Metadata {
+            obsoletes_dist: vec![
+                "foo-bar (<2.0); os_name == \"posix\"".to_string(),
+                "Gorgon".to_string(),
+                "Foo.Bar".to_string(),
+            ],
+            ..Default::default()
+        };
+        assert_eq!(metadata.remove_obsoletes_dist("Foo_Bar"), 2);
+        assert_eq!(metadata.obsoletes_dist, ["Gorgon"]);
+    }
+
+    #[test]
+    fn test_merge_classifiers_from() {
+        let mut metadata = Metadata {
+            classifiers: vec!["A".to_string(), "B".to_string()],
+            ..Default::default()
+        };
+        let other = Metadata {
+            classifiers: vec!["C".to_string(), "A".to_string(), "D".to_string()],
+            ..Default::default()
+        };
+
+        assert_eq!(metadata.merge_classifiers_from(&other), 2);
+        assert_eq!(metadata.classifiers, ["A", "B", "C", "D"]);
+        assert_eq!(metadata.merge_classifiers_from(&other), 0);
+    }
+
+    #[test]
+    fn test_merge_requires_dist_from() {
+        let mut metadata = Metadata {
+            requires_dist: vec![
+                "requests>=2.20".to_string(),
+                "foo-bar; python_version < \"3.10\"".to_string(),
+            ],
+            ..Default::default()
+        };
+        let other = Metadata {
+            requires_dist: vec![
+                "click".to_string(),
+                "Requests>=2.31".to_string(),
+                "Foo_Bar>=1;python_version<'3.10'".to_string(),
+                "foo-bar; python_version >= \"3.10\"".to_string(),
+                "click>=8".to_string(),
+            ],
+            ..Default::default()
+        };
+
+        assert_eq!(metadata.merge_requires_dist_from(&other), 2);
+        assert_eq!(
+            metadata.requires_dist,
+            [
+                "requests>=2.20",
+                "foo-bar; python_version < \"3.10\"",
+                "click",
+                "foo-bar; python_version >= \"3.10\"",
+            ]
+        );
+    }
+
     #[test]
     fn test_roundtrip() {
         let content = r#"Metadata-Version: 2.1
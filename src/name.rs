@@ -1,5 +1,8 @@
 //! Name normalization utilities for Python wheels (PEP 427)
 
+use crate::error::WheelInfoError;
+use crate::wheel_info::WheelTag;
+
 /// Normalize a package name for use in dist-info directory names.
 ///
 /// While PEP 503 normalizes to hyphens for PyPI URLs, dist-info directories
@@ -25,16 +28,150 @@ pub fn normalize_dist_info_name(name: &str) -> String {
     result
 }
 
+/// Normalize a project name for comparison (PEP 503).
+///
+/// Lowercases and collapses runs of `-`, `_` and `.` into a single `-`, so
+/// `Foo.Bar`, `foo_bar` and `FOO--bar` all compare equal.
+pub fn normalize_project_name(name: &str) -> String {
+    normalize_dist_info_name(name)
+        .to_ascii_lowercase()
+        .replace('_', "-")
+}
+
 /// Compute the dist-info directory name from package name and version
 pub fn dist_info_name(name: &str, version: &str) -> String {
     format!("{}-{}.dist-info", normalize_dist_info_name(name), version)
 }
 
+/// Escape a version for use in a wheel filename (PEP 427)
+///
+/// Normalized PEP 440 versions never contain `-`, but an unnormalized one
+/// (`1.0-1`) would split into an extra filename component, so each `-` is
+/// replaced with `_`, as bdist_wheel does.
+pub fn escape_version(version: &str) -> String {
+    version.replace('-', "_")
+}
+
+/// Extract the version embedded in a dist-info directory name.
+///
+/// `my_package-1.0.0.dist-info` yields `Some("1.0.0")`. Returns `None` if the
+/// name doesn't end in `.dist-info` or has no version component. Since the
+/// distribution part is normalized (no `-`), the version is everything after
+/// the first `-`.
+pub fn dist_info_version(dist_info: &str) -> Option<&str> {
+    let stem = dist_info.strip_suffix(".dist-info")?;
+    let (_, version) = stem.split_once('-')?;
+    if version.is_empty() {
+        None
+    } else {
+        Some(version)
+    }
+}
+
 /// Compute the data directory name from the package name and version
 pub fn data_dir_name(name: &str, version: &str) -> String {
     format!("{}-{}.data", normalize_dist_info_name(name), version)
 }
 
+/// A parsed PEP 427 wheel filename
+///
+/// Compressed tag sets (`py2.py3`) are kept as one list per component, in
+/// filename order.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WheelFilename {
+    pub name: String,
+    pub version: String,
+    pub build: Option<String>,
+    pub python: Vec<String>,
+    pub abi: Vec<String>,
+    pub platform: Vec<String>,
+}
+
+impl WheelFilename {
+    /// Parse `{name}-{version}(-{build})?-{python}-{abi}-{platform}.whl`
+    pub fn parse(filename: &str) -> Result<Self, WheelInfoError> {
+        let invalid = |reason: &str| {
+            WheelInfoError::Parse(format!("Invalid wheel filename '{}': {}", filename, reason))
+        };
+
+        let stem = filename
+            .strip_suffix(".whl")
+            .ok_or_else(|| invalid("missing .whl extension"))?;
+        let parts: Vec<&str> = stem.split('-').collect();
+        let (name, version, build, tags) = match parts.as_slice() {
+            [name, version, tags @ ..] if tags.len() == 3 => (name, version, None, tags),
+            [name, version, build, tags @ ..] if tags.len() == 3 => {
+                if !build.starts_with(|c: char| c.is_ascii_digit()) {
+                    return Err(invalid("build tag must start with a digit"));
+                }
+                (name, version, Some(build.to_string()), tags)
+            }
+            _ => return Err(invalid("expected 5 or 6 dash-separated components")),
+        };
+        if parts.iter().any(|part| part.is_empty()) {
+            return Err(invalid("empty component"));
+        }
+
+        let split = |component: &str| component.split('.').map(str::to_string).collect();
+        Ok(Self {
+            name: name.to_string(),
+            version: version.to_string(),
+            build,
+            python: split(tags[0]),
+            abi: split(tags[1]),
+            platform: split(tags[2]),
+        })
+    }
+
+    /// Expand the compressed tag sets into every tag the wheel claims
+    pub fn tags(&self) -> Vec<WheelTag> {
+        let mut tags = Vec::new();
+        for python in &self.python {
+            for abi in &self.abi {
+                for platform in &self.platform {
+                    tags.push(WheelTag {
+                        python: python.clone(),
+                        abi: abi.clone(),
+                        platform: platform.clone(),
+                    });
+                }
+            }
+        }
+        tags
+    }
+
+    /// A normalized form of the filename, usable as a dedup key
+    ///
+    /// The name is PEP 503-normalized (with `_` as separator so the result
+    /// still splits on `-`), tags are lowercased, and each compressed tag
+    /// set is sorted and deduplicated. Two uploads that differ only
+    /// cosmetically (`Foo-1.0-py3-none-any.whl` vs `foo-1.0-py3-none-any.whl`,
+    /// `py3.py2` vs `py2.py3`) get the same key.
+    ///
+    /// This is a heuristic for spotting duplicates, not the filename to
+    /// install or upload under: the version is compared as written.
+    pub fn canonical(&self) -> String {
+        let name = normalize_project_name(&self.name).replace('-', "_");
+        let join = |values: &[String]| {
+            let mut values: Vec<String> = values.iter().map(|v| v.to_ascii_lowercase()).collect();
+            values.sort();
+            values.dedup();
+            values.join(".")
+        };
+        let tags = format!(
+            "{}-{}-{}",
+            join(&self.python),
+            join(&self.abi),
+            join(&self.platform)
+        );
+
+        match &self.build {
+            Some(build) => format!("{}-{}-{}-{}.whl", name, self.version, build, tags),
+            None => format!("{}-{}-{}.whl", name, self.version, tags),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -60,6 +197,25 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_dist_info_version() {
+        assert_eq!(dist_info_version("my_package-1.0.0.dist-info"), Some("1.0.0"));
+        assert_eq!(
+            dist_info_version("torch-2.5.0+cu124.dist-info"),
+            Some("2.5.0+cu124")
+        );
+        assert_eq!(dist_info_version("my_package.dist-info"), None);
+        assert_eq!(dist_info_version("my_package-.dist-info"), None);
+        assert_eq!(dist_info_version("my_package-1.0.0.data"), None);
+    }
+
+    #[test]
+    fn test_escape_version() {
+        assert_eq!(escape_version("1.0.0"), "1.0.0");
+        assert_eq!(escape_version("1.0+cpu"), "1.0+cpu");
+        assert_eq!(escape_version("1.0-1"), "1.0_1");
+    }
+
     #[test]
     fn test_data_dir_name() {
         assert_eq!(
@@ -67,4 +223,42 @@ mod tests {
             "my_package-1.0.0.data"
         );
     }
+
+    #[test]
+    fn test_normalize_project_name() {
+        assert_eq!(normalize_project_name("Foo.Bar"), "foo-bar");
+        assert_eq!(normalize_project_name("foo__bar"), "foo-bar");
+        assert_eq!(normalize_project_name("FOO-_.bar"), "foo-bar");
+    }
+
+    #[test]
+    fn test_wheel_filename_parse() {
+        let parsed = WheelFilename::parse("my_pkg-1.0-1-py2.py3-none-any.whl").unwrap();
+        assert_eq!(parsed.name, "my_pkg");
+        assert_eq!(parsed.version, "1.0");
+        assert_eq!(parsed.build.as_deref(), Some("1"));
+        assert_eq!(parsed.python, vec!["py2", "py3"]);
+        assert_eq!(parsed.tags().len(), 2);
+
+        assert!(WheelFilename::parse("my_pkg-1.0.tar.gz").is_err());
+        assert!(WheelFilename::parse("my_pkg-1.0-none-any.whl").is_err());
+        assert!(WheelFilename::parse("my_pkg-1.0-x1-py3-none-any.whl").is_err());
+    }
+
+    #[test]
+    fn test_wheel_filename_canonical() {
+        let canonical = |f: &str| WheelFilename::parse(f).unwrap().canonical();
+        assert_eq!(
+            canonical("Foo-1.0-py3-none-any.whl"),
+            canonical("foo-1.0-py3-none-any.whl")
+        );
+        assert_eq!(
+            canonical("Foo.Bar-1.0-py3.py2-none-any.whl"),
+            "foo_bar-1.0-py2.py3-none-any.whl"
+        );
+        assert_ne!(
+            canonical("foo-1.0-py3-none-any.whl"),
+            canonical("foo-1.1-py3-none-any.whl")
+        );
+    }
 }
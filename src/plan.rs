@@ -0,0 +1,101 @@
+//! Dry-run reports of what `WheelEditor::save` will write
+//!
+//! `WheelEditor::plan` walks the source archive the way the writer does and
+//! sorts every member into raw-copied, rewritten or removed, without
+//! reading any member's content or writing anything.
+
+/// Why `save` writes a member out again instead of raw-copying it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RewriteReason {
+    /// METADATA, regenerated on every save
+    Metadata,
+    /// RECORD, regenerated on every save
+    Record,
+    /// WHEEL, regenerated from `WheelEditor::wheel_info` when tags or other
+    /// WHEEL fields changed, or when the edits need the extended writer
+    WheelInfo,
+    /// Content replaced through `modify_matching` (and so `strip_debug`) or
+    /// an `add_file` over an existing member
+    Modified,
+    /// Queued ELF modifications such as `set_rpath` or `set_soname`
+    Elf,
+    /// A new member from `add_file`
+    Added,
+    /// Missing from RECORD, so decompressed to hash it
+    Unrecorded,
+    /// Compressed with a method other than Stored or Deflate and re-encoded
+    /// for `WriteOptions::max_compatibility`
+    Reencoded,
+    /// `RECORD.jws` from `WriteOptions::record_signer`, over the new RECORD
+    Signature,
+}
+
+impl RewriteReason {
+    /// Kebab-case name, as shown by the CLI
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RewriteReason::Metadata => "metadata",
+            RewriteReason::Record => "record",
+            RewriteReason::WheelInfo => "wheel-info",
+            RewriteReason::Modified => "modified",
+            RewriteReason::Elf => "elf",
+            RewriteReason::Added => "added",
+            RewriteReason::Unrecorded => "unrecorded",
+            RewriteReason::Reencoded => "reencoded",
+            RewriteReason::Signature => "signature",
+        }
+    }
+
+    /// Whether this is one of the dist-info files a save generates, whose
+    /// cost doesn't grow with the wheel
+    pub fn is_control_file(&self) -> bool {
+        matches!(
+            self,
+            RewriteReason::Metadata
+                | RewriteReason::Record
+                | RewriteReason::WheelInfo
+                | RewriteReason::Signature
+        )
+    }
+}
+
+/// What `WheelEditor::save` would do with the current edits, as returned by
+/// `WheelEditor::plan`
+///
+/// Paths are in archive order, with generated and added files last. Reads
+/// that don't change what is written, like `WriteOptions::verify_hashes`,
+/// aren't listed; `WheelEditor::constant_time_blockers` covers those.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SavePlan {
+    /// The dist-info directory of the output wheel
+    pub dist_info: String,
+    /// Members copied as raw compressed bytes, as `(source path, output
+    /// path)`; the two differ when a name or version change renames the
+    /// dist-info or `.data` directory
+    pub copied: Vec<(String, String)>,
+    /// Members written out again, by output path
+    pub rewritten: Vec<(String, RewriteReason)>,
+    /// Source members left out: those passed to `remove_file`, and RECORD
+    /// signatures, which would no longer verify (see
+    /// `WheelEditor::dropped_signatures`)
+    pub removed: Vec<String>,
+}
+
+impl SavePlan {
+    /// True if only METADATA, RECORD, WHEEL and the RECORD signature are
+    /// rewritten
+    pub fn is_constant_time(&self) -> bool {
+        self.rewritten
+            .iter()
+            .all(|(_, reason)| reason.is_control_file())
+    }
+
+    /// Members rewritten for any reason other than being a dist-info
+    /// control file
+    pub fn rewritten_payload(&self) -> impl Iterator<Item = (&str, RewriteReason)> {
+        self.rewritten
+            .iter()
+            .filter(|(_, reason)| !reason.is_control_file())
+            .map(|(path, reason)| (path.as_str(), *reason))
+    }
+}
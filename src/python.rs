@@ -1,5 +1,9 @@
 //! Python bindings for editwheel using PyO3
 
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use std::path::Path;
+
 use pyo3::exceptions::PyFileNotFoundError;
 use pyo3::exceptions::PyIOError;
 use pyo3::exceptions::PyValueError;
@@ -8,26 +12,75 @@ use pyo3::types::PyAny;
 use pyo3::types::PyBytes;
 use pyo3::types::PyList;
 
+use crate::ElfModification;
+use crate::HashAlgorithm;
+use crate::MetadataValue;
+use crate::OpenLimits;
+use crate::Record;
+use crate::RecordSigner;
+use crate::Role;
 use crate::ValidationError;
 use crate::ValidationResult;
 use crate::WheelEditor;
 use crate::WheelError;
+use crate::WheelFilename;
+use crate::WheelInfoError;
+use crate::WheelTag;
+use crate::format_address_list as rust_format_address_list;
+use crate::metadata_differences as rust_metadata_differences;
+use crate::metadata_matches as rust_metadata_matches;
 use crate::normalize_dist_info_name as rust_normalize_dist_info_name;
 
-/// Render a `ValidationError` as a single human-readable line.
-fn format_validation_error(err: &ValidationError) -> String {
-    match err {
-        ValidationError::HashMismatch {
-            path,
-            expected,
-            actual,
-        } => format!("hash mismatch for {path}: expected {expected}, got {actual}"),
-        ValidationError::MissingFile { path } => {
-            format!("missing file (in RECORD but not in archive): {path}")
-        }
-        ValidationError::ExtraFile { path } => {
-            format!("extra file (in archive but not in RECORD): {path}")
-        }
+/// A parsed requirement as `(name, extras, [(operator, version)], url, marker)`
+type RequirementTuple = (
+    String,
+    Vec<String>,
+    Vec<(String, String)>,
+    Option<String>,
+    Option<String>,
+);
+
+/// A save plan as `(dist_info, copied, rewritten, removed)`
+type PlanTuple = (
+    String,
+    Vec<(String, String)>,
+    Vec<(String, &'static str)>,
+    Vec<String>,
+);
+
+/// Parse an ELF modification spec such as "set-runpath=$ORIGIN" or
+/// "strip-debug".
+fn parse_elf_modification(spec: &str) -> PyResult<ElfModification> {
+    let (op, value) = match spec.split_once('=') {
+        Some((op, value)) => (op, Some(value)),
+        None => (spec, None),
+    };
+    match (op, value) {
+        ("set-rpath", Some(value)) => Ok(ElfModification::SetRpath(value.to_string())),
+        ("set-runpath", Some(value)) => Ok(ElfModification::SetRunpath(value.to_string())),
+        ("set-soname", Some(value)) => Ok(ElfModification::SetSoname(value.to_string())),
+        ("set-interpreter", Some(value)) => Ok(ElfModification::SetInterpreter(value.to_string())),
+        ("remove-rpath", None) => Ok(ElfModification::RemoveRpath),
+        ("remove-runpath", None) => Ok(ElfModification::RemoveRunpath),
+        ("strip-debug", None) => Ok(ElfModification::StripDebug),
+        _ => Err(PyValueError::new_err(format!(
+            "invalid ELF modification '{spec}': expected set-rpath=PATH, set-runpath=PATH, \
+             set-soname=NAME, set-interpreter=PATH, remove-rpath, remove-runpath, or strip-debug"
+        ))),
+    }
+}
+
+/// Render an `ElfModification` in the spec syntax `parse_elf_modification`
+/// accepts.
+fn format_elf_modification(modification: &ElfModification) -> String {
+    match modification {
+        ElfModification::SetRpath(value) => format!("set-rpath={value}"),
+        ElfModification::SetRunpath(value) => format!("set-runpath={value}"),
+        ElfModification::SetSoname(value) => format!("set-soname={value}"),
+        ElfModification::SetInterpreter(value) => format!("set-interpreter={value}"),
+        ElfModification::RemoveRpath => "remove-rpath".to_string(),
+        ElfModification::RemoveRunpath => "remove-runpath".to_string(),
+        ElfModification::StripDebug => "strip-debug".to_string(),
     }
 }
 
@@ -40,13 +93,19 @@ fn format_validation_error(err: &ValidationError) -> String {
 pub struct PyValidationResult {
     is_valid: bool,
     errors: Vec<String>,
+    details: Vec<PyValidationIssue>,
 }
 
 impl PyValidationResult {
     fn from_rust(result: ValidationResult) -> Self {
         Self {
             is_valid: result.is_valid(),
-            errors: result.errors.iter().map(format_validation_error).collect(),
+            errors: result.errors.iter().map(ToString::to_string).collect(),
+            details: result
+                .errors
+                .iter()
+                .map(PyValidationIssue::from_rust)
+                .collect(),
         }
     }
 }
@@ -65,6 +124,12 @@ impl PyValidationResult {
         self.errors.clone()
     }
 
+    /// The same errors as structured `ValidationIssue` objects, in order.
+    #[getter]
+    fn details(&self) -> Vec<PyValidationIssue> {
+        self.details.clone()
+    }
+
     /// Bool conversion: True iff the wheel is valid (so
     /// `if editor.validate(): ...` works as expected).
     fn __bool__(&self) -> bool {
@@ -83,28 +148,175 @@ impl PyValidationResult {
     }
 }
 
+/// Python exceptions raised for wheel errors
+///
+/// Every class derives from `EditwheelError`, itself a `ValueError`, so
+/// callers can catch one specific failure, any editwheel failure, or keep
+/// catching `ValueError` as before. I/O and ZIP errors stay `OSError`s.
+mod exceptions {
+    use pyo3::create_exception;
+    use pyo3::exceptions::PyValueError;
+
+    create_exception!(
+        editwheel,
+        EditwheelError,
+        PyValueError,
+        "Base class for errors raised by editwheel."
+    );
+    create_exception!(
+        editwheel,
+        InvalidWheelError,
+        EditwheelError,
+        "The archive isn't a well-formed wheel."
+    );
+    create_exception!(
+        editwheel,
+        MetadataError,
+        EditwheelError,
+        "METADATA is missing a field or has an invalid value."
+    );
+    create_exception!(
+        editwheel,
+        RecordError,
+        EditwheelError,
+        "RECORD can't be parsed or disagrees with the archive."
+    );
+    create_exception!(
+        editwheel,
+        ElfError,
+        EditwheelError,
+        "An ELF file can't be read or patched."
+    );
+    create_exception!(
+        editwheel,
+        WheelInfoError,
+        EditwheelError,
+        "WHEEL can't be parsed or a tag edit was refused."
+    );
+    create_exception!(
+        editwheel,
+        EntryPointsError,
+        EditwheelError,
+        "entry_points.txt can't be parsed."
+    );
+    create_exception!(
+        editwheel,
+        ArchiveLimitError,
+        EditwheelError,
+        "The archive exceeds one of the limits it was opened with."
+    );
+    create_exception!(
+        editwheel,
+        PatternError,
+        EditwheelError,
+        "A glob pattern can't be parsed."
+    );
+}
+
 /// Convert WheelError to PyErr
 impl From<WheelError> for PyErr {
     fn from(err: WheelError) -> PyErr {
+        let message = err.to_string();
         match &err {
             WheelError::Io(io_err) => {
                 if io_err.kind() == std::io::ErrorKind::NotFound {
-                    PyFileNotFoundError::new_err(err.to_string())
+                    PyFileNotFoundError::new_err(message)
                 } else {
-                    PyIOError::new_err(err.to_string())
+                    PyIOError::new_err(message)
                 }
             }
-            WheelError::InvalidWheel(_) => PyValueError::new_err(err.to_string()),
-            WheelError::Metadata(_) => PyValueError::new_err(err.to_string()),
-            WheelError::Record(_) => PyValueError::new_err(err.to_string()),
-            WheelError::Zip(_) => PyIOError::new_err(err.to_string()),
-            WheelError::Elf(_) => PyValueError::new_err(err.to_string()),
-            WheelError::WheelInfo(_) => PyValueError::new_err(err.to_string()),
-            WheelError::GlobPattern(_) => PyValueError::new_err(err.to_string()),
+            WheelError::Zip(_) => PyIOError::new_err(message),
+            WheelError::InvalidWheel(_) => exceptions::InvalidWheelError::new_err(message),
+            WheelError::Metadata(_) => exceptions::MetadataError::new_err(message),
+            WheelError::Record(_) => exceptions::RecordError::new_err(message),
+            WheelError::Elf(_) => exceptions::ElfError::new_err(message),
+            WheelError::WheelInfo(_) => exceptions::WheelInfoError::new_err(message),
+            WheelError::EntryPoints(_) => exceptions::EntryPointsError::new_err(message),
+            WheelError::GlobPattern(_) => exceptions::PatternError::new_err(message),
+            WheelError::LimitExceeded { .. } => exceptions::ArchiveLimitError::new_err(message),
+        }
+    }
+}
+
+/// One problem found by `WheelEditor.validate()`.
+///
+/// This describes the problem; it is not an exception. `kind` names the
+/// check that failed; `expected` and `actual` are set where the check
+/// compares two values (for a version mismatch, METADATA's version is
+/// expected and the dist-info directory's is actual).
+#[pyclass(name = "ValidationIssue", frozen)]
+#[derive(Clone)]
+pub struct PyValidationIssue {
+    /// One of "hash_mismatch", "missing_file", "extra_file",
+    /// "invalid_record_size", "missing_record_entry",
+    /// "dist_info_version_mismatch" or "unsupported_hash_algorithm"
+    #[pyo3(get)]
+    kind: &'static str,
+    /// The archive path the problem is about, if any
+    #[pyo3(get)]
+    path: Option<String>,
+    #[pyo3(get)]
+    expected: Option<String>,
+    #[pyo3(get)]
+    actual: Option<String>,
+    /// The human-readable line also listed in `ValidationResult.errors`
+    #[pyo3(get)]
+    message: String,
+}
+
+impl PyValidationIssue {
+    fn from_rust(err: &ValidationError) -> Self {
+        let (kind, path, expected, actual) = match err {
+            ValidationError::HashMismatch {
+                path,
+                expected,
+                actual,
+            } => ("hash_mismatch", Some(path), Some(expected), Some(actual)),
+            ValidationError::MissingFile { path } => ("missing_file", Some(path), None, None),
+            ValidationError::ExtraFile { path } => ("extra_file", Some(path), None, None),
+            ValidationError::InvalidRecordSize { path, value } => {
+                ("invalid_record_size", Some(path), None, Some(value))
+            }
+            ValidationError::MissingRecordEntry { path } => {
+                ("missing_record_entry", Some(path), None, None)
+            }
+            ValidationError::DistInfoVersionMismatch {
+                dist_info_version,
+                metadata_version,
+            } => (
+                "dist_info_version_mismatch",
+                None,
+                Some(metadata_version),
+                Some(dist_info_version),
+            ),
+            ValidationError::UnsupportedHashAlgorithm { path, hash } => {
+                ("unsupported_hash_algorithm", Some(path), None, Some(hash))
+            }
+        };
+        Self {
+            kind,
+            path: path.cloned(),
+            expected: expected.cloned(),
+            actual: actual.cloned(),
+            message: err.to_string(),
         }
     }
 }
 
+#[pymethods]
+impl PyValidationIssue {
+    fn __str__(&self) -> String {
+        self.message.clone()
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "ValidationIssue(kind={:?}, message={:?})",
+            self.kind, self.message
+        )
+    }
+}
+
 /// A class to edit Python wheel metadata and repack the wheel.
 ///
 /// This is a high-performance Rust implementation that achieves constant-time
@@ -113,27 +325,95 @@ impl From<WheelError> for PyErr {
 #[pyclass(name = "WheelEditor")]
 pub struct PyWheelEditor {
     inner: WheelEditor,
+    /// Save in place when a `with` block exits without an exception
+    autosave: bool,
 }
 
 #[pymethods]
 impl PyWheelEditor {
     /// Initialize the WheelEditor with a path to a wheel file.
     ///
+    /// The optional limits guard against pathological archives from
+    /// untrusted sources; they are checked against the sizes declared in the
+    /// central directory, before anything is decompressed.
+    ///
     /// Args:
     ///     wheel_path: Path to the wheel file to edit
+    ///     max_entries: Maximum number of archive entries
+    ///     max_total_uncompressed: Maximum total uncompressed size in bytes
+    ///     max_ratio: Maximum uncompressed/compressed size ratio of any entry
+    ///     strict: Reject a wheel whose RECORD doesn't list every member, and
+    ///         keep rejecting such members at save time
+    ///     autosave: When used as a context manager, save the wheel in place
+    ///         on leaving the `with` block, unless it raised
     ///
     /// Raises:
     ///     FileNotFoundError: If wheel file does not exist
-    ///     ValueError: If file is not a valid wheel
+    ///     ValueError: If file is not a valid wheel, exceeds a limit, or (with
+    ///         `strict`) has members missing from RECORD
     #[new]
-    fn new(wheel_path: &str) -> PyResult<Self> {
+    #[pyo3(signature = (wheel_path, *, max_entries = None, max_total_uncompressed = None, max_ratio = None, strict = false, autosave = false))]
+    fn new(
+        wheel_path: &str,
+        max_entries: Option<usize>,
+        max_total_uncompressed: Option<u64>,
+        max_ratio: Option<f64>,
+        strict: bool,
+        autosave: bool,
+    ) -> PyResult<Self> {
         // Check file extension
         if !wheel_path.ends_with(".whl") {
             return Err(PyValueError::new_err("File does not have .whl extension"));
         }
 
-        let editor = WheelEditor::open(wheel_path)?;
-        Ok(Self { inner: editor })
+        let limits = OpenLimits {
+            max_entries,
+            max_total_uncompressed,
+            max_ratio,
+        };
+        let editor = WheelEditor::open_with_limits(wheel_path, limits)?.strict(strict)?;
+        Ok(Self {
+            inner: editor,
+            autosave,
+        })
+    }
+
+    fn __enter__(slf: PyRef<'_, Self>) -> PyRef<'_, Self> {
+        slf
+    }
+
+    /// Leave a `with` block, saving in place if the editor was opened with
+    /// `autosave=True` and the block didn't raise. Exceptions propagate.
+    fn __exit__(
+        &mut self,
+        exc_type: Option<&Bound<'_, PyAny>>,
+        _exc_value: Option<&Bound<'_, PyAny>>,
+        _traceback: Option<&Bound<'_, PyAny>>,
+    ) -> PyResult<bool> {
+        if self.autosave && exc_type.is_none() {
+            self.inner.save_in_place()?;
+        }
+        Ok(false)
+    }
+
+    /// Open a wheel held in memory.
+    ///
+    /// Args:
+    ///     data: The complete wheel archive as bytes
+    ///
+    /// Returns:
+    ///     A WheelEditor with no file behind it: save it with an explicit
+    ///     path or `save_to_bytes()`, since `save()` without a path fails
+    ///
+    /// Raises:
+    ///     ValueError: If the data is not a valid wheel
+    #[staticmethod]
+    fn from_bytes(data: &Bound<'_, PyBytes>) -> PyResult<Self> {
+        let editor = WheelEditor::from_bytes(data.as_bytes())?;
+        Ok(Self {
+            inner: editor,
+            autosave: false,
+        })
     }
 
     /// Get the package name
@@ -184,6 +464,55 @@ impl PyWheelEditor {
         self.inner.set_description(description);
     }
 
+    /// Get the description content type (e.g. "text/markdown")
+    #[getter]
+    fn description_content_type(&self) -> Option<&str> {
+        self.inner.description_content_type()
+    }
+
+    /// Set the description content type (not validated; see
+    /// `set_description_content_type_checked`)
+    #[setter]
+    fn set_description_content_type(&mut self, content_type: String) {
+        self.inner.set_description_content_type(content_type);
+    }
+
+    /// Set the description content type after validating it.
+    ///
+    /// Args:
+    ///     content_type: "text/plain", "text/x-rst", or "text/markdown",
+    ///         optionally with "; charset=UTF-8" and (markdown only)
+    ///         "; variant=GFM" or "; variant=CommonMark"
+    ///
+    /// Raises:
+    ///     ValueError: If the media type or a parameter is not allowed
+    fn set_description_content_type_checked(&mut self, content_type: &str) -> PyResult<()> {
+        Ok(self
+            .inner
+            .set_description_content_type_checked(content_type)?)
+    }
+
+    /// Get the long description's size in UTF-8 bytes (0 if there is none)
+    #[getter]
+    fn description_byte_length(&self) -> usize {
+        self.inner.metadata().description_byte_length()
+    }
+
+    /// Check that the long description fits within a size limit.
+    ///
+    /// A pre-upload lint for index limits such as PyPI's; separate from
+    /// `validate`.
+    ///
+    /// Args:
+    ///     max: Maximum allowed size in bytes
+    ///
+    /// Raises:
+    ///     ValueError: If the description is larger than `max`, naming its
+    ///         content type
+    fn check_description_size(&self, max: usize) -> PyResult<()> {
+        Ok(self.inner.check_description_size(max)?)
+    }
+
     /// Get the package author
     #[getter]
     fn author(&self) -> Option<&str> {
@@ -208,6 +537,48 @@ impl PyWheelEditor {
         self.inner.set_author_email(email);
     }
 
+    /// Get the authors and maintainers as one list.
+    ///
+    /// Combines Author/Author-email and Maintainer/Maintainer-email, pairing
+    /// bare email addresses with the names in the name field.
+    ///
+    /// Returns:
+    ///     List of (name, email, role) tuples, where name or email may be
+    ///     None and role is "author" or "maintainer"
+    #[getter]
+    fn contributors(&self) -> Vec<(Option<String>, Option<String>, &'static str)> {
+        self.inner
+            .contributors()
+            .into_iter()
+            .map(|c| {
+                let role = match c.role {
+                    Role::Author => "author",
+                    Role::Maintainer => "maintainer",
+                };
+                (c.name, c.email, role)
+            })
+            .collect()
+    }
+
+    /// Parse Author-email into (display name, address) pairs.
+    ///
+    /// Handles quoted names and comma-separated lists; the raw
+    /// `author_email` stays authoritative.
+    ///
+    /// Returns:
+    ///     List of (name or None, address) tuples
+    fn parsed_author_emails(&self) -> Vec<(Option<String>, String)> {
+        self.inner.parsed_author_emails()
+    }
+
+    /// Parse Maintainer-email into (display name, address) pairs.
+    ///
+    /// Returns:
+    ///     List of (name or None, address) tuples
+    fn parsed_maintainer_emails(&self) -> Vec<(Option<String>, String)> {
+        self.inner.parsed_maintainer_emails()
+    }
+
     /// Get the package license
     #[getter]
     fn license(&self) -> Option<&str> {
@@ -220,6 +591,50 @@ impl PyWheelEditor {
         self.inner.set_license(license);
     }
 
+    /// Get the raw Keywords field, as written in METADATA
+    #[getter]
+    fn keywords(&self) -> Option<&str> {
+        self.inner.keywords()
+    }
+
+    /// Set the raw Keywords field
+    #[setter]
+    fn set_keywords(&mut self, keywords: String) {
+        self.inner.set_keywords(keywords);
+    }
+
+    /// Get the keywords as a list.
+    ///
+    /// The delimiter is detected: comma-separated if the field contains a
+    /// comma, otherwise whitespace-separated.
+    #[getter]
+    fn keywords_list(&self) -> Vec<String> {
+        self.inner.keywords_list()
+    }
+
+    /// Set the keywords from a list, written comma-separated. An empty list
+    /// removes the field.
+    #[setter]
+    fn set_keywords_list(&mut self, keywords: Vec<String>) {
+        self.inner.set_keywords_list(&keywords);
+    }
+
+    /// Append a keyword unless it is already listed.
+    ///
+    /// The field is rewritten comma-separated.
+    ///
+    /// Returns:
+    ///     True if the keyword was added
+    fn add_keyword(&mut self, keyword: &str) -> bool {
+        self.inner.add_keyword(keyword)
+    }
+
+    /// Rewrite the Keywords field in the comma-separated form (e.g.
+    /// "packaging metadata" becomes "packaging,metadata").
+    fn normalize_keywords(&mut self) {
+        self.inner.normalize_keywords();
+    }
+
     /// Get the Python version requirement
     #[getter]
     fn requires_python(&self) -> Option<&str> {
@@ -256,6 +671,59 @@ impl PyWheelEditor {
         self.inner.set_requires_dist(deps);
     }
 
+    /// Parse every dependency (Requires-Dist) as a PEP 508 requirement.
+    ///
+    /// `requires_dist` keeps the raw strings; this is a structured view.
+    ///
+    /// Returns:
+    ///     List of (name, extras, specifiers, url, marker) tuples, where
+    ///     specifiers is a list of (operator, version) pairs
+    ///
+    /// Raises:
+    ///     ValueError: If an entry is not a valid PEP 508 requirement
+    fn parsed_requires_dist(&self) -> PyResult<Vec<RequirementTuple>> {
+        let requirements = self
+            .inner
+            .metadata()
+            .parsed_requires_dist()
+            .map_err(WheelError::from)?;
+        Ok(requirements
+            .into_iter()
+            .map(|r| {
+                let specifiers = r
+                    .specifiers
+                    .into_iter()
+                    .map(|s| (s.operator, s.version))
+                    .collect();
+                (r.name, r.extras, specifiers, r.url, r.marker)
+            })
+            .collect())
+    }
+
+    /// Get the fields declared Dynamic (PEP 643)
+    #[getter]
+    fn dynamic(&self) -> Vec<String> {
+        self.inner.dynamic().to_vec()
+    }
+
+    /// Set the fields declared Dynamic
+    #[setter]
+    fn set_dynamic(&mut self, fields: Vec<String>) {
+        self.inner.set_dynamic(fields);
+    }
+
+    /// Get the distributions this one renders obsolete (Obsoletes-Dist)
+    #[getter]
+    fn obsoletes_dist(&self) -> Vec<String> {
+        self.inner.obsoletes_dist().to_vec()
+    }
+
+    /// Set the Obsoletes-Dist entries
+    #[setter]
+    fn set_obsoletes_dist(&mut self, entries: Vec<String>) {
+        self.inner.set_obsoletes_dist(entries);
+    }
+
     /// Get the project URLs
     #[getter]
     fn project_urls(&self) -> Vec<String> {
@@ -268,6 +736,58 @@ impl PyWheelEditor {
         self.inner.set_project_urls(urls);
     }
 
+    /// Append classifiers from another wheel that aren't already present.
+    ///
+    /// Classifiers are compared exactly; existing entries come first, then
+    /// new ones in the other wheel's order.
+    ///
+    /// Args:
+    ///     other: WheelEditor for the wheel to merge from
+    ///
+    /// Returns:
+    ///     Number of classifiers added
+    fn merge_classifiers_from(slf: &Bound<'_, Self>, other: &Bound<'_, PyWheelEditor>) -> usize {
+        // Copy first: `other` may be this editor, which can't be borrowed
+        // while it is mutably borrowed
+        let metadata = other.borrow().inner.metadata().clone();
+        slf.borrow_mut().inner.merge_classifiers_from(&metadata)
+    }
+
+    /// Append dependencies from another wheel that aren't already present.
+    ///
+    /// Requirements are compared by normalized project name and marker
+    /// (version specifiers are ignored, and the existing entry wins).
+    /// Existing entries come first, then new ones in the other wheel's order.
+    ///
+    /// Args:
+    ///     other: WheelEditor for the wheel to merge from
+    ///
+    /// Returns:
+    ///     Number of dependencies added
+    fn merge_requires_dist_from(slf: &Bound<'_, Self>, other: &Bound<'_, PyWheelEditor>) -> usize {
+        let metadata = other.borrow().inner.metadata().clone();
+        slf.borrow_mut().inner.merge_requires_dist_from(&metadata)
+    }
+
+    /// Rewrite METADATA for an older Metadata-Version.
+    ///
+    /// Below 2.4, License-Expression becomes the License value and
+    /// License-File entries are dropped; below 2.2, Dynamic is dropped.
+    ///
+    /// Args:
+    ///     target: (major, minor) version to write, from (2, 1) to (2, 4)
+    ///
+    /// Returns:
+    ///     List of human-readable descriptions of what was dropped or changed
+    ///
+    /// Raises:
+    ///     ValueError: If the target is unsupported or newer than the
+    ///         current Metadata-Version, or License-Expression would be lost
+    ///         because License is already set; nothing is changed
+    fn downgrade_metadata_version(&mut self, target: (u8, u8)) -> PyResult<Vec<String>> {
+        Ok(self.inner.downgrade_metadata_version(target)?)
+    }
+
     /// Get the python tag (e.g., "cp312" or "py3")
     #[getter]
     fn python_tag(&self) -> Option<String> {
@@ -283,6 +803,17 @@ impl PyWheelEditor {
         self.inner.set_python_tag(&python);
     }
 
+    /// Set the Python tag for all tags after checking it is plausible.
+    ///
+    /// Args:
+    ///     python: A single interpreter tag such as "py3" or "cp311"
+    ///
+    /// Raises:
+    ///     ValueError: If the tag doesn't look like a PEP 425 Python tag
+    fn set_python_tag_checked(&mut self, python: &str) -> PyResult<()> {
+        Ok(self.inner.set_python_tag_checked(python)?)
+    }
+
     /// Get the ABI tag (e.g., "cp312" or "none")
     #[getter]
     fn abi_tag(&self) -> Option<String> {
@@ -298,6 +829,61 @@ impl PyWheelEditor {
         self.inner.set_abi_tag(&abi);
     }
 
+    /// Collapse exact duplicate WHEEL Tag lines, keeping the first of each.
+    ///
+    /// Returns:
+    ///     Number of tags removed
+    fn dedup_tags(&mut self) -> usize {
+        self.inner.dedup_tags()
+    }
+
+    /// Get every WHEEL Tag line (e.g., ["py2-none-any", "py3-none-any"])
+    #[getter]
+    fn tags(&self) -> Vec<String> {
+        self.inner
+            .wheel_info()
+            .tags
+            .iter()
+            .map(WheelTag::serialize)
+            .collect()
+    }
+
+    /// Get the WHEEL tags in compact form (e.g., ["py2.py3-none-any"])
+    #[getter]
+    fn compressed_tags(&self) -> Vec<String> {
+        self.inner.wheel_info().compressed_tags()
+    }
+
+    /// Add a WHEEL Tag line unless it is already present.
+    ///
+    /// Args:
+    ///     tag: A "python-abi-platform" tag (e.g., "py2-none-any")
+    ///
+    /// Returns:
+    ///     True if the tag was added
+    ///
+    /// Raises:
+    ///     ValueError: If the tag doesn't have three parts
+    fn add_tag(&mut self, tag: &str) -> PyResult<bool> {
+        let tag = WheelTag::parse(tag).map_err(WheelError::from)?;
+        Ok(self.inner.add_tag(tag))
+    }
+
+    /// Remove a WHEEL Tag line. Saving fails if no tags are left.
+    ///
+    /// Args:
+    ///     tag: A "python-abi-platform" tag (e.g., "py2-none-any")
+    ///
+    /// Returns:
+    ///     True if the tag was present
+    ///
+    /// Raises:
+    ///     ValueError: If the tag doesn't have three parts
+    fn remove_tag(&mut self, tag: &str) -> PyResult<bool> {
+        let tag = WheelTag::parse(tag).map_err(WheelError::from)?;
+        Ok(self.inner.remove_tag(&tag))
+    }
+
     /// Get the platform tag (e.g., "linux_x86_64" or "manylinux_2_28_x86_64")
     #[getter]
     fn platform_tag(&self) -> Option<String> {
@@ -310,7 +896,21 @@ impl PyWheelEditor {
     ///     The filename string (e.g., "package-1.0.0-cp312-cp312-linux_x86_64.whl")
     #[getter]
     fn filename(&self) -> String {
-        self.inner.filename()
+        self.inner.wheel_filename()
+    }
+
+    /// Same as `filename`, matching the Rust `wheel_filename()`.
+    #[getter]
+    fn wheel_filename(&self) -> String {
+        self.inner.wheel_filename()
+    }
+
+    /// A normalized form of the filename, usable as a dedup key.
+    ///
+    /// See canonical_wheel_filename(); this is not the installable filename.
+    #[getter]
+    fn canonical_filename(&self) -> String {
+        self.inner.canonical_filename()
     }
 
     /// Set the platform tag for all tags in the wheel.
@@ -320,95 +920,814 @@ impl PyWheelEditor {
     ///
     /// Args:
     ///     platform: The new platform tag (e.g., "manylinux_2_28_x86_64")
+    ///
+    /// Raises:
+    ///     ValueError: If the platform is empty
     #[setter]
-    fn set_platform_tag(&mut self, platform: String) {
+    fn set_platform_tag(&mut self, platform: String) -> PyResult<()> {
+        if platform.is_empty() {
+            let err = WheelInfoError::InvalidTag("platform tag cannot be empty".to_string());
+            return Err(WheelError::from(err).into());
+        }
         self.inner.set_platform_tag(&platform);
+        Ok(())
     }
 
-    /// Get the RPATH of a specific file in the wheel.
+    /// Change the python, ABI and/or platform of every tag at once.
     ///
-    /// Returns the effective RPATH (prefers RUNPATH over RPATH).
+    /// Components left as None are unchanged; tags that become identical
+    /// are collapsed. The new filename is available as `filename`. This
+    /// does not check that the wheel's binaries match the new tags.
     ///
     /// Args:
-    ///     path: Path to the file within the wheel (e.g., "torch/lib/libtorch.so")
-    ///
-    /// Returns:
-    ///     The RPATH string, or None if not set
+    ///     python: New python tag (e.g., "cp312")
+    ///     abi: New ABI tag (e.g., "abi3")
+    ///     platform: New platform tag (e.g., "manylinux_2_28_x86_64")
     ///
     /// Raises:
-    ///     ValueError: If the file is not found or is not a valid ELF
-    fn get_rpath(&self, path: &str) -> PyResult<Option<String>> {
-        Ok(self.inner.get_rpath(path)?)
+    ///     ValueError: If the python tag isn't a plausible PEP 425 tag, or
+    ///         the ABI or platform is empty or contains "-" or whitespace;
+    ///         nothing is changed
+    #[pyo3(signature = (*, python = None, abi = None, platform = None))]
+    fn retag(
+        &mut self,
+        python: Option<&str>,
+        abi: Option<&str>,
+        platform: Option<&str>,
+    ) -> PyResult<()> {
+        Ok(self.inner.retag(python, abi, platform)?)
     }
 
-    /// Set the RPATH for files matching a glob pattern.
-    ///
-    /// This modifies all ELF files in the wheel that match the given glob pattern.
-    /// Uses RUNPATH (preferred over RPATH) for setting the library search path.
+    /// Set the platform tag, refusing to lower the required libc version.
     ///
     /// Args:
-    ///     pattern: Glob pattern to match files (e.g., "torch/lib/*.so")
-    ///     rpath: The new RPATH value (e.g., "$ORIGIN:$ORIGIN/../lib")
-    ///
-    /// Returns:
-    ///     Number of files modified
+    ///     platform: The new platform tag (e.g., "manylinux_2_28_x86_64")
     ///
-    /// Example:
-    ///     >>> editor.set_rpath("torch/lib/*.so", "$ORIGIN:$ORIGIN/../../nccl_lib/lib")
-    ///     15
-    fn set_rpath(&mut self, pattern: &str, rpath: &str) -> PyResult<usize> {
-        Ok(self.inner.set_rpath(pattern, rpath)?)
+    /// Raises:
+    ///     ValueError: If the platform is empty, or the current and new tags
+    ///         are both manylinux (or both musllinux) and the new one
+    ///         requires an older libc
+    fn set_platform_tag_checked(&mut self, platform: &str) -> PyResult<()> {
+        Ok(self.inner.set_platform_tag_checked(platform)?)
     }
 
-    /// Add a dependency (Requires-Dist) to the wheel.
-    ///
-    /// This is a convenience method equivalent to appending to requires_dist.
-    ///
-    /// Args:
-    ///     dep: The dependency specification (e.g., "nccl-lib>=1.0")
-    fn add_requires_dist(&mut self, dep: &str) {
-        self.inner.add_requires_dist(dep);
+    /// Get the (major, minor) libc version required by the primary platform
+    /// tag, e.g. (2, 28) for "manylinux_2_28_x86_64" or (2, 17) for
+    /// "manylinux2014_x86_64". None for non-manylinux/musllinux platforms.
+    #[getter]
+    fn libc_version(&self) -> Option<(u32, u32)> {
+        self.inner.libc_version()
     }
 
-    /// Get the dist-info directory name as it would appear in the saved wheel.
-    ///
-    /// Reflects the *current* metadata, so this is safe to use for
-    /// constructing a path to pass to `add_file` even after `name` or
-    /// `version` has been changed.
-    ///
-    /// Returns:
-    ///     The dist-info directory name (e.g., "torch-2.5.0.dist-info")
+    /// Get the WHEEL Generator field
     #[getter]
-    fn dist_info_dir(&self) -> String {
-        self.inner.dist_info_dir()
+    fn generator(&self) -> Option<&str> {
+        self.inner.generator()
     }
 
-    /// Add a new file to the wheel archive.
-    ///
-    /// Args:
-    ///     path: Full archive path for the new file. If the dist-info
-    ///           directory is renamed at save time (because the package name
-    ///           or version changed), paths under the old prefix are
-    ///           rewritten to the new prefix automatically.
-    ///     content: File content as bytes.
-    ///
-    /// Raises:
-    ///     ValueError: At save time, if `path` collides with a file already
-    ///                 in the source archive or with a generated dist-info
-    ///                 file (METADATA, RECORD, WHEEL).
-    ///
-    /// Example:
-    ///     >>> editor.add_file(
-    ///     ...     f"{editor.dist_info_dir}/build-details.json",
-    ///     ...     json.dumps(details).encode(),
-    ///     ... )
-    fn add_file(&mut self, path: &str, content: &Bound<'_, PyBytes>) {
-        self.inner.add_file(path.to_string(), content.as_bytes().to_vec());
+    /// Replace the WHEEL Generator field verbatim
+    #[setter]
+    fn set_generator(&mut self, generator: String) {
+        self.inner.set_generator_exact(generator);
     }
 
-    /// True if any new files have been queued via `add_file`.
-    fn has_added_files(&self) -> bool {
-        self.inner.has_added_files()
+    /// Get the WHEEL Build field as (number, suffix), e.g. (1, "a") for
+    /// "1a". None if the field is absent or doesn't start with a digit.
+    #[getter]
+    fn build_tag(&self) -> Option<(u32, String)> {
+        self.inner.build_tag().map(|tag| (tag.number, tag.suffix))
+    }
+
+    /// Set the numeric part of the WHEEL Build field, keeping any suffix
+    fn set_build_number(&mut self, number: u32) {
+        self.inner.set_build_number(number);
+    }
+
+    /// Append "editwheel-rs <version>" to the WHEEL Generator field,
+    /// preserving the original generator (e.g. "bdist_wheel (0.40.0);
+    /// editwheel-rs 0.3.0"). Stamping twice is a no-op.
+    fn stamp_generator(&mut self) {
+        self.inner.stamp_generator();
+    }
+
+    /// Whether the output is written for maximum compatibility with very old
+    /// extractors (every entry Stored or Deflate-compressed, minimal
+    /// version-needed-to-extract). Entries using other compression methods
+    /// are re-encoded, so saving is no longer constant-time for them.
+    #[getter]
+    fn max_compatibility(&self) -> bool {
+        self.inner.write_options().max_compatibility
+    }
+
+    /// Enable or disable maximum-compatibility output.
+    #[setter]
+    fn set_max_compatibility(&mut self, enabled: bool) {
+        self.inner.set_max_compatibility(enabled);
+    }
+
+    /// Whether `save` fails on members missing from RECORD instead of
+    /// silently decompressing and hashing them.
+    #[getter]
+    fn strict_record(&self) -> bool {
+        self.inner.write_options().strict_record
+    }
+
+    /// Enable or disable strict RECORD mode.
+    #[setter]
+    fn set_strict_record(&mut self, strict: bool) {
+        self.inner.set_strict_record(strict);
+    }
+
+    /// Whether `save` fails if `validate_metadata()` finds a problem, such
+    /// as a field newer than the declared Metadata-Version.
+    #[getter]
+    fn strict_metadata(&self) -> bool {
+        self.inner.strict_metadata()
+    }
+
+    /// Enable or disable strict metadata mode.
+    #[setter]
+    fn set_strict_metadata(&mut self, strict: bool) {
+        self.inner.set_strict_metadata(strict);
+    }
+
+    /// Whether `save` checks every copied member against its RECORD hash.
+    ///
+    /// This reads the whole wheel, so saving is no longer constant-time.
+    #[getter]
+    fn verify_hashes(&self) -> bool {
+        self.inner.write_options().verify_hashes
+    }
+
+    /// Enable or disable hash verification on save.
+    #[setter]
+    fn set_verify_hashes(&mut self, verify: bool) {
+        self.inner.set_verify_hashes(verify);
+    }
+
+    /// Compression for every entry `save` generates: "stored", "deflated",
+    /// or None (the default) to keep Stored members Stored and deflate the
+    /// rest.
+    #[getter]
+    fn compression(&self) -> Option<&'static str> {
+        match self.inner.write_options().compression? {
+            zip::CompressionMethod::Stored => Some("stored"),
+            _ => Some("deflated"),
+        }
+    }
+
+    /// Set the compression for generated entries.
+    ///
+    /// Raises:
+    ///     ValueError: If the method is not "stored", "deflated" or None
+    #[setter]
+    fn set_compression(&mut self, method: Option<&str>) -> PyResult<()> {
+        let method = match method {
+            None => None,
+            Some("stored") => Some(zip::CompressionMethod::Stored),
+            Some("deflated") => Some(zip::CompressionMethod::Deflated),
+            Some(other) => {
+                return Err(PyValueError::new_err(format!(
+                    "unsupported compression method: {other} (expected stored or deflated)"
+                )));
+            }
+        };
+        self.inner.set_compression(method);
+        Ok(())
+    }
+
+    /// Deflate level (0-9) for the entries `save` generates, or None for the
+    /// default.
+    #[getter]
+    fn compression_level(&self) -> Option<i64> {
+        self.inner.write_options().compression_level
+    }
+
+    /// Set the Deflate level for generated entries.
+    ///
+    /// Raises:
+    ///     ValueError: If the level is outside 0-9
+    #[setter]
+    fn set_compression_level(&mut self, level: Option<i64>) -> PyResult<()> {
+        Ok(self.inner.set_compression_level(level)?)
+    }
+
+    /// Fixed timestamp (seconds since the Unix epoch) for the entries `save`
+    /// generates, or None to use the current time.
+    ///
+    /// Set it (e.g. to SOURCE_DATE_EPOCH) to make saved wheels reproducible.
+    #[getter]
+    fn source_date_epoch(&self) -> Option<u64> {
+        self.inner.write_options().source_date_epoch
+    }
+
+    /// Set or clear the fixed timestamp for generated entries.
+    #[setter]
+    fn set_source_date_epoch(&mut self, epoch: Option<u64>) {
+        self.inner.set_source_date_epoch(epoch);
+    }
+
+    /// Hash algorithm used for the RECORD entries of rewritten and added
+    /// files ("sha256" by default).
+    #[getter]
+    fn hash_algorithm(&self) -> &'static str {
+        self.inner.write_options().hash_algorithm.name()
+    }
+
+    /// Set the RECORD hash algorithm: "sha256", "sha384", "sha512",
+    /// "blake2b" or "blake2s".
+    ///
+    /// Raises:
+    ///     ValueError: If the algorithm is not supported
+    #[setter]
+    fn set_hash_algorithm(&mut self, algorithm: &str) -> PyResult<()> {
+        let algorithm = HashAlgorithm::from_name(algorithm).ok_or_else(|| {
+            PyValueError::new_err(format!("unsupported hash algorithm: {algorithm}"))
+        })?;
+        self.inner.set_hash_algorithm(algorithm);
+        Ok(())
+    }
+
+    /// Write RECORD entries without a hash or size for matching members.
+    ///
+    /// Replaces any previously set patterns. Mirrors build backends that
+    /// ship generated files such as `*.pyc` unhashed.
+    ///
+    /// Args:
+    ///     patterns: Glob patterns matched against output paths
+    ///
+    /// Raises:
+    ///     ValueError: If a pattern is invalid or matches METADATA or WHEEL,
+    ///         which must always be hashed
+    fn set_hashless_patterns(&mut self, patterns: Vec<String>) -> PyResult<()> {
+        let patterns: Vec<&str> = patterns.iter().map(String::as_str).collect();
+        Ok(self.inner.set_hashless_patterns(&patterns)?)
+    }
+
+    /// Get the RPATH of a specific file in the wheel.
+    ///
+    /// Returns the effective RPATH (prefers RUNPATH over RPATH).
+    ///
+    /// Args:
+    ///     path: Path to the file within the wheel (e.g., "torch/lib/libtorch.so")
+    ///
+    /// Returns:
+    ///     The RPATH string, or None if not set
+    ///
+    /// Raises:
+    ///     ValueError: If the file is not found or is not a valid ELF
+    ///     IOError: If the file is in the wheel but can't be read (e.g. corrupt data)
+    fn get_rpath(&self, path: &str) -> PyResult<Option<String>> {
+        Ok(self.inner.get_rpath(path)?)
+    }
+
+    /// Get the dynamic-linking details of a specific file in the wheel.
+    ///
+    /// The file is read and parsed once.
+    ///
+    /// Args:
+    ///     path: Path to the file within the wheel (e.g., "torch/lib/libtorch.so")
+    ///
+    /// Returns:
+    ///     Tuple of (rpath, runpath, needed, soname, interpreter); needed is
+    ///     a list of DT_NEEDED entries, the others are None when unset
+    ///
+    /// Raises:
+    ///     ValueError: If the file is not found or is not a valid ELF
+    #[allow(clippy::type_complexity)]
+    fn elf_info(
+        &self,
+        path: &str,
+    ) -> PyResult<(
+        Option<String>,
+        Option<String>,
+        Vec<String>,
+        Option<String>,
+        Option<String>,
+    )> {
+        let info = self.inner.elf_info(path)?;
+        Ok((
+            info.rpath,
+            info.runpath,
+            info.needed,
+            info.soname,
+            info.interpreter,
+        ))
+    }
+
+    /// Get the libraries a specific file in the wheel links against.
+    ///
+    /// Args:
+    ///     path: Path to the file within the wheel (e.g., "torch/lib/libtorch.so")
+    ///
+    /// Returns:
+    ///     The DT_NEEDED entries (e.g., ["libc.so.6"]), in load order
+    ///
+    /// Raises:
+    ///     ValueError: If the file is not found or is not a valid ELF
+    ///     IOError: If the file is in the wheel but can't be read (e.g. corrupt data)
+    fn needed_libraries(&self, path: &str) -> PyResult<Vec<String>> {
+        Ok(self.inner.needed_libraries(path)?)
+    }
+
+    /// Set the RPATH for files matching a glob pattern.
+    ///
+    /// This modifies all ELF files in the wheel that match the given glob pattern.
+    /// Uses RUNPATH (preferred over RPATH) for setting the library search path.
+    ///
+    /// Args:
+    ///     pattern: Glob pattern to match files (e.g., "torch/lib/*.so")
+    ///     rpath: The new RPATH value (e.g., "$ORIGIN:$ORIGIN/../lib")
+    ///
+    /// Returns:
+    ///     Number of files modified
+    ///
+    /// Example:
+    ///     >>> editor.set_rpath("torch/lib/*.so", "$ORIGIN:$ORIGIN/../../nccl_lib/lib")
+    ///     15
+    fn set_rpath(&mut self, pattern: &str, rpath: &str) -> PyResult<usize> {
+        Ok(self.inner.set_rpath(pattern, rpath)?)
+    }
+
+    /// Set the SONAME of ELF files matching a glob pattern.
+    ///
+    /// Use this to rename a vendored library so it can't clash with a system
+    /// library of the same name at load time.
+    ///
+    /// Args:
+    ///     pattern: Glob pattern to match files (e.g., "torch.libs/libgomp*.so*")
+    ///     soname: The new SONAME (e.g., "libgomp-a34b3233.so.1")
+    ///
+    /// Returns:
+    ///     Number of files modified
+    fn set_soname(&mut self, pattern: &str, soname: &str) -> PyResult<usize> {
+        Ok(self.inner.set_soname(pattern, soname)?)
+    }
+
+    /// Set the program interpreter of ELF executables matching a glob pattern.
+    ///
+    /// Files without a PT_INTERP segment (shared libraries) are skipped.
+    ///
+    /// Args:
+    ///     pattern: Glob pattern to match files (e.g., "pkg-1.0.data/scripts/*")
+    ///     interpreter: The new interpreter path
+    ///         (e.g., "/opt/glibc/lib/ld-linux-x86-64.so.2")
+    ///
+    /// Returns:
+    ///     Number of files modified
+    fn set_interpreter(&mut self, pattern: &str, interpreter: &str) -> PyResult<usize> {
+        Ok(self.inner.set_interpreter(pattern, interpreter)?)
+    }
+
+    /// Remove the RPATH and RUNPATH from files matching a glob pattern.
+    ///
+    /// The entries are deleted rather than set to an empty string.
+    ///
+    /// Args:
+    ///     pattern: Glob pattern to match files (e.g., "torch/lib/*.so")
+    ///
+    /// Returns:
+    ///     Number of files modified
+    fn remove_rpath(&mut self, pattern: &str) -> PyResult<usize> {
+        Ok(self.inner.remove_rpath(pattern)?)
+    }
+
+    /// Strip debug sections from ELF files matching a glob pattern.
+    ///
+    /// Removes ".debug_*" sections that lie after the loaded segments of
+    /// each matched shared library, recording the smaller content.
+    ///
+    /// Args:
+    ///     pattern: Glob pattern to match files (e.g., "torch/lib/*.so")
+    ///
+    /// Returns:
+    ///     Total number of bytes saved
+    fn strip_debug(&mut self, pattern: &str) -> PyResult<usize> {
+        Ok(self.inner.strip_debug(pattern)?)
+    }
+
+    /// ELF files skipped by set_rpath(), remove_rpath(), set_soname(),
+    /// set_interpreter() and strip_debug() because modifying them failed.
+    ///
+    /// Returns:
+    ///     List of (path, error message) tuples, oldest first
+    #[getter]
+    fn warnings(&self) -> Vec<(String, String)> {
+        self.inner
+            .warnings()
+            .iter()
+            .map(|warning| (warning.path.clone(), warning.error.clone()))
+            .collect()
+    }
+
+    /// Discard the warnings collected so far.
+    fn clear_warnings(&mut self) {
+        self.inner.clear_warnings();
+    }
+
+    /// Set the RPATH like set_rpath(), then check where it points.
+    ///
+    /// Each "$ORIGIN"-relative entry is resolved against the modified
+    /// library's directory inside the wheel. Entries whose directory has no
+    /// members (or that climb out of the wheel) produce a warning; system
+    /// paths are skipped.
+    ///
+    /// Args:
+    ///     pattern: Glob pattern to match files (e.g., "torch/lib/*.so")
+    ///     rpath: The new RPATH value (e.g., "$ORIGIN/../torch.libs")
+    ///
+    /// Returns:
+    ///     Tuple of (number of files modified, list of warning strings)
+    fn set_rpath_checked(&mut self, pattern: &str, rpath: &str) -> PyResult<(usize, Vec<String>)> {
+        let result = self.inner.set_rpath_checked(pattern, rpath)?;
+        Ok((result.modified, result.warnings))
+    }
+
+    /// Apply a recipe of ELF modification rules in order.
+    ///
+    /// Each rule is a glob pattern and a list of modification specs:
+    /// "set-rpath=PATH", "set-runpath=PATH", "set-soname=NAME",
+    /// "set-interpreter=PATH", "remove-rpath", "remove-runpath", or
+    /// "strip-debug". Rules compose, so a file matched
+    /// by several rules gets all of their modifications.
+    ///
+    /// Args:
+    ///     rules: List of (pattern, [spec, ...]) tuples
+    ///
+    /// Returns:
+    ///     Tuple of (dict mapping each modified path to the specs applied to
+    ///     it, list of (path, error message) failures, list of (path, spec)
+    ///     modifications skipped because they don't apply, such as
+    ///     "set-interpreter" on a shared library)
+    ///
+    /// Raises:
+    ///     ValueError: If a spec or glob pattern is invalid
+    ///
+    /// Example:
+    ///     >>> editor.apply_elf_rules([
+    ///     ...     ("torch/lib/*.so", ["remove-rpath", "set-runpath=$ORIGIN"]),
+    ///     ...     ("torch/lib/*.so", ["strip-debug"]),
+    ///     ... ])
+    #[allow(clippy::type_complexity)]
+    fn apply_elf_rules(
+        &mut self,
+        rules: Vec<(String, Vec<String>)>,
+    ) -> PyResult<(
+        BTreeMap<String, Vec<String>>,
+        Vec<(String, String)>,
+        Vec<(String, String)>,
+    )> {
+        let rules = rules
+            .into_iter()
+            .map(|(pattern, specs)| {
+                let modifications = specs
+                    .iter()
+                    .map(|spec| parse_elf_modification(spec))
+                    .collect::<PyResult<Vec<_>>>()?;
+                Ok((pattern, modifications))
+            })
+            .collect::<PyResult<Vec<_>>>()?;
+        let report = self.inner.apply_elf_rules(&rules)?;
+        let applied = report
+            .applied
+            .iter()
+            .map(|(path, modifications)| {
+                let specs = modifications.iter().map(format_elf_modification).collect();
+                (path.clone(), specs)
+            })
+            .collect();
+        let skipped = report
+            .skipped
+            .iter()
+            .map(|(path, modification)| (path.clone(), format_elf_modification(modification)))
+            .collect();
+        Ok((applied, report.failures, skipped))
+    }
+
+    /// Apply a content transform to every file matching a glob pattern.
+    ///
+    /// Each matching file is read once and passed to `func(path, content)`.
+    /// Returning new bytes replaces the file's content and updates its
+    /// RECORD hash; returning None leaves it unchanged.
+    ///
+    /// Args:
+    ///     pattern: Glob pattern to match files (e.g., "pkg/**/*.txt")
+    ///     func: Callable taking (path: str, content: bytes) and returning
+    ///         bytes or None
+    ///
+    /// Returns:
+    ///     Number of files modified
+    ///
+    /// Raises:
+    ///     Any exception raised by `func`. Files transformed before the
+    ///     exception keep their new content.
+    ///
+    /// Example:
+    ///     >>> editor.modify_matching(
+    ///     ...     "pkg/*.cfg",
+    ///     ...     lambda path, data: data.replace(b"/build", b"/opt") if b"/build" in data else None,
+    ///     ... )
+    ///     2
+    fn modify_matching(&mut self, pattern: &str, func: &Bound<'_, PyAny>) -> PyResult<usize> {
+        let error: RefCell<Option<PyErr>> = RefCell::new(None);
+        let count = self.inner.modify_matching(pattern, |path, content| {
+            if error.borrow().is_some() {
+                return None;
+            }
+            let result = func
+                .call1((path, PyBytes::new(func.py(), content)))
+                .and_then(|value| value.extract::<Option<Vec<u8>>>());
+            match result {
+                Ok(new_content) => new_content,
+                Err(e) => {
+                    *error.borrow_mut() = Some(e);
+                    None
+                }
+            }
+        })?;
+        match error.into_inner() {
+            Some(e) => Err(e),
+            None => Ok(count),
+        }
+    }
+
+    /// Move a top-level package directory, e.g. after renaming the project.
+    ///
+    /// On save, every file under `old_top/` (also under `.data/purelib/` and
+    /// `.data/platlib/`) is written under `new_top/` and RECORD follows, and
+    /// a `top_level.txt` line naming `old_top` is updated. Only paths
+    /// change: imports of `old_top`, `.pth` files, entry point values and
+    /// extension module init symbols are not rewritten, and a top-level
+    /// `old_top.py` module isn't moved. Other methods keep taking source
+    /// paths.
+    ///
+    /// Args:
+    ///     old_top: Current package directory name, e.g. "oldpkg"
+    ///     new_top: New package directory name, e.g. "newpkg"
+    ///
+    /// Returns:
+    ///     Number of files moved (0 if there is no such package)
+    ///
+    /// Raises:
+    ///     InvalidWheelError: If a name isn't a plain directory name or the
+    ///         wheel already has a `new_top` package
+    fn rename_package(&mut self, old_top: &str, new_top: &str) -> PyResult<usize> {
+        Ok(self.inner.rename_package(old_top, new_top)?)
+    }
+
+    /// List the entry points in the wheel's `entry_points.txt`.
+    ///
+    /// Args:
+    ///     group: Only list this group, e.g. "console_scripts"
+    ///
+    /// Returns:
+    ///     List of (group, name, value) tuples in file order; empty if the
+    ///     wheel has no entry_points.txt
+    ///
+    /// Raises:
+    ///     EntryPointsError: If entry_points.txt can't be parsed
+    #[pyo3(signature = (group=None))]
+    fn entry_points(&self, group: Option<&str>) -> PyResult<Vec<(String, String, String)>> {
+        let Some(entry_points) = self.inner.entry_points()? else {
+            return Ok(Vec::new());
+        };
+        Ok(entry_points
+            .groups
+            .iter()
+            .filter(|g| group.is_none_or(|name| g.name == name))
+            .flat_map(|g| {
+                g.entries
+                    .iter()
+                    .map(|entry| (g.name.clone(), entry.name.clone(), entry.value.clone()))
+            })
+            .collect())
+    }
+
+    /// Set an entry point, adding it (and its group) if needed.
+    ///
+    /// entry_points.txt is created if the wheel has none. Groups that
+    /// aren't changed keep their original text.
+    ///
+    /// Args:
+    ///     group: Entry point group, e.g. "console_scripts"
+    ///     name: Entry point name, e.g. the script name
+    ///     value: Object reference, e.g. "pkg.cli:main"
+    fn set_entry_point(&mut self, group: &str, name: &str, value: &str) -> PyResult<()> {
+        Ok(self
+            .inner
+            .modify_entry_points(|entry_points| entry_points.set(group, name, value))?)
+    }
+
+    /// Remove an entry point.
+    ///
+    /// Returns:
+    ///     True if it existed
+    fn remove_entry_point(&mut self, group: &str, name: &str) -> PyResult<bool> {
+        Ok(self
+            .inner
+            .modify_entry_points(|entry_points| entry_points.remove(group, name))?)
+    }
+
+    /// Rename an entry point, keeping its value and position.
+    ///
+    /// Returns:
+    ///     True if `old` existed
+    ///
+    /// Raises:
+    ///     EntryPointsError: If `group` already has an entry point named `new`
+    fn rename_entry_point(&mut self, group: &str, old: &str, new: &str) -> PyResult<bool> {
+        let renamed = self
+            .inner
+            .modify_entry_points(|entry_points| entry_points.rename(group, old, new))?;
+        Ok(renamed.map_err(WheelError::from)?)
+    }
+
+    /// Recompute the RECORD hash and size for a single file.
+    ///
+    /// Content is taken from pending modifications (e.g. via `set_rpath`)
+    /// if present, otherwise from the source archive.
+    ///
+    /// Args:
+    ///     path: Path to the file within the wheel
+    ///
+    /// Raises:
+    ///     ValueError: If the file is not found in the wheel
+    fn refresh_record_hash(&mut self, path: &str) -> PyResult<()> {
+        Ok(self.inner.refresh_record_hash(path)?)
+    }
+
+    /// Add a dependency (Requires-Dist) to the wheel.
+    ///
+    /// This is a convenience method equivalent to appending to requires_dist.
+    ///
+    /// Args:
+    ///     dep: The dependency specification (e.g., "nccl-lib>=1.0")
+    fn add_requires_dist(&mut self, dep: &str) {
+        self.inner.add_requires_dist(dep);
+    }
+
+    /// Add an Obsoletes-Dist entry to the wheel.
+    ///
+    /// Args:
+    ///     entry: The obsoleted distribution (e.g., 'Gorgon (<3.0); os_name == "posix"')
+    fn add_obsoletes_dist(&mut self, entry: &str) {
+        self.inner.add_obsoletes_dist(entry);
+    }
+
+    /// Remove the Obsoletes-Dist entries for a project.
+    ///
+    /// Entries are matched by normalized project name, ignoring version
+    /// clauses and markers.
+    ///
+    /// Args:
+    ///     name: The project name (e.g., "Foo_Bar" matches "foo-bar (<2.0)")
+    ///
+    /// Returns:
+    ///     Number of entries removed
+    fn remove_obsoletes_dist(&mut self, name: &str) -> usize {
+        self.inner.remove_obsoletes_dist(name)
+    }
+
+    /// Get the dist-info directory name as it would appear in the saved wheel.
+    ///
+    /// Reflects the *current* metadata, so this is safe to use for
+    /// constructing a path to pass to `add_file` even after `name` or
+    /// `version` has been changed.
+    ///
+    /// Returns:
+    ///     The dist-info directory name (e.g., "torch-2.5.0.dist-info")
+    #[getter]
+    fn dist_info_dir(&self) -> String {
+        self.inner.dist_info_dir()
+    }
+
+    /// Get the current metadata as a PEP 566 JSON string.
+    ///
+    /// Keys are lowercased field names with hyphens replaced by underscores,
+    /// multiple-use fields (e.g. `classifier`, `requires_dist`) and unknown
+    /// headers (e.g. `license_file`) are arrays, and the long description is
+    /// under `description`.
+    ///
+    /// Returns:
+    ///     The JSON-encoded metadata
+    #[cfg(feature = "json")]
+    fn metadata_json(&self) -> String {
+        self.inner.metadata().to_pep566_json().to_string()
+    }
+
+    /// Replace the metadata with one given as a PEP 566 JSON string.
+    ///
+    /// The inverse of `metadata_json()`: every field is taken from `json`,
+    /// so fields it doesn't mention are cleared.
+    ///
+    /// Args:
+    ///     json: PEP 566 JSON metadata, e.g. the output of `metadata_json()`
+    ///
+    /// Raises:
+    ///     ValueError: If `json` is not valid JSON, lacks `name` or
+    ///                 `version`, or has a value that isn't a string or list
+    ///                 of strings
+    #[cfg(feature = "json")]
+    fn set_metadata_json(&mut self, json: &str) -> PyResult<()> {
+        let metadata = crate::Metadata::from_json(json).map_err(WheelError::from)?;
+        *self.inner.metadata_mut() = metadata;
+        Ok(())
+    }
+
+    /// Get the exact METADATA bytes `save` will write into the archive.
+    ///
+    /// Write these as the PEP 658 ".metadata" sidecar served next to the
+    /// wheel, which must byte-match the wheel's METADATA.
+    ///
+    /// Returns:
+    ///     The serialized METADATA, reflecting pending edits
+    fn extract_metadata_bytes<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyBytes>> {
+        Ok(PyBytes::new(py, &self.inner.extract_metadata_bytes()?))
+    }
+
+    /// Get the version embedded in the source wheel's dist-info directory name.
+    ///
+    /// Parsed from the directory in the archive (e.g. "pkg-1.0.0.dist-info"
+    /// gives "1.0.0"), not from METADATA, so it can disagree with `version`
+    /// in a malformed wheel. `validate()` reports such a mismatch.
+    ///
+    /// Returns:
+    ///     The version string, or None if the directory name has no version
+    #[getter]
+    fn dist_info_version(&self) -> Option<&str> {
+        self.inner.dist_info_version()
+    }
+
+    /// Add a new file to the wheel archive.
+    ///
+    /// A path that already exists in the wheel replaces that file, like a
+    /// modification.
+    ///
+    /// Args:
+    ///     path: Full archive path for the new file. If the dist-info
+    ///           directory is renamed at save time (because the package name
+    ///           or version changed), paths under the old prefix are
+    ///           rewritten to the new prefix automatically.
+    ///     content: File content as bytes.
+    ///
+    /// Raises:
+    ///     ValueError: At save time, if `path` collides with a generated
+    ///                 dist-info file (METADATA, RECORD, WHEEL).
+    ///
+    /// Example:
+    ///     >>> editor.add_file(
+    ///     ...     f"{editor.dist_info_dir}/build-details.json",
+    ///     ...     json.dumps(details).encode(),
+    ///     ... )
+    fn add_file(&mut self, path: &str, content: &Bound<'_, PyBytes>) {
+        self.inner.add_file(path.to_string(), content.as_bytes().to_vec());
+    }
+
+    /// True if any new files have been queued via `add_file`.
+    fn has_added_files(&self) -> bool {
+        self.inner.has_added_files()
+    }
+
+    /// Remove a file from the wheel archive.
+    ///
+    /// The file and its RECORD entry are left out on save, and any pending
+    /// changes to it are discarded. Removing a file queued with `add_file`
+    /// unqueues it.
+    ///
+    /// Args:
+    ///     path: Full archive path of the file, as in the source wheel.
+    ///
+    /// Returns:
+    ///     True if the file was in the wheel, False if there was nothing to
+    ///     remove.
+    ///
+    /// Raises:
+    ///     ValueError: If `path` is the dist-info METADATA, RECORD or WHEEL.
+    fn remove_file(&mut self, path: &str) -> PyResult<bool> {
+        Ok(self.inner.remove_file(path)?)
+    }
+
+    /// Check every Requires-Dist, Obsoletes-Dist and Provides-Dist entry
+    /// against the PEP 508 grammar.
+    ///
+    /// Returns:
+    ///     List of messages, one per malformed entry, naming the entry and
+    ///     whether its name, extras, version specifier, URL or marker is
+    ///     invalid (empty if all entries parse)
+    fn validate_dependencies(&self) -> Vec<String> {
+        self.inner.validate_dependencies().err().unwrap_or_default()
+    }
+
+    /// Lint the current (edited) metadata.
+    ///
+    /// Checks that Name and Version are set, that Description-Content-Type
+    /// is valid, and that every Requires-Dist entry parses. Reads no file
+    /// content.
+    ///
+    /// Returns:
+    ///     List of problems (empty if the metadata is valid)
+    fn validate_metadata(&self) -> Vec<String> {
+        self.inner.validate_metadata().err().unwrap_or_default()
     }
 
     /// Validate the wheel: every file in RECORD must exist in the archive
@@ -421,11 +1740,308 @@ impl PyWheelEditor {
     /// not the in-memory pending edits.
     ///
     /// Returns:
-    ///     A `ValidationResult` with `is_valid` and `errors` properties.
+    ///     A `ValidationResult` with `is_valid`, `errors` (strings) and
+    ///     `details` (`ValidationIssue` objects) properties.
     fn validate(&self) -> PyResult<PyValidationResult> {
         Ok(PyValidationResult::from_rust(self.inner.validate()?))
     }
 
+    /// Validate the wheel against an externally-provided RECORD.
+    ///
+    /// The wheel's own RECORD is ignored, so this verifies the contents
+    /// against a trusted manifest (e.g. from a signed source) even if the
+    /// embedded RECORD was tampered with. Like `validate`, this checks the
+    /// wheel on disk and is O(wheel_size).
+    ///
+    /// Args:
+    ///     record: Contents of the trusted RECORD file (CSV)
+    ///
+    /// Returns:
+    ///     A `ValidationResult` with `is_valid` and `errors` properties.
+    ///
+    /// Raises:
+    ///     ValueError: If `record` can't be parsed
+    fn validate_against(&self, record: &str) -> PyResult<PyValidationResult> {
+        let record = Record::parse(record).map_err(WheelError::from)?;
+        Ok(PyValidationResult::from_rust(
+            self.inner.validate_against(&record)?,
+        ))
+    }
+
+    /// List members that don't map cleanly to an install location.
+    ///
+    /// Every file should live in the dist-info directory, a
+    /// `.data/<scheme>/` directory with a known scheme, a package directory,
+    /// or be a top-level module. Files queued via `add_file` are included.
+    ///
+    /// Returns:
+    ///     List of offending archive paths (empty if the layout is clean)
+    fn check_install_layout(&self) -> PyResult<Vec<String>> {
+        Ok(self.inner.check_install_layout()?)
+    }
+
+    /// Read the decompressed content of a single file.
+    ///
+    /// Pending changes are reflected: a modified or added file returns the
+    /// bytes `save()` would write.
+    ///
+    /// Args:
+    ///     path: Full archive path of the file.
+    ///
+    /// Returns:
+    ///     The file content as bytes
+    ///
+    /// Raises:
+    ///     ValueError: If the file isn't in the wheel or has been removed
+    fn read_file<'py>(&self, py: Python<'py>, path: &str) -> PyResult<Bound<'py, PyBytes>> {
+        let content = self.inner.read_file(path)?;
+        Ok(PyBytes::new(py, &content))
+    }
+
+    /// Get the names of every file in the source wheel, in archive order.
+    ///
+    /// Directory entries are left out; `members()` includes them.
+    fn file_names(&self) -> PyResult<Vec<String>> {
+        Ok(self.inner.file_names()?)
+    }
+
+    /// List every member of the source wheel from the central directory.
+    ///
+    /// Nothing is decompressed, so this is cheap even for large wheels.
+    /// Pending edits are not reflected.
+    ///
+    /// Returns:
+    ///     List of (name, compressed_size, uncompressed_size, method, crc32,
+    ///     is_dir) tuples, where method is e.g. "Deflated" or "Stored"
+    #[allow(clippy::type_complexity)]
+    fn members(&self) -> PyResult<Vec<(String, u64, u64, String, u32, bool)>> {
+        Ok(self
+            .inner
+            .members()?
+            .into_iter()
+            .map(|m| {
+                (
+                    m.name,
+                    m.compressed_size,
+                    m.uncompressed_size,
+                    format!("{:?}", m.method),
+                    m.crc32,
+                    m.is_dir,
+                )
+            })
+            .collect())
+    }
+
+    /// List members that would be unsafe to extract.
+    ///
+    /// Flags absolute member names, names with ".." segments, and members
+    /// whose Unix mode marks them as symlinks. editwheel never extracts
+    /// wheels itself, but installers and other downstream tools do, so use
+    /// this as a pre-flight check on untrusted wheels.
+    ///
+    /// Returns:
+    ///     List of offending archive paths (empty if all are safe)
+    fn check_safe_paths(&self) -> PyResult<Vec<String>> {
+        Ok(self.inner.check_safe_paths()?)
+    }
+
+    /// List non-fatal problems with the wheel.
+    ///
+    /// Covers purelib wheels with platform tags, filename/METADATA/WHEEL
+    /// disagreements, python tags excluded by Requires-Python, missing
+    /// RECORD entries for METADATA or WHEEL, weak RECORD hashes, and
+    /// members that are unsafe to extract.
+    ///
+    /// Returns:
+    ///     List of (code, severity, message) tuples; severity is "warning"
+    ///     or "error" and code is a stable identifier such as "weak-hash"
+    fn lint(&self) -> PyResult<Vec<(String, String, String)>> {
+        Ok(self
+            .inner
+            .lint()?
+            .into_iter()
+            .map(|lint| {
+                (
+                    lint.code.to_string(),
+                    lint.severity.as_str().to_string(),
+                    lint.message,
+                )
+            })
+            .collect())
+    }
+
+    /// Extract every member of the source wheel into a directory.
+    ///
+    /// Paths are preserved, intermediate directories are created and (on
+    /// Unix) stored read/write/execute bits are restored; setuid, setgid
+    /// and sticky bits are dropped. Pending edits are not applied; save()
+    /// first to unpack the edited wheel.
+    ///
+    /// Args:
+    ///     dest: Directory to extract into
+    ///
+    /// Raises:
+    ///     ValueError: If any member has an unsafe path (see check_safe_paths)
+    ///     IOError: If a file cannot be written
+    fn unpack(&self, dest: &str) -> PyResult<()> {
+        Ok(self.inner.unpack(dest)?)
+    }
+
+    /// Check whether `save` can run in constant time with the current edits.
+    ///
+    /// True iff no payload files are modified or added and every source
+    /// member can be raw-copied (only METADATA, WHEEL, and RECORD change).
+    fn is_constant_time(&self) -> PyResult<bool> {
+        Ok(self.inner.is_constant_time()?)
+    }
+
+    /// List source members that RECORD doesn't list.
+    ///
+    /// `save` recovers these by hashing them, unless strict RECORD mode is
+    /// enabled, in which case it fails.
+    ///
+    /// Returns:
+    ///     Archive paths, in archive order
+    fn unrecorded_files(&self) -> PyResult<Vec<String>> {
+        Ok(self.inner.unrecorded_files()?)
+    }
+
+    /// List RECORD signatures (`RECORD.jws`, `RECORD.p7s`) `save` will drop.
+    ///
+    /// They sign the RECORD the wheel was built with, which every save
+    /// regenerates. Use `set_record_signer` to write a fresh `RECORD.jws`.
+    ///
+    /// Returns:
+    ///     Archive paths, in archive order
+    fn dropped_signatures(&self) -> PyResult<Vec<String>> {
+        Ok(self.inner.dropped_signatures()?)
+    }
+
+    /// Sign the RECORD `save` writes, or stop signing.
+    ///
+    /// Args:
+    ///     func: Callable taking the new RECORD as bytes and returning the
+    ///         content of `RECORD.jws` as bytes, or None to stop signing
+    ///
+    /// An exception raised by `func` makes `save` raise RecordError with
+    /// its message.
+    #[pyo3(signature = (func))]
+    fn set_record_signer(&mut self, func: Option<Py<PyAny>>) {
+        let signer = func.map(|func| {
+            RecordSigner::new(move |record| {
+                Python::attach(|py| {
+                    func.call1(py, (PyBytes::new(py, record),))
+                        .and_then(|signature| signature.extract::<Vec<u8>>(py))
+                        .map_err(|e| e.to_string())
+                })
+            })
+        });
+        self.inner.set_record_signer(signer);
+    }
+
+    /// Explain why `save` would not run in constant time.
+    ///
+    /// Returns:
+    ///     List of human-readable reasons (empty if the save is constant-time)
+    fn constant_time_blockers(&self) -> PyResult<Vec<String>> {
+        Ok(self.inner.constant_time_blockers()?)
+    }
+
+    /// Dry-run `save()`: what would be raw-copied, rewritten or removed.
+    ///
+    /// Only the central directory is read and nothing is written.
+    ///
+    /// Returns:
+    ///     A `(dist_info, copied, rewritten, removed)` tuple: the output's
+    ///     dist-info directory, `(source_path, output_path)` pairs copied as
+    ///     raw compressed bytes, `(output_path, reason)` pairs written out
+    ///     again, and source paths left out. `reason` is one of "metadata",
+    ///     "record", "wheel-info", "modified", "elf", "added", "unrecorded"
+    ///     or "reencoded".
+    fn plan(&self) -> PyResult<PlanTuple> {
+        let plan = self.inner.plan()?;
+        let rewritten = plan
+            .rewritten
+            .into_iter()
+            .map(|(path, reason)| (path, reason.as_str()))
+            .collect();
+        Ok((plan.dist_info, plan.copied, rewritten, plan.removed))
+    }
+
+    /// Whether every pending rewrite fits the slot of the member it replaces.
+    ///
+    /// Experimental: this is the precondition for patching the archive in
+    /// place without rewriting it, which is not implemented yet. Added
+    /// files, a renamed dist-info directory, or an unreadable source give
+    /// False.
+    ///
+    /// Returns:
+    ///     True if METADATA, RECORD, WHEEL and all modified files compress to
+    ///     no more than their original compressed sizes
+    fn can_patch_in_place(&self) -> bool {
+        self.inner.can_patch_in_place()
+    }
+
+    /// List every change made since the wheel was opened.
+    ///
+    /// METADATA fields come first (e.g. "Version"), then WHEEL fields
+    /// prefixed "WHEEL:" (e.g. "WHEEL:Tag"), then files as "file:<path>"
+    /// with their old and new sha256 hashes. Multi-value fields join their
+    /// values with newlines.
+    ///
+    /// Returns:
+    ///     List of (field, old, new) tuples; old/new are None when the
+    ///     field or file didn't exist before or after the edit
+    ///
+    /// Raises:
+    ///     IOError: If a file with queued ELF modifications can't be read
+    ///     ElfError: If queued ELF modifications can't be applied
+    fn changes(&self) -> PyResult<Vec<(String, Option<String>, Option<String>)>> {
+        Ok(self
+            .inner
+            .changes()?
+            .into_iter()
+            .map(|c| (c.field, c.old, c.new))
+            .collect())
+    }
+
+    /// Restrict which METADATA fields may change before saving.
+    ///
+    /// Every save checks the edits against the wheel as opened and fails if
+    /// a field outside the allowlist changed.
+    ///
+    /// Args:
+    ///     allowed: Header names that may be edited (e.g. ["Summary",
+    ///         "Classifier"]), matched case-insensitively
+    fn set_editable_fields(&mut self, allowed: Vec<String>) {
+        let allowed: Vec<&str> = allowed.iter().map(|s| s.as_str()).collect();
+        self.inner.set_editable_fields(&allowed);
+    }
+
+    /// Check that only fields allowed by set_editable_fields() have changed.
+    ///
+    /// Raises:
+    ///     ValueError: If a frozen field was edited
+    fn enforce_editable_fields(&self) -> PyResult<()> {
+        Ok(self.inner.enforce_editable_fields()?)
+    }
+
+    /// Dry-run the RECORD rewrite that save() would perform.
+    ///
+    /// Renamed paths (e.g. after a version change) show up as a removal of
+    /// the old path and an addition of the new one.
+    ///
+    /// Returns:
+    ///     List of (path, old_hash, new_hash) tuples for RECORD entries whose
+    ///     hash would change; old_hash is None for added entries and
+    ///     new_hash is None for removed ones
+    ///
+    /// Raises:
+    ///     IOError: If the source wheel cannot be read
+    fn record_hash_changes(&self) -> PyResult<Vec<(String, Option<String>, Option<String>)>> {
+        Ok(self.inner.record_hash_changes()?)
+    }
+
     /// Check if any files have been modified.
     ///
     /// Returns True if any ELF files have been modified (e.g., via set_rpath).
@@ -436,154 +2052,152 @@ impl PyWheelEditor {
     /// Get a metadata value by key.
     ///
     /// Args:
-    ///     key: The metadata field name (e.g., "Author", "License")
+    ///     key: The metadata field name (e.g., "Author", "License"),
+    ///          matched case-insensitively
     ///
     /// Returns:
     ///     The value as a string for single-value fields, or a list of strings
     ///     for multi-value fields. Returns None if the field is not set.
     fn get_metadata(&self, py: Python<'_>, key: &str) -> PyResult<Py<PyAny>> {
-        let metadata = self.inner.metadata();
-
-        // Multi-value fields return lists
-        let multi_value: Option<&Vec<String>> = match key {
-            "Classifier" => Some(&metadata.classifiers),
-            "Platform" => Some(&metadata.platform),
-            "Requires-Dist" => Some(&metadata.requires_dist),
-            "Requires-External" => Some(&metadata.requires_external),
-            "Project-URL" => Some(&metadata.project_url),
-            "Provides-Extra" => Some(&metadata.provides_extra),
-            "Provides-Dist" => Some(&metadata.provides_dist),
-            "Obsoletes-Dist" => Some(&metadata.obsoletes_dist),
-            _ => None,
-        };
-
-        if let Some(values) = multi_value {
-            let list = PyList::new(py, values)?;
-            return Ok(list.into());
-        }
-
-        // Single-value fields return strings or None
-        let single_value: Option<&str> = match key {
-            "Metadata-Version" => Some(&metadata.metadata_version),
-            "Name" => Some(&metadata.name),
-            "Version" => Some(&metadata.version),
-            "Summary" => metadata.summary.as_deref(),
-            "Description" => metadata.description.as_deref(),
-            "Description-Content-Type" => metadata.description_content_type.as_deref(),
-            "Home-page" | "Home-Page" => metadata.home_page.as_deref(),
-            "Download-URL" => metadata.download_url.as_deref(),
-            "Author" => metadata.author.as_deref(),
-            "Author-email" | "Author-Email" => metadata.author_email.as_deref(),
-            "Maintainer" => metadata.maintainer.as_deref(),
-            "Maintainer-email" | "Maintainer-Email" => metadata.maintainer_email.as_deref(),
-            "License" => metadata.license.as_deref(),
-            "Keywords" => metadata.keywords.as_deref(),
-            "Requires-Python" => metadata.requires_python.as_deref(),
-            _ => {
-                // Check extra headers
-                if let Some(values) = metadata.extra_headers.get(key) {
-                    if values.len() == 1 {
-                        return Ok(values[0].clone().into_pyobject(py)?.into_any().unbind());
-                    } else {
-                        let list = PyList::new(py, values)?;
-                        return Ok(list.into());
-                    }
-                }
-                None
-            }
-        };
-
-        match single_value {
-            Some(v) => Ok(v.into_pyobject(py)?.into_any().unbind()),
-            None => Ok(py.None()),
+        match self.inner.metadata().get(key) {
+            MetadataValue::Multi(values) => Ok(PyList::new(py, values)?.into()),
+            MetadataValue::Single(Some(value)) => Ok(value.into_pyobject(py)?.into_any().unbind()),
+            MetadataValue::Single(None) => Ok(py.None()),
         }
     }
 
     /// Set a metadata value by key.
     ///
     /// Args:
-    ///     key: The metadata field name (e.g., "Author", "License")
+    ///     key: The metadata field name (e.g., "Author", "License"),
+    ///          matched case-insensitively
     ///     value: The value to set (string for single-value fields,
-    ///            list of strings for multi-value fields)
+    ///            list of strings for multi-value fields). A string given
+    ///            for a multi-value field is stored as a one-element list,
+    ///            and None removes the field.
+    ///
+    /// Raises:
+    ///     ValueError: If a list is given for a single-value field (unless
+    ///                 it has exactly one element), or None for
+    ///                 Metadata-Version, Name or Version
     fn set_metadata(&mut self, py: Python<'_>, key: &str, value: Py<PyAny>) -> PyResult<()> {
-        let metadata = self.inner.metadata_mut();
-
-        // Check if it's a list (multi-value field)
-        if let Ok(list) = value.downcast_bound::<PyList>(py) {
-            let values: Vec<String> = list.extract()?;
-
-            match key {
-                "Classifier" => metadata.classifiers = values,
-                "Platform" => metadata.platform = values,
-                "Requires-Dist" => metadata.requires_dist = values,
-                "Requires-External" => metadata.requires_external = values,
-                "Project-URL" => metadata.project_url = values,
-                "Provides-Extra" => metadata.provides_extra = values,
-                "Provides-Dist" => metadata.provides_dist = values,
-                "Obsoletes-Dist" => metadata.obsoletes_dist = values,
-                _ => {
-                    metadata.extra_headers.insert(key.to_string(), values);
-                }
-            }
-            return Ok(());
-        }
+        let value = if value.is_none(py) {
+            MetadataValue::Single(None)
+        } else if let Ok(list) = value.downcast_bound::<PyList>(py) {
+            MetadataValue::Multi(list.extract()?)
+        } else {
+            MetadataValue::Single(Some(value.extract(py)?))
+        };
+        self.inner
+            .metadata_mut()
+            .set(key, value)
+            .map_err(WheelError::from)?;
+        Ok(())
+    }
+
+    /// Remove a metadata field by key.
+    ///
+    /// Args:
+    ///     key: The metadata field name (e.g., "Home-page"), matched
+    ///          case-insensitively. Unknown fields are removed from the
+    ///          extra headers.
+    ///
+    /// Returns:
+    ///     True if the field had a value
+    ///
+    /// Raises:
+    ///     ValueError: If the field is Metadata-Version, Name or Version
+    fn remove_metadata(&mut self, key: &str) -> PyResult<bool> {
+        Ok(self
+            .inner
+            .metadata_mut()
+            .remove_field(key)
+            .map_err(WheelError::from)?)
+    }
 
-        // Single value
-        let str_value: String = value.extract(py)?;
-
-        match key {
-            "Metadata-Version" => metadata.metadata_version = str_value,
-            "Name" => metadata.name = str_value,
-            "Version" => metadata.version = str_value,
-            "Summary" => metadata.summary = Some(str_value),
-            "Description" => metadata.description = Some(str_value),
-            "Description-Content-Type" => metadata.description_content_type = Some(str_value),
-            "Home-page" | "Home-Page" => metadata.home_page = Some(str_value),
-            "Download-URL" => metadata.download_url = Some(str_value),
-            "Author" => metadata.author = Some(str_value),
-            "Author-email" | "Author-Email" => metadata.author_email = Some(str_value),
-            "Maintainer" => metadata.maintainer = Some(str_value),
-            "Maintainer-email" | "Maintainer-Email" => metadata.maintainer_email = Some(str_value),
-            "License" => metadata.license = Some(str_value),
-            "Keywords" => metadata.keywords = Some(str_value),
-            "Requires-Python" => metadata.requires_python = Some(str_value),
+    /// Save the edited wheel with updated metadata.
+    ///
+    /// Args:
+    ///     output_path: Path for the output wheel. If None, or the path of
+    ///                  the wheel being edited, a temporary file is created
+    ///                  and then moved to overwrite the original, keeping the
+    ///                  original's permissions (and, where allowed,
+    ///                  ownership).
+    ///
+    /// Raises:
+    ///     IOError: If the wheel cannot be saved
+    #[pyo3(signature = (output_path = None))]
+    fn save(&mut self, output_path: Option<&str>) -> PyResult<()> {
+        match output_path {
+            Some(path) if !self.inner.is_source_path(Path::new(path)) => {
+                self.inner.save(path)?;
+                Ok(())
+            }
             _ => {
-                metadata
-                    .extra_headers
-                    .insert(key.to_string(), vec![str_value]);
+                self.inner.save_in_place()?;
+                Ok(())
             }
         }
-
-        Ok(())
     }
 
-    /// Save the edited wheel with updated metadata.
+    /// Save the edited wheel into a directory, named after its current
+    /// metadata and tags (see `filename`).
     ///
     /// Args:
-    ///     output_path: Path for the output wheel. If None, a temporary file
-    ///                  is created and then moved to overwrite the original.
+    ///     directory: Directory to write the wheel into
+    ///
+    /// Returns:
+    ///     The path of the saved wheel
     ///
     /// Raises:
     ///     IOError: If the wheel cannot be saved
+    fn save_with_generated_name(&self, directory: &str) -> PyResult<String> {
+        let path = self.inner.save_with_generated_name(directory)?;
+        Ok(path.to_string_lossy().into_owned())
+    }
+
+    /// Save the edited wheel and fsync it to stable storage.
+    ///
+    /// Like `save`, but the file (and on Unix its directory) is fsynced
+    /// before returning, so a crash can't leave a partially-written wheel
+    /// that looks complete. Each fsync waits for the device, which can be
+    /// much slower than the save itself; use `save` when durability isn't
+    /// needed.
+    ///
+    /// Args:
+    ///     output_path: Path for the output wheel. If None, or the path of
+    ///                  the wheel being edited, a temporary file is durably
+    ///                  written and then moved to overwrite the original.
+    ///
+    /// Raises:
+    ///     IOError: If the wheel cannot be saved or synced
     #[pyo3(signature = (output_path = None))]
-    fn save(&self, output_path: Option<&str>) -> PyResult<()> {
+    fn save_durable(&mut self, output_path: Option<&str>) -> PyResult<()> {
         match output_path {
-            Some(path) => {
-                self.inner.save(path)?;
+            Some(path) if !self.inner.is_source_path(Path::new(path)) => {
+                self.inner.save_durable(path)?;
                 Ok(())
             }
-            None => {
-                // Save to a temp file, then overwrite original
-                // Get the original path from the inner editor
-                let original_path = self.get_wheel_path();
-                let temp_path = format!("{}.tmp", original_path);
-                self.inner.save(&temp_path)?;
-                std::fs::rename(&temp_path, &original_path)?;
+            _ => {
+                self.inner.save_in_place_durable()?;
                 Ok(())
             }
         }
     }
 
+    /// Save the modified wheel into memory instead of a file.
+    ///
+    /// Returns:
+    ///     The complete wheel archive as bytes
+    ///
+    /// Raises:
+    ///     IOError: If the wheel cannot be written
+    fn save_to_bytes<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyBytes>> {
+        let mut buffer = std::io::Cursor::new(Vec::new());
+        self.inner.save_to_writer(&mut buffer)?;
+        Ok(PyBytes::new(py, buffer.get_ref()))
+    }
+
     /// Get the path to the wheel file
     fn get_wheel_path(&self) -> String {
         // Access the path from the inner struct
@@ -620,6 +2234,95 @@ fn normalize_dist_info_name(name: &str) -> String {
     rust_normalize_dist_info_name(name)
 }
 
+/// Normalize a wheel filename into a dedup key.
+///
+/// The name is PEP 503-normalized and each compressed tag set is sorted, so
+/// "Foo-1.0-py3-none-any.whl" and "foo-1.0-py3-none-any.whl" give the same
+/// key. This is a heuristic for spotting duplicate uploads, not the filename
+/// to install under.
+///
+/// Args:
+///     filename: A PEP 427 wheel filename (no directory part)
+///
+/// Returns:
+///     The canonical filename
+///
+/// Raises:
+///     ValueError: If the filename is not a valid wheel filename
+#[pyfunction]
+fn canonical_wheel_filename(filename: &str) -> PyResult<String> {
+    let parsed = WheelFilename::parse(filename).map_err(WheelError::from)?;
+    Ok(parsed.canonical())
+}
+
+/// Diff the METADATA of two wheels, skipping the given fields.
+///
+/// Meant for release checks such as "the Linux and macOS wheels of 1.2.3
+/// carry the same metadata". Only METADATA is compared, not WHEEL tags or
+/// files.
+///
+/// Args:
+///     a: Path to the first wheel
+///     b: Path to the second wheel
+///     ignore: Header names to skip (e.g. ["Platform"]), matched
+///         case-insensitively
+///
+/// Returns:
+///     List of (field, value in a, value in b) tuples; an empty list means
+///     the metadata is equal
+///
+/// Raises:
+///     IOError: If either wheel cannot be read
+#[pyfunction]
+#[pyo3(signature = (a, b, ignore = Vec::new()))]
+fn metadata_differences(
+    a: &str,
+    b: &str,
+    ignore: Vec<String>,
+) -> PyResult<Vec<(String, Option<String>, Option<String>)>> {
+    let ignore: Vec<&str> = ignore.iter().map(String::as_str).collect();
+    let diff = rust_metadata_differences(Path::new(a), Path::new(b), &ignore)?;
+    Ok(diff.into_iter().map(|c| (c.field, c.old, c.new)).collect())
+}
+
+/// Check whether two wheels carry the same METADATA, skipping the given
+/// fields.
+///
+/// `metadata_differences` lists what differs.
+///
+/// Args:
+///     a: Path to the first wheel
+///     b: Path to the second wheel
+///     ignore: Header names to skip (e.g. ["Platform"]), matched
+///         case-insensitively
+///
+/// Returns:
+///     True if no other field differs
+///
+/// Raises:
+///     IOError: If either wheel cannot be read
+#[pyfunction]
+#[pyo3(signature = (a, b, ignore = Vec::new()))]
+fn metadata_matches(a: &str, b: &str, ignore: Vec<String>) -> PyResult<bool> {
+    let ignore: Vec<&str> = ignore.iter().map(String::as_str).collect();
+    Ok(rust_metadata_matches(Path::new(a), Path::new(b), &ignore)?)
+}
+
+/// Build an RFC 5322 address list for Author-email or Maintainer-email.
+///
+/// Names containing specials such as commas or dots are quoted, so the
+/// result parses back to the same pairs.
+///
+/// Args:
+///     addresses: List of (name or None, address) tuples
+///
+/// Returns:
+///     The combined string, e.g. '"Doe, Jane" <jane@example.org>, team@example.org'
+#[pyfunction]
+fn format_address_list(addresses: Vec<(Option<String>, String)>) -> String {
+    rust_format_address_list(&addresses)
+}
+
 /// editwheel: High-performance Python wheel metadata editor
 ///
 /// This module provides a fast way to edit Python wheel metadata without
@@ -629,6 +2332,36 @@ fn normalize_dist_info_name(name: &str) -> String {
 fn editwheel(m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_class::<PyWheelEditor>()?;
     m.add_class::<PyValidationResult>()?;
+    m.add_class::<PyValidationIssue>()?;
     m.add_function(wrap_pyfunction!(normalize_dist_info_name, m)?)?;
+    m.add_function(wrap_pyfunction!(canonical_wheel_filename, m)?)?;
+    m.add_function(wrap_pyfunction!(metadata_differences, m)?)?;
+    m.add_function(wrap_pyfunction!(metadata_matches, m)?)?;
+    m.add_function(wrap_pyfunction!(format_address_list, m)?)?;
+    let py = m.py();
+    m.add(
+        "EditwheelError",
+        py.get_type::<exceptions::EditwheelError>(),
+    )?;
+    m.add(
+        "InvalidWheelError",
+        py.get_type::<exceptions::InvalidWheelError>(),
+    )?;
+    m.add("MetadataError", py.get_type::<exceptions::MetadataError>())?;
+    m.add("RecordError", py.get_type::<exceptions::RecordError>())?;
+    m.add("ElfError", py.get_type::<exceptions::ElfError>())?;
+    m.add(
+        "WheelInfoError",
+        py.get_type::<exceptions::WheelInfoError>(),
+    )?;
+    m.add(
+        "EntryPointsError",
+        py.get_type::<exceptions::EntryPointsError>(),
+    )?;
+    m.add(
+        "ArchiveLimitError",
+        py.get_type::<exceptions::ArchiveLimitError>(),
+    )?;
+    m.add("PatternError", py.get_type::<exceptions::PatternError>())?;
     Ok(())
 }
@@ -2,7 +2,10 @@
 
 mod types;
 
+pub use types::HashAlgorithm;
 pub use types::Record;
 pub use types::RecordEntry;
 pub use types::hash_content;
+pub use types::hash_content_with;
 pub use types::hash_reader;
+pub use types::hash_reader_with;
@@ -4,13 +4,18 @@ use std::io::Read;
 
 use base64::Engine;
 use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use blake2::Blake2b512;
+use blake2::Blake2s256;
 use sha2::Digest;
 use sha2::Sha256;
+use sha2::Sha384;
+use sha2::Sha512;
 
 use crate::error::RecordError;
 
 /// Single entry in RECORD file
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct RecordEntry {
     pub path: String,
     pub hash: Option<String>,
@@ -24,15 +29,50 @@ impl RecordEntry {
 }
 
 /// Complete RECORD file
+///
+/// With the `serde` feature this serializes as the bare list of entries.
 #[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
 pub struct Record {
     pub entries: Vec<RecordEntry>,
 }
 
 impl Record {
     /// Parse RECORD from CSV content
+    ///
+    /// A size field that is present but not a valid `u64` (e.g. `-1`) is
+    /// treated as absent. Use `parse_strict` to reject it instead.
     pub fn parse(content: &str) -> Result<Self, RecordError> {
+        Ok(Self::parse_impl(content)?.0)
+    }
+
+    /// Parse RECORD from CSV content, rejecting malformed size fields.
+    ///
+    /// Unlike `parse`, a size field that is present but doesn't parse as a
+    /// `u64` returns `RecordError::InvalidSize` instead of silently becoming
+    /// `None`, so corrupt RECORDs aren't masked. Only the first malformed
+    /// size is reported; `invalid_sizes` lists all of them.
+    pub fn parse_strict(content: &str) -> Result<Self, RecordError> {
+        let (record, mut invalid) = Self::parse_impl(content)?;
+        if invalid.is_empty() {
+            Ok(record)
+        } else {
+            Err(invalid.swap_remove(0))
+        }
+    }
+
+    /// Every malformed size field in RECORD content
+    ///
+    /// Returns one `RecordError::InvalidSize` per entry whose size is
+    /// present but doesn't parse as a `u64`, in file order.
+    pub fn invalid_sizes(content: &str) -> Result<Vec<RecordError>, RecordError> {
+        Ok(Self::parse_impl(content)?.1)
+    }
+
+    fn parse_impl(content: &str) -> Result<(Self, Vec<RecordError>), RecordError> {
         let mut entries = Vec::new();
+        let mut invalid = Vec::new();
         let mut reader = csv::ReaderBuilder::new()
             .has_headers(false)
             .flexible(true)
@@ -51,15 +91,24 @@ impl Record {
                 .filter(|s| !s.is_empty())
                 .map(|s| s.to_string());
 
-            let size = record
-                .get(2)
-                .filter(|s| !s.is_empty())
-                .and_then(|s| s.parse().ok());
+            let size = match record.get(2).filter(|s| !s.is_empty()) {
+                Some(s) => match s.parse() {
+                    Ok(size) => Some(size),
+                    Err(_) => {
+                        invalid.push(RecordError::InvalidSize {
+                            path: path.clone(),
+                            value: s.to_string(),
+                        });
+                        None
+                    }
+                },
+                None => None,
+            };
 
             entries.push(RecordEntry { path, hash, size });
         }
 
-        Ok(Record { entries })
+        Ok((Record { entries }, invalid))
     }
 
     /// Serialize RECORD to CSV format
@@ -90,18 +139,92 @@ impl Record {
     }
 }
 
+/// Digest algorithm of a RECORD hash
+///
+/// PEP 376 allows any algorithm in `hashlib.algorithms_guaranteed`; these
+/// are the ones PEP 427 still accepts. The name is the prefix of the hash
+/// (`sha512=<base64url_no_padding>`).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum HashAlgorithm {
+    #[default]
+    Sha256,
+    Sha384,
+    Sha512,
+    /// BLAKE2b with a 64-byte digest, as `hashlib.blake2b()` defaults to
+    Blake2b,
+    /// BLAKE2s with a 32-byte digest, as `hashlib.blake2s()` defaults to
+    Blake2s,
+}
+
+impl HashAlgorithm {
+    /// Every supported algorithm
+    pub const ALL: [HashAlgorithm; 5] = [
+        HashAlgorithm::Sha256,
+        HashAlgorithm::Sha384,
+        HashAlgorithm::Sha512,
+        HashAlgorithm::Blake2b,
+        HashAlgorithm::Blake2s,
+    ];
+
+    /// Name as used in RECORD and by `hashlib`
+    pub fn name(&self) -> &'static str {
+        match self {
+            HashAlgorithm::Sha256 => "sha256",
+            HashAlgorithm::Sha384 => "sha384",
+            HashAlgorithm::Sha512 => "sha512",
+            HashAlgorithm::Blake2b => "blake2b",
+            HashAlgorithm::Blake2s => "blake2s",
+        }
+    }
+
+    /// Look up an algorithm by name, case-insensitively
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|algorithm| algorithm.name().eq_ignore_ascii_case(name))
+    }
+
+    /// The algorithm a RECORD hash (`<name>=<digest>`) was computed with
+    ///
+    /// Returns `None` for a hash with no `=` or an unsupported algorithm.
+    pub fn of_hash(hash: &str) -> Option<Self> {
+        let (name, _) = hash.split_once('=')?;
+        Self::from_name(name)
+    }
+}
+
 /// Compute SHA256 hash in wheel format: sha256=<base64url_no_padding>
 pub fn hash_content(content: &[u8]) -> String {
-    let mut hasher = Sha256::new();
-    hasher.update(content);
-    let digest = hasher.finalize();
-    let encoded = URL_SAFE_NO_PAD.encode(&digest);
-    format!("sha256={}", encoded)
+    hash_content_with(content, HashAlgorithm::Sha256)
+}
+
+/// Compute a hash in wheel format with the given algorithm
+pub fn hash_content_with(content: &[u8], algorithm: HashAlgorithm) -> String {
+    // Reading from a slice can't fail
+    hash_reader_with(content, algorithm).expect("reading from a slice")
 }
 
 /// Compute SHA256 hash of a reader's contents
-pub fn hash_reader<R: Read>(mut reader: R) -> std::io::Result<String> {
-    let mut hasher = Sha256::new();
+pub fn hash_reader<R: Read>(reader: R) -> std::io::Result<String> {
+    hash_reader_with(reader, HashAlgorithm::Sha256)
+}
+
+/// Compute a hash of a reader's contents with the given algorithm
+pub fn hash_reader_with<R: Read>(reader: R, algorithm: HashAlgorithm) -> std::io::Result<String> {
+    let digest = match algorithm {
+        HashAlgorithm::Sha256 => digest_reader::<Sha256, R>(reader)?,
+        HashAlgorithm::Sha384 => digest_reader::<Sha384, R>(reader)?,
+        HashAlgorithm::Sha512 => digest_reader::<Sha512, R>(reader)?,
+        HashAlgorithm::Blake2b => digest_reader::<Blake2b512, R>(reader)?,
+        HashAlgorithm::Blake2s => digest_reader::<Blake2s256, R>(reader)?,
+    };
+    let encoded = URL_SAFE_NO_PAD.encode(&digest);
+    Ok(format!("{}={}", algorithm.name(), encoded))
+}
+
+/// Feed a reader through digest `D` and return the raw digest bytes
+fn digest_reader<D: Digest, R: Read>(mut reader: R) -> std::io::Result<Vec<u8>> {
+    let mut hasher = D::new();
     let mut buffer = [0u8; 8192];
 
     loop {
@@ -112,15 +235,53 @@ pub fn hash_reader<R: Read>(mut reader: R) -> std::io::Result<String> {
         hasher.update(&buffer[..bytes_read]);
     }
 
-    let digest = hasher.finalize();
-    let encoded = URL_SAFE_NO_PAD.encode(&digest);
-    Ok(format!("sha256={}", encoded))
+    Ok(hasher.finalize().to_vec())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_hash_content_with() {
+        // Digests of b"abc", cross-checked against Python's hashlib
+        let expected = [
+            (
+                HashAlgorithm::Sha256,
+                "sha256=ungWv48Bz-pBQUDeXa4iI7ADYaOWF3qctBD_YfIAFa0",
+            ),
+            (
+                HashAlgorithm::Sha384,
+                "sha384=ywB1P0WjXou1oD1pmsZQBycsMqsO3tFjGotgWkP_W-2AhgcroefMI1i67KE0yCWn",
+            ),
+            (
+                HashAlgorithm::Sha512,
+                "sha512=3a81oZNherrMQXNJriBBMRLm-k6JqX6iCp7u5ktV05ohkpkqJ0_BqDa6PCOj_uu9RU1EI2Q86A4qmslPpUyknw",
+            ),
+            (
+                HashAlgorithm::Blake2b,
+                "blake2b=uoClP5gcTQ1qJ5e2nxL26UwhLxRoWsS3SxK7b9v_otF9h8U5Kqt5LcJS1d5FM8yVGNOKqNvxklq5I4bt1ACZIw",
+            ),
+            (
+                HashAlgorithm::Blake2s,
+                "blake2s=UIxejDJ8FOLhpyujTutFLzdFiyCe1jopTZmbTIZnWYI",
+            ),
+        ];
+        for (algorithm, hash) in expected {
+            assert_eq!(hash_content_with(b"abc", algorithm), hash);
+            assert_eq!(hash_reader_with(&b"abc"[..], algorithm).unwrap(), hash);
+            assert_eq!(HashAlgorithm::of_hash(hash), Some(algorithm));
+        }
+        assert_eq!(hash_content(b"abc"), expected[0].1);
+
+        assert_eq!(
+            HashAlgorithm::from_name("SHA512"),
+            Some(HashAlgorithm::Sha512)
+        );
+        assert_eq!(HashAlgorithm::of_hash("md5=abc"), None);
+        assert_eq!(HashAlgorithm::of_hash("abc"), None);
+    }
+
     #[test]
     fn test_hash_content() {
         let content = b"Hello, World!";
@@ -146,6 +307,53 @@ test_package-1.0.0.dist-info/RECORD,,"#;
         assert!(record.entries[2].hash.is_none());
     }
 
+    #[test]
+    fn test_record_parse_strict_rejects_bogus_size() {
+        for bogus in ["-1", "999999999999999999999", "12abc"] {
+            let content = format!("pkg/__init__.py,sha256=abc,{bogus}\npkg/RECORD,,\n");
+
+            // Lenient parsing keeps the historical behavior.
+            let record = Record::parse(&content).unwrap();
+            assert_eq!(record.entries[0].size, None);
+
+            match Record::parse_strict(&content) {
+                Err(RecordError::InvalidSize { path, value }) => {
+                    assert_eq!(path, "pkg/__init__.py");
+                    assert_eq!(value, bogus);
+                }
+                other => panic!("expected InvalidSize for {bogus}, got {other:?}"),
+            }
+        }
+    }
+
+    #[test]
+    fn test_record_invalid_sizes_lists_every_entry() {
+        let content = "a.py,sha256=abc,-1\nb.py,sha256=abc,10\nc.py,sha256=abc,x\n";
+        let invalid = Record::invalid_sizes(content).unwrap();
+        let paths: Vec<_> = invalid
+            .iter()
+            .map(|e| match e {
+                RecordError::InvalidSize { path, .. } => path.as_str(),
+                other => panic!("expected InvalidSize, got {other:?}"),
+            })
+            .collect();
+        assert_eq!(paths, ["a.py", "c.py"]);
+
+        // parse_strict still fails on the first one
+        assert!(matches!(
+            Record::parse_strict(content),
+            Err(RecordError::InvalidSize { path, .. }) if path == "a.py"
+        ));
+    }
+
+    #[test]
+    fn test_record_parse_strict_accepts_valid() {
+        let content = "pkg/__init__.py,sha256=abc,18446744073709551615\npkg/RECORD,,\n";
+        let record = Record::parse_strict(content).unwrap();
+        assert_eq!(record.entries[0].size, Some(u64::MAX));
+        assert_eq!(record.entries[1].size, None);
+    }
+
     #[test]
     fn test_record_roundtrip() {
         let original = r#"test/__init__.py,sha256=abc,10
@@ -0,0 +1,42 @@
+//! Comparing wheels built from the same release
+
+use std::fs::File;
+use std::io::BufReader;
+use std::path::Path;
+
+use crate::changes::FieldChange;
+use crate::changes::metadata_diff;
+use crate::error::WheelError;
+use crate::metadata::Metadata;
+
+use super::reader::WheelReader;
+
+/// Diff the METADATA of the wheels at `a` and `b`, skipping the fields
+/// named in `ignore`
+///
+/// Meant for release checks such as "the Linux and macOS wheels of 1.2.3
+/// carry the same metadata", with platform-specific fields (e.g.
+/// `Platform`) ignored. Only METADATA is compared, not WHEEL tags or
+/// files. Returns the differing fields (see `metadata_diff`), so an empty
+/// list means the metadata is equal.
+pub fn metadata_differences(
+    a: &Path,
+    b: &Path,
+    ignore: &[&str],
+) -> Result<Vec<FieldChange>, WheelError> {
+    let (a, b) = (read_metadata(a)?, read_metadata(b)?);
+    Ok(metadata_diff(&a, &b, ignore))
+}
+
+/// Whether the wheels at `a` and `b` carry the same METADATA, skipping the
+/// fields named in `ignore`
+///
+/// `metadata_differences` lists what differs when this returns `false`.
+pub fn metadata_matches(a: &Path, b: &Path, ignore: &[&str]) -> Result<bool, WheelError> {
+    Ok(metadata_differences(a, b, ignore)?.is_empty())
+}
+
+/// Read only METADATA, without the RECORD and WHEEL `WheelEditor::open` parses
+fn read_metadata(path: &Path) -> Result<Metadata, WheelError> {
+    WheelReader::new(BufReader::new(File::open(path)?))?.read_metadata()
+}
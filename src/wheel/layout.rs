@@ -0,0 +1,114 @@
+//! Install layout checks - verify every member maps to an install location
+
+/// Schemes allowed under `<name>-<version>.data/` (PEP 427)
+const DATA_SCHEMES: &[&str] = &["purelib", "platlib", "headers", "scripts", "data"];
+
+/// Extensions of modules that may legitimately live at the archive root
+/// (e.g. `six.py`, `_cffi_backend.cpython-312-x86_64-linux-gnu.so`, `.pth` hooks)
+const ROOT_MODULE_SUFFIXES: &[&str] = &[".py", ".pyi", ".so", ".pyd", ".pth"];
+
+/// Find archive members that don't map cleanly to an install location.
+///
+/// Members are expected to be one of:
+/// - inside the wheel's own `.dist-info` directory
+/// - inside its `.data/<scheme>/` directory, where scheme is one of
+///   purelib, platlib, headers, scripts, or data
+/// - inside a package directory (installed to purelib/platlib according to
+///   `Root-Is-Purelib`)
+/// - a top-level module file (`.py`, `.so`, `.pth`, ...)
+///
+/// Anything else, such as a stray `README.md` at the archive root, a second
+/// `.dist-info` directory, an unknown `.data` scheme, or a path that escapes
+/// the install root, is returned. Directory entries are ignored.
+pub fn check_install_layout<'a>(
+    names: impl IntoIterator<Item = &'a str>,
+    dist_info_prefix: &str,
+) -> Vec<String> {
+    let data_dir = format!(
+        "{}.data",
+        dist_info_prefix
+            .strip_suffix(".dist-info")
+            .unwrap_or(dist_info_prefix)
+    );
+
+    let mut problems = Vec::new();
+    for name in names {
+        if name.ends_with('/') {
+            continue;
+        }
+        if !maps_to_install_location(name, dist_info_prefix, &data_dir) {
+            problems.push(name.to_string());
+        }
+    }
+    problems
+}
+
+fn maps_to_install_location(name: &str, dist_info_prefix: &str, data_dir: &str) -> bool {
+    if name.starts_with('/') || name.split('/').any(|part| part == "..") {
+        return false;
+    }
+
+    let Some((top, rest)) = name.split_once('/') else {
+        // File at the archive root: only top-level modules are installable
+        return ROOT_MODULE_SUFFIXES.iter().any(|ext| name.ends_with(ext));
+    };
+
+    if top == dist_info_prefix {
+        return true;
+    }
+    if top == data_dir {
+        return match rest.split_once('/') {
+            Some((scheme, file)) => DATA_SCHEMES.contains(&scheme) && !file.is_empty(),
+            None => false,
+        };
+    }
+
+    // Any other .dist-info / .data directory belongs to a different
+    // distribution and would confuse installers.
+    !(top.ends_with(".dist-info") || top.ends_with(".data"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DIST_INFO: &str = "pkg-1.0.0.dist-info";
+
+    #[test]
+    fn test_well_formed_layout() {
+        let names = [
+            "pkg/",
+            "pkg/__init__.py",
+            "pkg/lib/libfoo.so",
+            "six.py",
+            "pkg-1.0.0.data/scripts/tool",
+            "pkg-1.0.0.data/headers/pkg.h",
+            "pkg-1.0.0.dist-info/METADATA",
+            "pkg-1.0.0.dist-info/RECORD",
+        ];
+        assert!(check_install_layout(names, DIST_INFO).is_empty());
+    }
+
+    #[test]
+    fn test_malformed_layout() {
+        let names = [
+            "pkg/__init__.py",
+            "README.md",
+            "pkg-1.0.0.data/bogus/file.txt",
+            "pkg-1.0.0.data/loose.txt",
+            "other-2.0.dist-info/METADATA",
+            "pkg/../../etc/passwd",
+            "pkg-1.0.0.dist-info/METADATA",
+        ];
+        assert_eq!(
+            check_install_layout(names, DIST_INFO),
+            vec![
+                "README.md",
+                "pkg-1.0.0.data/bogus/file.txt",
+                "pkg-1.0.0.data/loose.txt",
+                "other-2.0.dist-info/METADATA",
+                "pkg/../../etc/passwd",
+            ]
+        );
+    }
+}
@@ -0,0 +1,77 @@
+//! Resource limits for opening untrusted wheels
+
+use std::io::Read;
+use std::io::Seek;
+
+use zip::ZipArchive;
+
+use crate::error::WheelError;
+
+/// Limits an archive must stay within to be opened
+///
+/// Every check uses the sizes declared in the central directory, so nothing
+/// is decompressed. The default has no limits.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct OpenLimits {
+    /// Maximum number of entries in the archive
+    pub max_entries: Option<usize>,
+    /// Maximum sum of the declared uncompressed sizes of all entries
+    pub max_total_uncompressed: Option<u64>,
+    /// Maximum uncompressed-to-compressed size ratio of any single entry
+    pub max_ratio: Option<f64>,
+}
+
+/// Check an archive against `limits`
+///
+/// Returns `WheelError::LimitExceeded` naming the first limit exceeded.
+pub fn check_limits<R: Read + Seek>(
+    archive: &mut ZipArchive<R>,
+    limits: &OpenLimits,
+) -> Result<(), WheelError> {
+    if let Some(max) = limits.max_entries {
+        if archive.len() > max {
+            return Err(WheelError::LimitExceeded {
+                limit: "max_entries",
+                reason: format!("{} entries, limit is {}", archive.len(), max),
+            });
+        }
+    }
+    if limits.max_total_uncompressed.is_none() && limits.max_ratio.is_none() {
+        return Ok(());
+    }
+
+    let mut total: u64 = 0;
+    for i in 0..archive.len() {
+        let entry = archive.by_index_raw(i)?;
+        total = total.saturating_add(entry.size());
+
+        if let Some(max) = limits.max_ratio {
+            // A zero-byte payload counts as one byte to avoid dividing by zero
+            let ratio = entry.size() as f64 / entry.compressed_size().max(1) as f64;
+            if ratio > max {
+                return Err(WheelError::LimitExceeded {
+                    limit: "max_ratio",
+                    reason: format!(
+                        "'{}' expands {}x ({} -> {} bytes), limit is {}x",
+                        entry.name(),
+                        ratio.round(),
+                        entry.compressed_size(),
+                        entry.size(),
+                        max
+                    ),
+                });
+            }
+        }
+    }
+
+    if let Some(max) = limits.max_total_uncompressed {
+        if total > max {
+            return Err(WheelError::LimitExceeded {
+                limit: "max_total_uncompressed",
+                reason: format!("{} bytes uncompressed, limit is {}", total, max),
+            });
+        }
+    }
+
+    Ok(())
+}
@@ -1,10 +1,36 @@
 //! Wheel reading, writing, and validation
 
+mod compare;
+mod layout;
+mod limits;
 mod reader;
+mod safety;
 mod validator;
 mod writer;
 
+pub use compare::metadata_differences;
+pub use compare::metadata_matches;
+pub use layout::check_install_layout;
+pub use limits::OpenLimits;
+pub use limits::check_limits;
 pub use reader::WheelReader;
+pub use safety::check_safe_paths;
+pub use validator::validate_record_control_files;
 pub use validator::validate_wheel;
+pub use validator::validate_wheel_against;
 pub use writer::write_modified;
+pub use writer::DEFLATE_LEVELS;
+pub use writer::DOS_EPOCH;
+pub use writer::RECORD_SIGNATURES;
+pub use writer::RecordSigner;
+pub use writer::WriteOptions;
+pub use writer::project_record;
 pub use writer::write_modified_extended;
+pub use writer::write_modified_with_options;
+
+pub(crate) use writer::check_compression_level;
+pub(crate) use writer::clear_hashless_entries;
+pub(crate) use writer::is_record_signature;
+pub(crate) use writer::rename_package_path;
+pub(crate) use writer::renamed_path;
+pub(crate) use writer::split_package_path;
@@ -2,6 +2,7 @@
 
 use std::io::Read;
 use std::io::Seek;
+use std::io::SeekFrom;
 
 use zip::ZipArchive;
 
@@ -10,6 +11,86 @@ use crate::metadata::Metadata;
 use crate::record::Record;
 use crate::wheel_info::WheelInfo;
 
+use super::limits::OpenLimits;
+use super::limits::check_limits;
+
+/// gzip member header (RFC 1952)
+const GZIP_MAGIC: &[u8] = &[0x1f, 0x8b];
+/// xz stream header
+const XZ_MAGIC: &[u8] = &[0xfd, b'7', b'z', b'X', b'Z', 0x00];
+/// POSIX tar stores "ustar" at this offset of the first header block
+const TAR_MAGIC_OFFSET: usize = 257;
+const TAR_MAGIC: &[u8] = b"ustar";
+
+/// End-of-central-directory record signature
+const EOCD_SIGNATURE: &[u8] = b"PK\x05\x06";
+/// Size of the EOCD record without its trailing comment
+const EOCD_MIN_SIZE: u64 = 22;
+/// Longest possible archive comment after the EOCD record
+const MAX_COMMENT_LEN: u64 = u16::MAX as u64;
+
+/// Check whether the input ends with a ZIP end-of-central-directory record.
+///
+/// Looks at the last 22 bytes first (the common no-comment case) and only
+/// scans the maximal comment window if that misses. Returns the presence of
+/// the record and the input length, leaving the reader at its original
+/// position.
+fn find_eocd<R: Read + Seek>(reader: &mut R) -> Result<(bool, u64), WheelError> {
+    let start = reader.stream_position()?;
+    let len = reader.seek(SeekFrom::End(0))?;
+
+    let mut found = false;
+    for window in [EOCD_MIN_SIZE, EOCD_MIN_SIZE + MAX_COMMENT_LEN] {
+        let tail_len = len.min(window);
+        reader.seek(SeekFrom::Start(len - tail_len))?;
+        let mut tail = vec![0u8; tail_len as usize];
+        reader.read_exact(&mut tail)?;
+        if tail
+            .windows(EOCD_SIGNATURE.len())
+            .any(|w| w == EOCD_SIGNATURE)
+        {
+            found = true;
+            break;
+        }
+    }
+
+    reader.seek(SeekFrom::Start(start))?;
+    Ok((found, len))
+}
+
+/// Sniff the leading bytes for container formats that are commonly mistaken
+/// for wheels (e.g. a wheel wrapped in gzip/tar for transport).
+///
+/// Returns the detected format name, leaving the reader at its original
+/// position.
+fn detect_wrapped_archive<R: Read + Seek>(
+    reader: &mut R,
+) -> Result<Option<&'static str>, WheelError> {
+    let start = reader.stream_position()?;
+    let mut header = [0u8; TAR_MAGIC_OFFSET + 5];
+    let mut filled = 0;
+    while filled < header.len() {
+        let n = reader.read(&mut header[filled..])?;
+        if n == 0 {
+            break;
+        }
+        filled += n;
+    }
+    reader.seek(SeekFrom::Start(start))?;
+
+    let header = &header[..filled];
+    let kind = if header.starts_with(GZIP_MAGIC) {
+        Some("gzip")
+    } else if header.starts_with(XZ_MAGIC) {
+        Some("xz")
+    } else if header.get(TAR_MAGIC_OFFSET..) == Some(TAR_MAGIC) {
+        Some("tar")
+    } else {
+        None
+    };
+    Ok(kind)
+}
+
 /// Reader for Python wheel files
 pub struct WheelReader<R: Read + Seek> {
     archive: ZipArchive<R>,
@@ -18,7 +99,27 @@ pub struct WheelReader<R: Read + Seek> {
 
 impl<R: Read + Seek> WheelReader<R> {
     /// Create a new wheel reader from a reader
-    pub fn new(reader: R) -> Result<Self, WheelError> {
+    ///
+    /// Inputs that look like gzip, xz, or tar containers are rejected up
+    /// front with `WheelError::InvalidWheel` rather than the opaque ZIP error
+    /// they would otherwise produce. So are inputs without an
+    /// end-of-central-directory record, which almost always means a
+    /// truncated download.
+    pub fn new(mut reader: R) -> Result<Self, WheelError> {
+        if let Some(kind) = detect_wrapped_archive(&mut reader)? {
+            return Err(WheelError::InvalidWheel(format!(
+                "file appears to be {}-compressed; decompress before opening",
+                kind
+            )));
+        }
+        let (has_eocd, len) = find_eocd(&mut reader)?;
+        if !has_eocd {
+            return Err(WheelError::InvalidWheel(format!(
+                "archive appears truncated or is not a zip (no end-of-central-directory record); file is {} bytes",
+                len
+            )));
+        }
+
         let mut archive = ZipArchive::new(reader)?;
         let dist_info_prefix = Self::find_dist_info_prefix(&mut archive)?;
 
@@ -50,6 +151,11 @@ impl<R: Read + Seek> WheelReader<R> {
         &self.dist_info_prefix
     }
 
+    /// Check the archive against resource limits (see `check_limits`)
+    pub fn check_limits(&mut self, limits: &OpenLimits) -> Result<(), WheelError> {
+        check_limits(&mut self.archive, limits)
+    }
+
     /// Read and parse the METADATA file
     pub fn read_metadata(&mut self) -> Result<Metadata, WheelError> {
         let path = format!("{}/METADATA", self.dist_info_prefix);
@@ -68,6 +174,17 @@ impl<R: Read + Seek> WheelReader<R> {
         Ok(Record::parse(&content)?)
     }
 
+    /// Read and parse the RECORD file, rejecting malformed size fields
+    ///
+    /// See `Record::parse_strict`.
+    pub fn read_record_strict(&mut self) -> Result<Record, WheelError> {
+        let path = format!("{}/RECORD", self.dist_info_prefix);
+        let mut file = self.archive.by_name(&path)?;
+        let mut content = String::new();
+        file.read_to_string(&mut content)?;
+        Ok(Record::parse_strict(&content)?)
+    }
+
     /// Read the WHEEL file content
     pub fn read_wheel_file(&mut self) -> Result<String, WheelError> {
         let path = format!("{}/WHEEL", self.dist_info_prefix);
@@ -103,3 +220,86 @@ impl<R: Read + Seek> WheelReader<R> {
         self.archive.len() == 0
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use super::*;
+
+    fn open_err(data: Vec<u8>) -> WheelError {
+        match WheelReader::new(Cursor::new(data)) {
+            Ok(_) => panic!("expected WheelReader::new to fail"),
+            Err(e) => e,
+        }
+    }
+
+    #[test]
+    fn test_gzip_input_rejected_with_clear_message() {
+        let mut data = vec![0x1f, 0x8b, 0x08, 0x00];
+        data.resize(64, 0);
+        match open_err(data) {
+            WheelError::InvalidWheel(msg) => assert_eq!(
+                msg,
+                "file appears to be gzip-compressed; decompress before opening"
+            ),
+            other => panic!("expected InvalidWheel, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_xz_input_rejected_with_clear_message() {
+        let mut data = XZ_MAGIC.to_vec();
+        data.resize(64, 0);
+        match open_err(data) {
+            WheelError::InvalidWheel(msg) => assert!(msg.contains("xz-compressed"), "{msg}"),
+            other => panic!("expected InvalidWheel, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_tar_input_rejected_with_clear_message() {
+        let mut data = vec![0u8; 512];
+        data[..8].copy_from_slice(b"pkg.whl\0");
+        data[TAR_MAGIC_OFFSET..TAR_MAGIC_OFFSET + 5].copy_from_slice(TAR_MAGIC);
+        match open_err(data) {
+            WheelError::InvalidWheel(msg) => assert!(msg.contains("tar-compressed"), "{msg}"),
+            other => panic!("expected InvalidWheel, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_garbage_input_rejected_as_not_a_zip() {
+        match open_err(b"definitely not a wheel".to_vec()) {
+            WheelError::InvalidWheel(msg) => {
+                assert!(msg.contains("no end-of-central-directory record"), "{msg}")
+            }
+            other => panic!("expected InvalidWheel, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_truncated_zip_rejected_with_clear_message() {
+        let mut zip = zip::ZipWriter::new(Cursor::new(Vec::new()));
+        zip.start_file(
+            "pkg-1.0.dist-info/METADATA",
+            zip::write::SimpleFileOptions::default(),
+        )
+        .unwrap();
+        std::io::Write::write_all(&mut zip, &[b'x'; 256]).unwrap();
+        let mut data = zip.finish().unwrap().into_inner();
+        data.truncate(data.len() / 2);
+        let len = data.len();
+
+        match open_err(data) {
+            WheelError::InvalidWheel(msg) => assert_eq!(
+                msg,
+                format!(
+                    "archive appears truncated or is not a zip (no end-of-central-directory record); file is {} bytes",
+                    len
+                )
+            ),
+            other => panic!("expected InvalidWheel, got {other:?}"),
+        }
+    }
+}
@@ -0,0 +1,74 @@
+//! Path safety checks - flag members that are dangerous to extract
+
+/// File type bits of a Unix mode
+const S_IFMT: u32 = 0o170000;
+
+/// File type bits marking a symbolic link
+const S_IFLNK: u32 = 0o120000;
+
+/// Find archive members that are unsafe to extract.
+///
+/// Each member is given as its name plus its Unix mode (from the ZIP
+/// external attributes), if any. A member is flagged if its name is
+/// absolute (`/etc/passwd`, `\evil`, `C:evil`), contains a `..` segment
+/// (with either separator), or its mode marks it as a symlink, which an
+/// extractor could follow to write outside the install root.
+pub fn check_safe_paths<'a>(
+    members: impl IntoIterator<Item = (&'a str, Option<u32>)>,
+) -> Vec<String> {
+    members
+        .into_iter()
+        .filter(|(name, mode)| is_unsafe_path(name) || mode.is_some_and(is_symlink_mode))
+        .map(|(name, _)| name.to_string())
+        .collect()
+}
+
+fn is_unsafe_path(name: &str) -> bool {
+    let bytes = name.as_bytes();
+    let has_drive = bytes.len() >= 2 && bytes[0].is_ascii_alphabetic() && bytes[1] == b':';
+    name.starts_with('/')
+        || name.starts_with('\\')
+        || has_drive
+        || name.split(['/', '\\']).any(|part| part == "..")
+}
+
+fn is_symlink_mode(mode: u32) -> bool {
+    mode & S_IFMT == S_IFLNK
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_safe_paths() {
+        let members = [
+            ("pkg/__init__.py", Some(0o100644)),
+            ("pkg/..data", Some(0o100644)),
+            ("pkg-1.0.0.dist-info/METADATA", None),
+        ];
+        assert!(check_safe_paths(members).is_empty());
+    }
+
+    #[test]
+    fn test_unsafe_paths() {
+        let members = [
+            ("pkg/__init__.py", None),
+            ("../evil", None),
+            ("pkg/..\\..\\evil", None),
+            ("/etc/passwd", None),
+            ("C:evil.dll", None),
+            ("pkg/link", Some(0o120777)),
+        ];
+        assert_eq!(
+            check_safe_paths(members),
+            vec![
+                "../evil",
+                "pkg/..\\..\\evil",
+                "/etc/passwd",
+                "C:evil.dll",
+                "pkg/link",
+            ]
+        );
+    }
+}
@@ -9,13 +9,30 @@ use zip::ZipArchive;
 use crate::error::ValidationError;
 use crate::error::ValidationResult;
 use crate::error::WheelError;
+use crate::record::HashAlgorithm;
 use crate::record::Record;
-use crate::record::hash_content;
+use crate::record::hash_content_with;
+use crate::wheel::is_record_signature;
 
 /// Validate all file hashes in a wheel against the RECORD file
 pub fn validate_wheel<R: Read + Seek>(
     archive: &mut ZipArchive<R>,
     record: &Record,
+) -> Result<ValidationResult, WheelError> {
+    validate_wheel_against(archive, record)
+}
+
+/// Validate a wheel's contents against an externally-provided RECORD
+///
+/// The RECORD embedded in the wheel is never read, so this answers "does
+/// the wheel match THIS record" rather than "does the wheel match its own
+/// record". Use it with a RECORD obtained from a trusted source (e.g. a
+/// signed manifest) when the embedded one could have been tampered with.
+/// Every hashed entry must exist with a matching hash, and every archive
+/// member apart from the embedded RECORD must appear in `trusted_record`.
+pub fn validate_wheel_against<R: Read + Seek>(
+    archive: &mut ZipArchive<R>,
+    trusted_record: &Record,
 ) -> Result<ValidationResult, WheelError> {
     let mut result = ValidationResult::default();
 
@@ -29,8 +46,10 @@ pub fn validate_wheel<R: Read + Seek>(
         }
     }
 
+    let dist_info = dist_info_dir(&archive_files).map(str::to_string);
+
     // Check each RECORD entry
-    for entry in &record.entries {
+    for entry in &trusted_record.entries {
         // Skip RECORD itself (it has no hash)
         if entry.hash.is_none() {
             continue;
@@ -44,13 +63,23 @@ pub fn validate_wheel<R: Read + Seek>(
             continue;
         }
 
+        // Recompute with the algorithm the RECORD entry names
+        let expected_hash = entry.hash.as_ref().unwrap();
+        let Some(algorithm) = HashAlgorithm::of_hash(expected_hash) else {
+            result.errors.push(ValidationError::UnsupportedHashAlgorithm {
+                path: entry.path.clone(),
+                hash: expected_hash.clone(),
+            });
+            archive_files.remove(&entry.path);
+            continue;
+        };
+
         // Read file contents and compute hash
         let mut file = archive.by_name(&entry.path)?;
         let mut contents = Vec::new();
         file.read_to_end(&mut contents)?;
 
-        let actual_hash = hash_content(&contents);
-        let expected_hash = entry.hash.as_ref().unwrap();
+        let actual_hash = hash_content_with(&contents, algorithm);
 
         if &actual_hash != expected_hash {
             result.errors.push(ValidationError::HashMismatch {
@@ -65,9 +94,13 @@ pub fn validate_wheel<R: Read + Seek>(
     }
 
     // Check for files in archive but not in RECORD
-    // (excluding RECORD itself which is allowed to not have a hash entry for itself)
+    // (excluding the dist-info RECORD itself which is allowed to not have a
+    // hash entry for itself, and its signatures, which RECORD can't list)
     for path in archive_files {
-        if !path.ends_with("/RECORD") {
+        let unlisted = dist_info.as_deref().is_some_and(|dist_info| {
+            path == format!("{}/RECORD", dist_info) || is_record_signature(&path, dist_info)
+        });
+        if !unlisted {
             result.errors.push(ValidationError::ExtraFile { path });
         }
     }
@@ -75,6 +108,35 @@ pub fn validate_wheel<R: Read + Seek>(
     Ok(result)
 }
 
+/// The wheel's `.dist-info` directory: the one at the archive root that
+/// holds a RECORD, or `None` unless there is exactly one
+fn dist_info_dir(archive_files: &HashSet<String>) -> Option<&str> {
+    let mut dirs = archive_files
+        .iter()
+        .filter_map(|path| path.strip_suffix("/RECORD"))
+        .filter(|dir| !dir.contains('/') && dir.ends_with(".dist-info"));
+    let dir = dirs.next()?;
+    dirs.next().is_none().then_some(dir)
+}
+
+/// Check that RECORD has hashed entries for the wheel's own METADATA and
+/// WHEEL files.
+///
+/// `validate_wheel` compares files and RECORD in both directions but has no
+/// notion of which files are required; installers reject a wheel whose
+/// RECORD omits these control files, so they are checked explicitly.
+pub fn validate_record_control_files(
+    record: &Record,
+    dist_info_prefix: &str,
+) -> Vec<ValidationError> {
+    ["METADATA", "WHEEL"]
+        .iter()
+        .map(|name| format!("{}/{}", dist_info_prefix, name))
+        .filter(|path| !record.find(path).is_some_and(|entry| entry.hash.is_some()))
+        .map(|path| ValidationError::MissingRecordEntry { path })
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use std::io::Cursor;
@@ -85,6 +147,7 @@ mod tests {
 
     use super::*;
     use crate::record::RecordEntry;
+    use crate::record::hash_content;
 
     fn create_valid_wheel() -> (Vec<u8>, Record) {
         let mut buf = Cursor::new(Vec::new());
@@ -140,4 +203,129 @@ mod tests {
         assert_eq!(result.errors.len(), 1);
         matches!(&result.errors[0], ValidationError::HashMismatch { .. });
     }
+
+    #[test]
+    fn test_validate_other_hash_algorithms() {
+        let (wheel_data, mut record) = create_valid_wheel();
+        let mut archive = ZipArchive::new(Cursor::new(wheel_data)).unwrap();
+
+        for algorithm in [HashAlgorithm::Sha512, HashAlgorithm::Blake2b] {
+            record.entries[0].hash = Some(hash_content_with(b"test content", algorithm));
+            assert!(validate_wheel(&mut archive, &record).unwrap().is_valid());
+        }
+
+        record.entries[0].hash = Some("sha512=wronghash".to_string());
+        let result = validate_wheel(&mut archive, &record).unwrap();
+        assert!(matches!(
+            &result.errors[..],
+            [ValidationError::HashMismatch { actual, .. }] if actual.starts_with("sha512=")
+        ));
+
+        record.entries[0].hash = Some("md5=abc".to_string());
+        let result = validate_wheel(&mut archive, &record).unwrap();
+        assert!(matches!(
+            &result.errors[..],
+            [ValidationError::UnsupportedHashAlgorithm { path, .. }] if path == "test.py"
+        ));
+    }
+
+    #[test]
+    fn test_validate_against_trusted_record() {
+        let (wheel_data, embedded) = create_valid_wheel();
+
+        // A tampered wheel whose embedded RECORD was updated to match.
+        let mut buf = Cursor::new(Vec::new());
+        {
+            let mut zip = ZipWriter::new(&mut buf);
+            let options = SimpleFileOptions::default();
+            zip.start_file("test.py", options).unwrap();
+            zip.write_all(b"malicious content").unwrap();
+            zip.start_file("pkg-1.0.dist-info/RECORD", options).unwrap();
+            zip.finish().unwrap();
+        }
+        let mut tampered_record = embedded.clone();
+        tampered_record.entries[0].hash = Some(hash_content(b"malicious content"));
+
+        let mut archive = ZipArchive::new(Cursor::new(buf.into_inner())).unwrap();
+        assert!(validate_wheel(&mut archive, &tampered_record).unwrap().is_valid());
+
+        let result = validate_wheel_against(&mut archive, &embedded).unwrap();
+        assert!(matches!(
+            &result.errors[..],
+            [ValidationError::HashMismatch { path, .. }] if path == "test.py"
+        ));
+
+        let mut archive = ZipArchive::new(Cursor::new(wheel_data)).unwrap();
+        assert!(validate_wheel_against(&mut archive, &embedded).unwrap().is_valid());
+    }
+
+    #[test]
+    fn test_validate_only_exempts_dist_info_record_and_signatures() {
+        let (_, record) = create_valid_wheel();
+        let mut buf = Cursor::new(Vec::new());
+        {
+            let mut zip = ZipWriter::new(&mut buf);
+            let options = SimpleFileOptions::default();
+            zip.start_file("test.py", options).unwrap();
+            zip.write_all(b"test content").unwrap();
+            zip.start_file("pkg-1.0.dist-info/RECORD", options).unwrap();
+            zip.start_file("pkg-1.0.dist-info/RECORD.jws", options)
+                .unwrap();
+            zip.start_file("pkg/RECORD", options).unwrap();
+            zip.start_file("pkg/RECORD.jws", options).unwrap();
+            zip.start_file("evil/x/RECORD", options).unwrap();
+            zip.finish().unwrap();
+        }
+
+        let mut archive = ZipArchive::new(Cursor::new(buf.into_inner())).unwrap();
+        let result = validate_wheel_against(&mut archive, &record).unwrap();
+        let mut extra: Vec<&str> = result
+            .errors
+            .iter()
+            .map(|error| match error {
+                ValidationError::ExtraFile { path } => path.as_str(),
+                other => panic!("unexpected error {other:?}"),
+            })
+            .collect();
+        extra.sort();
+        assert_eq!(extra, ["evil/x/RECORD", "pkg/RECORD", "pkg/RECORD.jws"]);
+    }
+
+    #[test]
+    fn test_validate_record_control_files() {
+        let record = Record::parse(
+            "pkg-1.0.dist-info/METADATA,sha256=abc,10\npkg-1.0.dist-info/WHEEL,sha256=def,20\npkg-1.0.dist-info/RECORD,,\n",
+        )
+        .unwrap();
+        assert!(validate_record_control_files(&record, "pkg-1.0.dist-info").is_empty());
+    }
+
+    #[test]
+    fn test_validate_record_missing_wheel_entry() {
+        let record = Record::parse(
+            "pkg-1.0.dist-info/METADATA,sha256=abc,10\npkg-1.0.dist-info/RECORD,,\n",
+        )
+        .unwrap();
+        let errors = validate_record_control_files(&record, "pkg-1.0.dist-info");
+        assert_eq!(errors.len(), 1);
+        match &errors[0] {
+            ValidationError::MissingRecordEntry { path } => {
+                assert_eq!(path, "pkg-1.0.dist-info/WHEEL")
+            }
+            other => panic!("expected MissingRecordEntry, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_validate_record_unhashed_metadata_entry() {
+        let record = Record::parse(
+            "pkg-1.0.dist-info/METADATA,,\npkg-1.0.dist-info/WHEEL,sha256=def,20\n",
+        )
+        .unwrap();
+        let errors = validate_record_control_files(&record, "pkg-1.0.dist-info");
+        assert!(matches!(
+            &errors[..],
+            [ValidationError::MissingRecordEntry { path }] if path == "pkg-1.0.dist-info/METADATA"
+        ));
+    }
 }
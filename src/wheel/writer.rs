@@ -12,17 +12,311 @@ use zip::ZipWriter;
 use zip::write::SimpleFileOptions;
 
 use std::collections::HashMap;
+use std::collections::HashSet;
+use std::sync::Arc;
 
+use crate::error::RecordError;
 use crate::error::WheelError;
 use crate::metadata::Metadata;
 use crate::name::data_dir_name;
+use crate::record::HashAlgorithm;
 use crate::record::Record;
 use crate::record::RecordEntry;
 use crate::record::hash_content;
+use crate::record::hash_content_with;
+use crate::record::hash_reader_with;
 use crate::wheel_info::WheelInfo;
 
+/// Settings controlling how the output archive is encoded.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct WriteOptions {
+    /// Maximize compatibility with very old extractors (e.g. ancient pip).
+    ///
+    /// The `zip` crate already writes the minimal version-needed-to-extract
+    /// for each entry's compression method (1.0 for Stored, 2.0 for
+    /// Deflate). With this set, every entry is also guaranteed to use one of
+    /// those two methods: source entries compressed with anything else
+    /// (bzip2, LZMA, zstd, ...) are decompressed and re-encoded with Deflate
+    /// instead of being raw-copied, so they are no longer constant-time.
+    /// Zip64 (version 4.5) cannot be disabled for entries that genuinely
+    /// exceed 4 GiB.
+    pub max_compatibility: bool,
+    /// Fail instead of silently hashing members missing from RECORD.
+    ///
+    /// By default a source member with no RECORD entry is decompressed,
+    /// hashed and recompressed so the output RECORD is complete, which is
+    /// slow for large members and breaks constant-time saves. With this set,
+    /// such a member makes the write fail with `WheelError::InvalidWheel`.
+    /// Directory entries are exempt.
+    pub strict_record: bool,
+    /// Members whose RECORD entries are written without a hash or size.
+    ///
+    /// Some build backends leave generated files such as `.pyc` unhashed,
+    /// the same way RECORD lists itself. Patterns are matched against the
+    /// output path. The dist-info `METADATA` and `WHEEL` are always hashed.
+    pub hashless_patterns: Vec<glob::Pattern>,
+    /// Algorithm for the RECORD hashes of rewritten and added files.
+    ///
+    /// Unchanged members keep the hash RECORD already has for them,
+    /// whatever its algorithm. Defaults to SHA-256.
+    pub hash_algorithm: HashAlgorithm,
+    /// Check every copied member against its RECORD hash before writing it.
+    ///
+    /// By default unchanged members are raw-copied on trust, so a corrupted
+    /// source wheel is reproduced with a RECORD that no longer matches.
+    /// With this set, each member with a RECORD hash is decompressed and
+    /// hashed first, and a mismatch fails the write with
+    /// `RecordError::HashMismatch`. This reads the whole wheel, so saving
+    /// becomes O(wheel size) instead of constant-time.
+    pub verify_hashes: bool,
+    /// Timestamp, in seconds since the Unix epoch, for every entry the
+    /// writer generates (METADATA, RECORD, WHEEL, modified and added files).
+    ///
+    /// By default those entries get the current time, so saving the same
+    /// edit twice gives byte-different wheels. Setting this (typically to
+    /// `SOURCE_DATE_EPOCH`, or `DOS_EPOCH`) makes the output reproducible.
+    /// Raw-copied members always keep their original timestamps. Values
+    /// outside the zip format's 1980-2107 range are clamped.
+    pub source_date_epoch: Option<u64>,
+    /// Compression method for every entry the writer generates.
+    ///
+    /// By default a rewritten member (METADATA, RECORD, WHEEL, a modified
+    /// file, or an added file replacing one) keeps the method of the source
+    /// entry it replaces if that was Stored, so deliberately uncompressed
+    /// libraries stay mmap-friendly, and is deflated otherwise; new files are
+    /// deflated. Setting this uses one method for all of them (limited to
+    /// Stored and Deflate under `max_compatibility`). Raw-copied members are
+    /// unaffected either way.
+    pub compression: Option<zip::CompressionMethod>,
+    /// Deflate level (within `DEFLATE_LEVELS`) for every entry the writer
+    /// generates and deflates; `None` uses the zip crate's default.
+    ///
+    /// Stored entries and raw-copied members are unaffected, since neither
+    /// is compressed here. An out-of-range level fails the write with
+    /// `WheelError::InvalidWheel` before anything is written.
+    pub compression_level: Option<i64>,
+    /// Signs the regenerated RECORD, writing the result as `RECORD.jws`.
+    ///
+    /// A `RECORD.jws` or `RECORD.p7s` in the source signs the old RECORD,
+    /// so the writers always drop it. With a signer set, a new `RECORD.jws`
+    /// over the RECORD just written follows it in the archive. Like RECORD
+    /// itself, the signature isn't listed in RECORD.
+    pub record_signer: Option<RecordSigner>,
+    /// Top-level package directories to move, as `(old, new)` pairs.
+    ///
+    /// Every member under `old/` is written under `new/` instead, whether at
+    /// the archive root or in the `.data/purelib/` and `.data/platlib/`
+    /// directories, and its RECORD line follows it. Members are still
+    /// raw-copied, so the rename keeps saves constant-time. Only paths
+    /// change: see `WheelEditor::rename_package` for what isn't rewritten.
+    pub package_renames: Vec<(String, String)>,
+}
+
+/// Callback that signs a regenerated RECORD (see
+/// `WriteOptions::record_signer`)
+///
+/// Called with the exact RECORD bytes written. Returns the content of the
+/// new `RECORD.jws`, a JSON Web Signature as described in PEP 427, or an
+/// error message that fails the write with `RecordError::Signing`.
+#[derive(Clone)]
+pub struct RecordSigner(Arc<dyn Fn(&[u8]) -> Result<Vec<u8>, String> + Send + Sync>);
+
+impl RecordSigner {
+    pub fn new(sign: impl Fn(&[u8]) -> Result<Vec<u8>, String> + Send + Sync + 'static) -> Self {
+        Self(Arc::new(sign))
+    }
+
+    /// Sign `record`, the serialized RECORD
+    pub fn sign(&self, record: &[u8]) -> Result<Vec<u8>, WheelError> {
+        (self.0)(record).map_err(|e| RecordError::Signing(e).into())
+    }
+}
+
+impl std::fmt::Debug for RecordSigner {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RecordSigner").finish_non_exhaustive()
+    }
+}
+
+/// Two signers are equal only if one is a clone of the other
+impl PartialEq for RecordSigner {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl Eq for RecordSigner {}
+
+/// Signature files a wheel may ship next to RECORD (PEP 427)
+///
+/// Both sign RECORD's exact bytes, and every save regenerates RECORD.
+pub const RECORD_SIGNATURES: [&str; 2] = ["RECORD.jws", "RECORD.p7s"];
+
+/// Whether `name` is one of `RECORD_SIGNATURES` directly under `dist_info`
+pub(crate) fn is_record_signature(name: &str, dist_info: &str) -> bool {
+    name.strip_prefix(dist_info)
+        .and_then(|rest| rest.strip_prefix('/'))
+        .is_some_and(|file| RECORD_SIGNATURES.contains(&file))
+}
+
+/// Deflate levels the zip crate accepts, from no compression (0, Deflate
+/// stored blocks) to the smallest output
+pub const DEFLATE_LEVELS: std::ops::RangeInclusive<i64> = 0..=9;
+
+/// Check a `WriteOptions::compression_level` against `DEFLATE_LEVELS`
+pub(crate) fn check_compression_level(level: Option<i64>) -> Result<(), WheelError> {
+    match level {
+        Some(level) if !DEFLATE_LEVELS.contains(&level) => Err(WheelError::InvalidWheel(format!(
+            "compression level {} is out of range ({}-{})",
+            level,
+            DEFLATE_LEVELS.start(),
+            DEFLATE_LEVELS.end()
+        ))),
+        _ => Ok(()),
+    }
+}
+
+/// Compression method for an entry the writer rewrites
+///
+/// `compression` wins if set. Otherwise a Stored source entry stays Stored
+/// and anything else, including a brand new file (`None`), is deflated:
+/// other methods aren't worth a dependency on their encoders, and the
+/// source's Deflate level isn't recorded in the archive.
+fn rewrite_method(
+    compression: Option<zip::CompressionMethod>,
+    source_method: Option<zip::CompressionMethod>,
+) -> zip::CompressionMethod {
+    match (compression, source_method) {
+        (Some(method), _) => method,
+        (None, Some(zip::CompressionMethod::Stored)) => zip::CompressionMethod::Stored,
+        (None, _) => zip::CompressionMethod::Deflated,
+    }
+}
+
+/// 1980-01-01T00:00:00Z, the earliest timestamp a zip entry can hold
+pub const DOS_EPOCH: u64 = 315_532_800;
+
+/// Convert a Unix timestamp to a zip timestamp, clamped to the 1980-2107
+/// range the format can represent
+fn zip_datetime(epoch: u64) -> zip::DateTime {
+    let (days, seconds) = ((epoch / 86_400) as i64, epoch % 86_400);
+
+    // Civil date from days since 1970-01-01 (Howard Hinnant's algorithm)
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let day_of_era = z - era * 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 {
+        month_index + 3
+    } else {
+        month_index - 9
+    };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+
+    if year < 1980 {
+        return zip::DateTime::default();
+    }
+    if year > 2107 {
+        return zip::DateTime::from_date_and_time(2107, 12, 31, 23, 59, 58).unwrap_or_default();
+    }
+    zip::DateTime::from_date_and_time(
+        year as u16,
+        month as u8,
+        day as u8,
+        (seconds / 3_600) as u8,
+        (seconds % 3_600 / 60) as u8,
+        (seconds % 60) as u8,
+    )
+    .unwrap_or_default()
+}
+
+/// Normalize an archive member path to use `/` separators.
+///
+/// The wheel spec requires forward slashes in both ZIP member names and
+/// RECORD entries. Paths built via `std::path` on Windows (or copied from a
+/// sloppy source archive) can contain `\`, so every name the writer emits
+/// goes through here.
+fn to_archive_path(path: &str) -> String {
+    path.replace('\\', "/")
+}
+
+/// The archive path a source member or added file is written under
+///
+/// Applies the same dist-info and `.data` directory rename as the writers
+/// when a name or version change moves `old_dist_info` to `new_dist_info`.
+/// Every writer maps names through here, so `\` separators are normalized
+/// the same way whichever path a save takes.
+///
+/// # Errors
+/// `WheelError::InvalidWheel` if a rename is needed and `old_dist_info`
+/// doesn't end with `.dist-info`.
+pub(crate) fn renamed_path(
+    name: &str,
+    old_dist_info: &str,
+    new_dist_info: &str,
+    new_data_dir: &str,
+) -> Result<String, WheelError> {
+    let name = to_archive_path(name);
+    if old_dist_info == new_dist_info {
+        return Ok(name);
+    }
+    let old_data_dir = match old_dist_info.strip_suffix(".dist-info") {
+        Some(stem) => format!("{}.data", stem),
+        None => {
+            return Err(WheelError::InvalidWheel(format!(
+                "dist-info directory '{}' doesn't end with .dist-info",
+                old_dist_info
+            )));
+        }
+    };
+    Ok(if name.starts_with(old_dist_info) {
+        name.replacen(old_dist_info, new_dist_info, 1)
+    } else if name.starts_with(&old_data_dir) {
+        name.replacen(&old_data_dir, new_data_dir, 1)
+    } else {
+        name
+    })
+}
+
+/// Split a member path around its top-level package directory
+///
+/// Gives `("", "pkg", "/mod.py")` for `pkg/mod.py`, and
+/// `("pkg-1.0.data/purelib/", "pkg", "/mod.py")` under the `.data/purelib/`
+/// and `.data/platlib/` directories, which install to the same place. Paths
+/// outside a directory there (top-level modules, scripts, headers) give
+/// `None`.
+pub(crate) fn split_package_path(name: &str) -> Option<(&str, &str, &str)> {
+    let tail = match name.split_once(".data/") {
+        Some((data_dir, tail)) if !data_dir.contains('/') => ["purelib/", "platlib/"]
+            .iter()
+            .find_map(|scheme| tail.strip_prefix(scheme))?,
+        _ => name,
+    };
+    let prefix = &name[..name.len() - tail.len()];
+    let slash = tail.find('/')?;
+    Some((prefix, &tail[..slash], &tail[slash..]))
+}
+
+/// Apply `WriteOptions::package_renames` to an output path
+pub(crate) fn rename_package_path(name: String, renames: &[(String, String)]) -> String {
+    let renamed = split_package_path(&name).and_then(|(prefix, package, rest)| {
+        renames
+            .iter()
+            .find(|(old, _)| old == package)
+            .map(|(_, new)| format!("{}{}{}", prefix, new, rest))
+    });
+    renamed.unwrap_or(name)
+}
+
 /// Write a modified wheel by copying files
 ///
+/// `RECORD_SIGNATURES` in the source are left out, since they sign the
+/// RECORD being replaced.
+///
 /// # Arguments
 /// * `source` - The source wheel archive
 /// * `output` - The output writer
@@ -46,15 +340,11 @@ pub fn write_modified<R: Read + Seek, W: Write + Seek>(
     let new_metadata_path = format!("{}/METADATA", new_dist_info);
     let new_record_path = format!("{}/RECORD", new_dist_info);
 
-    let needs_rename = old_dist_info != new_dist_info;
-
-    let old_data_dir = format!(
-        "{}.data",
-        old_dist_info
-            .strip_suffix(".dist-info")
-            .expect("old_dist_info must end with .dist-info")
-    );
     let new_data_dir = data_dir_name(&metadata.name, &metadata.version);
+    let rename_path = |name: &str| renamed_path(name, old_dist_info, new_dist_info, &new_data_dir);
+
+    let mut metadata_method = None;
+    let mut record_method = None;
 
     // Phase 1: Copy all files using raw copy (no decompression)
     for i in 0..source.len() {
@@ -62,18 +352,22 @@ pub fn write_modified<R: Read + Seek, W: Write + Seek>(
         let name = entry.name().to_string();
 
         // Skip METADATA and RECORD - we'll write new versions
-        if name == old_metadata_path || name == old_record_path {
+        if name == old_metadata_path {
+            metadata_method = Some(entry.compression());
+            continue;
+        }
+        if name == old_record_path {
+            record_method = Some(entry.compression());
+            continue;
+        }
+
+        // Signatures over the old RECORD would no longer verify
+        if is_record_signature(&name, old_dist_info) {
             continue;
         }
 
         // Determine the new path (handle dist-info and .data rename for version changes)
-        let new_name = if needs_rename && name.starts_with(old_dist_info) {
-            name.replacen(old_dist_info, new_dist_info, 1)
-        } else if needs_rename && name.starts_with(&old_data_dir) {
-            name.replacen(&old_data_dir, &new_data_dir, 1)
-        } else {
-            name.clone()
-        };
+        let new_name = rename_path(&name)?;
 
         // Use raw copy - copies compressed bytes directly without decompression
         if new_name != name {
@@ -91,6 +385,8 @@ pub fn write_modified<R: Read + Seek, W: Write + Seek>(
             ));
         } else {
             // File not in RECORD - need to compute hash (rare case)
+            #[cfg(feature = "logging")]
+            log::debug!("'{}' is not in RECORD; hashing it", name);
             let mut entry = source.by_index(i)?;
             let mut content = Vec::new();
             std::io::copy(&mut entry, &mut content)?;
@@ -108,8 +404,10 @@ pub fn write_modified<R: Read + Seek, W: Write + Seek>(
     let metadata_hash = hash_content(&metadata_bytes);
     let metadata_size = metadata_bytes.len() as u64;
 
-    let options = SimpleFileOptions::default().compression_method(zip::CompressionMethod::Deflated);
-    writer.start_file(&new_metadata_path, options)?;
+    let options = |source_method| {
+        SimpleFileOptions::default().compression_method(rewrite_method(None, source_method))
+    };
+    writer.start_file(&new_metadata_path, options(metadata_method))?;
     writer.write_all(&metadata_bytes)?;
 
     new_record_entries.push(RecordEntry::new(
@@ -119,6 +417,7 @@ pub fn write_modified<R: Read + Seek, W: Write + Seek>(
     ));
 
     // Phase 3: Write new RECORD (RECORD itself has no hash)
+    order_like_record(&mut new_record_entries, original_record, rename_path)?;
     new_record_entries.push(RecordEntry::new(new_record_path.clone(), None, None));
 
     let record = Record {
@@ -126,7 +425,7 @@ pub fn write_modified<R: Read + Seek, W: Write + Seek>(
     };
     let record_content = record.serialize();
 
-    writer.start_file(&new_record_path, options)?;
+    writer.start_file(&new_record_path, options(record_method))?;
     writer.write_all(record_content.as_bytes())?;
 
     // Finalize the archive
@@ -142,6 +441,7 @@ pub fn write_modified<R: Read + Seek, W: Write + Seek>(
 /// - Modified binary files (e.g., .so files with changed RPATH)
 /// - Modified WHEEL file (e.g., changed platform tags)
 /// - Newly added files (e.g., a `build-details.json` stamped into dist-info)
+/// - Removed files, which are dropped along with their RECORD entries
 ///
 /// # Arguments
 /// * `source` - The source wheel archive
@@ -154,8 +454,12 @@ pub fn write_modified<R: Read + Seek, W: Write + Seek>(
 /// * `added_files` - Map of new file paths (full archive paths) to content.
 ///                   Paths under `old_dist_info` / old `.data` dir are
 ///                   rewritten to the new prefixes when versions change.
-///                   Collisions with files in the source archive return
+///                   A path that already exists in the source archive
+///                   replaces that file, like a modification. Collisions
+///                   with the generated METADATA, RECORD or WHEEL return
 ///                   `WheelError::InvalidWheel`.
+/// * `removed_files` - Source archive paths to leave out of the output and
+///                     its RECORD
 /// * `wheel_info` - Optional modified WHEEL info (if None, uses original)
 pub fn write_modified_extended<R: Read + Seek, W: Write + Seek>(
     source: &mut ZipArchive<R>,
@@ -166,9 +470,46 @@ pub fn write_modified_extended<R: Read + Seek, W: Write + Seek>(
     new_dist_info: &str,
     modified_files: &HashMap<String, Vec<u8>>,
     added_files: &HashMap<String, Vec<u8>>,
+    removed_files: &HashSet<String>,
     wheel_info: Option<&WheelInfo>,
 ) -> Result<(), WheelError> {
+    write_modified_with_options(
+        source,
+        output,
+        metadata,
+        original_record,
+        old_dist_info,
+        new_dist_info,
+        modified_files,
+        added_files,
+        removed_files,
+        wheel_info,
+        &WriteOptions::default(),
+    )
+}
+
+/// Write a modified wheel like `write_modified_extended`, with additional
+/// control over how the output archive is encoded.
+///
+/// # Arguments
+/// Same as `write_modified_extended`, plus:
+/// * `write_options` - Output encoding settings (see `WriteOptions`)
+pub fn write_modified_with_options<R: Read + Seek, W: Write + Seek>(
+    source: &mut ZipArchive<R>,
+    output: W,
+    metadata: &Metadata,
+    original_record: &Record,
+    old_dist_info: &str,
+    new_dist_info: &str,
+    modified_files: &HashMap<String, Vec<u8>>,
+    added_files: &HashMap<String, Vec<u8>>,
+    removed_files: &HashSet<String>,
+    wheel_info: Option<&WheelInfo>,
+    write_options: &WriteOptions,
+) -> Result<(), WheelError> {
+    check_compression_level(write_options.compression_level)?;
     let mut writer = ZipWriter::new(output);
+    let hash_algorithm = write_options.hash_algorithm;
     let mut new_record_entries: Vec<RecordEntry> = Vec::new();
 
     let old_metadata_path = format!("{}/METADATA", old_dist_info);
@@ -177,36 +518,42 @@ pub fn write_modified_extended<R: Read + Seek, W: Write + Seek>(
     let new_metadata_path = format!("{}/METADATA", new_dist_info);
     let new_record_path = format!("{}/RECORD", new_dist_info);
     let new_wheel_path = format!("{}/WHEEL", new_dist_info);
+    let new_signature_path = format!("{}/RECORD.jws", new_dist_info);
 
-    let needs_rename = old_dist_info != new_dist_info;
-    let options = SimpleFileOptions::default().compression_method(zip::CompressionMethod::Deflated);
+    let mut options =
+        SimpleFileOptions::default().compression_method(zip::CompressionMethod::Deflated);
+    if let Some(epoch) = write_options.source_date_epoch {
+        options = options.last_modified_time(zip_datetime(epoch));
+    }
+    let options_for = |source_method| {
+        let method = match rewrite_method(write_options.compression, source_method) {
+            method @ (zip::CompressionMethod::Stored | zip::CompressionMethod::Deflated) => method,
+            _ if write_options.max_compatibility => zip::CompressionMethod::Deflated,
+            method => method,
+        };
+        let level = write_options
+            .compression_level
+            .filter(|_| method != zip::CompressionMethod::Stored);
+        options.compression_method(method).compression_level(level)
+    };
+    let (mut metadata_method, mut record_method, mut wheel_method) = (None, None, None);
 
-    let old_data_dir = format!(
-        "{}.data",
-        old_dist_info
-            .strip_suffix(".dist-info")
-            .expect("old_dist_info must end with .dist-info")
-    );
     let new_data_dir = data_dir_name(&metadata.name, &metadata.version);
 
-    // Closure that mirrors the dist-info / .data rename applied to source
-    // entries, so callers can use either the old or new prefix when calling
-    // `add_file`.
-    let rename_path = |name: &str| -> String {
-        if needs_rename && name.starts_with(old_dist_info) {
-            name.replacen(old_dist_info, new_dist_info, 1)
-        } else if needs_rename && name.starts_with(&old_data_dir) {
-            name.replacen(&old_data_dir, &new_data_dir, 1)
-        } else {
-            name.to_string()
-        }
+    // Mirrors the dist-info / .data rename applied to source entries, so
+    // callers can use either the old or new prefix when calling `add_file`.
+    // Package renames apply on top, to both.
+    let rename_path = |name: &str| -> Result<String, WheelError> {
+        let name = renamed_path(name, old_dist_info, new_dist_info, &new_data_dir)?;
+        Ok(rename_package_path(name, &write_options.package_renames))
     };
 
-    // Build the final paths for added files up-front so we can detect
-    // collisions with files in the source archive before writing anything.
+    // Build the final paths for added files up-front so collisions with the
+    // generated files are caught before writing anything, and added files
+    // that replace a source file can be written in its place.
     let mut added_final: HashMap<String, &Vec<u8>> = HashMap::new();
     for (path, content) in added_files {
-        let final_path = rename_path(path);
+        let final_path = rename_path(path)?;
         if final_path == new_metadata_path
             || final_path == new_record_path
             || final_path == new_wheel_path
@@ -216,58 +563,64 @@ pub fn write_modified_extended<R: Read + Seek, W: Write + Seek>(
                 final_path
             )));
         }
-        added_final.insert(final_path, content);
-    }
-    for i in 0..source.len() {
-        let name = source.by_index_raw(i)?.name().to_string();
-        let final_name = rename_path(&name);
-        if added_final.contains_key(&final_name) {
+        if write_options.record_signer.is_some() && final_path == new_signature_path {
             return Err(WheelError::InvalidWheel(format!(
-                "add_file path '{}' collides with an existing file in the source archive",
-                final_name
+                "add_file path '{}' collides with the RECORD signature the record signer writes",
+                final_path
             )));
         }
+        added_final.insert(final_path, content);
     }
 
     // Phase 1: Copy all files, handling modifications
     for i in 0..source.len() {
-        let entry = source.by_index_raw(i)?;
+        let mut entry = source.by_index_raw(i)?;
         let name = entry.name().to_string();
 
         // Skip files we'll write new versions of
-        if name == old_metadata_path || name == old_record_path {
+        if name == old_metadata_path {
+            metadata_method = Some(entry.compression());
+            continue;
+        }
+        if name == old_record_path {
+            record_method = Some(entry.compression());
             continue;
         }
 
         // Skip WHEEL file if we have a modified version
         if wheel_info.is_some() && name == old_wheel_path {
+            wheel_method = Some(entry.compression());
+            continue;
+        }
+
+        // Skip files removed from the wheel, and signatures over the old
+        // RECORD, which would no longer verify
+        if removed_files.contains(&name) || is_record_signature(&name, old_dist_info) {
             continue;
         }
 
         // Determine the new path (handle dist-info and .data rename for version changes)
-        let new_name = if needs_rename && name.starts_with(old_dist_info) {
-            name.replacen(old_dist_info, new_dist_info, 1)
-        } else if needs_rename && name.starts_with(&old_data_dir) {
-            name.replacen(&old_data_dir, &new_data_dir, 1)
-        } else {
-            name.clone()
-        };
+        let new_name = rename_path(&name)?;
 
-        // Check if this file has been modified
-        if let Some(modified_content) = modified_files.get(&name) {
+        // Check if this file has been modified or replaced by an added file
+        let replacement = added_final
+            .remove(&new_name)
+            .or_else(|| modified_files.get(&name));
+        if let Some(modified_content) = replacement {
             // Write the modified content
+            let file_options = options_for(Some(entry.compression()));
             drop(entry); // Release the raw entry
             // Enable ZIP64 for large files (>4GB)
             let file_options = if modified_content.len() as u64 > 0xFFFFFFFF {
-                options.large_file(true)
+                file_options.large_file(true)
             } else {
-                options
+                file_options
             };
             writer.start_file(&new_name, file_options)?;
             writer.write_all(modified_content)?;
 
             // Compute new hash for modified content
-            let hash = hash_content(modified_content);
+            let hash = hash_content_with(modified_content, hash_algorithm);
             new_record_entries.push(RecordEntry::new(
                 new_name,
                 Some(hash),
@@ -276,8 +629,39 @@ pub fn write_modified_extended<R: Read + Seek, W: Write + Seek>(
         } else {
             // Preserve original hash from RECORD if available
             if let Some(record_entry) = original_record.find(&name) {
-                // Use raw copy - copies compressed bytes directly without decompression
-                if new_name != name {
+                let expected = record_entry.hash.as_deref();
+                if let Some(expected) = expected.filter(|_| write_options.verify_hashes) {
+                    drop(entry);
+                    verify_member(source, i, &name, expected)?;
+                    entry = source.by_index_raw(i)?;
+                }
+
+                if write_options.max_compatibility
+                    && !matches!(
+                        entry.compression(),
+                        zip::CompressionMethod::Stored | zip::CompressionMethod::Deflated
+                    )
+                {
+                    // Re-encode with Deflate so old extractors can read it;
+                    // the content (and so the RECORD hash) is unchanged.
+                    #[cfg(feature = "logging")]
+                    log::debug!(
+                        "re-encoding '{}' ({:?}) with Deflate for max_compatibility",
+                        name,
+                        entry.compression()
+                    );
+                    drop(entry);
+                    let mut decompressed = source.by_index(i)?;
+                    let options = options.compression_level(write_options.compression_level);
+                    let file_options = if decompressed.size() > 0xFFFFFFFF {
+                        options.large_file(true)
+                    } else {
+                        options
+                    };
+                    writer.start_file(&new_name, file_options)?;
+                    std::io::copy(&mut decompressed, &mut writer)?;
+                } else if new_name != name {
+                    // Use raw copy - copies compressed bytes directly without decompression
                     writer.raw_copy_file_rename(entry, &new_name)?;
                 } else {
                     writer.raw_copy_file(entry)?;
@@ -288,20 +672,29 @@ pub fn write_modified_extended<R: Read + Seek, W: Write + Seek>(
                     record_entry.hash.clone(),
                     record_entry.size,
                 ));
+            } else if write_options.strict_record && !name.ends_with('/') {
+                return Err(WheelError::InvalidWheel(format!(
+                    "'{}' is not listed in RECORD (strict RECORD mode is enabled; \
+                     add a RECORD entry, e.g. with refresh_record_hash, before saving)",
+                    name
+                )));
             } else {
                 // File not in RECORD - need to compute hash (rare case)
                 // First drop the raw entry, then read the decompressed content
+                #[cfg(feature = "logging")]
+                log::debug!("'{}' is not in RECORD; hashing it", name);
+                let file_options = options_for(Some(entry.compression()));
                 drop(entry);
                 let mut decompressed = source.by_index(i)?;
                 let mut content = Vec::new();
                 std::io::copy(&mut decompressed, &mut content)?;
-                let hash = hash_content(&content);
+                let hash = hash_content_with(&content, hash_algorithm);
 
                 // Write the content normally, enabling ZIP64 for large files
                 let file_options = if content.len() as u64 > 0xFFFFFFFF {
-                    options.large_file(true)
+                    file_options.large_file(true)
                 } else {
-                    options
+                    file_options
                 };
                 writer.start_file(&new_name, file_options)?;
                 writer.write_all(&content)?;
@@ -318,10 +711,10 @@ pub fn write_modified_extended<R: Read + Seek, W: Write + Seek>(
     // Phase 2: Write new WHEEL file if modified
     if let Some(wheel_info) = wheel_info {
         let wheel_bytes = wheel_info.serialize().into_bytes();
-        let wheel_hash = hash_content(&wheel_bytes);
+        let wheel_hash = hash_content_with(&wheel_bytes, hash_algorithm);
         let wheel_size = wheel_bytes.len() as u64;
 
-        writer.start_file(&new_wheel_path, options)?;
+        writer.start_file(&new_wheel_path, options_for(wheel_method))?;
         writer.write_all(&wheel_bytes)?;
 
         new_record_entries.push(RecordEntry::new(
@@ -333,10 +726,10 @@ pub fn write_modified_extended<R: Read + Seek, W: Write + Seek>(
 
     // Phase 3: Write new METADATA
     let metadata_bytes = metadata.serialize().into_bytes();
-    let metadata_hash = hash_content(&metadata_bytes);
+    let metadata_hash = hash_content_with(&metadata_bytes, hash_algorithm);
     let metadata_size = metadata_bytes.len() as u64;
 
-    writer.start_file(&new_metadata_path, options)?;
+    writer.start_file(&new_metadata_path, options_for(metadata_method))?;
     writer.write_all(&metadata_bytes)?;
 
     new_record_entries.push(RecordEntry::new(
@@ -351,14 +744,14 @@ pub fn write_modified_extended<R: Read + Seek, W: Write + Seek>(
     added_sorted.sort_by(|a, b| a.0.cmp(b.0));
     for (final_path, content) in added_sorted {
         let file_options = if content.len() as u64 > 0xFFFFFFFF {
-            options.large_file(true)
+            options_for(None).large_file(true)
         } else {
-            options
+            options_for(None)
         };
         writer.start_file(final_path, file_options)?;
         writer.write_all(content)?;
 
-        let hash = hash_content(content);
+        let hash = hash_content_with(content, hash_algorithm);
         new_record_entries.push(RecordEntry::new(
             final_path.clone(),
             Some(hash),
@@ -367,6 +760,12 @@ pub fn write_modified_extended<R: Read + Seek, W: Write + Seek>(
     }
 
     // Phase 4: Write new RECORD (RECORD itself has no hash)
+    order_like_record(&mut new_record_entries, original_record, rename_path)?;
+    clear_hashless_entries(
+        &mut new_record_entries,
+        &write_options.hashless_patterns,
+        new_dist_info,
+    );
     new_record_entries.push(RecordEntry::new(new_record_path.clone(), None, None));
 
     let record = Record {
@@ -374,15 +773,205 @@ pub fn write_modified_extended<R: Read + Seek, W: Write + Seek>(
     };
     let record_content = record.serialize();
 
-    writer.start_file(&new_record_path, options)?;
+    writer.start_file(&new_record_path, options_for(record_method))?;
     writer.write_all(record_content.as_bytes())?;
 
+    // Phase 5: Sign the new RECORD
+    if let Some(signer) = &write_options.record_signer {
+        let signature = signer.sign(record_content.as_bytes())?;
+        writer.start_file(&new_signature_path, options_for(None))?;
+        writer.write_all(&signature)?;
+    }
+
     // Finalize the archive
     writer.finish()?;
 
     Ok(())
 }
 
+/// Check a source member's content against its RECORD hash
+fn verify_member<R: Read + Seek>(
+    source: &mut ZipArchive<R>,
+    index: usize,
+    name: &str,
+    expected: &str,
+) -> Result<(), WheelError> {
+    let algorithm = HashAlgorithm::of_hash(expected).ok_or_else(|| {
+        WheelError::InvalidWheel(format!(
+            "cannot verify '{}': unsupported RECORD hash '{}'",
+            name, expected
+        ))
+    })?;
+    let actual = hash_reader_with(source.by_index(index)?, algorithm)?;
+    if actual != expected {
+        return Err(RecordError::HashMismatch {
+            path: name.to_string(),
+            expected: expected.to_string(),
+            actual,
+        }
+        .into());
+    }
+    Ok(())
+}
+
+/// Sort `entries` into the order of the source RECORD
+///
+/// An entry whose source path (mapped through `rename`) has a line in
+/// `original_record` takes that line's position, so regenerated METADATA and
+/// WHEEL and modified files stay where they were. Everything else (members
+/// RECORD didn't list, added files) follows in its current order. The
+/// RECORD-of-RECORD line is never among `entries`: callers append a fresh
+/// one last, whatever the source said about itself.
+fn order_like_record(
+    entries: &mut [RecordEntry],
+    original_record: &Record,
+    rename: impl Fn(&str) -> Result<String, WheelError>,
+) -> Result<(), WheelError> {
+    let positions: HashMap<String, usize> = original_record
+        .entries
+        .iter()
+        .enumerate()
+        .map(|(i, entry)| Ok((rename(&entry.path)?, i)))
+        .collect::<Result<_, WheelError>>()?;
+    entries.sort_by_key(|entry| positions.get(&entry.path).copied().unwrap_or(usize::MAX));
+    Ok(())
+}
+
+/// Blank the hash and size of every entry matching one of `patterns`
+///
+/// `METADATA` and `WHEEL` under `dist_info` keep theirs, since installers
+/// need them to verify the wheel.
+pub(crate) fn clear_hashless_entries(
+    entries: &mut [RecordEntry],
+    patterns: &[glob::Pattern],
+    dist_info: &str,
+) {
+    if patterns.is_empty() {
+        return;
+    }
+    let metadata_path = format!("{}/METADATA", dist_info);
+    let wheel_path = format!("{}/WHEEL", dist_info);
+    for entry in entries {
+        if entry.path != metadata_path
+            && entry.path != wheel_path
+            && patterns.iter().any(|pattern| pattern.matches(&entry.path))
+        {
+            entry.hash = None;
+            entry.size = None;
+        }
+    }
+}
+
+/// Compute the RECORD that `write_modified_with_options` would write,
+/// without writing an archive.
+///
+/// Takes the same inputs as `write_modified_extended`, plus the
+/// `WriteOptions::hash_algorithm` new hashes are computed with. Members
+/// missing from `original_record` are decompressed and hashed, exactly as
+/// the writer does; everything else only touches the central directory.
+pub fn project_record<R: Read + Seek>(
+    source: &mut ZipArchive<R>,
+    metadata: &Metadata,
+    original_record: &Record,
+    old_dist_info: &str,
+    new_dist_info: &str,
+    modified_files: &HashMap<String, Vec<u8>>,
+    added_files: &HashMap<String, Vec<u8>>,
+    removed_files: &HashSet<String>,
+    wheel_info: Option<&WheelInfo>,
+    hash_algorithm: HashAlgorithm,
+) -> Result<Record, WheelError> {
+    let mut entries: Vec<RecordEntry> = Vec::new();
+
+    let old_metadata_path = format!("{}/METADATA", old_dist_info);
+    let old_record_path = format!("{}/RECORD", old_dist_info);
+    let old_wheel_path = format!("{}/WHEEL", old_dist_info);
+
+    let new_data_dir = data_dir_name(&metadata.name, &metadata.version);
+
+    // Same rename as the writer applies to source entries and added files
+    let rename_path = |name: &str| renamed_path(name, old_dist_info, new_dist_info, &new_data_dir);
+
+    let mut added_final: HashMap<String, &Vec<u8>> = added_files
+        .iter()
+        .map(|(path, content)| Ok((rename_path(path)?, content)))
+        .collect::<Result<_, WheelError>>()?;
+
+    for i in 0..source.len() {
+        let name = source.by_index_raw(i)?.name().to_string();
+        if name == old_metadata_path
+            || name == old_record_path
+            || (wheel_info.is_some() && name == old_wheel_path)
+            || removed_files.contains(&name)
+            || is_record_signature(&name, old_dist_info)
+        {
+            continue;
+        }
+
+        let new_name = rename_path(&name)?;
+        let replacement = added_final
+            .remove(&new_name)
+            .or_else(|| modified_files.get(&name));
+        if let Some(modified_content) = replacement {
+            entries.push(RecordEntry::new(
+                new_name,
+                Some(hash_content_with(modified_content, hash_algorithm)),
+                Some(modified_content.len() as u64),
+            ));
+        } else if let Some(record_entry) = original_record.find(&name) {
+            entries.push(RecordEntry::new(
+                new_name,
+                record_entry.hash.clone(),
+                record_entry.size,
+            ));
+        } else {
+            let mut decompressed = source.by_index(i)?;
+            let mut content = Vec::new();
+            std::io::copy(&mut decompressed, &mut content)?;
+            entries.push(RecordEntry::new(
+                new_name,
+                Some(hash_content_with(&content, hash_algorithm)),
+                Some(content.len() as u64),
+            ));
+        }
+    }
+
+    if let Some(wheel_info) = wheel_info {
+        let wheel_bytes = wheel_info.serialize().into_bytes();
+        entries.push(RecordEntry::new(
+            format!("{}/WHEEL", new_dist_info),
+            Some(hash_content_with(&wheel_bytes, hash_algorithm)),
+            Some(wheel_bytes.len() as u64),
+        ));
+    }
+
+    let metadata_bytes = metadata.serialize().into_bytes();
+    entries.push(RecordEntry::new(
+        format!("{}/METADATA", new_dist_info),
+        Some(hash_content_with(&metadata_bytes, hash_algorithm)),
+        Some(metadata_bytes.len() as u64),
+    ));
+
+    let mut added_sorted: Vec<(String, &Vec<u8>)> = added_final.into_iter().collect();
+    added_sorted.sort_by(|a, b| a.0.cmp(&b.0));
+    for (final_path, content) in added_sorted {
+        entries.push(RecordEntry::new(
+            final_path,
+            Some(hash_content_with(content, hash_algorithm)),
+            Some(content.len() as u64),
+        ));
+    }
+
+    order_like_record(&mut entries, original_record, rename_path)?;
+    entries.push(RecordEntry::new(
+        format!("{}/RECORD", new_dist_info),
+        None,
+        None,
+    ));
+
+    Ok(Record { entries })
+}
+
 #[cfg(test)]
 mod tests {
     use std::io::Cursor;
@@ -456,6 +1045,115 @@ mod tests {
         assert!(result.len() >= 3);
     }
 
+    #[test]
+    fn test_to_archive_path() {
+        assert_eq!(to_archive_path("pkg/mod.py"), "pkg/mod.py");
+        assert_eq!(to_archive_path("pkg\\sub\\mod.py"), "pkg/sub/mod.py");
+
+        // On Windows this joins with `\`; elsewhere it's already `/`.
+        let built = std::path::PathBuf::from("pkg").join("sub").join("mod.py");
+        assert_eq!(to_archive_path(&built.to_string_lossy()), "pkg/sub/mod.py");
+    }
+
+    #[test]
+    fn test_renamed_path() {
+        let rename = |name: &str| {
+            renamed_path(
+                name,
+                "pkg-1.0.dist-info",
+                "pkg-2.0.dist-info",
+                "pkg-2.0.data",
+            )
+        };
+        assert_eq!(
+            rename("pkg-1.0.dist-info\\WHEEL").unwrap(),
+            "pkg-2.0.dist-info/WHEEL"
+        );
+        assert_eq!(
+            rename("pkg-1.0.data\\scripts\\tool").unwrap(),
+            "pkg-2.0.data/scripts/tool"
+        );
+        assert_eq!(rename("pkg\\mod.py").unwrap(), "pkg/mod.py");
+
+        // Unchanged directories never need the old name's `.data` directory
+        let unchanged = renamed_path("pkg\\mod.py", "pkg", "pkg", "pkg.data").unwrap();
+        assert_eq!(unchanged, "pkg/mod.py");
+
+        let err = renamed_path("pkg/mod.py", "pkg-1.0", "pkg-2.0.dist-info", "pkg-2.0.data");
+        assert!(matches!(err, Err(WheelError::InvalidWheel(_))));
+    }
+
+    #[test]
+    fn test_rename_package_path() {
+        let renames = vec![("old".to_string(), "new".to_string())];
+        let rename = |path: &str| rename_package_path(path.to_string(), &renames);
+        assert_eq!(rename("old/__init__.py"), "new/__init__.py");
+        assert_eq!(rename("old/"), "new/");
+        assert_eq!(
+            rename("pkg-1.0.data/platlib/old/_ext.so"),
+            "pkg-1.0.data/platlib/new/_ext.so"
+        );
+        assert_eq!(rename("older/mod.py"), "older/mod.py");
+        assert_eq!(rename("old.py"), "old.py");
+        assert_eq!(
+            rename("pkg-1.0.data/scripts/old/x"),
+            "pkg-1.0.data/scripts/old/x"
+        );
+        assert_eq!(rename("sub/old/mod.py"), "sub/old/mod.py");
+    }
+
+    #[test]
+    fn test_write_modified_extended_normalizes_separators() {
+        let wheel_data = create_test_wheel();
+        let mut source = ZipArchive::new(Cursor::new(wheel_data)).unwrap();
+
+        let mut metadata = Metadata::default();
+        metadata.metadata_version = "2.1".to_string();
+        metadata.name = "test-pkg".to_string();
+        metadata.version = "1.0.1".to_string();
+
+        let record = Record::parse("test_pkg/__init__.py,sha256=abc,21\n").unwrap();
+
+        let added_path = std::path::PathBuf::from("test_pkg-1.0.0.dist-info").join("extra.txt");
+        let mut added_files = HashMap::new();
+        added_files.insert(added_path.to_string_lossy().into_owned(), b"x".to_vec());
+        added_files.insert("test_pkg\\data\\blob.bin".to_string(), b"y".to_vec());
+
+        let mut output = Cursor::new(Vec::new());
+        write_modified_extended(
+            &mut source,
+            &mut output,
+            &metadata,
+            &record,
+            "test_pkg-1.0.0.dist-info",
+            "test_pkg-1.0.1.dist-info",
+            &HashMap::new(),
+            &added_files,
+            &HashSet::new(),
+            None,
+        )
+        .unwrap();
+
+        let mut result = ZipArchive::new(Cursor::new(output.into_inner())).unwrap();
+        for i in 0..result.len() {
+            let name = result.by_index(i).unwrap().name().to_string();
+            assert!(!name.contains('\\'), "member name uses backslash: {name}");
+        }
+        assert!(result.by_name("test_pkg-1.0.1.dist-info/extra.txt").is_ok());
+        assert!(result.by_name("test_pkg/data/blob.bin").is_ok());
+
+        let mut content = String::new();
+        result
+            .by_name("test_pkg-1.0.1.dist-info/RECORD")
+            .unwrap()
+            .read_to_string(&mut content)
+            .unwrap();
+        let record = Record::parse(&content).unwrap();
+        assert!(record.entries.iter().all(|e| !e.path.contains('\\')));
+        assert!(record.find("test_pkg-1.0.1.dist-info/extra.txt").is_some());
+        assert!(record.find("test_pkg/data/blob.bin").is_some());
+    }
+
     #[test]
     fn test_write_modified_version_change() {
         let wheel_data = create_test_wheel();
@@ -496,6 +1194,21 @@ mod tests {
         assert!(found_new_metadata, "New METADATA path not found");
     }
 
+    #[test]
+    fn test_zip_datetime() {
+        assert_eq!(zip_datetime(0), zip::DateTime::default());
+        assert_eq!(zip_datetime(DOS_EPOCH), zip::DateTime::default());
+        assert_eq!(
+            zip_datetime(1_700_000_000),
+            zip::DateTime::from_date_and_time(2023, 11, 14, 22, 13, 20).unwrap()
+        );
+        assert_eq!(
+            zip_datetime(951_782_400),
+            zip::DateTime::from_date_and_time(2000, 2, 29, 0, 0, 0).unwrap()
+        );
+        assert_eq!(zip_datetime(u64::MAX / 2).year(), 2107);
+    }
+
     fn create_test_wheel_with_data() -> Vec<u8> {
         let mut buf = Cursor::new(Vec::new());
         {
@@ -607,6 +1320,7 @@ mod tests {
             "test_pkg-1.0.1.dist-info",
             &modified_files,
             &added_files,
+            &HashSet::new(),
             Some(&wheel_info),
         )
         .unwrap();
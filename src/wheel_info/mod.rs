@@ -2,5 +2,11 @@
 
 mod types;
 
+pub use types::BuildTag;
+pub use types::Libc;
 pub use types::WheelInfo;
 pub use types::WheelTag;
+pub use types::compress_tags;
+pub use types::platform_libc;
+pub use types::validate_python_tag;
+pub(crate) use types::validate_tag_component;
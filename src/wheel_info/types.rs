@@ -7,6 +7,7 @@ use crate::error::WheelInfoError;
 
 /// Wheel tag representing a compatibility tag (python-abi-platform)
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct WheelTag {
     pub python: String,   // e.g., "cp311", "py3"
     pub abi: String,      // e.g., "cp311", "none"
@@ -31,19 +32,260 @@ impl WheelTag {
         })
     }
 
+    /// Parse a possibly compressed tag such as `py2.py3-none-any`
+    ///
+    /// Each dot-separated component is a set, and the result is their cross
+    /// product in order (python, then abi, then platform), so
+    /// `cp38.cp39-abi3-manylinux1_x86_64` gives two tags. A tag without dots
+    /// gives exactly what `parse` would.
+    pub fn parse_compressed(s: &str) -> Result<Vec<Self>, WheelInfoError> {
+        let tag = Self::parse(s)?;
+        let split = |component: &str| -> Result<Vec<String>, WheelInfoError> {
+            let values: Vec<String> = component.split('.').map(str::to_string).collect();
+            if values.iter().any(String::is_empty) {
+                return Err(WheelInfoError::InvalidTag(format!(
+                    "Empty component in compressed tag: '{}'",
+                    s
+                )));
+            }
+            Ok(values)
+        };
+        let (pythons, abis, platforms) =
+            (split(&tag.python)?, split(&tag.abi)?, split(&tag.platform)?);
+
+        let mut tags = Vec::new();
+        for python in &pythons {
+            for abi in &abis {
+                for platform in &platforms {
+                    tags.push(Self {
+                        python: python.clone(),
+                        abi: abi.clone(),
+                        platform: platform.clone(),
+                    });
+                }
+            }
+        }
+        Ok(tags)
+    }
+
     /// Serialize the tag back to string format
     pub fn serialize(&self) -> String {
         format!("{}-{}-{}", self.python, self.abi, self.platform)
     }
+
+    /// The `(major, minor)` libc version the platform requires, if it is a
+    /// manylinux or musllinux platform (see `platform_libc`)
+    pub fn libc_version(&self) -> Option<(u32, u32)> {
+        platform_libc(&self.platform).map(|(_, version)| version)
+    }
+}
+
+/// Compress a set of tags into as few dotted tags as the set allows
+///
+/// Tags that differ in a single component are merged, repeatedly, so
+/// `py2-none-any` and `py3-none-any` become `py2.py3-none-any`. Every
+/// result expands (see `WheelTag::parse_compressed`) to tags from the
+/// input, and together they cover all of it; a set that isn't a cross
+/// product comes back as several strings. Values keep first-seen order.
+pub fn compress_tags(tags: &[WheelTag]) -> Vec<String> {
+    let mut groups: Vec<[Vec<&str>; 3]> = Vec::new();
+    for tag in tags {
+        let group = [
+            vec![tag.python.as_str()],
+            vec![tag.abi.as_str()],
+            vec![tag.platform.as_str()],
+        ];
+        if !groups.contains(&group) {
+            groups.push(group);
+        }
+    }
+
+    let same = |a: &[&str], b: &[&str]| a.len() == b.len() && a.iter().all(|v| b.contains(v));
+    'merge: loop {
+        for i in 0..groups.len() {
+            for j in i + 1..groups.len() {
+                let differing: Vec<usize> = (0..3)
+                    .filter(|&k| !same(&groups[i][k], &groups[j][k]))
+                    .collect();
+                match differing[..] {
+                    // An earlier merge made the two identical
+                    [] => {
+                        groups.remove(j);
+                        continue 'merge;
+                    }
+                    [k] => {
+                        let other = groups.remove(j);
+                        for value in &other[k] {
+                            if !groups[i][k].contains(value) {
+                                groups[i][k].push(value);
+                            }
+                        }
+                        continue 'merge;
+                    }
+                    _ => {}
+                }
+            }
+        }
+        break;
+    }
+
+    groups
+        .iter()
+        .map(|[python, abi, platform]| {
+            format!(
+                "{}-{}-{}",
+                python.join("."),
+                abi.join("."),
+                platform.join(".")
+            )
+        })
+        .collect()
+}
+
+/// C library family targeted by a Linux platform tag
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Libc {
+    /// `manylinux` tags (PEP 513, 571, 599, 600)
+    Glibc,
+    /// `musllinux` tags (PEP 656)
+    Musl,
+}
+
+/// Legacy manylinux aliases and the glibc version each stands for
+const MANYLINUX_ALIASES: &[(&str, (u32, u32))] = &[
+    ("manylinux1_", (2, 5)),
+    ("manylinux2010_", (2, 12)),
+    ("manylinux2014_", (2, 17)),
+];
+
+/// Parse the libc family and `(major, minor)` version a platform tag requires
+///
+/// Handles PEP 600 `manylinux_X_Y_arch`, PEP 656 `musllinux_X_Y_arch`, and
+/// the legacy `manylinux1`/`manylinux2010`/`manylinux2014` aliases (glibc
+/// 2.5, 2.12 and 2.17). For a compressed tag set such as
+/// `manylinux_2_17_x86_64.manylinux2014_x86_64`, the lowest version of the
+/// first tag's family is returned. Returns `None` for other platforms
+/// (e.g. `linux_x86_64`, `any`).
+pub fn platform_libc(platform: &str) -> Option<(Libc, (u32, u32))> {
+    let mut parsed = platform.split('.').filter_map(parse_single_platform_libc);
+    let (libc, first) = parsed.next()?;
+    let lowest = parsed
+        .filter(|(other, _)| *other == libc)
+        .map(|(_, version)| version)
+        .fold(first, |lowest, version| lowest.min(version));
+    Some((libc, lowest))
+}
+
+fn parse_single_platform_libc(platform: &str) -> Option<(Libc, (u32, u32))> {
+    for (alias, version) in MANYLINUX_ALIASES {
+        if platform.strip_prefix(alias).is_some_and(|arch| !arch.is_empty()) {
+            return Some((Libc::Glibc, *version));
+        }
+    }
+
+    let (libc, rest) = if let Some(rest) = platform.strip_prefix("manylinux_") {
+        (Libc::Glibc, rest)
+    } else if let Some(rest) = platform.strip_prefix("musllinux_") {
+        (Libc::Musl, rest)
+    } else {
+        return None;
+    };
+    let mut parts = rest.splitn(3, '_');
+    let major = parts.next()?.parse().ok()?;
+    let minor = parts.next()?.parse().ok()?;
+    let arch = parts.next()?;
+    if arch.is_empty() {
+        return None;
+    }
+    Some((libc, (major, minor)))
+}
+
+/// Check that a Python tag looks like a PEP 425 interpreter tag
+///
+/// That's an implementation abbreviation followed by a version, e.g. `py3`,
+/// `cp311`, `pp310` or `graalpy311`. Compressed tag sets (`py2.py3`) are
+/// rejected: each WHEEL `Tag` line carries a single Python tag.
+pub fn validate_python_tag(python: &str) -> Result<(), WheelInfoError> {
+    let digits = python.trim_start_matches(|c: char| c.is_ascii_lowercase());
+    let plausible = digits.len() < python.len()
+        && !digits.is_empty()
+        && digits.chars().all(|c| c.is_ascii_digit());
+    if plausible {
+        Ok(())
+    } else {
+        Err(WheelInfoError::InvalidTag(format!(
+            "'{}' is not a plausible Python tag (expected e.g. py3 or cp311)",
+            python
+        )))
+    }
+}
+
+/// Check that an ABI or platform tag can go in a `Tag` line and filename
+///
+/// `kind` names the component in the error. The tag must be non-empty and
+/// contain no `-` (which separates the components) or whitespace.
+pub(crate) fn validate_tag_component(kind: &str, tag: &str) -> Result<(), WheelInfoError> {
+    if tag.is_empty() {
+        return Err(WheelInfoError::InvalidTag(format!(
+            "{} tag cannot be empty",
+            kind
+        )));
+    }
+    if tag.contains(|c: char| c == '-' || c.is_whitespace()) {
+        return Err(WheelInfoError::InvalidTag(format!(
+            "'{}' is not a valid {} tag (no '-' or whitespace allowed)",
+            tag, kind
+        )));
+    }
+    Ok(())
+}
+
+/// A PEP 427 build tag: a number followed by an arbitrary suffix
+///
+/// `123` is `{ number: 123, suffix: "" }` and `0rc1` is
+/// `{ number: 0, suffix: "rc1" }`. Installers order builds by the number,
+/// then the suffix, which the derived `Ord` matches.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct BuildTag {
+    pub number: u32,
+    pub suffix: String,
+}
+
+impl BuildTag {
+    /// Split a build tag into its leading number and suffix
+    ///
+    /// Returns `None` if it doesn't start with a digit or the number doesn't
+    /// fit in a `u32`.
+    pub fn parse(build: &str) -> Option<Self> {
+        let digits = build
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(build.len());
+        let (number, suffix) = build.split_at(digits);
+        Some(Self {
+            number: number.parse().ok()?,
+            suffix: suffix.to_string(),
+        })
+    }
+}
+
+impl std::fmt::Display for BuildTag {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}{}", self.number, self.suffix)
+    }
 }
 
 /// WHEEL file information per PEP 427
 #[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
 pub struct WheelInfo {
     pub wheel_version: String,
     pub generator: Option<String>,
     pub root_is_purelib: bool,
+    /// Every tag, with compressed `Tag` lines expanded (see
+    /// `compressed_tags` for the compact form)
     pub tags: Vec<WheelTag>,
+    /// The `Build` field verbatim (see `build_tag` for its parts)
     pub build: Option<String>,
     /// For preserving unknown headers
     pub extra_headers: HashMap<String, Vec<String>>,
@@ -86,10 +328,7 @@ impl WheelInfo {
             "Root-Is-Purelib" => {
                 self.root_is_purelib = value.eq_ignore_ascii_case("true");
             }
-            "Tag" => {
-                let tag = WheelTag::parse(value)?;
-                self.tags.push(tag);
-            }
+            "Tag" => self.tags.extend(WheelTag::parse_compressed(value)?),
             "Build" => self.build = Some(value.to_string()),
             _ => {
                 // Preserve unknown headers
@@ -133,16 +372,112 @@ impl WheelInfo {
         output
     }
 
+    /// List every populated field as `(header name, values)` pairs
+    ///
+    /// Fields are in serialization order, with unknown headers sorted by
+    /// name. `Tag` holds one value per tag.
+    pub fn fields(&self) -> Vec<(String, Vec<String>)> {
+        let mut fields = vec![(
+            "Wheel-Version".to_string(),
+            vec![self.wheel_version.clone()],
+        )];
+        if let Some(ref gen) = self.generator {
+            fields.push(("Generator".to_string(), vec![gen.clone()]));
+        }
+        fields.push((
+            "Root-Is-Purelib".to_string(),
+            vec![self.root_is_purelib.to_string()],
+        ));
+        fields.push((
+            "Tag".to_string(),
+            self.tags.iter().map(WheelTag::serialize).collect(),
+        ));
+        if let Some(ref build) = self.build {
+            fields.push(("Build".to_string(), vec![build.clone()]));
+        }
+        let mut extra: Vec<_> = self.extra_headers.iter().collect();
+        extra.sort_by(|a, b| a.0.cmp(b.0));
+        for (key, values) in extra {
+            fields.push((key.clone(), values.clone()));
+        }
+        fields
+    }
+
+    /// The `Build` field split into number and suffix
+    ///
+    /// `None` if there is no build tag or it doesn't parse; an unparseable
+    /// one is still kept and written back verbatim.
+    pub fn build_tag(&self) -> Option<BuildTag> {
+        self.build.as_deref().and_then(BuildTag::parse)
+    }
+
+    /// Set the build number, keeping the suffix of the current build tag
+    ///
+    /// A missing or unparseable build tag is replaced by the bare number.
+    pub fn set_build_number(&mut self, number: u32) {
+        let suffix = self.build_tag().map(|tag| tag.suffix).unwrap_or_default();
+        self.build = Some(BuildTag { number, suffix }.to_string());
+    }
+
     /// Get the primary python tag (first tag's python component)
     pub fn python(&self) -> Option<&str> {
         self.tags.first().map(|t| t.python.as_str())
     }
 
     /// Set the python component for all tags
+    ///
+    /// Tags that become identical (e.g. `py2-none-any` and `py3-none-any`
+    /// set to `py3`) are collapsed into one.
     pub fn set_python(&mut self, python: &str) {
         for tag in &mut self.tags {
             tag.python = python.to_string();
         }
+        self.dedup_tags();
+    }
+
+    /// Remove exact duplicate tags, keeping the first occurrence of each
+    ///
+    /// Returns the number of tags removed.
+    pub fn dedup_tags(&mut self) -> usize {
+        let before = self.tags.len();
+        let mut seen = Vec::new();
+        self.tags.retain(|tag| {
+            if seen.contains(tag) {
+                false
+            } else {
+                seen.push(tag.clone());
+                true
+            }
+        });
+        before - self.tags.len()
+    }
+
+    /// The tags in compact form, e.g. `["py2.py3-none-any"]`
+    ///
+    /// The WHEEL file itself is written with one expanded tag per line.
+    pub fn compressed_tags(&self) -> Vec<String> {
+        compress_tags(&self.tags)
+    }
+
+    /// Append a tag unless an identical one is already present
+    ///
+    /// Returns whether the tag was added.
+    pub fn add_tag(&mut self, tag: WheelTag) -> bool {
+        if self.tags.contains(&tag) {
+            return false;
+        }
+        self.tags.push(tag);
+        true
+    }
+
+    /// Remove every copy of a tag
+    ///
+    /// Returns whether anything was removed. This may leave no tags at all,
+    /// which the WHEEL file doesn't allow; saving rejects that.
+    pub fn remove_tag(&mut self, tag: &WheelTag) -> bool {
+        let before = self.tags.len();
+        self.tags.retain(|t| t != tag);
+        self.tags.len() != before
     }
 
     /// Get the primary ABI tag (first tag's abi component)
@@ -174,6 +509,148 @@ impl WheelInfo {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_build_tag() {
+        let parse = |build: &str| {
+            WheelInfo::parse(&format!(
+                "Wheel-Version: 1.0\nRoot-Is-Purelib: true\nTag: py3-none-any\nBuild: {build}\n"
+            ))
+            .unwrap()
+        };
+
+        let mut info = parse("0rc1");
+        assert_eq!(
+            info.build_tag(),
+            Some(BuildTag {
+                number: 0,
+                suffix: "rc1".to_string()
+            })
+        );
+        info.set_build_number(1);
+        assert_eq!(info.build.as_deref(), Some("1rc1"));
+        assert!(info.serialize().contains("Build: 1rc1\n"));
+
+        // Leading zeros survive until the number is changed
+        let info = parse("007");
+        assert_eq!(info.build_tag().unwrap().number, 7);
+        assert!(info.serialize().contains("Build: 007\n"));
+
+        // Unparseable tags are kept verbatim, and replaced by a new number
+        let mut info = parse("nightly");
+        assert_eq!(info.build_tag(), None);
+        assert!(info.serialize().contains("Build: nightly\n"));
+        info.set_build_number(2);
+        assert_eq!(info.build.as_deref(), Some("2"));
+
+        assert!(BuildTag::parse("2") < BuildTag::parse("10"));
+        assert!(BuildTag::parse("1a") > BuildTag::parse("1"));
+    }
+
+    #[test]
+    fn test_add_and_remove_tag() {
+        let mut info =
+            WheelInfo::parse("Wheel-Version: 1.0\nRoot-Is-Purelib: true\nTag: py3-none-any\n")
+                .unwrap();
+        let py2 = WheelTag::parse("py2-none-any").unwrap();
+        assert!(info.add_tag(py2.clone()));
+        assert!(!info.add_tag(py2.clone()));
+        assert_eq!(info.tags.len(), 2);
+        assert!(
+            info.serialize()
+                .ends_with("Tag: py3-none-any\nTag: py2-none-any\n")
+        );
+
+        assert!(info.remove_tag(&py2));
+        assert!(!info.remove_tag(&py2));
+        assert!(info.remove_tag(&WheelTag::parse("py3-none-any").unwrap()));
+        assert!(info.tags.is_empty());
+    }
+
+    #[test]
+    fn test_compressed_tags() {
+        let tags = WheelTag::parse_compressed("cp38.cp39-abi3-manylinux1_x86_64").unwrap();
+        assert_eq!(
+            tags.iter().map(WheelTag::serialize).collect::<Vec<_>>(),
+            vec!["cp38-abi3-manylinux1_x86_64", "cp39-abi3-manylinux1_x86_64"]
+        );
+        assert_eq!(
+            compress_tags(&tags),
+            vec!["cp38.cp39-abi3-manylinux1_x86_64"]
+        );
+        assert!(WheelTag::parse_compressed("py2.-none-any").is_err());
+
+        // Compressed Tag lines are expanded, and written back one per line
+        let info = WheelInfo::parse(
+            "Wheel-Version: 1.0\nRoot-Is-Purelib: false\n\
+             Tag: cp311-cp311-manylinux_2_17_x86_64.manylinux2014_x86_64\n",
+        )
+        .unwrap();
+        assert_eq!(info.tags.len(), 2);
+        assert_eq!(
+            info.compressed_tags(),
+            vec!["cp311-cp311-manylinux_2_17_x86_64.manylinux2014_x86_64"]
+        );
+        assert!(info.serialize().contains(
+            "Tag: cp311-cp311-manylinux_2_17_x86_64\nTag: cp311-cp311-manylinux2014_x86_64\n"
+        ));
+
+        // A set that isn't a cross product needs several compact tags
+        let mixed: Vec<WheelTag> = ["py2-none-any", "py3-none-any", "cp311-cp311-linux_x86_64"]
+            .into_iter()
+            .map(|tag| WheelTag::parse(tag).unwrap())
+            .collect();
+        assert_eq!(
+            compress_tags(&mixed),
+            vec!["py2.py3-none-any", "cp311-cp311-linux_x86_64"]
+        );
+    }
+
+    #[test]
+    fn test_validate_python_tag() {
+        for tag in ["py3", "cp311", "pp310", "graalpy311"] {
+            assert!(validate_python_tag(tag).is_ok(), "{tag}");
+        }
+        for tag in ["", "3", "py", "py2.py3", "cp3x", "CP311", "py-3"] {
+            assert!(validate_python_tag(tag).is_err(), "{tag}");
+        }
+    }
+
+    #[test]
+    fn test_validate_tag_component() {
+        for tag in ["none", "abi3", "manylinux_2_28_x86_64", "any"] {
+            assert!(validate_tag_component("ABI", tag).is_ok(), "{tag}");
+        }
+        for tag in ["", "linux-x86_64", "any ", "many\tlinux"] {
+            assert!(validate_tag_component("platform", tag).is_err(), "{tag:?}");
+        }
+    }
+
+    #[test]
+    fn test_set_python_collapses_duplicate_tags() {
+        let mut info = WheelInfo::parse(
+            "Wheel-Version: 1.0\nRoot-Is-Purelib: true\nTag: py2-none-any\nTag: py3-none-any\n",
+        )
+        .unwrap();
+        info.set_python("py3");
+        assert_eq!(info.tags.len(), 1);
+        assert_eq!(info.tags[0].serialize(), "py3-none-any");
+    }
+
+    #[test]
+    fn test_dedup_tags() {
+        let mut info = WheelInfo::parse(
+            "Wheel-Version: 1.0\nRoot-Is-Purelib: false\nTag: cp311-cp311-linux_x86_64\nTag: cp311-abi3-linux_x86_64\nTag: cp311-cp311-linux_x86_64\n",
+        )
+        .unwrap();
+        assert_eq!(info.dedup_tags(), 1);
+        let tags: Vec<String> = info.tags.iter().map(WheelTag::serialize).collect();
+        assert_eq!(
+            tags,
+            ["cp311-cp311-linux_x86_64", "cp311-abi3-linux_x86_64"]
+        );
+        assert_eq!(info.dedup_tags(), 0);
+    }
+
     #[test]
     fn test_parse_wheel_tag() {
         let tag = WheelTag::parse("cp311-cp311-linux_x86_64").unwrap();
@@ -213,6 +690,44 @@ Tag: py2-none-any
         assert_eq!(info.tags[1].python, "py2");
     }
 
+    #[test]
+    fn test_platform_libc_pep600() {
+        assert_eq!(
+            platform_libc("manylinux_2_28_x86_64"),
+            Some((Libc::Glibc, (2, 28)))
+        );
+        assert_eq!(
+            platform_libc("manylinux_2_17_aarch64"),
+            Some((Libc::Glibc, (2, 17)))
+        );
+        assert_eq!(
+            platform_libc("musllinux_1_2_x86_64"),
+            Some((Libc::Musl, (1, 2)))
+        );
+        assert_eq!(platform_libc("manylinux_2_x86_64"), None);
+        assert_eq!(platform_libc("manylinux_2_28_"), None);
+        assert_eq!(platform_libc("linux_x86_64"), None);
+        assert_eq!(platform_libc("any"), None);
+        assert_eq!(platform_libc("macosx_11_0_arm64"), None);
+    }
+
+    #[test]
+    fn test_platform_libc_legacy_aliases() {
+        assert_eq!(platform_libc("manylinux1_x86_64"), Some((Libc::Glibc, (2, 5))));
+        assert_eq!(platform_libc("manylinux2010_i686"), Some((Libc::Glibc, (2, 12))));
+        assert_eq!(
+            platform_libc("manylinux2014_aarch64"),
+            Some((Libc::Glibc, (2, 17)))
+        );
+        assert_eq!(
+            platform_libc("manylinux_2_28_x86_64.manylinux2014_x86_64"),
+            Some((Libc::Glibc, (2, 17)))
+        );
+
+        let tag = WheelTag::parse("cp312-cp312-manylinux2014_x86_64").unwrap();
+        assert_eq!(tag.libc_version(), Some((2, 17)));
+    }
+
     #[test]
     fn test_set_platform() {
         let content = r#"Wheel-Version: 1.0
@@ -17,7 +17,14 @@ import zipfile
 from pathlib import Path
 
 import pytest
-from editwheel import normalize_dist_info_name, WheelEditor
+from editwheel import (
+    EditwheelError,
+    EntryPointsError,
+    MetadataError,
+    normalize_dist_info_name,
+    PatternError,
+    WheelEditor,
+)
 
 
 def create_test_wheel(temp_dir: Path) -> Path:
@@ -258,6 +265,47 @@ class TestGetSetMetadata:
             editor.set_metadata("Classifier", new_classifiers)
             assert editor.get_metadata("Classifier") == new_classifiers
 
+    def test_set_metadata_list_on_single_value_field_raises(self):
+        """Test that a list for a single-value field is rejected."""
+        with tempfile.TemporaryDirectory() as temp_dir:
+            temp_path = Path(temp_dir)
+            test_wheel = create_test_wheel(temp_path)
+
+            editor = WheelEditor(str(test_wheel))
+
+            with pytest.raises(ValueError, match="Summary"):
+                editor.set_metadata("Summary", ["a", "b"])
+            assert editor.get_metadata("Summary") == "A test package for wheel editor validation"
+
+    def test_errors_raise_specific_exceptions(self):
+        """Test that errors map onto the editwheel exception hierarchy."""
+        with tempfile.TemporaryDirectory() as temp_dir:
+            temp_path = Path(temp_dir)
+            test_wheel = create_test_wheel(temp_path)
+
+            editor = WheelEditor(str(test_wheel))
+
+            with pytest.raises(MetadataError, match="Name") as excinfo:
+                editor.remove_metadata("Name")
+            assert isinstance(excinfo.value, EditwheelError)
+            assert isinstance(excinfo.value, ValueError)
+
+            with pytest.raises(PatternError) as excinfo:
+                editor.set_hashless_patterns(["pkg/[*.pyc"])
+            assert isinstance(excinfo.value, EditwheelError)
+
+    def test_set_metadata_string_on_multi_value_field(self):
+        """Test that a string for a multi-value field becomes a one-element list."""
+        with tempfile.TemporaryDirectory() as temp_dir:
+            temp_path = Path(temp_dir)
+            test_wheel = create_test_wheel(temp_path)
+
+            editor = WheelEditor(str(test_wheel))
+
+            editor.set_metadata("Requires-Dist", "click>=8.0")
+            assert editor.get_metadata("Requires-Dist") == ["click>=8.0"]
+            assert editor.requires_dist == ["click>=8.0"]
+
     def test_custom_metadata_fields(self):
         """Test setting custom metadata fields."""
         with tempfile.TemporaryDirectory() as temp_dir:
@@ -333,6 +381,22 @@ class TestSaveWheel:
             assert new_editor.version == "1.0.1"
             assert new_editor.summary == "Overwritten wheel"
 
+    def test_save_with_generated_name(self):
+        """The output is named after the current metadata and tags."""
+        with tempfile.TemporaryDirectory() as temp_dir:
+            temp_path = Path(temp_dir)
+            test_wheel = create_test_wheel(temp_path)
+
+            editor = WheelEditor(str(test_wheel))
+            editor.version = "1.0.1"
+            expected = "test_package-1.0.1-py3-none-any.whl"
+            assert editor.wheel_filename == expected
+            assert editor.filename == expected
+
+            saved = editor.save_with_generated_name(str(temp_path))
+            assert saved == str(temp_path / expected)
+            assert WheelEditor(saved).version == "1.0.1"
+
     def test_record_file_updated(self):
         """Test that RECORD file is properly updated with new hashes."""
         with tempfile.TemporaryDirectory() as temp_dir:
@@ -692,6 +756,38 @@ class TestPlatformTag:
             new_editor = WheelEditor(str(output_path))
             assert new_editor.platform_tag == "manylinux_2_28_x86_64"
 
+    def test_set_empty_platform_tag(self):
+        """Test that an empty platform tag is rejected and nothing changes."""
+        with tempfile.TemporaryDirectory() as temp_dir:
+            temp_path = Path(temp_dir)
+            test_wheel = create_test_wheel(temp_path)
+
+            editor = WheelEditor(str(test_wheel))
+
+            with pytest.raises(ValueError, match="empty"):
+                editor.platform_tag = ""
+            assert editor.platform_tag == "any"
+
+    def test_retag(self):
+        """Test retag and that a bad component leaves every tag alone."""
+        with tempfile.TemporaryDirectory() as temp_dir:
+            temp_path = Path(temp_dir)
+            test_wheel = create_test_wheel(temp_path)
+
+            editor = WheelEditor(str(test_wheel))
+            editor.retag(python="cp312", abi="abi3", platform="linux_x86_64")
+            assert editor.filename == "test_package-1.0.0-cp312-abi3-linux_x86_64.whl"
+
+            for kwargs in [
+                {"python": "py-3"},
+                {"abi": ""},
+                {"platform": "linux-x86_64"},
+                {"abi": "none", "platform": "any thing"},
+            ]:
+                with pytest.raises(ValueError):
+                    editor.retag(**kwargs)
+            assert editor.filename == "test_package-1.0.0-cp312-abi3-linux_x86_64.whl"
+
 
 class TestRpathOperations:
     """Tests for RPATH operations on ELF files."""
@@ -751,6 +847,26 @@ class TestAddRequiresDist:
             new_editor = WheelEditor(str(output_path))
             assert "nccl-lib>=1.0" in new_editor.requires_dist
 
+    def test_merge_from_other_editor(self):
+        """Test merging dependencies and classifiers from another editor."""
+        with tempfile.TemporaryDirectory() as temp_dir:
+            temp_path = Path(temp_dir)
+            test_wheel = create_test_wheel(temp_path)
+
+            editor = WheelEditor(str(test_wheel))
+            other = WheelEditor(str(test_wheel))
+            other.add_requires_dist("nccl-lib>=1.0")
+            other.classifiers = other.classifiers + ["Topic :: Utilities"]
+
+            assert editor.merge_requires_dist_from(other) == 1
+            assert editor.merge_classifiers_from(other) == 1
+            assert "nccl-lib>=1.0" in editor.requires_dist
+            assert "Topic :: Utilities" in editor.classifiers
+
+            # Merging an editor into itself adds nothing
+            assert editor.merge_requires_dist_from(editor) == 0
+            assert editor.merge_classifiers_from(editor) == 0
+
 
 class TestCLI:
     """Tests for CLI commands."""
@@ -899,6 +1015,28 @@ class TestCLI:
             assert editor.python_tag == "py3", "python tag should be unchanged"
             assert editor.platform_tag == "any", "platform tag should be unchanged"
 
+    def test_cli_edit_invalid_abi_tag(self):
+        """Test CLI edit rejects an ABI tag that would break the filename."""
+        with tempfile.TemporaryDirectory() as temp_dir:
+            temp_path = Path(temp_dir)
+            test_wheel = create_test_wheel(temp_path)
+            output_path = temp_path / "edited.whl"
+
+            result = self._run_cli(
+                [
+                    "edit",
+                    str(test_wheel),
+                    "--abi-tag",
+                    "cp3-12",
+                    "-o",
+                    str(output_path),
+                ],
+            )
+
+            assert result.exit_code != 0
+            assert "abi tag" in result.stderr.lower()
+            assert not output_path.exists()
+
     def test_cli_edit_output_dir(self):
         """Test CLI edit with -o pointing to a directory uses computed filename."""
         with tempfile.TemporaryDirectory() as temp_dir:
@@ -1143,6 +1281,101 @@ class TestValidate:
             assert any("hash mismatch" in e for e in result.errors)
             assert any("__init__.py" in e for e in result.errors)
 
+            [detail] = result.details
+            assert detail.kind == "hash_mismatch"
+            assert detail.path == "test_package/__init__.py"
+            assert detail.expected != detail.actual
+            assert str(detail) == result.errors[0]
+            assert repr(detail).startswith('ValidationIssue(kind="hash_mismatch"')
+
+
+class TestRenamePackage:
+    """Tests for moving a top-level package directory."""
+
+    def test_rename_package(self):
+        """rename_package moves the package directory and its RECORD lines."""
+        with tempfile.TemporaryDirectory() as temp_dir:
+            temp_path = Path(temp_dir)
+            test_wheel = create_test_wheel(temp_path)
+            output = temp_path / "output.whl"
+
+            editor = WheelEditor(str(test_wheel))
+            editor.name = "forked-package"
+            assert editor.rename_package("missing", "other") == 0
+            assert editor.rename_package("test_package", "forked_package") == 2
+            editor.save(str(output))
+
+            saved = WheelEditor(str(output))
+            assert saved.read_file("forked_package/__init__.py")
+            top_level = "forked_package-1.0.0.dist-info/top_level.txt"
+            assert saved.read_file(top_level) == b"forked_package\n"
+            assert saved.validate().is_valid
+
+
+class TestEntryPoints:
+    """Tests for reading and editing entry_points.txt."""
+
+    def test_entry_points(self):
+        """Entry points are created, renamed and saved under the new dist-info."""
+        with tempfile.TemporaryDirectory() as temp_dir:
+            temp_path = Path(temp_dir)
+            test_wheel = create_test_wheel(temp_path)
+            output = temp_path / "output.whl"
+
+            editor = WheelEditor(str(test_wheel))
+            assert editor.entry_points() == []
+            editor.set_entry_point("console_scripts", "old-tool", "test_package:main")
+            assert editor.rename_entry_point("console_scripts", "old-tool", "new-tool")
+            assert not editor.remove_entry_point("gui_scripts", "old-tool")
+            editor.version = "2.0.0"
+            editor.save(str(output))
+
+            saved = WheelEditor(str(output))
+            assert saved.entry_points("console_scripts") == [
+                ("console_scripts", "new-tool", "test_package:main")
+            ]
+            assert saved.validate().is_valid
+
+    def test_rename_entry_point_onto_existing_name(self):
+        """Renaming onto another entry point's name fails and changes nothing."""
+        with tempfile.TemporaryDirectory() as temp_dir:
+            temp_path = Path(temp_dir)
+            test_wheel = create_test_wheel(temp_path)
+
+            editor = WheelEditor(str(test_wheel))
+            editor.set_entry_point("console_scripts", "old-tool", "test_package:old")
+            editor.set_entry_point("console_scripts", "new-tool", "test_package:new")
+            with pytest.raises(EntryPointsError, match="already exists"):
+                editor.rename_entry_point("console_scripts", "old-tool", "new-tool")
+            assert editor.entry_points("console_scripts") == [
+                ("console_scripts", "old-tool", "test_package:old"),
+                ("console_scripts", "new-tool", "test_package:new"),
+            ]
+
+
+class TestContextManager:
+    """Tests for using WheelEditor in a `with` block."""
+
+    def test_context_manager_autosave(self):
+        """Leaving a `with` block saves in place only with autosave."""
+        with tempfile.TemporaryDirectory() as temp_dir:
+            temp_path = Path(temp_dir)
+            test_wheel = create_test_wheel(temp_path)
+
+            with WheelEditor(str(test_wheel)) as editor:
+                editor.summary = "Not saved"
+            assert WheelEditor(str(test_wheel)).summary != "Not saved"
+
+            with pytest.raises(RuntimeError):
+                with WheelEditor(str(test_wheel), autosave=True) as editor:
+                    editor.summary = "Not saved either"
+                    raise RuntimeError("abort")
+            assert WheelEditor(str(test_wheel)).summary != "Not saved either"
+
+            with WheelEditor(str(test_wheel), autosave=True) as editor:
+                editor.summary = "Saved"
+            assert WheelEditor(str(test_wheel)).summary == "Saved"
+
 
 class TestCLIValidate:
     """Tests for `editwheel validate` CLI subcommand."""
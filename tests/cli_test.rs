@@ -0,0 +1,115 @@
+//! Tests for the `editwheel` binary, run as a subprocess.
+//!
+//! Run with:
+//!   cargo test --features cli --test cli_test
+
+#![cfg(feature = "cli")]
+
+use std::fs::File;
+use std::io::Write;
+use std::path::Path;
+use std::path::PathBuf;
+use std::process::Command;
+use std::process::Output;
+
+use editwheel::WheelEditor;
+use editwheel::hash_content;
+use tempfile::TempDir;
+use zip::ZipWriter;
+use zip::write::SimpleFileOptions;
+
+/// Write a minimal pure-Python wheel for test_pkg 1.0.0 into `dir`
+fn create_test_wheel(dir: &Path) -> PathBuf {
+    let wheel_path = dir.join("test_pkg-1.0.0-py3-none-any.whl");
+    let files: [(&str, &[u8]); 3] = [
+        ("test_pkg/__init__.py", b"__version__ = '1.0.0'\n"),
+        (
+            "test_pkg-1.0.0.dist-info/METADATA",
+            b"Metadata-Version: 2.1\nName: test-pkg\nVersion: 1.0.0\n",
+        ),
+        (
+            "test_pkg-1.0.0.dist-info/WHEEL",
+            b"Wheel-Version: 1.0\nRoot-Is-Purelib: true\nTag: py3-none-any\n",
+        ),
+    ];
+
+    let mut zip = ZipWriter::new(File::create(&wheel_path).unwrap());
+    let options = SimpleFileOptions::default();
+    let mut record = String::new();
+    for (name, content) in files {
+        zip.start_file(name, options).unwrap();
+        zip.write_all(content).unwrap();
+        record.push_str(&format!(
+            "{},{},{}\n",
+            name,
+            hash_content(content),
+            content.len()
+        ));
+    }
+    record.push_str("test_pkg-1.0.0.dist-info/RECORD,,\n");
+    zip.start_file("test_pkg-1.0.0.dist-info/RECORD", options)
+        .unwrap();
+    zip.write_all(record.as_bytes()).unwrap();
+    zip.finish().unwrap();
+    wheel_path
+}
+
+fn editwheel(args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_editwheel"))
+        .args(args)
+        .output()
+        .expect("Failed to run editwheel")
+}
+
+#[test]
+fn test_set_version_saves_under_generated_name() {
+    let temp_dir = TempDir::new().unwrap();
+    let wheel_path = create_test_wheel(temp_dir.path());
+    let original = std::fs::read(&wheel_path).unwrap();
+
+    let output = editwheel(&["set-version", "1.0.1", wheel_path.to_str().unwrap()]);
+    assert_eq!(output.status.code(), Some(0), "{output:?}");
+
+    let saved = temp_dir.path().join("test_pkg-1.0.1-py3-none-any.whl");
+    assert_eq!(WheelEditor::open(&saved).unwrap().version(), "1.0.1");
+    assert_eq!(std::fs::read(&wheel_path).unwrap(), original);
+}
+
+#[test]
+fn test_unchanged_filename_needs_output_or_in_place() {
+    let temp_dir = TempDir::new().unwrap();
+    let wheel_path = create_test_wheel(temp_dir.path());
+    let wheel = wheel_path.to_str().unwrap();
+    let original = std::fs::read(&wheel_path).unwrap();
+
+    let output = editwheel(&["set-rpath", "**/*.so", "$ORIGIN", wheel]);
+    assert_eq!(output.status.code(), Some(2), "{output:?}");
+    assert!(String::from_utf8_lossy(&output.stderr).contains("--in-place"));
+    assert_eq!(std::fs::read(&wheel_path).unwrap(), original);
+
+    let output = editwheel(&["set-rpath", "**/*.so", "$ORIGIN", "--in-place", wheel]);
+    assert_eq!(output.status.code(), Some(0), "{output:?}");
+    let editor = WheelEditor::open(&wheel_path).unwrap();
+    assert!(editor.validate().unwrap().is_valid());
+    assert_eq!(
+        std::fs::read_dir(temp_dir.path()).unwrap().count(),
+        1,
+        "no other file was written"
+    );
+}
+
+#[test]
+fn test_exit_codes() {
+    let temp_dir = TempDir::new().unwrap();
+    let wheel_path = create_test_wheel(temp_dir.path());
+
+    let output = editwheel(&["validate", wheel_path.to_str().unwrap()]);
+    assert_eq!(output.status.code(), Some(0), "{output:?}");
+
+    let missing = temp_dir.path().join("missing-1.0-py3-none-any.whl");
+    let output = editwheel(&["show", missing.to_str().unwrap()]);
+    assert_eq!(output.status.code(), Some(3), "{output:?}");
+
+    let output = editwheel(&["set-version"]);
+    assert_eq!(output.status.code(), Some(2), "{output:?}");
+}
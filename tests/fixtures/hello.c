@@ -0,0 +1,7 @@
+// Source of hello, built with:
+//
+//     gcc -Os -s -o hello hello.c
+
+#include <stdio.h>
+
+int main(void) { return puts("hello") < 0; }
@@ -0,0 +1,8 @@
+// Source of librpath.so, built with:
+//
+//     gcc -shared -fPIC -Os -s -o librpath.so librpath.c \
+//         -Wl,-soname,librpath.so.1 -Wl,-rpath,'$ORIGIN/../lib:$ORIGIN'
+
+#include <stdio.h>
+
+void hello(void) { puts("hello"); }
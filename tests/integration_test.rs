@@ -10,12 +10,16 @@ use std::collections::HashSet;
 use std::fs::File;
 use std::io::BufReader;
 use std::io::Read;
+use std::io::Write;
 use std::path::Path;
 use std::process::Command;
 
 use editwheel::WheelEditor;
 use editwheel::hash_content;
+use editwheel::record::hash_reader;
 use tempfile::TempDir;
+use zip::ZipWriter;
+use zip::write::SimpleFileOptions;
 
 /// Test wheel info from PyPI
 struct TestWheel {
@@ -761,3 +765,71 @@ fn test_combined_elf_and_metadata_edits() {
 
     println!("\n✅ Combined edits test passed!");
 }
+
+/// Round-trip a wheel whose single Stored member is larger than 4 GiB, so
+/// the member and the offsets of everything after it need Zip64.
+///
+/// Writes about 8 GiB to the temp dir and hashes 4 GiB twice, so it's
+/// ignored by default. Run with:
+///   cargo test --release --test integration_test test_zip64_round_trip -- --ignored
+#[test]
+#[ignore]
+fn test_zip64_round_trip() {
+    const SIZE: u64 = (4 << 30) + 1;
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let wheel_path = temp_dir.path().join("big_pkg-1.0.0-py3-none-any.whl");
+    let edited_path = temp_dir.path().join("big_pkg-1.0.1-py3-none-any.whl");
+    let member = "big_pkg/weights.bin";
+    let metadata = b"Metadata-Version: 2.1\nName: big-pkg\nVersion: 1.0.0\n";
+    let wheel = b"Wheel-Version: 1.0\nRoot-Is-Purelib: true\nTag: py3-none-any\n";
+
+    let weights_hash = hash_reader(std::io::repeat(0).take(SIZE)).unwrap();
+    let record = format!(
+        "{member},{weights_hash},{SIZE}\n\
+         big_pkg-1.0.0.dist-info/METADATA,{},{}\n\
+         big_pkg-1.0.0.dist-info/WHEEL,{},{}\n\
+         big_pkg-1.0.0.dist-info/RECORD,,\n",
+        hash_content(metadata),
+        metadata.len(),
+        hash_content(wheel),
+        wheel.len()
+    );
+
+    let mut zip = ZipWriter::new(File::create(&wheel_path).unwrap());
+    let stored = SimpleFileOptions::default()
+        .compression_method(zip::CompressionMethod::Stored)
+        .large_file(true);
+    zip.start_file(member, stored).unwrap();
+    std::io::copy(&mut std::io::repeat(0).take(SIZE), &mut zip).unwrap();
+    for (name, content) in [
+        ("big_pkg-1.0.0.dist-info/METADATA", metadata.as_slice()),
+        ("big_pkg-1.0.0.dist-info/WHEEL", wheel.as_slice()),
+        ("big_pkg-1.0.0.dist-info/RECORD", record.as_bytes()),
+    ] {
+        zip.start_file(name, SimpleFileOptions::default()).unwrap();
+        zip.write_all(content).unwrap();
+    }
+    zip.finish().unwrap();
+
+    let mut editor = WheelEditor::open(&wheel_path).expect("Failed to open wheel");
+    editor.set_version("1.0.1");
+    editor.save(&edited_path).expect("Failed to save edited wheel");
+
+    let mut archive = zip::ZipArchive::new(File::open(&edited_path).unwrap()).unwrap();
+    {
+        let mut entry = archive.by_name(member).unwrap();
+        assert_eq!(entry.size(), SIZE);
+        assert_eq!(entry.compression(), zip::CompressionMethod::Stored);
+        // Reading to the end also checks the CRC
+        assert_eq!(hash_reader(&mut entry).unwrap(), weights_hash);
+    }
+
+    // The regenerated METADATA lies past the 4 GiB mark
+    let mut metadata = String::new();
+    archive
+        .by_name("big_pkg-1.0.1.dist-info/METADATA")
+        .unwrap()
+        .read_to_string(&mut metadata)
+        .unwrap();
+    assert!(metadata.contains("Version: 1.0.1"), "{}", metadata);
+}